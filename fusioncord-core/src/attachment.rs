@@ -0,0 +1,219 @@
+//! Kinds of attachments along with what's needed to preview them.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use twilight_model::{
+    channel::Attachment,
+    id::{marker::AttachmentMarker, Id},
+};
+
+use crate::highlight;
+
+/// Prefix of the names of attachments that are hidden until clicked.
+pub const SPOILER_PREFIX: &str = "SPOILER_";
+/// Text files larger than this are only linked to.
+pub const MAX_TEXT_PREVIEW_SIZE: u64 = 512 * 1024;
+
+/// How an attachment is previewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Image,
+    Video,
+    Audio,
+    /// Recorded voice message, which comes with a waveform.
+    VoiceMessage,
+    Text,
+    Other,
+}
+
+/// Text of a text file, fetched on demand.
+#[derive(Debug, Clone)]
+pub enum TextPreview {
+    Loading,
+    Loaded(String),
+    Failed,
+}
+
+/// Keeps track of the text files that were previewed.
+#[derive(Debug, Default)]
+pub struct AttachmentStore {
+    texts: HashMap<Id<AttachmentMarker>, TextPreview>,
+}
+
+impl AttachmentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(&self, attachment_id: Id<AttachmentMarker>) -> Option<&TextPreview> {
+        self.texts.get(&attachment_id)
+    }
+
+    /// Marks the text of a file as being fetched.
+    ///
+    /// Returns whether it must be fetched, `false` if it already was.
+    pub fn start_fetch(&mut self, attachment_id: Id<AttachmentMarker>) -> bool {
+        if self.texts.contains_key(&attachment_id) {
+            return false;
+        }
+        self.texts.insert(attachment_id, TextPreview::Loading);
+        true
+    }
+
+    /// Inserts the fetched text of a file, `None` if fetching it failed.
+    pub fn insert_text(&mut self, attachment_id: Id<AttachmentMarker>, text: Option<String>) {
+        let text = text.map_or(TextPreview::Failed, TextPreview::Loaded);
+        self.texts.insert(attachment_id, text);
+    }
+}
+
+pub fn kind(attachment: &Attachment) -> AttachmentKind {
+    if attachment.waveform.is_some() {
+        return AttachmentKind::VoiceMessage;
+    }
+
+    let content_type = attachment.content_type.as_deref().unwrap_or_default();
+    // parameters like the charset come after the media type
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    match media_type.split_once('/') {
+        Some(("image", _)) => AttachmentKind::Image,
+        Some(("video", _)) => AttachmentKind::Video,
+        Some(("audio", _)) => AttachmentKind::Audio,
+        Some(("text", _)) | Some(("application", "json" | "xml" | "x-sh" | "toml")) => {
+            AttachmentKind::Text
+        }
+        // source files are often uploaded without a media type
+        _ if highlight::grammar_for_filename(&attachment.filename).is_some() => {
+            AttachmentKind::Text
+        }
+        _ => AttachmentKind::Other,
+    }
+}
+
+pub fn is_spoiler(attachment: &Attachment) -> bool {
+    attachment.filename.starts_with(SPOILER_PREFIX)
+}
+
+/// Whether the text of a file is small enough to be previewed.
+pub fn has_text_preview(attachment: &Attachment) -> bool {
+    kind(attachment) == AttachmentKind::Text && attachment.size <= MAX_TEXT_PREVIEW_SIZE
+}
+
+/// Heights of the bars of the waveform of a voice message, from 0 to 1.
+///
+/// The samples are combined into the given amount of bars, each as high as
+/// the loudest sample it covers. Returns an empty waveform if it can't be
+/// decoded.
+pub fn waveform(attachment: &Attachment, bars: usize) -> Vec<f32> {
+    let Some(samples) = attachment
+        .waveform
+        .as_ref()
+        .and_then(|waveform| STANDARD.decode(waveform).ok())
+        .filter(|samples| !samples.is_empty())
+    else {
+        return Vec::new();
+    };
+
+    (0..bars)
+        .map(|bar| {
+            let start = bar * samples.len() / bars;
+            let end = ((bar + 1) * samples.len() / bars).max(start + 1);
+            let loudest = samples[start..end.min(samples.len())]
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or_default();
+
+            f32::from(loudest) / f32::from(u8::MAX)
+        })
+        .collect()
+}
+
+/// Formats the size of a file, like `1.5 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} bytes");
+    }
+    let mut size = bytes as f64 / 1024.;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if size < 1024. {
+            break;
+        }
+        size /= 1024.;
+        unit = next_unit;
+    }
+
+    format!("{size:.2} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{channel::Attachment, id::Id};
+
+    use super::{format_size, is_spoiler, kind, waveform, AttachmentKind};
+
+    fn attachment(filename: &str, content_type: Option<&str>) -> Attachment {
+        Attachment {
+            content_type: content_type.map(ToOwned::to_owned),
+            ephemeral: false,
+            duration_secs: None,
+            filename: filename.to_owned(),
+            flags: None,
+            description: None,
+            height: None,
+            id: Id::new(1),
+            proxy_url: String::new(),
+            size: 0,
+            url: String::new(),
+            waveform: None,
+            width: None,
+        }
+    }
+
+    #[test]
+    fn kinds() {
+        let kind_of = |filename, content_type| kind(&attachment(filename, content_type));
+
+        assert_eq!(kind_of("a.png", Some("image/png")), AttachmentKind::Image);
+        assert_eq!(kind_of("a.mp4", Some("video/mp4")), AttachmentKind::Video);
+        assert_eq!(
+            kind_of("a.txt", Some("text/plain; charset=utf-8")),
+            AttachmentKind::Text
+        );
+        assert_eq!(kind_of("main.rs", None), AttachmentKind::Text);
+        assert_eq!(
+            kind_of("a.zip", Some("application/zip")),
+            AttachmentKind::Other
+        );
+        assert!(is_spoiler(&attachment("SPOILER_a.png", None)));
+        assert!(!is_spoiler(&attachment("spoiler_a.png", None)));
+    }
+
+    #[test]
+    fn waveforms() {
+        let mut voice = attachment("voice-message.ogg", Some("audio/ogg"));
+        // samples 0, 51, 255 and 102
+        voice.waveform = Some("ADP/Zg==".to_owned());
+
+        assert_eq!(kind(&voice), AttachmentKind::VoiceMessage);
+        assert_eq!(waveform(&voice, 2), [0.2, 1.]);
+        assert_eq!(waveform(&voice, 4), [0., 0.2, 1., 0.4]);
+        // bars are repeated when there are more bars than samples
+        assert_eq!(waveform(&voice, 8).len(), 8);
+
+        voice.waveform = Some("not base64!".to_owned());
+        assert!(waveform(&voice, 2).is_empty());
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1536), "1.50 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.00 MB");
+    }
+}
@@ -0,0 +1,956 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet, VecDeque},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use twilight_model::{
+    channel::{
+        message::{Reaction, ReactionCountDetails},
+        Channel, ChannelType, Message,
+    },
+    gateway::{
+        event::DispatchEvent,
+        payload::incoming::{MessageUpdate, ThreadListSync, ThreadMembersUpdate},
+        GatewayReaction,
+    },
+    guild::{Guild, PartialGuild},
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
+        Id,
+    },
+    user::{CurrentUser, User},
+    util::Timestamp,
+};
+
+use crate::reaction::same_emoji;
+
+/// Maximum amount of messages kept per channel, older ones are evicted first.
+const MESSAGE_CACHE_SIZE: usize = 200;
+/// First second of 2015 in milliseconds since the unix epoch.
+const DISCORD_EPOCH_MILLIS: u64 = 1_420_070_400_000;
+/// Increment of the nonces, which tells apart the ones generated within the
+/// same millisecond.
+static NONCE_INCREMENT: AtomicU64 = AtomicU64::new(0);
+
+/// A category together with the channels it contains, in display order.
+#[derive(Debug)]
+pub struct ChannelGroup<'a> {
+    /// The category, `None` for the channels without a parent.
+    pub category: Option<&'a Channel>,
+    pub channels: Vec<&'a Channel>,
+}
+
+/// A message sent by the current user that wasn't echoed back by the gateway
+/// yet.
+#[derive(Debug, Clone)]
+pub struct PendingMessage {
+    /// Nonce the message was sent with.
+    pub nonce: String,
+    pub content: String,
+    pub timestamp: Timestamp,
+    /// Whether the message could not be sent.
+    pub failed: bool,
+}
+
+/// In-memory state of the guilds, channels and messages received from the
+/// gateway.
+///
+/// Must be fed every dispatch event through [`Cache::update`].
+#[derive(Debug, Default)]
+pub struct Cache {
+    current_user: Option<CurrentUser>,
+    /// Guilds in the order they were received, their channels and threads
+    /// are moved into [`Cache::channels`].
+    guilds: Vec<Guild>,
+    channels: HashMap<Id<ChannelMarker>, Channel>,
+    messages: HashMap<Id<ChannelMarker>, VecDeque<Message>>,
+    pending: HashMap<Id<ChannelMarker>, Vec<PendingMessage>>,
+    /// Channels whose recent messages were fetched over the REST API, or are
+    /// being fetched.
+    history: HashSet<Id<ChannelMarker>>,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_user = Some(ready.user.clone());
+                self.guilds.clear();
+                self.channels.clear();
+                self.messages.clear();
+                self.pending.clear();
+                self.history.clear();
+
+                for channel in &ready.private_channels {
+                    self.insert_channel(channel.clone());
+                }
+            }
+            DispatchEvent::UserUpdate(user) => self.current_user = Some(user.0.clone()),
+            DispatchEvent::GuildCreate(guild) => self.insert_guild(guild.0.clone()),
+            DispatchEvent::GuildUpdate(guild) => self.update_guild(guild),
+            DispatchEvent::GuildDelete(guild) if guild.unavailable => {
+                if let Some(guild) = self.guilds.iter_mut().find(|cached| cached.id == guild.id) {
+                    guild.unavailable = true;
+                }
+            }
+            DispatchEvent::GuildDelete(guild) => self.remove_guild(guild.id),
+            DispatchEvent::GuildEmojisUpdate(update) => {
+                let guild = self
+                    .guilds
+                    .iter_mut()
+                    .find(|guild| guild.id == update.guild_id);
+                if let Some(guild) = guild {
+                    guild.emojis = update.emojis.clone();
+                }
+            }
+            DispatchEvent::GuildStickersUpdate(update) => {
+                let guild = self
+                    .guilds
+                    .iter_mut()
+                    .find(|guild| guild.id == update.guild_id);
+                if let Some(guild) = guild {
+                    guild.stickers = update.stickers.clone();
+                }
+            }
+            DispatchEvent::ChannelCreate(channel) => self.insert_channel(channel.0.clone()),
+            DispatchEvent::ChannelUpdate(channel) => self.insert_channel(channel.0.clone()),
+            DispatchEvent::ThreadCreate(thread) => self.insert_channel(thread.0.clone()),
+            DispatchEvent::ThreadUpdate(thread) => self.insert_channel(thread.0.clone()),
+            DispatchEvent::ChannelDelete(channel) => self.remove_channel(channel.id),
+            DispatchEvent::ChannelRecipientAdd(add) => {
+                if let Some(recipients) = self.recipients_mut(add.channel_id) {
+                    recipients.retain(|user| user.id != add.user.id);
+                    recipients.push(add.user.clone());
+                }
+            }
+            DispatchEvent::ChannelRecipientRemove(remove) => {
+                if let Some(recipients) = self.recipients_mut(remove.channel_id) {
+                    recipients.retain(|user| user.id != remove.user.id);
+                }
+            }
+            DispatchEvent::ThreadDelete(thread) => self.remove_channel(thread.id),
+            DispatchEvent::ThreadListSync(sync) => self.sync_threads(sync),
+            DispatchEvent::ThreadMembersUpdate(update) => self.update_thread_members(update),
+            DispatchEvent::MessageCreate(message) => self.insert_message(message.0.clone()),
+            DispatchEvent::MessageUpdate(update) => self.update_message(update),
+            DispatchEvent::MessageDelete(delete) => {
+                self.remove_messages(delete.channel_id, &[delete.id]);
+            }
+            DispatchEvent::MessageDeleteBulk(delete) => {
+                self.remove_messages(delete.channel_id, &delete.ids);
+            }
+            DispatchEvent::ReactionAdd(reaction) => self.add_reaction(reaction),
+            DispatchEvent::ReactionRemove(reaction) => self.remove_reaction(reaction),
+            DispatchEvent::ReactionRemoveAll(remove) => {
+                if let Some(message) = self.message_mut(remove.channel_id, remove.message_id) {
+                    message.reactions.clear();
+                }
+            }
+            DispatchEvent::ReactionRemoveEmoji(remove) => {
+                if let Some(message) = self.message_mut(remove.channel_id, remove.message_id) {
+                    message
+                        .reactions
+                        .retain(|reaction| !same_emoji(&reaction.emoji, &remove.emoji));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Inserts guilds that weren't received through a dispatch event, such as
+    /// the ones sent along with the READY payload.
+    pub fn insert_guilds(&mut self, guilds: impl IntoIterator<Item = Guild>) {
+        for guild in guilds {
+            self.insert_guild(guild);
+        }
+    }
+
+    /// Marks the recent messages of a channel as being fetched.
+    ///
+    /// Returns whether they must be fetched, `false` if they already were
+    /// during this session. They're inserted with [`Cache::insert_history`].
+    pub fn start_history_fetch(&mut self, channel_id: Id<ChannelMarker>) -> bool {
+        self.history.insert(channel_id)
+    }
+
+    /// Inserts the recent messages of a channel, `None` if fetching them
+    /// failed so that they're fetched again.
+    pub fn insert_history(
+        &mut self,
+        channel_id: Id<ChannelMarker>,
+        messages: Option<Vec<Message>>,
+    ) {
+        match messages {
+            Some(messages) => self.insert_messages(messages),
+            None => {
+                self.history.remove(&channel_id);
+            }
+        }
+    }
+
+    /// Inserts messages that weren't received through a dispatch event, such
+    /// as the ones fetched over the REST API.
+    pub fn insert_messages(&mut self, messages: impl IntoIterator<Item = Message>) {
+        for message in messages {
+            self.insert_message(message);
+        }
+    }
+
+    /// Inserts threads that weren't received through a dispatch event, such
+    /// as the archived ones fetched over the REST API.
+    pub fn insert_threads(&mut self, threads: impl IntoIterator<Item = Channel>) {
+        for thread in threads {
+            self.insert_channel(thread);
+        }
+    }
+
+    pub fn current_user(&self) -> Option<&CurrentUser> {
+        self.current_user.as_ref()
+    }
+
+    /// All available guilds, in the order they were received.
+    pub fn guilds(&self) -> impl Iterator<Item = &Guild> {
+        self.guilds.iter().filter(|guild| !guild.unavailable)
+    }
+
+    pub fn guild(&self, guild_id: Id<GuildMarker>) -> Option<&Guild> {
+        self.guilds.iter().find(|guild| guild.id == guild_id)
+    }
+
+    pub fn channel(&self, channel_id: Id<ChannelMarker>) -> Option<&Channel> {
+        self.channels.get(&channel_id)
+    }
+
+    /// Channels of a guild grouped by category, excluding threads.
+    ///
+    /// Channels without a category come first, followed by the categories
+    /// ordered by their position. Within a group text channels are listed
+    /// before voice channels, both ordered by their position.
+    pub fn channel_groups(&self, guild_id: Id<GuildMarker>) -> Vec<ChannelGroup<'_>> {
+        let mut categories = Vec::new();
+        let mut children: HashMap<_, Vec<_>> = HashMap::new();
+
+        for channel in self.channels.values() {
+            if channel.guild_id != Some(guild_id) || channel.kind.is_thread() {
+                continue;
+            }
+
+            if channel.kind == ChannelType::GuildCategory {
+                categories.push(channel);
+            } else {
+                children.entry(channel.parent_id).or_default().push(channel);
+            }
+        }
+
+        categories.sort_by_key(|category| (category.position, category.id));
+
+        let mut groups = Vec::with_capacity(categories.len() + 1);
+        for category in [None].into_iter().chain(categories.into_iter().map(Some)) {
+            let mut channels = children
+                .remove(&category.map(|category: &Channel| category.id))
+                .unwrap_or_default();
+            channels.sort_by_key(|channel| (is_voice(channel), channel.position, channel.id));
+
+            groups.push(ChannelGroup { category, channels });
+        }
+
+        groups
+    }
+
+    /// DMs and group DMs of the current user, the ones with the most recent
+    /// messages first.
+    pub fn private_channels(&self) -> Vec<&Channel> {
+        let mut channels = self
+            .channels
+            .values()
+            .filter(|channel| is_private(channel))
+            .collect::<Vec<_>>();
+        // channels without messages are ordered by when they were created
+        channels.sort_by_key(|channel| {
+            Reverse(
+                channel
+                    .last_message_id
+                    .map_or(channel.id.get(), |id| id.get()),
+            )
+        });
+
+        channels
+    }
+
+    /// Cached threads of a channel, both active and archived, in no
+    /// particular order.
+    pub fn threads(&self, parent_id: Id<ChannelMarker>) -> impl Iterator<Item = &Channel> {
+        self.channels
+            .values()
+            .filter(move |channel| channel.kind.is_thread() && channel.parent_id == Some(parent_id))
+    }
+
+    /// Cached messages of a channel, oldest first.
+    pub fn messages(&self, channel_id: Id<ChannelMarker>) -> impl Iterator<Item = &Message> {
+        self.messages.get(&channel_id).into_iter().flatten()
+    }
+
+    pub fn message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+    ) -> Option<&Message> {
+        let messages = self.messages.get(&channel_id)?;
+        let index = messages
+            .binary_search_by_key(&message_id, |message| message.id)
+            .ok()?;
+
+        messages.get(index)
+    }
+
+    /// Messages of the current user in a channel that are still being sent,
+    /// oldest first.
+    pub fn pending_messages(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> impl Iterator<Item = &PendingMessage> {
+        self.pending.get(&channel_id).into_iter().flatten()
+    }
+
+    /// Shows a message of the current user until it is echoed back by the
+    /// gateway.
+    ///
+    /// Returns the nonce the message must be sent with.
+    pub fn insert_pending_message(
+        &mut self,
+        channel_id: Id<ChannelMarker>,
+        content: String,
+    ) -> String {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let nonce = nonce(since_epoch);
+
+        self.pending
+            .entry(channel_id)
+            .or_default()
+            .push(PendingMessage {
+                nonce: nonce.clone(),
+                content,
+                timestamp: Timestamp::from_micros(since_epoch.as_micros() as i64)
+                    .expect("system time out of range"),
+                failed: false,
+            });
+
+        nonce
+    }
+
+    /// Marks a pending message as failed to send.
+    pub fn fail_pending_message(&mut self, channel_id: Id<ChannelMarker>, nonce: &str) {
+        if let Some(message) = self
+            .pending
+            .get_mut(&channel_id)
+            .and_then(|pending| pending.iter_mut().find(|message| message.nonce == nonce))
+        {
+            message.failed = true;
+        }
+    }
+
+    pub fn remove_pending_message(&mut self, channel_id: Id<ChannelMarker>, nonce: &str) {
+        if let Some(pending) = self.pending.get_mut(&channel_id) {
+            pending.retain(|message| message.nonce != nonce);
+        }
+    }
+
+    fn insert_guild(&mut self, mut guild: Guild) {
+        for mut channel in guild.channels.drain(..).chain(guild.threads.drain(..)) {
+            // channels nested in a guild don't include the guild id
+            channel.guild_id = Some(guild.id);
+            self.channels.insert(channel.id, channel);
+        }
+
+        match self.guilds.iter_mut().find(|cached| cached.id == guild.id) {
+            Some(cached) => *cached = guild,
+            None => self.guilds.push(guild),
+        }
+    }
+
+    fn update_guild(&mut self, update: &PartialGuild) {
+        let Some(guild) = self.guilds.iter_mut().find(|guild| guild.id == update.id) else {
+            return;
+        };
+
+        guild.banner = update.banner;
+        guild.description = update.description.clone();
+        guild.emojis = update.emojis.clone();
+        guild.features = update.features.clone();
+        guild.icon = update.icon;
+        guild.name = update.name.clone();
+        guild.owner_id = update.owner_id;
+        guild.roles = update.roles.clone();
+        guild.splash = update.splash;
+        guild.system_channel_id = update.system_channel_id;
+    }
+
+    fn remove_guild(&mut self, guild_id: Id<GuildMarker>) {
+        self.guilds.retain(|guild| guild.id != guild_id);
+
+        let (messages, history) = (&mut self.messages, &mut self.history);
+        self.channels.retain(|channel_id, channel| {
+            let keep = channel.guild_id != Some(guild_id);
+            if !keep {
+                messages.remove(channel_id);
+                history.remove(channel_id);
+            }
+
+            keep
+        });
+    }
+
+    fn insert_channel(&mut self, channel: Channel) {
+        self.channels.insert(channel.id, channel);
+    }
+
+    fn recipients_mut(&mut self, channel_id: Id<ChannelMarker>) -> Option<&mut Vec<User>> {
+        let channel = self.channels.get_mut(&channel_id)?;
+
+        Some(channel.recipients.get_or_insert_with(Vec::new))
+    }
+
+    fn remove_channel(&mut self, channel_id: Id<ChannelMarker>) {
+        self.channels.remove(&channel_id);
+        self.messages.remove(&channel_id);
+        self.pending.remove(&channel_id);
+        self.history.remove(&channel_id);
+    }
+
+    /// Replaces the active threads of the synced channels, the ones missing
+    /// from the list were archived or can no longer be accessed.
+    fn sync_threads(&mut self, sync: &ThreadListSync) {
+        // an empty list of channels means the whole guild is synced
+        let synced = |channel: &Channel| {
+            channel.guild_id == Some(sync.guild_id)
+                && (sync.channel_ids.is_empty()
+                    || channel
+                        .parent_id
+                        .is_some_and(|parent_id| sync.channel_ids.contains(&parent_id)))
+        };
+        let removed = self
+            .channels
+            .values()
+            .filter(|channel| channel.kind.is_thread() && !is_archived(channel) && synced(channel))
+            .filter(|channel| !sync.threads.iter().any(|thread| thread.id == channel.id))
+            .map(|channel| channel.id)
+            .collect::<Vec<_>>();
+
+        for thread_id in removed {
+            self.remove_channel(thread_id);
+        }
+
+        for thread in &sync.threads {
+            let mut thread = thread.clone();
+            thread.guild_id = Some(sync.guild_id);
+            self.insert_channel(thread);
+        }
+    }
+
+    fn update_thread_members(&mut self, update: &ThreadMembersUpdate) {
+        if let Some(thread) = self.channels.get_mut(&update.id) {
+            // the count stops at 50
+            thread.member_count = Some(i8::try_from(update.member_count).unwrap_or(i8::MAX));
+        }
+    }
+
+    fn insert_message(&mut self, message: Message) {
+        let channel_id = message.channel_id;
+        if let Some(nonce) = &message.nonce {
+            self.remove_pending_message(channel_id, nonce);
+        }
+
+        let messages = self.messages.entry(channel_id).or_default();
+
+        match messages.binary_search_by_key(&message.id, |cached| cached.id) {
+            Ok(index) => messages[index] = message,
+            Err(index) => messages.insert(index, message),
+        }
+
+        if messages.len() > MESSAGE_CACHE_SIZE {
+            messages.pop_front();
+        }
+
+        let newest_id = messages.back().map(|message| message.id.cast());
+        if let Some(channel) = self.channels.get_mut(&channel_id) {
+            channel.last_message_id = channel.last_message_id.max(newest_id);
+        }
+    }
+
+    fn update_message(&mut self, update: &MessageUpdate) {
+        let Some(message) = self.message_mut(update.channel_id, update.id) else {
+            return;
+        };
+
+        if let Some(attachments) = &update.attachments {
+            message.attachments = attachments.clone();
+        }
+        if let Some(content) = &update.content {
+            message.content = content.clone();
+        }
+        if let Some(edited_timestamp) = update.edited_timestamp {
+            message.edited_timestamp = Some(edited_timestamp);
+        }
+        if let Some(embeds) = &update.embeds {
+            message.embeds = embeds.clone();
+        }
+        if let Some(pinned) = update.pinned {
+            message.pinned = pinned;
+        }
+    }
+
+    fn add_reaction(&mut self, reaction: &GatewayReaction) {
+        let own = self.is_current_user(reaction.user_id);
+        let Some(message) = self.message_mut(reaction.channel_id, reaction.message_id) else {
+            return;
+        };
+
+        let index = message
+            .reactions
+            .iter()
+            .position(|cached| same_emoji(&cached.emoji, &reaction.emoji))
+            .unwrap_or_else(|| {
+                message.reactions.push(Reaction {
+                    burst_colors: Vec::new(),
+                    count: 0,
+                    count_details: None,
+                    emoji: reaction.emoji.clone(),
+                    me: false,
+                    me_burst: false,
+                });
+                message.reactions.len() - 1
+            });
+        let cached = &mut message.reactions[index];
+
+        // reactions without details only contain normal reactions
+        let details = cached.count_details.get_or_insert(ReactionCountDetails {
+            burst: 0,
+            normal: cached.count,
+        });
+        cached.count += 1;
+
+        if reaction.burst {
+            details.burst += 1;
+            cached.me_burst |= own;
+            if cached.burst_colors.is_empty() {
+                cached.burst_colors = reaction.burst_colors.clone();
+            }
+        } else {
+            details.normal += 1;
+            cached.me |= own;
+        }
+    }
+
+    fn remove_reaction(&mut self, reaction: &GatewayReaction) {
+        let own = self.is_current_user(reaction.user_id);
+        let Some(message) = self.message_mut(reaction.channel_id, reaction.message_id) else {
+            return;
+        };
+        let Some(index) = message
+            .reactions
+            .iter()
+            .position(|cached| same_emoji(&cached.emoji, &reaction.emoji))
+        else {
+            return;
+        };
+        let cached = &mut message.reactions[index];
+
+        let details = cached.count_details.get_or_insert(ReactionCountDetails {
+            burst: 0,
+            normal: cached.count,
+        });
+        cached.count = cached.count.saturating_sub(1);
+
+        if reaction.burst {
+            details.burst = details.burst.saturating_sub(1);
+            cached.me_burst &= !own;
+        } else {
+            details.normal = details.normal.saturating_sub(1);
+            cached.me &= !own;
+        }
+
+        if cached.count == 0 {
+            message.reactions.remove(index);
+        }
+    }
+
+    fn is_current_user(&self, user_id: Id<UserMarker>) -> bool {
+        self.current_user
+            .as_ref()
+            .is_some_and(|current_user| current_user.id == user_id)
+    }
+
+    fn remove_messages(&mut self, channel_id: Id<ChannelMarker>, ids: &[Id<MessageMarker>]) {
+        if let Some(messages) = self.messages.get_mut(&channel_id) {
+            messages.retain(|message| !ids.contains(&message.id));
+        }
+    }
+
+    fn message_mut(
+        &mut self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+    ) -> Option<&mut Message> {
+        let messages = self.messages.get_mut(&channel_id)?;
+        let index = messages
+            .binary_search_by_key(&message_id, |message| message.id)
+            .ok()?;
+
+        messages.get_mut(index)
+    }
+}
+
+/// Whether a thread is archived, `false` for other channels.
+pub fn is_archived(channel: &Channel) -> bool {
+    channel
+        .thread_metadata
+        .as_ref()
+        .is_some_and(|metadata| metadata.archived)
+}
+
+/// Whether a channel is a DM or group DM.
+pub fn is_private(channel: &Channel) -> bool {
+    matches!(channel.kind, ChannelType::Private | ChannelType::Group)
+}
+
+/// Name of a DM or group DM, the names of its recipients if a group DM wasn't
+/// named.
+pub fn private_channel_name(channel: &Channel) -> String {
+    if let Some(name) = channel.name.as_deref().filter(|name| !name.is_empty()) {
+        return name.to_owned();
+    }
+
+    let names = channel
+        .recipients
+        .iter()
+        .flatten()
+        .map(|user| user.global_name.as_deref().unwrap_or(&user.name))
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        "Unnamed".to_owned()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn is_voice(channel: &Channel) -> bool {
+    matches!(
+        channel.kind,
+        ChannelType::GuildVoice | ChannelType::GuildStageVoice
+    )
+}
+
+/// Generates the nonce of a message or interaction sent at the given time.
+///
+/// Nonces are snowflakes of the time they were sent at, like the ones the
+/// official client uses. Their low bits hold an increment, so that the ones
+/// generated at the same time are still unique.
+pub(crate) fn nonce(since_epoch: Duration) -> String {
+    let millis = since_epoch.as_millis() as u64;
+    // snowflakes only have 12 bits of increment
+    let increment = NONCE_INCREMENT.fetch_add(1, Ordering::Relaxed) & 0xFFF;
+
+    ((millis.saturating_sub(DISCORD_EPOCH_MILLIS) << 22) | increment).to_string()
+}
+
+/// When an entity was created, which is encoded in its snowflake id.
+pub fn snowflake_timestamp<T>(id: Id<T>) -> Timestamp {
+    let millis = (id.get() >> 22) + DISCORD_EPOCH_MILLIS;
+
+    Timestamp::from_micros(millis as i64 * 1000).expect("snowflake timestamp out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use twilight_model::{
+        channel::{
+            message::{Reaction, ReactionCountDetails},
+            Channel,
+        },
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{
+                MessageCreate, ReactionAdd, ReactionRemove, ReactionRemoveAll, ReactionRemoveEmoji,
+                ThreadCreate, ThreadListSync,
+            },
+            GatewayReaction,
+        },
+        id::{marker::MessageMarker, Id},
+    };
+
+    use super::{nonce, snowflake_timestamp, Cache, DISCORD_EPOCH_MILLIS};
+    use crate::fixtures::{message, ready, CHANNEL_ID, CURRENT_USER_ID, GUILD_ID, OTHER_USER_ID};
+
+    const MESSAGE_ID: u64 = 100;
+
+    fn reaction(user_id: u64, emoji: &Value, burst: bool) -> GatewayReaction {
+        serde_json::from_value(json!({
+            "burst": burst,
+            "burst_colors": if burst { json!(["#ff0000"]) } else { json!([]) },
+            "channel_id": CHANNEL_ID.to_string(),
+            "emoji": emoji,
+            "guild_id": GUILD_ID.to_string(),
+            "member": null,
+            "message_id": MESSAGE_ID.to_string(),
+            "user_id": user_id.to_string(),
+        }))
+        .unwrap()
+    }
+
+    fn add(cache: &mut Cache, user_id: u64, emoji: &Value, burst: bool) {
+        let add = ReactionAdd(reaction(user_id, emoji, burst));
+        cache.update(&DispatchEvent::ReactionAdd(Box::new(add)));
+    }
+
+    fn remove(cache: &mut Cache, user_id: u64, emoji: &Value, burst: bool) {
+        let remove = ReactionRemove(reaction(user_id, emoji, burst));
+        cache.update(&DispatchEvent::ReactionRemove(Box::new(remove)));
+    }
+
+    fn reactions(cache: &Cache) -> &[Reaction] {
+        &cache
+            .message(Id::new(CHANNEL_ID), Id::new(MESSAGE_ID))
+            .unwrap()
+            .reactions
+    }
+
+    /// Thread of a channel, the guild id is left out like in thread list
+    /// syncs.
+    fn thread(id: u64, parent_id: u64, archived: bool) -> Channel {
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "member_count": 1,
+            "message_count": 1,
+            "name": "thread",
+            "parent_id": parent_id.to_string(),
+            "thread_metadata": {
+                "archive_timestamp": "2023-11-14T22:13:20+00:00",
+                "archived": archived,
+                "auto_archive_duration": 1440,
+                "locked": false,
+            },
+            "type": 11,
+        }))
+        .unwrap()
+    }
+
+    fn sync(cache: &mut Cache, channel_ids: &[u64], threads: Vec<Channel>) {
+        cache.update(&DispatchEvent::ThreadListSync(ThreadListSync {
+            channel_ids: channel_ids.iter().copied().map(Id::new).collect(),
+            guild_id: Id::new(GUILD_ID),
+            members: Vec::new(),
+            threads,
+        }));
+    }
+
+    fn thread_ids(cache: &Cache, parent_id: u64) -> Vec<u64> {
+        let mut ids = cache
+            .threads(Id::new(parent_id))
+            .map(|thread| thread.id.get())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    /// Cache with a message that has two reactions with a crab from other
+    /// users, which were sent without count details.
+    fn cache() -> (Cache, Value) {
+        let crab = json!({ "id": null, "name": "🦀" });
+        let mut cache = Cache::new();
+        cache.update(&ready());
+        let message = message(
+            MESSAGE_ID,
+            &json!({ "reactions": [{ "count": 2, "emoji": crab, "me": false }] }),
+        );
+        cache.update(&DispatchEvent::MessageCreate(Box::new(MessageCreate(
+            message,
+        ))));
+
+        (cache, crab)
+    }
+
+    #[test]
+    fn nonces() {
+        let since_epoch = Duration::from_millis(DISCORD_EPOCH_MILLIS + 1_000);
+        let first = nonce(since_epoch);
+        let second = nonce(since_epoch);
+        assert_ne!(first, second);
+
+        for nonce in [first, second] {
+            let id = Id::<MessageMarker>::new(nonce.parse().unwrap());
+            assert_eq!(
+                snowflake_timestamp(id).as_micros(),
+                since_epoch.as_micros() as i64
+            );
+        }
+    }
+
+    #[test]
+    fn reaction_counts() {
+        let (mut cache, crab) = cache();
+        let details = |reaction: &Reaction| {
+            let details = reaction.count_details.unwrap();
+            (reaction.count, details.normal, details.burst)
+        };
+
+        add(&mut cache, CURRENT_USER_ID, &crab, false);
+        let reaction = &reactions(&cache)[0];
+        assert_eq!(details(reaction), (3, 3, 0));
+        assert!(reaction.me && !reaction.me_burst);
+
+        add(&mut cache, OTHER_USER_ID, &crab, true);
+        let reaction = &reactions(&cache)[0];
+        assert_eq!(details(reaction), (4, 3, 1));
+        assert!(!reaction.me_burst);
+        assert_eq!(reaction.burst_colors, ["#ff0000"]);
+
+        add(&mut cache, CURRENT_USER_ID, &crab, true);
+        remove(&mut cache, CURRENT_USER_ID, &crab, false);
+        let reaction = &reactions(&cache)[0];
+        assert_eq!(details(reaction), (4, 2, 2));
+        assert!(!reaction.me && reaction.me_burst);
+
+        // reactions with a new emoji are added, and removed with their last
+        // reactor
+        let ferris = json!({ "animated": false, "id": "30", "name": "ferris" });
+        add(&mut cache, OTHER_USER_ID, &ferris, false);
+        assert_eq!(reactions(&cache).len(), 2);
+        assert_eq!(details(&reactions(&cache)[1]), (1, 1, 0));
+        // the name of custom emojis isn't always sent
+        remove(
+            &mut cache,
+            OTHER_USER_ID,
+            &json!({ "id": "30", "name": null }),
+            false,
+        );
+        assert_eq!(reactions(&cache).len(), 1);
+
+        remove(&mut cache, CURRENT_USER_ID, &crab, true);
+        assert_eq!(
+            reactions(&cache)[0].count_details,
+            Some(ReactionCountDetails {
+                burst: 1,
+                normal: 2,
+            })
+        );
+        assert!(!reactions(&cache)[0].me_burst);
+    }
+
+    #[test]
+    fn removed_reactions() {
+        let (mut cache, crab) = cache();
+        let ferris = json!({ "animated": false, "id": "30", "name": "ferris" });
+        add(&mut cache, OTHER_USER_ID, &ferris, false);
+
+        cache.update(&DispatchEvent::ReactionRemoveEmoji(ReactionRemoveEmoji {
+            channel_id: Id::new(CHANNEL_ID),
+            emoji: serde_json::from_value(crab).unwrap(),
+            guild_id: Id::new(GUILD_ID),
+            message_id: Id::new(MESSAGE_ID),
+        }));
+        assert_eq!(reactions(&cache).len(), 1);
+        assert_eq!(reactions(&cache)[0].count, 1);
+
+        cache.update(&DispatchEvent::ReactionRemoveAll(ReactionRemoveAll {
+            channel_id: Id::new(CHANNEL_ID),
+            message_id: Id::new(MESSAGE_ID),
+            guild_id: Some(Id::new(GUILD_ID)),
+        }));
+        assert!(reactions(&cache).is_empty());
+    }
+
+    #[test]
+    fn synced_threads() {
+        let mut cache = Cache::new();
+        cache.update(&ready());
+        for thread in [
+            thread(30, 20, false),
+            thread(31, 20, true),
+            thread(32, 21, false),
+            thread(33, 21, false),
+        ] {
+            let thread = Channel {
+                guild_id: Some(Id::new(GUILD_ID)),
+                ..thread
+            };
+            cache.update(&DispatchEvent::ThreadCreate(Box::new(ThreadCreate(thread))));
+        }
+
+        // active threads missing from the synced channels are removed, the
+        // archived ones aren't part of the sync
+        sync(&mut cache, &[20], vec![thread(34, 20, false)]);
+        assert_eq!(thread_ids(&cache, 20), [31, 34]);
+        assert_eq!(thread_ids(&cache, 21), [32, 33]);
+        let synced = cache.channel(Id::new(34)).unwrap();
+        assert_eq!(synced.guild_id, Some(Id::new(GUILD_ID)));
+
+        // without channels the whole guild is synced
+        sync(&mut cache, &[], vec![thread(33, 21, false)]);
+        assert_eq!(thread_ids(&cache, 20), [31]);
+        assert_eq!(thread_ids(&cache, 21), [33]);
+    }
+
+    #[test]
+    fn thread_member_counts() {
+        let mut cache = Cache::new();
+        cache.update(&ready());
+        sync(&mut cache, &[], vec![thread(30, 20, false)]);
+
+        for (member_count, expected) in [(3, 3), (200, i8::MAX)] {
+            let update = serde_json::from_value(json!({
+                "guild_id": GUILD_ID.to_string(),
+                "id": "30",
+                "member_count": member_count,
+            }))
+            .unwrap();
+            cache.update(&DispatchEvent::ThreadMembersUpdate(update));
+
+            let thread = cache.channel(Id::new(30)).unwrap();
+            assert_eq!(thread.member_count, Some(expected));
+        }
+    }
+
+    #[test]
+    fn fetched_history() {
+        let mut cache = Cache::new();
+        cache.update(&ready());
+        assert!(cache.start_history_fetch(Id::new(CHANNEL_ID)));
+        assert!(!cache.start_history_fetch(Id::new(CHANNEL_ID)));
+
+        // messages received while fetching are kept, the fetched ones are
+        // newest first
+        let received = message(120, &json!({}));
+        cache.update(&DispatchEvent::MessageCreate(Box::new(MessageCreate(
+            received,
+        ))));
+        let fetched = [110, 100, 90].map(|id| message(id, &json!({})));
+        cache.insert_history(Id::new(CHANNEL_ID), Some(fetched.into()));
+        let ids = cache
+            .messages(Id::new(CHANNEL_ID))
+            .map(|message| message.id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [90, 100, 110, 120]);
+
+        // they're fetched again in the next session
+        cache.update(&ready());
+        assert_eq!(cache.messages(Id::new(CHANNEL_ID)).count(), 0);
+        assert!(cache.start_history_fetch(Id::new(CHANNEL_ID)));
+
+        // failed fetches are retried
+        cache.insert_history(Id::new(CHANNEL_ID), None);
+        assert!(cache.start_history_fetch(Id::new(CHANNEL_ID)));
+    }
+}
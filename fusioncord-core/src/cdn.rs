@@ -0,0 +1,111 @@
+//! URLs of the images hosted on the Discord CDN.
+
+use twilight_model::{
+    channel::message::sticker::StickerFormatType,
+    id::{
+        marker::{
+            ApplicationMarker, ChannelMarker, EmojiMarker, GuildMarker, RoleMarker, StickerMarker,
+            UserMarker,
+        },
+        Id,
+    },
+    util::ImageHash,
+};
+
+const CDN_URL: &str = "https://cdn.discordapp.com";
+const MEDIA_PROXY_URL: &str = "https://media.discordapp.net";
+const SPOTIFY_IMAGE_URL: &str = "https://i.scdn.co/image";
+
+pub fn guild_icon(guild_id: Id<GuildMarker>, icon: ImageHash, size: u16) -> String {
+    format!("{CDN_URL}/icons/{guild_id}/{icon}.png?size={size}")
+}
+
+/// Icon of a group DM.
+pub fn channel_icon(channel_id: Id<ChannelMarker>, icon: ImageHash, size: u16) -> String {
+    format!("{CDN_URL}/channel-icons/{channel_id}/{icon}.png?size={size}")
+}
+
+pub fn user_avatar(user_id: Id<UserMarker>, avatar: ImageHash, size: u16) -> String {
+    format!("{CDN_URL}/avatars/{user_id}/{avatar}.png?size={size}")
+}
+
+/// Avatar shown for users that didn't set one.
+///
+/// Users that migrated to the new username system have a discriminator of `0`,
+/// their default avatar is derived from their id instead.
+pub fn default_user_avatar(user_id: Id<UserMarker>, discriminator: u16) -> String {
+    let index = match discriminator {
+        0 => (user_id.get() >> 22) % 6,
+        discriminator => u64::from(discriminator) % 5,
+    };
+
+    format!("{CDN_URL}/embed/avatars/{index}.png")
+}
+
+pub fn emoji(emoji_id: Id<EmojiMarker>, animated: bool, size: u16) -> String {
+    let extension = if animated { "gif" } else { "png" };
+
+    format!("{CDN_URL}/emojis/{emoji_id}.{extension}?size={size}")
+}
+
+/// Image of a sticker, Lottie stickers are a JSON animation instead.
+pub fn sticker(sticker_id: Id<StickerMarker>, format: StickerFormatType) -> String {
+    match format {
+        StickerFormatType::Lottie => format!("{CDN_URL}/stickers/{sticker_id}.json"),
+        // GIF stickers are only served by the media proxy
+        StickerFormatType::Gif => format!("{MEDIA_PROXY_URL}/stickers/{sticker_id}.gif"),
+        _ => format!("{CDN_URL}/stickers/{sticker_id}.png"),
+    }
+}
+
+/// Image of an activity asset, which is either the id of an asset of the
+/// application or a prefixed external image.
+///
+/// Returns `None` for the assets of unknown external sources.
+pub fn activity_asset(
+    application_id: Option<Id<ApplicationMarker>>,
+    asset: &str,
+    size: u16,
+) -> Option<String> {
+    if let Some(path) = asset.strip_prefix("mp:") {
+        return Some(format!("{MEDIA_PROXY_URL}/{path}"));
+    }
+    if let Some(id) = asset.strip_prefix("spotify:") {
+        return Some(format!("{SPOTIFY_IMAGE_URL}/{id}"));
+    }
+    if asset.contains(':') {
+        return None;
+    }
+
+    application_id.map(|application_id| {
+        format!("{CDN_URL}/app-assets/{application_id}/{asset}.png?size={size}")
+    })
+}
+
+pub fn role_icon(role_id: Id<RoleMarker>, icon: ImageHash, size: u16) -> String {
+    format!("{CDN_URL}/role-icons/{role_id}/{icon}.png?size={size}")
+}
+
+pub fn user_banner(user_id: Id<UserMarker>, banner: ImageHash, size: u16) -> String {
+    format!("{CDN_URL}/banners/{user_id}/{banner}.png?size={size}")
+}
+
+/// Avatar of a user specific to a guild.
+pub fn member_avatar(
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    avatar: ImageHash,
+    size: u16,
+) -> String {
+    format!("{CDN_URL}/guilds/{guild_id}/users/{user_id}/avatars/{avatar}.png?size={size}")
+}
+
+/// Banner of a user specific to a guild.
+pub fn member_banner(
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    banner: ImageHash,
+    size: u16,
+) -> String {
+    format!("{CDN_URL}/guilds/{guild_id}/users/{user_id}/banners/{banner}.png?size={size}")
+}
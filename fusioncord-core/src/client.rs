@@ -10,20 +10,26 @@ use std::{
     time::{Duration, Instant},
 };
 
-use serde::de::DeserializeSeed;
+use serde::{de::DeserializeSeed, Deserialize};
 use serde_json::{Deserializer, Map, Value};
-use tokio::{select, time::interval};
+use tokio::{select, sync::mpsc::UnboundedReceiver, time::interval};
 use tokio_tungstenite::tungstenite::{self, protocol::CloseFrame};
 use tracing::{error, info, trace, warn};
-use twilight_model::gateway::{
-    event::{DispatchEvent, GatewayEvent, GatewayEventDeserializer},
-    payload::outgoing::{identify::IdentifyInfo, Heartbeat, Identify},
-    CloseCode,
+use twilight_model::{
+    gateway::{
+        event::{DispatchEvent, GatewayEvent, GatewayEventDeserializer},
+        payload::outgoing::{
+            identify::IdentifyInfo, update_presence::UpdatePresencePayload, Heartbeat, Identify,
+            UpdatePresence,
+        },
+        CloseCode, GatewayOpcode,
+    },
+    guild::Guild,
 };
 
 use crate::{
     connection::{Connection, Message, ReceiveError, SendError},
-    message::RenderMessage,
+    message::{notify, RenderMessage},
 };
 
 /// A client state-machine.
@@ -41,19 +47,23 @@ impl<S: ClientState> Client<S> {
     }
 
     async fn deserialize_gateway_event(&mut self) -> Result<GatewayEvent, ClientError> {
-        let json = match self.connection.read().await? {
-            Message::Text(json) => json,
-            _ => return Err(ClientError::UnexpectedMessageType),
-        };
+        let json = self.read_text().await?;
 
-        // trace!("got here");
-        GatewayEventDeserializer::from_json(&json)
+        Ok(Self::parse_gateway_event(&json)?)
+    }
+
+    async fn read_text(&mut self) -> Result<String, ClientError> {
+        match self.connection.read().await? {
+            Message::Text(json) => Ok(json),
+            _ => Err(ClientError::UnexpectedMessageType),
+        }
+    }
+
+    fn parse_gateway_event(json: &str) -> Result<GatewayEvent, serde_json::Error> {
+        GatewayEventDeserializer::from_json(json)
             .expect("missing opcode")
-            .deserialize(&mut Deserializer::from_str(&json))
-            .map_err(|e| {
-                error!("An error occurred while deserializing a payload: {e:#?}");
-                ClientError::from(e)
-            })
+            .deserialize(&mut Deserializer::from_str(json))
+            .inspect_err(|e| error!("An error occurred while deserializing a payload: {e:#?}"))
     }
 }
 
@@ -107,13 +117,26 @@ impl Client<WaitingForIdentify> {
 }
 
 impl Client<WaitingForReady> {
+    /// Waits for the Ready event, after which the commands are sent over the
+    /// gateway as they're received.
     pub async fn wait_for_ready(
         mut self,
-        _tx: Sender<RenderMessage>,
+        tx: Sender<RenderMessage>,
+        commands: UnboundedReceiver<GatewayCommand>,
     ) -> Result<Client<Initialized>, ClientError> {
-        let event = self.deserialize_gateway_event().await?;
+        let json = self.read_text().await?;
+        let event = Self::parse_gateway_event(&json)?;
+
+        if let GatewayEvent::Dispatch(seq, event @ DispatchEvent::Ready(_)) = event {
+            info!("Successfully received the Ready event");
+            notify(&tx, RenderMessage::Dispatch(event));
+            notify(
+                &tx,
+                RenderMessage::InitialData {
+                    guilds: Self::parse_ready_guilds(&json),
+                },
+            );
 
-        if let GatewayEvent::Dispatch(seq, DispatchEvent::Ready(_payload)) = event {
             return Ok(Self::with_state(
                 self.connection,
                 Initialized {
@@ -123,12 +146,49 @@ impl Client<WaitingForReady> {
                     last_seq: seq,
                     client_specific_payloads: Map::new(),
                     interrupted: Arc::new(AtomicBool::new(false)),
+                    tx,
+                    commands,
                 },
             ));
         }
 
         Err(ClientError::NoReady)
     }
+
+    /// User accounts receive their guilds in full in the READY payload instead
+    /// of through GUILD_CREATE events, which the [`Ready`] model doesn't keep.
+    ///
+    /// Guilds that can't be deserialized, such as unavailable ones, are skipped.
+    ///
+    /// [`Ready`]: twilight_model::gateway::payload::incoming::Ready
+    fn parse_ready_guilds(json: &str) -> Vec<Guild> {
+        #[derive(Deserialize)]
+        struct Payload {
+            d: ReadyGuilds,
+        }
+
+        #[derive(Deserialize)]
+        struct ReadyGuilds {
+            guilds: Vec<Value>,
+        }
+
+        let guilds = match serde_json::from_str::<Payload>(json) {
+            Ok(payload) => payload.d.guilds,
+            Err(e) => {
+                error!("Failed to deserialize the guilds of the Ready event: {e}");
+                return Vec::new();
+            }
+        };
+
+        guilds
+            .into_iter()
+            .filter_map(|guild| {
+                serde_json::from_value(guild)
+                    .inspect_err(|e| warn!("Skipping guild of the Ready event: {e}"))
+                    .ok()
+            })
+            .collect()
+    }
 }
 
 impl Client<Initialized> {
@@ -140,6 +200,9 @@ impl Client<Initialized> {
                 _ = heartbeat_ticker.tick() => {
                     self.send_heartbeat().await?;
                 }
+                Some(command) = self.state.commands.recv() => {
+                    self.send_command(command).await?;
+                }
                 message = self.connection.read() => {
                     match message.unwrap() {
                         // TODO: don't clone txt
@@ -173,8 +236,23 @@ impl Client<Initialized> {
         Ok(())
     }
 
+    async fn send_command(&mut self, command: GatewayCommand) -> Result<(), ClientError> {
+        match command {
+            GatewayCommand::UpdatePresence(payload) => {
+                trace!("Updating presence to {:?}", payload.status);
+                let payload = UpdatePresence {
+                    d: payload,
+                    op: GatewayOpcode::PresenceUpdate,
+                };
+                self.connection.send(payload).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn handle_message(&mut self, json: String) -> Result<(), ClientError> {
-        let event = self.deserialize_gateway_event().await.map_err(|e| {
+        let event = Self::parse_gateway_event(&json).inspect_err(|e| {
             let err_msg = e.to_string();
             if err_msg.contains("unknown variant") {
                 let event_name = &err_msg["unknown variant".len()..err_msg.rfind('`').unwrap()];
@@ -190,7 +268,6 @@ impl Client<Initialized> {
                 )
                 .unwrap();
             }
-            e
         })?;
 
         match event {
@@ -223,16 +300,19 @@ impl Client<Initialized> {
     }
 
     async fn handle_dispatch_event(&mut self, event: DispatchEvent) {
-        match event {
-            DispatchEvent::Ready(_ready) => {
-                // TODO: initialize client state
-                info!("Successfully received the Ready event");
-            }
-            _ => warn!("Unimplemented dispatch event {:?}", event.kind()),
-        }
+        trace!("Received dispatch event {:?}", event.kind());
+        notify(&self.tx, RenderMessage::Dispatch(event));
     }
 }
 
+/// A command sent over the gateway once the client is initialized.
+#[derive(Debug)]
+pub enum GatewayCommand {
+    /// Changes the presence of the current user, the activities may be
+    /// empty unlike for bots.
+    UpdatePresence(UpdatePresencePayload),
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 pub enum ClientError {
@@ -308,4 +388,6 @@ pub struct Initialized {
     last_seq: u64,
     client_specific_payloads: Map<String, Value>,
     interrupted: Arc<AtomicBool>,
+    tx: Sender<RenderMessage>,
+    commands: UnboundedReceiver<GatewayCommand>,
 }
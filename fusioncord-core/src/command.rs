@@ -0,0 +1,796 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+use twilight_model::{
+    application::{
+        command::{
+            Command, CommandIndex, CommandOption, CommandOptionChoice, CommandOptionChoiceValue,
+            CommandOptionType, CommandOptionValue as BoundValue, CommandType,
+        },
+        interaction::application_command::{CommandData, CommandDataOption, CommandOptionValue},
+    },
+    gateway::event::DispatchEvent,
+    id::{
+        marker::{ChannelMarker, GenericMarker, GuildMarker},
+        Id,
+    },
+};
+
+/// Where commands are used, the commands that are available differ per
+/// guild and private channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandScope {
+    Guild(Id<GuildMarker>),
+    /// A private channel, where the commands of the bots in it are available.
+    Channel(Id<ChannelMarker>),
+}
+
+impl CommandScope {
+    pub fn new(guild_id: Option<Id<GuildMarker>>, channel_id: Id<ChannelMarker>) -> Self {
+        match guild_id {
+            Some(guild_id) => Self::Guild(guild_id),
+            None => Self::Channel(channel_id),
+        }
+    }
+}
+
+/// Commands available in a scope, fetched on demand.
+#[derive(Debug, Clone)]
+pub enum Commands {
+    Loading,
+    Loaded(Box<CommandIndex>),
+    Failed,
+}
+
+/// A command, or one of its subcommands, that can be picked.
+#[derive(Debug, Clone)]
+pub struct CommandEntry {
+    /// Name of the application the command belongs to.
+    pub application_name: Option<String>,
+    pub command: Command,
+    /// Names of the subcommand group and subcommand, if any.
+    pub path: Vec<String>,
+    pub description: String,
+    /// Options to fill in.
+    pub options: Vec<CommandOption>,
+}
+
+impl CommandEntry {
+    /// Name as it's typed, such as `role add`.
+    pub fn name(&self) -> String {
+        let mut name = self.command.name.clone();
+        for segment in &self.path {
+            name.push(' ');
+            name.push_str(segment);
+        }
+
+        name
+    }
+}
+
+/// Value entered for an option of a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionInput {
+    /// Text of a string, integer or number option.
+    Text(String),
+    Boolean(bool),
+    /// Picked user, channel, role or mentionable.
+    Id(Id<GenericMarker>),
+}
+
+/// Why the value of an option can't be sent.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OptionError {
+    #[error("this option is required")]
+    Missing,
+    #[error("must be a whole number")]
+    NotInteger,
+    #[error("must be a number")]
+    NotNumber,
+    #[error("must be at least {0}")]
+    TooSmall(String),
+    #[error("must be at most {0}")]
+    TooLarge(String),
+    #[error("must be at least {0} characters long")]
+    TooShort(u16),
+    #[error("must be at most {0} characters long")]
+    TooLong(u16),
+    #[error("options of this type aren't supported")]
+    Unsupported,
+}
+
+/// Choices suggested for the option being typed.
+#[derive(Debug)]
+struct Autocomplete {
+    nonce: String,
+    /// `None` until the application responded.
+    choices: Option<Vec<CommandOptionChoice>>,
+}
+
+/// Keeps track of the commands that can be used in every guild and private
+/// channel the current user opened, and the choices applications suggest
+/// while an option is typed.
+///
+/// Must be fed every dispatch event through [`CommandStore::update`].
+#[derive(Debug, Default)]
+pub struct CommandStore {
+    commands: HashMap<CommandScope, Commands>,
+    autocomplete: Option<Autocomplete>,
+}
+
+impl CommandStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => {
+                self.commands.clear();
+                self.autocomplete = None;
+            }
+            DispatchEvent::GuildDelete(guild) => {
+                self.commands.remove(&CommandScope::Guild(guild.id));
+            }
+            // applications were added or removed, the commands are fetched
+            // again when needed
+            DispatchEvent::GuildIntegrationsUpdate(update) => {
+                self.commands.remove(&CommandScope::Guild(update.guild_id));
+            }
+            DispatchEvent::ApplicationCommandAutocompleteResponse(response) => {
+                if let Some(autocomplete) = &mut self.autocomplete {
+                    if autocomplete.nonce == response.nonce {
+                        autocomplete.choices = Some(response.choices.clone());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn commands(&self, scope: CommandScope) -> Option<&Commands> {
+        self.commands.get(&scope)
+    }
+
+    /// Marks the commands of a scope as being fetched.
+    ///
+    /// Returns whether they must be fetched, `false` if they already were.
+    pub fn start_fetch(&mut self, scope: CommandScope) -> bool {
+        if self.commands.contains_key(&scope) {
+            return false;
+        }
+        self.commands.insert(scope, Commands::Loading);
+        true
+    }
+
+    /// Inserts the fetched commands of a scope, `None` if fetching them
+    /// failed.
+    pub fn insert_commands(&mut self, scope: CommandScope, index: Option<Box<CommandIndex>>) {
+        let commands = index.map_or(Commands::Failed, Commands::Loaded);
+        self.commands.insert(scope, commands);
+    }
+
+    /// Chat input commands and subcommands of a scope whose name contains
+    /// the query, the ones starting with it first.
+    pub fn search(&self, scope: CommandScope, query: &str) -> Vec<CommandEntry> {
+        let Some(Commands::Loaded(index)) = self.commands.get(&scope) else {
+            return Vec::new();
+        };
+        let query = query.trim().to_lowercase();
+
+        let mut entries = index
+            .application_commands
+            .iter()
+            .filter(|command| command.kind == CommandType::ChatInput)
+            .flat_map(|command| {
+                let application_name = index
+                    .applications
+                    .iter()
+                    .find(|application| Some(application.id) == command.application_id)
+                    .map(|application| application.name.clone());
+                entries(application_name, command)
+            })
+            .filter(|entry| entry.name().contains(&query))
+            .collect::<Vec<_>>();
+        entries.sort_by_cached_key(|entry| {
+            let name = entry.name();
+            (!name.starts_with(&query), name)
+        });
+
+        entries
+    }
+
+    /// Waits for the choices of the autocomplete interaction sent with the
+    /// given nonce, replacing the previous ones.
+    pub fn start_autocomplete(&mut self, nonce: String) {
+        self.autocomplete = Some(Autocomplete {
+            nonce,
+            choices: None,
+        });
+    }
+
+    /// Choices suggested for the option being typed, `None` while they're
+    /// being fetched or when no option is being typed.
+    pub fn autocomplete_choices(&self) -> Option<&[CommandOptionChoice]> {
+        self.autocomplete.as_ref()?.choices.as_deref()
+    }
+
+    pub fn clear_autocomplete(&mut self) {
+        self.autocomplete = None;
+    }
+}
+
+/// Entries of a command, one per subcommand if it has any.
+fn entries(application_name: Option<String>, command: &Command) -> Vec<CommandEntry> {
+    let entry = |path: Vec<String>, description: &str, options: &[CommandOption]| CommandEntry {
+        application_name: application_name.clone(),
+        command: command.clone(),
+        path,
+        description: description.to_owned(),
+        options: options.to_vec(),
+    };
+    let is_subcommand = |option: &CommandOption| {
+        matches!(
+            option.kind,
+            CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+        )
+    };
+
+    if !command.options.iter().any(is_subcommand) {
+        return vec![entry(Vec::new(), &command.description, &command.options)];
+    }
+
+    let mut entries = Vec::new();
+    for option in &command.options {
+        let options = option.options.as_deref().unwrap_or_default();
+        match option.kind {
+            CommandOptionType::SubCommand => {
+                entries.push(entry(
+                    vec![option.name.clone()],
+                    &option.description,
+                    options,
+                ));
+            }
+            CommandOptionType::SubCommandGroup => {
+                for subcommand in options {
+                    entries.push(entry(
+                        vec![option.name.clone(), subcommand.name.clone()],
+                        &subcommand.description,
+                        subcommand.options.as_deref().unwrap_or_default(),
+                    ));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    entries
+}
+
+/// Value to send for an option, `None` if it was left empty.
+pub fn parse_option(
+    option: &CommandOption,
+    input: Option<&OptionInput>,
+) -> Result<Option<CommandOptionValue>, OptionError> {
+    let input = match input {
+        Some(OptionInput::Text(text)) if text.trim().is_empty() => None,
+        input => input,
+    };
+    let Some(input) = input else {
+        return if option.required.unwrap_or_default() {
+            Err(OptionError::Missing)
+        } else {
+            Ok(None)
+        };
+    };
+
+    let value = match (option.kind, input) {
+        (CommandOptionType::String, OptionInput::Text(text)) => {
+            let length = text.chars().count();
+            if let Some(min_length) = option.min_length {
+                if length < usize::from(min_length) {
+                    return Err(OptionError::TooShort(min_length));
+                }
+            }
+            if let Some(max_length) = option.max_length {
+                if length > usize::from(max_length) {
+                    return Err(OptionError::TooLong(max_length));
+                }
+            }
+            CommandOptionValue::String(text.clone())
+        }
+        (CommandOptionType::Integer, OptionInput::Text(text)) => {
+            let integer = text
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| OptionError::NotInteger)?;
+            check_bounds(option, integer as f64)?;
+            CommandOptionValue::Integer(integer)
+        }
+        (CommandOptionType::Number, OptionInput::Text(text)) => {
+            let number = text
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .ok_or(OptionError::NotNumber)?;
+            check_bounds(option, number)?;
+            CommandOptionValue::Number(number)
+        }
+        (CommandOptionType::Boolean, OptionInput::Boolean(boolean)) => {
+            CommandOptionValue::Boolean(*boolean)
+        }
+        (CommandOptionType::User, OptionInput::Id(id)) => CommandOptionValue::User(id.cast()),
+        (CommandOptionType::Channel, OptionInput::Id(id)) => CommandOptionValue::Channel(id.cast()),
+        (CommandOptionType::Role, OptionInput::Id(id)) => CommandOptionValue::Role(id.cast()),
+        (CommandOptionType::Mentionable, OptionInput::Id(id)) => {
+            CommandOptionValue::Mentionable(*id)
+        }
+        _ => return Err(OptionError::Unsupported),
+    };
+
+    Ok(Some(value))
+}
+
+fn check_bounds(option: &CommandOption, value: f64) -> Result<(), OptionError> {
+    if let Some(min_value) = option.min_value {
+        if value < bound(min_value) {
+            return Err(OptionError::TooSmall(format_bound(min_value)));
+        }
+    }
+    if let Some(max_value) = option.max_value {
+        if value > bound(max_value) {
+            return Err(OptionError::TooLarge(format_bound(max_value)));
+        }
+    }
+
+    Ok(())
+}
+
+fn bound(value: BoundValue) -> f64 {
+    match value {
+        BoundValue::Integer(integer) => integer as f64,
+        BoundValue::Number(number) => number,
+    }
+}
+
+fn format_bound(value: BoundValue) -> String {
+    match value {
+        BoundValue::Integer(integer) => integer.to_string(),
+        BoundValue::Number(number) => number.to_string(),
+    }
+}
+
+/// Value of a choice as it's entered in the input of its option.
+pub fn choice_value(value: &CommandOptionChoiceValue) -> String {
+    match value {
+        CommandOptionChoiceValue::String(string) => string.clone(),
+        CommandOptionChoiceValue::Integer(integer) => integer.to_string(),
+        CommandOptionChoiceValue::Number(number) => number.to_string(),
+    }
+}
+
+/// Data of the interaction that runs a command with the entered options.
+///
+/// Returns `None` if any of the options has an invalid value.
+pub fn command_data(
+    entry: &CommandEntry,
+    inputs: &HashMap<String, OptionInput>,
+) -> Option<CommandData> {
+    let options = entry
+        .options
+        .iter()
+        .filter_map(|option| {
+            parse_option(option, inputs.get(&option.name))
+                .map(|value| {
+                    value.map(|value| CommandDataOption {
+                        name: option.name.clone(),
+                        value,
+                    })
+                })
+                .transpose()
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    data(entry, options)
+}
+
+/// Data of the interaction that asks the application of a command for
+/// choices of the option being typed.
+///
+/// The other options are included if they have a valid value.
+pub fn autocomplete_data(
+    entry: &CommandEntry,
+    inputs: &HashMap<String, OptionInput>,
+    focused: &str,
+) -> Option<CommandData> {
+    let options = entry
+        .options
+        .iter()
+        .filter_map(|option| {
+            let input = inputs.get(&option.name);
+            if option.name == focused {
+                let text = match input {
+                    Some(OptionInput::Text(text)) => text.clone(),
+                    _ => String::new(),
+                };
+                return Some(CommandDataOption {
+                    name: option.name.clone(),
+                    value: CommandOptionValue::Focused(text, option.kind),
+                });
+            }
+
+            let value = parse_option(option, input).ok().flatten()?;
+            Some(CommandDataOption {
+                name: option.name.clone(),
+                value,
+            })
+        })
+        .collect();
+
+    data(entry, options)
+}
+
+/// Nests options in the subcommand of an entry.
+fn data(entry: &CommandEntry, options: Vec<CommandDataOption>) -> Option<CommandData> {
+    let subcommand_index = entry.path.len().saturating_sub(1);
+    let options = entry
+        .path
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(options, |options, (index, name)| {
+            let value = if index == subcommand_index {
+                CommandOptionValue::SubCommand(options)
+            } else {
+                CommandOptionValue::SubCommandGroup(options)
+            };
+            vec![CommandDataOption {
+                name: name.clone(),
+                value,
+            }]
+        });
+
+    Some(CommandData {
+        guild_id: entry.command.guild_id,
+        id: entry.command.id?,
+        name: entry.command.name.clone(),
+        kind: CommandType::ChatInput,
+        options,
+        resolved: None,
+        target_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use twilight_model::{
+        application::{
+            command::{
+                Command, CommandIndex, CommandOption, CommandOptionChoice,
+                CommandOptionChoiceValue, CommandOptionType, CommandOptionValue as BoundValue,
+                CommandType,
+            },
+            interaction::application_command::{CommandDataOption, CommandOptionValue},
+        },
+        gateway::{
+            event::DispatchEvent, payload::incoming::ApplicationCommandAutocompleteResponse,
+        },
+        id::Id,
+    };
+
+    use super::{
+        autocomplete_data, choice_value, command_data, parse_option, CommandScope, CommandStore,
+        OptionError, OptionInput,
+    };
+
+    const SCOPE: CommandScope = CommandScope::Guild(Id::new(1));
+
+    fn option(name: &str, kind: CommandOptionType) -> CommandOption {
+        CommandOption {
+            autocomplete: None,
+            channel_types: None,
+            choices: None,
+            description: String::new(),
+            description_localizations: None,
+            kind,
+            max_length: None,
+            max_value: None,
+            min_length: None,
+            min_value: None,
+            name: name.to_owned(),
+            name_localizations: None,
+            options: None,
+            required: None,
+        }
+    }
+
+    fn command(name: &str, options: Vec<CommandOption>) -> Command {
+        Command {
+            application_id: Some(Id::new(2)),
+            default_member_permissions: None,
+            dm_permission: None,
+            description: String::new(),
+            description_localizations: None,
+            guild_id: None,
+            id: Some(Id::new(3)),
+            kind: CommandType::ChatInput,
+            name: name.to_owned(),
+            name_localizations: None,
+            nsfw: None,
+            options,
+            version: Id::new(4),
+        }
+    }
+
+    fn store(commands: Vec<Command>) -> CommandStore {
+        let mut store = CommandStore::new();
+        assert!(store.start_fetch(SCOPE));
+        assert!(!store.start_fetch(SCOPE));
+        store.insert_commands(
+            SCOPE,
+            Some(Box::new(CommandIndex {
+                applications: Vec::new(),
+                application_commands: commands,
+            })),
+        );
+
+        store
+    }
+
+    #[test]
+    fn search_subcommands() {
+        let role = command(
+            "role",
+            vec![CommandOption {
+                options: Some(vec![
+                    option("add", CommandOptionType::SubCommand),
+                    option("remove", CommandOptionType::SubCommand),
+                ]),
+                ..option("member", CommandOptionType::SubCommandGroup)
+            }],
+        );
+        let store = store(vec![
+            command("ban", Vec::new()),
+            command("autorole", Vec::new()),
+            role,
+        ]);
+
+        let names = |query| {
+            store
+                .search(SCOPE, query)
+                .iter()
+                .map(|entry| entry.name())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(""),
+            ["autorole", "ban", "role member add", "role member remove"]
+        );
+        assert_eq!(names("REM"), ["role member remove"]);
+        // names containing the query come after the ones starting with it
+        assert_eq!(
+            names("role"),
+            ["role member add", "role member remove", "autorole"]
+        );
+        assert!(store
+            .search(CommandScope::Channel(Id::new(1)), "")
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_bounds() {
+        let count = CommandOption {
+            min_value: Some(BoundValue::Integer(1)),
+            max_value: Some(BoundValue::Integer(10)),
+            required: Some(true),
+            ..option("count", CommandOptionType::Integer)
+        };
+        let text = |text: &str| OptionInput::Text(text.to_owned());
+
+        assert_eq!(
+            parse_option(&count, Some(&text("5"))),
+            Ok(Some(CommandOptionValue::Integer(5)))
+        );
+        assert_eq!(
+            parse_option(&count, Some(&text(" "))),
+            Err(OptionError::Missing)
+        );
+        assert_eq!(parse_option(&count, None), Err(OptionError::Missing));
+        assert_eq!(
+            parse_option(&count, Some(&text("1.5"))),
+            Err(OptionError::NotInteger)
+        );
+        assert_eq!(
+            parse_option(&count, Some(&text("11"))),
+            Err(OptionError::TooLarge("10".to_owned()))
+        );
+
+        let reason = CommandOption {
+            max_length: Some(3),
+            ..option("reason", CommandOptionType::String)
+        };
+        assert_eq!(parse_option(&reason, None), Ok(None));
+        assert_eq!(
+            parse_option(&reason, Some(&text("spam"))),
+            Err(OptionError::TooLong(3))
+        );
+        assert_eq!(
+            parse_option(&reason, Some(&OptionInput::Boolean(true))),
+            Err(OptionError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn options_nested_in_subcommand() {
+        let add = CommandOption {
+            options: Some(vec![
+                option("user", CommandOptionType::User),
+                option("note", CommandOptionType::String),
+            ]),
+            ..option("add", CommandOptionType::SubCommand)
+        };
+        let store = store(vec![command("role", vec![add])]);
+        let entry = &store.search(SCOPE, "role add")[0];
+        let inputs = HashMap::from([("user".to_owned(), OptionInput::Id(Id::new(5)))]);
+
+        let data = command_data(entry, &inputs).unwrap();
+        assert_eq!(data.id, Id::new(3));
+        assert_eq!(
+            data.options,
+            [CommandDataOption {
+                name: "add".to_owned(),
+                value: CommandOptionValue::SubCommand(vec![CommandDataOption {
+                    name: "user".to_owned(),
+                    value: CommandOptionValue::User(Id::new(5)),
+                }]),
+            }]
+        );
+
+        let data = autocomplete_data(entry, &inputs, "note").unwrap();
+        let CommandOptionValue::SubCommand(options) = &data.options[0].value else {
+            panic!("options aren't nested in the subcommand");
+        };
+        assert_eq!(
+            options[1].value,
+            CommandOptionValue::Focused(String::new(), CommandOptionType::String)
+        );
+    }
+
+    #[test]
+    fn required_options() {
+        let ban = command(
+            "ban",
+            vec![
+                CommandOption {
+                    required: Some(true),
+                    ..option("user", CommandOptionType::User)
+                },
+                option("reason", CommandOptionType::String),
+                option("days", CommandOptionType::Integer),
+            ],
+        );
+        let store = store(vec![ban]);
+        let entry = &store.search(SCOPE, "ban")[0];
+
+        // optional options can be left empty, required ones can't
+        let mut inputs = HashMap::from([("reason".to_owned(), OptionInput::Text(" ".to_owned()))]);
+        assert!(command_data(entry, &inputs).is_none());
+
+        inputs.insert("user".to_owned(), OptionInput::Id(Id::new(5)));
+        let data = command_data(entry, &inputs).unwrap();
+        assert_eq!(
+            data.options,
+            [CommandDataOption {
+                name: "user".to_owned(),
+                value: CommandOptionValue::User(Id::new(5)),
+            }]
+        );
+
+        // optional options that are filled in must be valid
+        inputs.insert("days".to_owned(), OptionInput::Text("a week".to_owned()));
+        assert!(command_data(entry, &inputs).is_none());
+        inputs.insert("days".to_owned(), OptionInput::Text("7".to_owned()));
+        let data = command_data(entry, &inputs).unwrap();
+        assert_eq!(data.options[1].value, CommandOptionValue::Integer(7));
+
+        // invalid options are left out while the application is asked for
+        // choices, even required ones
+        inputs.remove("user");
+        inputs.insert("days".to_owned(), OptionInput::Text("a week".to_owned()));
+        let data = autocomplete_data(entry, &inputs, "reason").unwrap();
+        assert_eq!(
+            data.options,
+            [CommandDataOption {
+                name: "reason".to_owned(),
+                value: CommandOptionValue::Focused(" ".to_owned(), CommandOptionType::String),
+            }]
+        );
+    }
+
+    #[test]
+    fn choices() {
+        let choice = |name: &str, value| CommandOptionChoice {
+            name: name.to_owned(),
+            name_localizations: None,
+            value,
+        };
+        let cases = [
+            (
+                CommandOptionType::String,
+                choice("Red", CommandOptionChoiceValue::String("red".to_owned())),
+                CommandOptionValue::String("red".to_owned()),
+            ),
+            (
+                CommandOptionType::Integer,
+                choice("Week", CommandOptionChoiceValue::Integer(7)),
+                CommandOptionValue::Integer(7),
+            ),
+            (
+                CommandOptionType::Number,
+                choice("Half", CommandOptionChoiceValue::Number(0.5)),
+                CommandOptionValue::Number(0.5),
+            ),
+        ];
+
+        // picked choices are entered as text and parsed like typed values
+        for (kind, choice, expected) in cases {
+            let option = CommandOption {
+                choices: Some(vec![choice.clone()]),
+                ..option("choice", kind)
+            };
+            let input = OptionInput::Text(choice_value(&choice.value));
+            assert_eq!(parse_option(&option, Some(&input)), Ok(Some(expected)));
+        }
+    }
+
+    #[test]
+    fn autocomplete_nonce() {
+        let response = |nonce: &str, name: &str| {
+            DispatchEvent::ApplicationCommandAutocompleteResponse(
+                ApplicationCommandAutocompleteResponse {
+                    choices: vec![CommandOptionChoice {
+                        name: name.to_owned(),
+                        name_localizations: None,
+                        value: CommandOptionChoiceValue::String(name.to_owned()),
+                    }],
+                    nonce: nonce.to_owned(),
+                },
+            )
+        };
+        let names = |store: &CommandStore| {
+            store.autocomplete_choices().map(|choices| {
+                choices
+                    .iter()
+                    .map(|choice| choice.name.clone())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        let mut store = CommandStore::new();
+        // responses nothing is waiting for are ignored
+        store.update(&response("1", "stale"));
+        assert_eq!(names(&store), None);
+
+        store.start_autocomplete("2".to_owned());
+        assert_eq!(names(&store), None);
+        store.update(&response("1", "stale"));
+        assert_eq!(names(&store), None);
+        store.update(&response("2", "fresh"));
+        assert_eq!(names(&store), Some(vec!["fresh".to_owned()]));
+
+        // the choices of the previous interaction are replaced
+        store.start_autocomplete("3".to_owned());
+        assert_eq!(names(&store), None);
+        store.update(&response("2", "stale"));
+        store.update(&response("3", "newer"));
+        assert_eq!(names(&store), Some(vec!["newer".to_owned()]));
+
+        store.clear_autocomplete();
+        store.update(&response("3", "newer"));
+        assert_eq!(names(&store), None);
+    }
+}
@@ -0,0 +1,545 @@
+use std::{collections::HashSet, ops::Range};
+
+use twilight_model::{
+    channel::{
+        message::{AllowedMentions, MentionType},
+        permission_overwrite::PermissionOverwrite,
+        Channel, ChannelType,
+    },
+    guild::{Emoji, Permissions},
+    id::{
+        marker::{ChannelMarker, EmojiMarker, GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    user::PremiumType,
+};
+
+use crate::{
+    cache::Cache,
+    emoji::{self, PickedEmoji, UnicodeEmoji},
+    role::RoleStore,
+};
+
+/// Suggestions offered at once.
+pub const SUGGESTION_LIMIT: usize = 10;
+/// Characters that must be typed after a `:` before emojis are suggested, as
+/// colons are common in regular text.
+const EMOJI_QUERY_MIN_LEN: usize = 2;
+
+/// What is being completed, depending on the character that was typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// Members and roles, after a `@`.
+    Mention,
+    /// Emojis, after a `:`.
+    Emoji,
+    /// Channels, after a `#`.
+    Channel,
+}
+
+/// The word being completed in the text of the composer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionQuery<'a> {
+    pub kind: CompletionKind,
+    /// What was typed after the trigger character.
+    pub query: &'a str,
+    /// Byte range of the word in the text, including the trigger character,
+    /// which is replaced by the picked suggestion.
+    pub range: Range<usize>,
+}
+
+/// Finds the word being completed, which is the one the cursor is placed at
+/// the end of.
+///
+/// `cursor` is a byte offset into `text`.
+pub fn completion_query(text: &str, cursor: usize) -> Option<CompletionQuery<'_>> {
+    let before = text.get(..cursor)?;
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    let word = &before[start..];
+
+    let mut chars = word.chars();
+    let kind = match chars.next()? {
+        '@' => CompletionKind::Mention,
+        ':' => CompletionKind::Emoji,
+        '#' => CompletionKind::Channel,
+        _ => return None,
+    };
+    let query = chars.as_str();
+
+    // a second colon ends the emoji, it was typed out
+    if kind == CompletionKind::Emoji
+        && (query.chars().count() < EMOJI_QUERY_MIN_LEN || query.contains(':'))
+    {
+        return None;
+    }
+
+    Some(CompletionQuery {
+        kind,
+        query,
+        range: start..cursor,
+    })
+}
+
+/// Something that can be inserted in place of the word being completed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suggestion {
+    Member {
+        user_id: Id<UserMarker>,
+        /// Nickname or global name of the member, if they have either.
+        display_name: Option<String>,
+        username: String,
+    },
+    Role {
+        role_id: Id<RoleMarker>,
+        name: String,
+        color: u32,
+    },
+    Everyone,
+    Here,
+    Channel {
+        channel_id: Id<ChannelMarker>,
+        name: String,
+        kind: ChannelType,
+    },
+    CustomEmoji {
+        emoji_id: Id<EmojiMarker>,
+        name: String,
+        animated: bool,
+    },
+    UnicodeEmoji(&'static UnicodeEmoji),
+}
+
+impl Suggestion {
+    /// Name the suggestion is shown with.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Member {
+                display_name,
+                username,
+                ..
+            } => display_name.as_deref().unwrap_or(username),
+            Self::Role { name, .. }
+            | Self::Channel { name, .. }
+            | Self::CustomEmoji { name, .. } => name,
+            Self::Everyone => "everyone",
+            Self::Here => "here",
+            Self::UnicodeEmoji(emoji) => emoji.name(),
+        }
+    }
+
+    /// The emoji to record the use of once the suggestion is picked.
+    pub fn emoji(&self) -> Option<PickedEmoji> {
+        match self {
+            Self::CustomEmoji {
+                emoji_id,
+                name,
+                animated,
+            } => Some(PickedEmoji::Custom {
+                id: *emoji_id,
+                name: name.clone(),
+                animated: *animated,
+            }),
+            Self::UnicodeEmoji(emoji) => Some(PickedEmoji::Unicode {
+                emoji: emoji.emoji.to_owned(),
+                name: emoji.name(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Text that replaces the word being completed, in the syntax Discord
+    /// expects on the wire.
+    pub fn replacement(&self) -> String {
+        match self {
+            Self::Member { user_id, .. } => format!("<@{user_id}>"),
+            Self::Role { role_id, .. } => format!("<@&{role_id}>"),
+            Self::Everyone => "@everyone".to_owned(),
+            Self::Here => "@here".to_owned(),
+            Self::Channel { channel_id, .. } => format!("<#{channel_id}>"),
+            Self::CustomEmoji { .. } | Self::UnicodeEmoji(_) => {
+                self.emoji().map(|emoji| emoji.text()).unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// How well a name matches a query, higher being better, `None` if it
+/// doesn't match at all.
+///
+/// Names starting with the query rank above the ones containing it, which
+/// rank above the ones containing its characters in order. Matching ignores
+/// case.
+pub fn fuzzy_score(name: &str, query: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+
+    if name.starts_with(&query) {
+        return Some(3);
+    }
+    if name.contains(&query) {
+        return Some(2);
+    }
+
+    let mut name_chars = name.chars();
+    query
+        .chars()
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+        .then_some(1)
+}
+
+/// Suggestions for the word being completed in a channel, the best matches
+/// first.
+pub fn complete(
+    cache: &Cache,
+    roles: &RoleStore,
+    channel_id: Id<ChannelMarker>,
+    query: &CompletionQuery<'_>,
+) -> Vec<Suggestion> {
+    let Some(channel) = cache.channel(channel_id) else {
+        return Vec::new();
+    };
+    let permissions = permissions_in(cache, roles, channel);
+
+    let candidates = match query.kind {
+        CompletionKind::Mention => mention_candidates(cache, roles, channel, permissions),
+        CompletionKind::Channel => channel_candidates(cache, roles, channel),
+        CompletionKind::Emoji => emoji_candidates(cache, roles, channel.guild_id),
+    };
+
+    let mut scored = candidates
+        .into_iter()
+        .filter_map(|(suggestion, names)| {
+            let score = names
+                .iter()
+                .filter_map(|name| fuzzy_score(name, query.query))
+                .max()?;
+            Some((score, suggestion))
+        })
+        .collect::<Vec<_>>();
+    // the sort is stable, equally good matches keep the order they were
+    // gathered in
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    scored
+        .into_iter()
+        .map(|(_, suggestion)| suggestion)
+        .take(SUGGESTION_LIMIT)
+        .collect()
+}
+
+/// Permissions of the current user in a channel, `None` outside of guilds.
+pub fn permissions_in(cache: &Cache, roles: &RoleStore, channel: &Channel) -> Option<Permissions> {
+    let guild_id = channel.guild_id?;
+    let user_id = cache.current_user()?.id;
+
+    Some(roles.permissions(guild_id, user_id, overwrites(cache, channel)))
+}
+
+/// Mentions the message being sent is allowed to make.
+///
+/// `@everyone` and `@here` only notify when the current user may mention
+/// them, `replied_user` is whether the author of the message being replied
+/// to is notified.
+pub fn allowed_mentions(replied_user: bool, mention_everyone: bool) -> AllowedMentions {
+    let mut parse = vec![MentionType::Roles, MentionType::Users];
+    if mention_everyone {
+        parse.push(MentionType::Everyone);
+    }
+
+    AllowedMentions {
+        parse,
+        replied_user,
+        ..AllowedMentions::default()
+    }
+}
+
+/// A suggestion along with the names it can be found by.
+type Candidate = (Suggestion, Vec<String>);
+
+/// Members who spoke in the channel recently come first, followed by the
+/// other members of the guild and its roles.
+fn mention_candidates(
+    cache: &Cache,
+    roles: &RoleStore,
+    channel: &Channel,
+    permissions: Option<Permissions>,
+) -> Vec<Candidate> {
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    let mut push_member =
+        |user_id, nick: Option<&str>, global_name: Option<&str>, username: &str| {
+            if !seen.insert(user_id) {
+                return;
+            }
+            let names = [nick, global_name, Some(username)]
+                .into_iter()
+                .flatten()
+                .map(str::to_owned)
+                .collect();
+            let suggestion = Suggestion::Member {
+                user_id,
+                display_name: nick.or(global_name).map(str::to_owned),
+                username: username.to_owned(),
+            };
+            candidates.push((suggestion, names));
+        };
+
+    for message in cache
+        .messages(channel.id)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+    {
+        let nick = message
+            .member
+            .as_ref()
+            .and_then(|member| member.nick.as_deref());
+        let author = &message.author;
+        push_member(author.id, nick, author.global_name.as_deref(), &author.name);
+    }
+
+    let Some(guild) = channel.guild_id.and_then(|guild_id| cache.guild(guild_id)) else {
+        for user in channel.recipients.iter().flatten() {
+            push_member(user.id, None, user.global_name.as_deref(), &user.name);
+        }
+        return candidates;
+    };
+
+    for member in &guild.members {
+        let user = &member.user;
+        push_member(
+            user.id,
+            member.nick.as_deref(),
+            user.global_name.as_deref(),
+            &user.name,
+        );
+    }
+
+    // mentioning @everyone allows mentioning roles that aren't mentionable
+    let mention_everyone =
+        permissions.is_some_and(|permissions| permissions.contains(Permissions::MENTION_EVERYONE));
+    candidates.extend(
+        roles
+            .guild_roles(guild.id)
+            .into_iter()
+            .filter(|role| role.id != guild.id.cast() && (role.mentionable || mention_everyone))
+            .map(|role| {
+                let suggestion = Suggestion::Role {
+                    role_id: role.id,
+                    name: role.name.clone(),
+                    color: role.color,
+                };
+                (suggestion, vec![role.name.clone()])
+            }),
+    );
+    if mention_everyone {
+        candidates.push((Suggestion::Everyone, vec!["everyone".to_owned()]));
+        candidates.push((Suggestion::Here, vec!["here".to_owned()]));
+    }
+
+    candidates
+}
+
+/// Channels of the guild the current user can see, in the order they're
+/// listed in.
+fn channel_candidates(cache: &Cache, roles: &RoleStore, channel: &Channel) -> Vec<Candidate> {
+    let Some(guild_id) = channel.guild_id else {
+        return Vec::new();
+    };
+
+    cache
+        .channel_groups(guild_id)
+        .into_iter()
+        .flat_map(|group| group.channels)
+        .filter(|channel| {
+            permissions_in(cache, roles, channel)
+                .is_some_and(|permissions| permissions.contains(Permissions::VIEW_CHANNEL))
+        })
+        .map(|channel| {
+            let name = channel.name.clone().unwrap_or_default();
+            let suggestion = Suggestion::Channel {
+                channel_id: channel.id,
+                name: name.clone(),
+                kind: channel.kind,
+            };
+            (suggestion, vec![name])
+        })
+        .collect()
+}
+
+/// Emojis of the current guild, followed by the Unicode ones and the emojis
+/// of other guilds, which can only be used with Nitro.
+fn emoji_candidates(
+    cache: &Cache,
+    roles: &RoleStore,
+    guild_id: Option<Id<GuildMarker>>,
+) -> Vec<Candidate> {
+    let Some(current_user) = cache.current_user() else {
+        return Vec::new();
+    };
+    let premium = current_user
+        .premium_type
+        .is_some_and(|premium_type| premium_type != PremiumType::None);
+
+    let usable = |emoji_guild_id: Id<GuildMarker>, emoji: &Emoji| {
+        let member_roles = roles
+            .member_roles(emoji_guild_id, current_user.id)
+            .unwrap_or_default();
+        emoji::usable(
+            emoji,
+            Some(emoji_guild_id) == guild_id,
+            premium,
+            member_roles,
+        )
+    };
+    let custom_emojis = |emoji_guild_id| {
+        cache
+            .guild(emoji_guild_id)
+            .into_iter()
+            .flat_map(|guild| &guild.emojis)
+            .filter(move |emoji| usable(emoji_guild_id, emoji))
+            .map(|emoji| {
+                let suggestion = Suggestion::CustomEmoji {
+                    emoji_id: emoji.id,
+                    name: emoji.name.clone(),
+                    animated: emoji.animated,
+                };
+                (suggestion, vec![emoji.name.clone()])
+            })
+    };
+
+    let mut candidates = guild_id
+        .into_iter()
+        .flat_map(custom_emojis)
+        .collect::<Vec<_>>();
+    candidates.extend(emoji::unicode_emojis().map(|emoji| {
+        let names = emoji.names.iter().map(|name| (*name).to_owned()).collect();
+        (Suggestion::UnicodeEmoji(emoji), names)
+    }));
+    if premium {
+        candidates.extend(
+            cache
+                .guilds()
+                .map(|guild| guild.id)
+                .filter(|id| Some(*id) != guild_id)
+                .flat_map(custom_emojis),
+        );
+    }
+
+    candidates
+}
+
+/// Permission overwrites that apply to a channel, threads use the ones of
+/// their parent.
+fn overwrites<'a>(cache: &'a Cache, channel: &'a Channel) -> &'a [PermissionOverwrite] {
+    let channel = if channel.kind.is_thread() {
+        match channel
+            .parent_id
+            .and_then(|parent_id| cache.channel(parent_id))
+        {
+            Some(parent) => parent,
+            None => return &[],
+        }
+    } else {
+        channel
+    };
+
+    channel.permission_overwrites.as_deref().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{channel::message::MentionType, id::Id};
+
+    use super::{
+        allowed_mentions, completion_query, fuzzy_score, CompletionKind, CompletionQuery,
+        Suggestion,
+    };
+    use crate::emoji;
+
+    #[test]
+    fn query_at_cursor() {
+        assert_eq!(
+            completion_query("hey @fer", 8),
+            Some(CompletionQuery {
+                kind: CompletionKind::Mention,
+                query: "fer",
+                range: 4..8,
+            })
+        );
+        assert_eq!(
+            completion_query("#gen and more", 4),
+            Some(CompletionQuery {
+                kind: CompletionKind::Channel,
+                query: "gen",
+                range: 0..4,
+            })
+        );
+        // the cursor must be at the end of the word
+        assert_eq!(completion_query("@ferris ", 8), None);
+        // triggers only start words
+        assert_eq!(completion_query("mail@example", 12), None);
+        // emojis need a couple of characters and aren't completed once closed
+        assert_eq!(completion_query("ok :c", 5), None);
+        assert_eq!(completion_query(":crab:", 6), None);
+        assert_eq!(
+            completion_query("é :cr", 6).map(|query| query.range),
+            Some(3..6)
+        );
+        // not at a character boundary
+        assert_eq!(completion_query("é", 1), None);
+    }
+
+    #[test]
+    fn fuzzy_ranking() {
+        assert_eq!(fuzzy_score("Ferris", "fer"), Some(3));
+        assert_eq!(fuzzy_score("the ferris", "FER"), Some(2));
+        assert_eq!(fuzzy_score("fast ferret", "fft"), Some(1));
+        assert_eq!(fuzzy_score("ferris", "rf"), None);
+        assert_eq!(fuzzy_score("anything", ""), Some(3));
+    }
+
+    #[test]
+    fn wire_syntax() {
+        let member = Suggestion::Member {
+            user_id: Id::new(1),
+            display_name: None,
+            username: "ferris".to_owned(),
+        };
+        assert_eq!(member.replacement(), "<@1>");
+        assert_eq!(member.name(), "ferris");
+
+        let role = Suggestion::Role {
+            role_id: Id::new(2),
+            name: "crabs".to_owned(),
+            color: 0,
+        };
+        assert_eq!(role.replacement(), "<@&2>");
+
+        let emoji = |animated| Suggestion::CustomEmoji {
+            emoji_id: Id::new(3),
+            name: "ferris".to_owned(),
+            animated,
+        };
+        assert_eq!(emoji(false).replacement(), "<:ferris:3>");
+        assert_eq!(emoji(true).replacement(), "<a:ferris:3>");
+
+        let unicode = Suggestion::UnicodeEmoji(emoji::by_name("crab").unwrap());
+        assert_eq!(unicode.replacement(), "🦀");
+    }
+
+    #[test]
+    fn everyone_only_with_permission() {
+        let mentions = allowed_mentions(true, false);
+        assert!(mentions.replied_user);
+        assert!(!mentions.parse.contains(&MentionType::Everyone));
+
+        assert!(allowed_mentions(false, true)
+            .parse
+            .contains(&MentionType::Everyone));
+    }
+}
@@ -0,0 +1,752 @@
+//! Desktop notifications through the freedesktop notification service, which
+//! is reached over the D-Bus session bus.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    env,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use futures_util::{future, stream, stream::BoxStream, StreamExt};
+use tokio::{fs, sync::Mutex};
+use tracing::warn;
+use twilight_model::{
+    channel::{ChannelType, Message},
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+};
+use zbus::{dbus_proxy, zvariant::Value, Connection};
+
+use crate::{
+    cache::{private_channel_name, Cache},
+    cdn,
+    markdown::{self, Inline},
+    message::MessageLink,
+};
+
+const APP_NAME: &str = "Fusioncord";
+/// Action invoked when the notification itself is clicked.
+const DEFAULT_ACTION: &str = "default";
+/// Maximum amount of characters of a message shown in a notification.
+const MAX_BODY_LEN: usize = 200;
+
+enum Signal {
+    Action(u32, String),
+    Closed(u32),
+}
+
+type Signals = BoxStream<'static, Signal>;
+
+#[dbus_proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Notifications {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+
+    fn close_notification(&self, id: u32) -> zbus::Result<()>;
+
+    #[dbus_proxy(signal)]
+    fn action_invoked(&self, id: u32, action_key: &str) -> zbus::Result<()>;
+
+    #[dbus_proxy(signal)]
+    fn notification_closed(&self, id: u32, reason: u32) -> zbus::Result<()>;
+}
+
+/// Content of the notification of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopNotification {
+    /// Message the notification is shown for, opened when it's clicked.
+    pub link: MessageLink,
+    /// The author followed by the channel and guild the message was sent in.
+    pub title: String,
+    /// The message without formatting.
+    pub body: String,
+    /// URL of the avatar of the author.
+    pub icon_url: Option<String>,
+}
+
+impl DesktopNotification {
+    pub fn new(cache: &Cache, message: &Message) -> Self {
+        let author = message
+            .member
+            .as_ref()
+            .and_then(|member| member.nick.as_deref())
+            .or(message.author.global_name.as_deref())
+            .unwrap_or(&message.author.name);
+
+        let channel = cache.channel(message.channel_id);
+        let title = match (channel, message.guild_id.and_then(|id| cache.guild(id))) {
+            (Some(channel), _) if channel.kind == ChannelType::Group => {
+                format!("{author} ({})", private_channel_name(channel))
+            }
+            (Some(channel), Some(guild)) => format!(
+                "{author} (#{}, {})",
+                channel.name.as_deref().unwrap_or_default(),
+                guild.name
+            ),
+            _ => author.to_owned(),
+        };
+
+        let icon_url = Some(match message.author.avatar {
+            Some(avatar) => cdn::user_avatar(message.author.id, avatar, 64),
+            None => cdn::default_user_avatar(message.author.id, message.author.discriminator),
+        });
+
+        Self {
+            link: MessageLink {
+                guild_id: message.guild_id,
+                channel_id: message.channel_id,
+                message_id: message.id,
+            },
+            title,
+            body: body(cache, message),
+            icon_url,
+        }
+    }
+}
+
+/// The notification shown for a channel.
+#[derive(Debug, Clone, Copy)]
+struct Shown {
+    id: u32,
+    link: MessageLink,
+    /// Amount of messages the notification was shown for.
+    count: u32,
+}
+
+/// Shows notifications for messages, replacing the notification of a
+/// channel when another message is sent in it rather than stacking them.
+#[derive(Debug)]
+pub struct DesktopNotifier {
+    proxy: NotificationsProxy<'static>,
+    shown: Mutex<HashMap<Id<ChannelMarker>, Shown>>,
+}
+
+impl DesktopNotifier {
+    /// Connects to the notification service of the session bus.
+    pub async fn session() -> zbus::Result<Self> {
+        Self::new(&Connection::session().await?).await
+    }
+
+    pub async fn new(connection: &Connection) -> zbus::Result<Self> {
+        Ok(Self {
+            proxy: NotificationsProxy::new(connection).await?,
+            shown: Mutex::default(),
+        })
+    }
+
+    pub async fn notify(&self, notification: DesktopNotification) -> zbus::Result<()> {
+        let icon = match &notification.icon_url {
+            Some(url) => cached_icon(url).await,
+            None => None,
+        };
+
+        // held until the notification is shown so that notifications of the
+        // same channel are always replaced in order
+        let mut shown = self.shown.lock().await;
+        let previous = shown.get(&notification.link.channel_id).copied();
+        let count = previous.map_or(1, |previous| previous.count + 1);
+
+        let summary = if count > 1 {
+            format!("{} ({count} messages)", notification.title)
+        } else {
+            notification.title
+        };
+        let icon = icon
+            .map(|path| format!("file://{}", path.display()))
+            .unwrap_or_default();
+        let hints = HashMap::from([("category", Value::from("im.received"))]);
+
+        let id = self
+            .proxy
+            .notify(
+                APP_NAME,
+                previous.map_or(0, |previous| previous.id),
+                &icon,
+                &summary,
+                &escape_markup(&notification.body),
+                &[DEFAULT_ACTION, "Open"],
+                hints,
+                -1,
+            )
+            .await?;
+
+        shown.insert(
+            notification.link.channel_id,
+            Shown {
+                id,
+                link: notification.link,
+                count,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Closes the notification of a channel, such as once it's read.
+    pub async fn clear(&self, channel_id: Id<ChannelMarker>) -> zbus::Result<()> {
+        let Some(shown) = self.shown.lock().await.remove(&channel_id) else {
+            return Ok(());
+        };
+
+        self.proxy.close_notification(shown.id).await
+    }
+
+    /// Calls `on_click` with the message of every notification that is
+    /// clicked, until the connection is closed.
+    pub async fn listen(&self, on_click: impl FnMut(MessageLink)) -> zbus::Result<()> {
+        let signals = self.subscribe().await?;
+        self.handle_signals(signals, on_click, |_| ()).await;
+        Ok(())
+    }
+
+    /// Subscribes to the clicks and closings of notifications, signals sent
+    /// before that are missed.
+    async fn subscribe(&self) -> zbus::Result<Signals> {
+        let actions = self
+            .proxy
+            .receive_action_invoked()
+            .await?
+            .filter_map(|signal| {
+                let args = signal.args().ok();
+                future::ready(args.map(|args| Signal::Action(args.id, args.action_key.to_owned())))
+            });
+        let closed = self
+            .proxy
+            .receive_notification_closed()
+            .await?
+            .filter_map(|signal| {
+                future::ready(signal.args().ok().map(|args| Signal::Closed(args.id)))
+            });
+
+        Ok(stream::select(actions, closed).boxed())
+    }
+
+    /// Handles signals until the connection is closed, calling `on_closed`
+    /// with the id of every notification that was closed.
+    async fn handle_signals(
+        &self,
+        mut signals: Signals,
+        mut on_click: impl FnMut(MessageLink),
+        mut on_closed: impl FnMut(u32),
+    ) {
+        while let Some(signal) = signals.next().await {
+            let mut shown = self.shown.lock().await;
+
+            match signal {
+                Signal::Action(id, action) => {
+                    let link = shown.values().find(|shown| shown.id == id).map(|s| s.link);
+                    if let (Some(link), DEFAULT_ACTION) = (link, action.as_str()) {
+                        shown.remove(&link.channel_id);
+                        drop(shown);
+                        on_click(link);
+                    }
+                }
+                // the next message of the channel gets a new notification
+                Signal::Closed(id) => {
+                    shown.retain(|_, shown| shown.id != id);
+                    drop(shown);
+                    on_closed(id);
+                }
+            }
+        }
+    }
+}
+
+/// Text of a message without formatting, shortened to fit a notification.
+fn body(cache: &Cache, message: &Message) -> String {
+    let mention_name = |inline: &Inline| match *inline {
+        Inline::UserMention(user_id) => user_name(cache, message, user_id),
+        Inline::RoleMention(role_id) => message
+            .guild_id
+            .and_then(|guild_id| cache.guild(guild_id))
+            .and_then(|guild| guild.roles.iter().find(|role| role.id == role_id))
+            .map(|role| role.name.clone()),
+        Inline::ChannelMention(channel_id) => cache
+            .channel(channel_id)
+            .and_then(|channel| channel.name.clone()),
+        _ => None,
+    };
+
+    let mut body = markdown::plain_text(&markdown::parse(&message.content), &mention_name);
+    if body.is_empty() {
+        body = match message.attachments.len() {
+            0 if !message.sticker_items.is_empty() => "Sent a sticker".to_owned(),
+            0 => String::new(),
+            1 => "Sent an attachment".to_owned(),
+            count => format!("Sent {count} attachments"),
+        };
+    }
+
+    if let Some((index, _)) = body.char_indices().nth(MAX_BODY_LEN) {
+        body.truncate(index);
+        body.push('…');
+    }
+
+    body
+}
+
+/// Name of a user mentioned in a message, from the mentions sent along with
+/// it or the members of its guild.
+fn user_name(cache: &Cache, message: &Message, user_id: Id<UserMarker>) -> Option<String> {
+    if let Some(mention) = message
+        .mentions
+        .iter()
+        .find(|mention| mention.id == user_id)
+    {
+        let nick = mention
+            .member
+            .as_ref()
+            .and_then(|member| member.nick.clone());
+        return Some(nick.unwrap_or_else(|| mention.name.clone()));
+    }
+
+    let guild = message
+        .guild_id
+        .and_then(|guild_id| cache.guild(guild_id))?;
+    let member = guild
+        .members
+        .iter()
+        .find(|member| member.user.id == user_id)?;
+
+    Some(
+        member
+            .nick
+            .clone()
+            .or_else(|| member.user.global_name.clone())
+            .unwrap_or_else(|| member.user.name.clone()),
+    )
+}
+
+/// Escapes the characters notification services may interpret as markup.
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Downloads an image to the cache directory, notification services only
+/// accept local icons.
+async fn cached_icon(url: &str) -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?
+        .join("fusioncord")
+        .join("icons");
+
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    let path = dir.join(format!("{:016x}.png", hasher.finish()));
+    if fs::try_exists(&path).await.unwrap_or(false) {
+        return Some(path);
+    }
+
+    let download = async {
+        let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+        fs::create_dir_all(&dir).await?;
+        fs::write(&path, bytes).await?;
+
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+    };
+    match download.await {
+        Ok(()) => Some(path),
+        Err(e) => {
+            warn!("Failed to download notification icon {url}: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use serde_json::json;
+    use tokio::{net::UnixStream, sync::mpsc, time};
+    use twilight_model::{guild::Guild, id::Id};
+    use zbus::{
+        dbus_interface, zvariant::OwnedValue, Connection, ConnectionBuilder, Guid, SignalContext,
+    };
+
+    use super::{body, escape_markup, DesktopNotification, DesktopNotifier};
+    use crate::{
+        cache::Cache,
+        fixtures::{self, CHANNEL_ID, GUILD_ID},
+        message::MessageLink,
+    };
+
+    const PATH: &str = "/org/freedesktop/Notifications";
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Shown {
+        replaces_id: u32,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+    }
+
+    /// Stand-in for the notification service of a desktop session.
+    #[derive(Default)]
+    struct FakeNotifications {
+        shown: Arc<Mutex<Vec<Shown>>>,
+        closed: Arc<Mutex<Vec<u32>>>,
+        last_id: u32,
+    }
+
+    #[dbus_interface(name = "org.freedesktop.Notifications")]
+    impl FakeNotifications {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &mut self,
+            _app_name: String,
+            replaces_id: u32,
+            _app_icon: String,
+            summary: String,
+            body: String,
+            actions: Vec<String>,
+            _hints: HashMap<String, OwnedValue>,
+            _expire_timeout: i32,
+        ) -> u32 {
+            self.shown.lock().unwrap().push(Shown {
+                replaces_id,
+                summary,
+                body,
+                actions,
+            });
+
+            if replaces_id != 0 {
+                return replaces_id;
+            }
+            self.last_id += 1;
+            self.last_id
+        }
+
+        fn close_notification(&mut self, id: u32) {
+            self.closed.lock().unwrap().push(id);
+        }
+
+        #[dbus_interface(signal)]
+        async fn action_invoked(
+            ctxt: &SignalContext<'_>,
+            id: u32,
+            action_key: &str,
+        ) -> zbus::Result<()>;
+
+        #[dbus_interface(signal)]
+        async fn notification_closed(
+            ctxt: &SignalContext<'_>,
+            id: u32,
+            reason: u32,
+        ) -> zbus::Result<()>;
+    }
+
+    struct Session {
+        service: Connection,
+        notifier: Arc<DesktopNotifier>,
+        shown: Arc<Mutex<Vec<Shown>>>,
+        closed: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl Session {
+        /// Connects a notifier to a stand-in service over a private
+        /// connection.
+        async fn new() -> Self {
+            let service = FakeNotifications::default();
+            let shown = Arc::clone(&service.shown);
+            let closed = Arc::clone(&service.closed);
+
+            let (server, client) = UnixStream::pair().unwrap();
+            let guid = Guid::generate();
+            let server = ConnectionBuilder::unix_stream(server)
+                .server(&guid)
+                .p2p()
+                .serve_at(PATH, service)
+                .unwrap()
+                .build();
+            let client = ConnectionBuilder::unix_stream(client).p2p().build();
+            let (service, client) = tokio::try_join!(server, client).unwrap();
+
+            Self {
+                service,
+                notifier: Arc::new(DesktopNotifier::new(&client).await.unwrap()),
+                shown,
+                closed,
+            }
+        }
+
+        fn shown(&self) -> Vec<Shown> {
+            self.shown.lock().unwrap().clone()
+        }
+
+        async fn emit_action(&self, id: u32, action_key: &str) {
+            let ctxt = SignalContext::new(&self.service, PATH).unwrap();
+            FakeNotifications::action_invoked(&ctxt, id, action_key)
+                .await
+                .unwrap();
+        }
+
+        async fn emit_closed(&self, id: u32) {
+            let ctxt = SignalContext::new(&self.service, PATH).unwrap();
+            FakeNotifications::notification_closed(&ctxt, id, 2)
+                .await
+                .unwrap();
+        }
+    }
+
+    fn notification(channel_id: u64, message_id: u64, body: &str) -> DesktopNotification {
+        DesktopNotification {
+            link: MessageLink {
+                guild_id: Some(Id::new(1)),
+                channel_id: Id::new(channel_id),
+                message_id: Id::new(message_id),
+            },
+            title: "foo (#general, bar)".to_owned(),
+            body: body.to_owned(),
+            icon_url: None,
+        }
+    }
+
+    /// Listens for clicks on the notifications of a session.
+    /// Listens to the signals of the session, returning the clicked messages
+    /// and closed notifications.
+    async fn listen(
+        session: &Session,
+    ) -> (
+        mpsc::UnboundedReceiver<MessageLink>,
+        mpsc::UnboundedReceiver<u32>,
+    ) {
+        let (click_tx, click_rx) = mpsc::unbounded_channel();
+        let (closed_tx, closed_rx) = mpsc::unbounded_channel();
+        let notifier = Arc::clone(&session.notifier);
+        // subscribed to before returning, the signals emitted next are seen
+        let signals = notifier.subscribe().await.unwrap();
+        tokio::spawn(async move {
+            notifier
+                .handle_signals(
+                    signals,
+                    |link| {
+                        let _ = click_tx.send(link);
+                    },
+                    |id| {
+                        let _ = closed_tx.send(id);
+                    },
+                )
+                .await
+        });
+
+        (click_rx, closed_rx)
+    }
+
+    /// Guild with a channel, a role and a member, as sent in READY.
+    fn guild() -> Guild {
+        serde_json::from_value(json!({
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "channels": [{
+                "id": CHANNEL_ID.to_string(),
+                "name": "general",
+                "position": 0,
+                "type": 0,
+            }],
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "features": [],
+            "icon": null,
+            "id": GUILD_ID.to_string(),
+            "members": [{
+                "deaf": false,
+                "flags": 0,
+                "joined_at": "2023-11-14T22:13:20+00:00",
+                "mute": false,
+                "nick": null,
+                "roles": [],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0",
+                    "id": "4",
+                    "username": "member",
+                },
+            }],
+            "mfa_level": 0,
+            "name": "guild",
+            "nsfw_level": 0,
+            "owner_id": "4",
+            "preferred_locale": "en-US",
+            "premium_progress_bar_enabled": false,
+            "premium_tier": 0,
+            "roles": [{
+                "color": 0,
+                "flags": 0,
+                "hoist": false,
+                "id": "30",
+                "managed": false,
+                "mentionable": true,
+                "name": "mods",
+                "permissions": "0",
+                "position": 1,
+            }],
+            "splash": null,
+            "system_channel_flags": 0,
+            "system_channel_id": null,
+            "verification_level": 0,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn notify() {
+        let session = Session::new().await;
+        session
+            .notifier
+            .notify(notification(2, 3, "<b>hi</b> & bye"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            session.shown(),
+            [Shown {
+                replaces_id: 0,
+                summary: "foo (#general, bar)".to_owned(),
+                body: "&lt;b&gt;hi&lt;/b&gt; &amp; bye".to_owned(),
+                actions: vec!["default".to_owned(), "Open".to_owned()],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn collapse_per_channel() {
+        let session = Session::new().await;
+        let notifier = &session.notifier;
+        notifier.notify(notification(2, 3, "first")).await.unwrap();
+        notifier.notify(notification(2, 4, "second")).await.unwrap();
+        notifier.notify(notification(5, 6, "other")).await.unwrap();
+
+        let shown = session.shown();
+        assert_eq!(shown[1].replaces_id, 1);
+        assert_eq!(shown[1].summary, "foo (#general, bar) (2 messages)");
+        assert_eq!(shown[1].body, "second");
+        assert_eq!(shown[2].replaces_id, 0);
+
+        notifier.clear(Id::new(2)).await.unwrap();
+        assert_eq!(*session.closed.lock().unwrap(), [1]);
+
+        notifier.notify(notification(2, 7, "third")).await.unwrap();
+        assert_eq!(session.shown()[3].replaces_id, 0);
+    }
+
+    #[tokio::test]
+    async fn click() {
+        let session = Session::new().await;
+        let (mut clicks, _closed) = listen(&session).await;
+
+        session
+            .notifier
+            .notify(notification(2, 3, "first"))
+            .await
+            .unwrap();
+        session
+            .notifier
+            .notify(notification(2, 4, "second"))
+            .await
+            .unwrap();
+        session.emit_action(1, "Open").await;
+        session.emit_action(1, "default").await;
+
+        let link = time::timeout(Duration::from_secs(5), clicks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.channel_id, Id::new(2));
+        assert_eq!(link.message_id, Id::new(4));
+        assert!(clicks.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn closed_by_user() {
+        let session = Session::new().await;
+        let (_clicks, mut closed) = listen(&session).await;
+
+        let notifier = &session.notifier;
+        notifier.notify(notification(2, 3, "first")).await.unwrap();
+        session.emit_closed(1).await;
+        let id = time::timeout(Duration::from_secs(5), closed.recv())
+            .await
+            .unwrap();
+        assert_eq!(id, Some(1));
+
+        // the next message of the channel isn't collapsed with the closed one
+        notifier.notify(notification(2, 4, "second")).await.unwrap();
+        assert_eq!(session.shown()[1].replaces_id, 0);
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            escape_markup("a < b && c > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+    }
+
+    #[test]
+    fn body_text() {
+        let mut cache = Cache::new();
+        cache.update(&fixtures::ready());
+        cache.insert_guilds([guild()]);
+
+        // user 3 is named by the mention sent along, user 4 is a member and
+        // user 5, role 31 and channel 21 aren't known
+        let message = fixtures::message(
+            1,
+            &json!({
+                "content": "<@3> <@4> <@5> <@&30> <@&31> <#20> <#21>\n||secret|| text",
+                "mentions": [{
+                    "avatar": null,
+                    "discriminator": "0",
+                    "id": "3",
+                    "member": {
+                        "deaf": false,
+                        "flags": 0,
+                        "joined_at": "2023-11-14T22:13:20+00:00",
+                        "mute": false,
+                        "nick": "nick",
+                        "roles": [],
+                    },
+                    "public_flags": 0,
+                    "username": "mentioned",
+                }],
+            }),
+        );
+        assert_eq!(
+            body(&cache, &message),
+            "@nick @member @user @mods @role #general #channel\n▮▮▮ text"
+        );
+    }
+}
@@ -0,0 +1,765 @@
+//! Unicode emojis along with the shortcodes Discord knows them by.
+//!
+//! Only the commonly used emojis are listed, their skin tone variants are
+//! derived from them.
+
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+    channel::message::ReactionType,
+    guild::Emoji,
+    id::{
+        marker::{EmojiMarker, RoleMarker},
+        Id,
+    },
+};
+
+/// A Unicode emoji and its shortcodes, the first one being the name Discord
+/// shows for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnicodeEmoji {
+    pub emoji: &'static str,
+    pub names: &'static [&'static str],
+    /// Whether the emoji can be shown in other skin tones.
+    pub skin_tones: bool,
+}
+
+impl UnicodeEmoji {
+    const fn new(emoji: &'static str, names: &'static [&'static str]) -> Self {
+        Self {
+            emoji,
+            names,
+            skin_tones: false,
+        }
+    }
+
+    const fn toned(emoji: &'static str, names: &'static [&'static str]) -> Self {
+        Self {
+            emoji,
+            names,
+            skin_tones: true,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.names[0]
+    }
+
+    /// The emoji in a skin tone, unchanged if it can't be shown in one.
+    pub fn with_skin_tone(&self, skin_tone: SkinTone) -> String {
+        let Some(modifier) = skin_tone.modifier().filter(|_| self.skin_tones) else {
+            return self.emoji.to_owned();
+        };
+
+        // the modifier follows the first character, replacing the variation
+        // selector that may follow it
+        let mut chars = self.emoji.chars();
+        let mut toned = chars.next().into_iter().collect::<String>();
+        toned.push(modifier);
+        toned.extend(chars.filter(|c| *c != VARIATION_SELECTOR));
+
+        toned
+    }
+}
+
+/// Requests the emoji presentation of the character it follows.
+const VARIATION_SELECTOR: char = '\u{fe0f}';
+
+/// Skin tone of the emojis of people and hands, picked in the picker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkinTone {
+    /// The yellow skin tone emojis are shown in without a modifier.
+    #[default]
+    Default,
+    Light,
+    MediumLight,
+    Medium,
+    MediumDark,
+    Dark,
+}
+
+impl SkinTone {
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::Light,
+        Self::MediumLight,
+        Self::Medium,
+        Self::MediumDark,
+        Self::Dark,
+    ];
+
+    /// The Fitzpatrick modifier of the skin tone.
+    pub const fn modifier(self) -> Option<char> {
+        match self {
+            Self::Default => None,
+            Self::Light => Some('\u{1f3fb}'),
+            Self::MediumLight => Some('\u{1f3fc}'),
+            Self::Medium => Some('\u{1f3fd}'),
+            Self::MediumDark => Some('\u{1f3fe}'),
+            Self::Dark => Some('\u{1f3ff}'),
+        }
+    }
+}
+
+/// An emoji picked to be sent in a message or reacted with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickedEmoji {
+    Unicode {
+        /// The emoji in the picked skin tone.
+        emoji: String,
+        name: &'static str,
+    },
+    Custom {
+        id: Id<EmojiMarker>,
+        name: String,
+        animated: bool,
+    },
+}
+
+impl PickedEmoji {
+    pub fn custom(emoji: &Emoji) -> Self {
+        Self::Custom {
+            id: emoji.id,
+            name: emoji.name.clone(),
+            animated: emoji.animated,
+        }
+    }
+
+    /// The emoji in the syntax of message contents.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Unicode { emoji, .. } => emoji.clone(),
+            Self::Custom {
+                id,
+                name,
+                animated: true,
+            } => format!("<a:{name}:{id}>"),
+            Self::Custom {
+                id,
+                name,
+                animated: false,
+            } => format!("<:{name}:{id}>"),
+        }
+    }
+
+    pub fn reaction_type(&self) -> ReactionType {
+        match self {
+            Self::Unicode { emoji, .. } => ReactionType::Unicode {
+                name: emoji.clone(),
+            },
+            Self::Custom { id, name, animated } => ReactionType::Custom {
+                animated: *animated,
+                id: *id,
+                name: Some(name.clone()),
+            },
+        }
+    }
+
+    /// Key of the emoji in the frecency settings, the name of Unicode emojis
+    /// regardless of their skin tone and the id of custom ones.
+    pub fn frecency_key(&self) -> String {
+        match self {
+            Self::Unicode { name, .. } => (*name).to_owned(),
+            Self::Custom { id, .. } => id.to_string(),
+        }
+    }
+}
+
+/// Whether the current user can use a custom emoji, either in the guild it
+/// belongs to or elsewhere.
+///
+/// Emojis can be restricted to members with certain roles, and outside of
+/// their guild or when animated they require Nitro.
+pub fn usable(
+    emoji: &Emoji,
+    in_own_guild: bool,
+    premium: bool,
+    member_roles: &[Id<RoleMarker>],
+) -> bool {
+    emoji.available
+        && (premium || (in_own_guild && !emoji.animated))
+        && (emoji.roles.is_empty() || emoji.roles.iter().any(|id| member_roles.contains(id)))
+}
+
+/// A group of related emojis, as they're split up in the picker.
+#[derive(Debug)]
+pub struct EmojiCategory {
+    pub name: &'static str,
+    pub emojis: &'static [UnicodeEmoji],
+}
+
+pub const CATEGORIES: &[EmojiCategory] = &[
+    EmojiCategory {
+        name: "People",
+        emojis: PEOPLE,
+    },
+    EmojiCategory {
+        name: "Nature",
+        emojis: NATURE,
+    },
+    EmojiCategory {
+        name: "Food",
+        emojis: FOOD,
+    },
+    EmojiCategory {
+        name: "Activities",
+        emojis: ACTIVITIES,
+    },
+    EmojiCategory {
+        name: "Travel",
+        emojis: TRAVEL,
+    },
+    EmojiCategory {
+        name: "Objects",
+        emojis: OBJECTS,
+    },
+    EmojiCategory {
+        name: "Symbols",
+        emojis: SYMBOLS,
+    },
+    EmojiCategory {
+        name: "Flags",
+        emojis: FLAGS,
+    },
+];
+
+/// Every known Unicode emoji, in the order of their categories.
+pub fn unicode_emojis() -> impl Iterator<Item = &'static UnicodeEmoji> {
+    CATEGORIES.iter().flat_map(|category| category.emojis)
+}
+
+/// Finds a Unicode emoji by one of its shortcodes, without the colons.
+pub fn by_name(name: &str) -> Option<&'static UnicodeEmoji> {
+    unicode_emojis().find(|emoji| emoji.names.contains(&name))
+}
+
+/// Finds a Unicode emoji by its characters.
+pub fn by_emoji(emoji: &str) -> Option<&'static UnicodeEmoji> {
+    unicode_emojis().find(|unicode_emoji| unicode_emoji.emoji == emoji)
+}
+
+const PEOPLE: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("😀", &["grinning"]),
+    UnicodeEmoji::new("😃", &["smiley"]),
+    UnicodeEmoji::new("😄", &["smile"]),
+    UnicodeEmoji::new("😁", &["grin"]),
+    UnicodeEmoji::new("😆", &["laughing", "satisfied"]),
+    UnicodeEmoji::new("😅", &["sweat_smile"]),
+    UnicodeEmoji::new("😂", &["joy"]),
+    UnicodeEmoji::new("🤣", &["rofl", "rolling_on_the_floor_laughing"]),
+    UnicodeEmoji::new("🙂", &["slight_smile", "slightly_smiling_face"]),
+    UnicodeEmoji::new("🙃", &["upside_down", "upside_down_face"]),
+    UnicodeEmoji::new("😉", &["wink"]),
+    UnicodeEmoji::new("😊", &["blush"]),
+    UnicodeEmoji::new("😇", &["innocent"]),
+    UnicodeEmoji::new("🥰", &["smiling_face_with_3_hearts"]),
+    UnicodeEmoji::new("😍", &["heart_eyes"]),
+    UnicodeEmoji::new("🤩", &["star_struck"]),
+    UnicodeEmoji::new("😘", &["kissing_heart"]),
+    UnicodeEmoji::new("😋", &["yum"]),
+    UnicodeEmoji::new("😛", &["stuck_out_tongue"]),
+    UnicodeEmoji::new("😜", &["stuck_out_tongue_winking_eye"]),
+    UnicodeEmoji::new("🤪", &["zany_face"]),
+    UnicodeEmoji::new("🤑", &["money_mouth", "money_mouth_face"]),
+    UnicodeEmoji::new("🤗", &["hugging", "hugging_face"]),
+    UnicodeEmoji::new("🤔", &["thinking", "thinking_face"]),
+    UnicodeEmoji::new("🤐", &["zipper_mouth", "zipper_mouth_face"]),
+    UnicodeEmoji::new("🤨", &["face_with_raised_eyebrow"]),
+    UnicodeEmoji::new("😐", &["neutral_face"]),
+    UnicodeEmoji::new("😑", &["expressionless"]),
+    UnicodeEmoji::new("😶", &["no_mouth"]),
+    UnicodeEmoji::new("😏", &["smirk"]),
+    UnicodeEmoji::new("😒", &["unamused"]),
+    UnicodeEmoji::new("🙄", &["rolling_eyes", "face_with_rolling_eyes"]),
+    UnicodeEmoji::new("😬", &["grimacing"]),
+    UnicodeEmoji::new("😌", &["relieved"]),
+    UnicodeEmoji::new("😔", &["pensive"]),
+    UnicodeEmoji::new("😪", &["sleepy"]),
+    UnicodeEmoji::new("🤤", &["drooling_face", "drool"]),
+    UnicodeEmoji::new("😴", &["sleeping"]),
+    UnicodeEmoji::new("😷", &["mask"]),
+    UnicodeEmoji::new("🤒", &["thermometer_face", "face_with_thermometer"]),
+    UnicodeEmoji::new("🤢", &["nauseated_face", "sick"]),
+    UnicodeEmoji::new("🤧", &["sneezing_face", "sneeze"]),
+    UnicodeEmoji::new("🥵", &["hot_face"]),
+    UnicodeEmoji::new("🥶", &["cold_face"]),
+    UnicodeEmoji::new("🥴", &["woozy_face"]),
+    UnicodeEmoji::new("😵", &["dizzy_face"]),
+    UnicodeEmoji::new("🤯", &["exploding_head"]),
+    UnicodeEmoji::new("🤠", &["cowboy", "face_with_cowboy_hat"]),
+    UnicodeEmoji::new("🥳", &["partying_face"]),
+    UnicodeEmoji::new("😎", &["sunglasses"]),
+    UnicodeEmoji::new("🤓", &["nerd", "nerd_face"]),
+    UnicodeEmoji::new("😕", &["confused"]),
+    UnicodeEmoji::new("😟", &["worried"]),
+    UnicodeEmoji::new("🙁", &["slight_frown", "slightly_frowning_face"]),
+    UnicodeEmoji::new("☹️", &["frowning2", "white_frowning_face"]),
+    UnicodeEmoji::new("😮", &["open_mouth"]),
+    UnicodeEmoji::new("😯", &["hushed"]),
+    UnicodeEmoji::new("😲", &["astonished"]),
+    UnicodeEmoji::new("😳", &["flushed"]),
+    UnicodeEmoji::new("🥺", &["pleading_face"]),
+    UnicodeEmoji::new("😨", &["fearful"]),
+    UnicodeEmoji::new("😰", &["cold_sweat"]),
+    UnicodeEmoji::new("😢", &["cry"]),
+    UnicodeEmoji::new("😭", &["sob"]),
+    UnicodeEmoji::new("😱", &["scream"]),
+    UnicodeEmoji::new("😖", &["confounded"]),
+    UnicodeEmoji::new("😣", &["persevere"]),
+    UnicodeEmoji::new("😞", &["disappointed"]),
+    UnicodeEmoji::new("😓", &["sweat"]),
+    UnicodeEmoji::new("😩", &["weary"]),
+    UnicodeEmoji::new("😫", &["tired_face"]),
+    UnicodeEmoji::new("🥱", &["yawning_face"]),
+    UnicodeEmoji::new("😤", &["triumph"]),
+    UnicodeEmoji::new("😡", &["rage"]),
+    UnicodeEmoji::new("😠", &["angry"]),
+    UnicodeEmoji::new("🤬", &["face_with_symbols_over_mouth"]),
+    UnicodeEmoji::new("😈", &["smiling_imp"]),
+    UnicodeEmoji::new("💀", &["skull", "skeleton"]),
+    UnicodeEmoji::new("💩", &["poop", "shit", "hankey", "poo"]),
+    UnicodeEmoji::new("🤡", &["clown", "clown_face"]),
+    UnicodeEmoji::new("👻", &["ghost"]),
+    UnicodeEmoji::new("👽", &["alien"]),
+    UnicodeEmoji::new("🤖", &["robot", "robot_face"]),
+    UnicodeEmoji::new("😺", &["smiley_cat"]),
+    UnicodeEmoji::new("🙈", &["see_no_evil"]),
+    UnicodeEmoji::new("🙉", &["hear_no_evil"]),
+    UnicodeEmoji::new("🙊", &["speak_no_evil"]),
+    UnicodeEmoji::toned("👋", &["wave"]),
+    UnicodeEmoji::toned("👌", &["ok_hand"]),
+    UnicodeEmoji::toned("🤏", &["pinching_hand"]),
+    UnicodeEmoji::toned("✌️", &["v"]),
+    UnicodeEmoji::toned(
+        "🤞",
+        &[
+            "fingers_crossed",
+            "hand_with_index_and_middle_finger_crossed",
+        ],
+    ),
+    UnicodeEmoji::toned("🤘", &["metal", "sign_of_the_horns"]),
+    UnicodeEmoji::toned("🤙", &["call_me", "call_me_hand"]),
+    UnicodeEmoji::toned("👈", &["point_left"]),
+    UnicodeEmoji::toned("👉", &["point_right"]),
+    UnicodeEmoji::toned("👆", &["point_up_2"]),
+    UnicodeEmoji::toned("👇", &["point_down"]),
+    UnicodeEmoji::toned("☝️", &["point_up"]),
+    UnicodeEmoji::toned("👍", &["thumbsup", "+1", "thumbup"]),
+    UnicodeEmoji::toned("👎", &["thumbsdown", "-1", "thumbdown"]),
+    UnicodeEmoji::toned("✊", &["fist"]),
+    UnicodeEmoji::toned("👊", &["punch"]),
+    UnicodeEmoji::toned("👏", &["clap"]),
+    UnicodeEmoji::toned("🙌", &["raised_hands"]),
+    UnicodeEmoji::toned("👐", &["open_hands"]),
+    UnicodeEmoji::toned("🙏", &["pray"]),
+    UnicodeEmoji::new("🤝", &["handshake", "shaking_hands"]),
+    UnicodeEmoji::toned("💪", &["muscle"]),
+    UnicodeEmoji::new("👀", &["eyes"]),
+    UnicodeEmoji::new("👁️", &["eye"]),
+    UnicodeEmoji::new("🧠", &["brain"]),
+    UnicodeEmoji::new("👅", &["tongue"]),
+    UnicodeEmoji::new("👄", &["lips"]),
+    UnicodeEmoji::toned("👶", &["baby"]),
+    UnicodeEmoji::toned("👨", &["man"]),
+    UnicodeEmoji::toned("👩", &["woman"]),
+    UnicodeEmoji::toned("🤷", &["person_shrugging", "shrug"]),
+    UnicodeEmoji::toned("🤦", &["person_facepalming", "face_palm", "facepalm"]),
+    UnicodeEmoji::new("❤️", &["heart"]),
+    UnicodeEmoji::new("🧡", &["orange_heart"]),
+    UnicodeEmoji::new("💛", &["yellow_heart"]),
+    UnicodeEmoji::new("💚", &["green_heart"]),
+    UnicodeEmoji::new("💙", &["blue_heart"]),
+    UnicodeEmoji::new("💜", &["purple_heart"]),
+    UnicodeEmoji::new("🖤", &["black_heart"]),
+    UnicodeEmoji::new("🤍", &["white_heart"]),
+    UnicodeEmoji::new("🤎", &["brown_heart"]),
+    UnicodeEmoji::new("💔", &["broken_heart"]),
+    UnicodeEmoji::new("💕", &["two_hearts"]),
+    UnicodeEmoji::new("💖", &["sparkling_heart"]),
+    UnicodeEmoji::new("💗", &["heartpulse"]),
+    UnicodeEmoji::new("💯", &["100"]),
+    UnicodeEmoji::new("💥", &["boom"]),
+    UnicodeEmoji::new("💤", &["zzz"]),
+    UnicodeEmoji::new("💬", &["speech_balloon"]),
+    UnicodeEmoji::new("💭", &["thought_balloon"]),
+    UnicodeEmoji::new("💢", &["anger"]),
+    UnicodeEmoji::new("💦", &["sweat_drops"]),
+    UnicodeEmoji::new("💨", &["dash"]),
+];
+
+const NATURE: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("🐶", &["dog"]),
+    UnicodeEmoji::new("🐱", &["cat"]),
+    UnicodeEmoji::new("🐭", &["mouse"]),
+    UnicodeEmoji::new("🐹", &["hamster"]),
+    UnicodeEmoji::new("🐰", &["rabbit"]),
+    UnicodeEmoji::new("🦊", &["fox", "fox_face"]),
+    UnicodeEmoji::new("🐻", &["bear"]),
+    UnicodeEmoji::new("🐼", &["panda_face"]),
+    UnicodeEmoji::new("🐯", &["tiger"]),
+    UnicodeEmoji::new("🦁", &["lion_face", "lion"]),
+    UnicodeEmoji::new("🐮", &["cow"]),
+    UnicodeEmoji::new("🐷", &["pig"]),
+    UnicodeEmoji::new("🐸", &["frog"]),
+    UnicodeEmoji::new("🐵", &["monkey_face"]),
+    UnicodeEmoji::new("🐔", &["chicken"]),
+    UnicodeEmoji::new("🐧", &["penguin"]),
+    UnicodeEmoji::new("🐦", &["bird"]),
+    UnicodeEmoji::new("🦆", &["duck"]),
+    UnicodeEmoji::new("🦅", &["eagle"]),
+    UnicodeEmoji::new("🦉", &["owl"]),
+    UnicodeEmoji::new("🦇", &["bat"]),
+    UnicodeEmoji::new("🐺", &["wolf"]),
+    UnicodeEmoji::new("🐴", &["horse"]),
+    UnicodeEmoji::new("🦄", &["unicorn", "unicorn_face"]),
+    UnicodeEmoji::new("🐝", &["bee"]),
+    UnicodeEmoji::new("🐛", &["bug"]),
+    UnicodeEmoji::new("🦋", &["butterfly"]),
+    UnicodeEmoji::new("🐌", &["snail"]),
+    UnicodeEmoji::new("🐢", &["turtle"]),
+    UnicodeEmoji::new("🐍", &["snake"]),
+    UnicodeEmoji::new("🦀", &["crab"]),
+    UnicodeEmoji::new("🐙", &["octopus"]),
+    UnicodeEmoji::new("🐟", &["fish"]),
+    UnicodeEmoji::new("🐬", &["dolphin"]),
+    UnicodeEmoji::new("🐳", &["whale"]),
+    UnicodeEmoji::new("🦈", &["shark"]),
+    UnicodeEmoji::new("🌻", &["sunflower"]),
+    UnicodeEmoji::new("🌹", &["rose"]),
+    UnicodeEmoji::new("🌸", &["cherry_blossom"]),
+    UnicodeEmoji::new("🌷", &["tulip"]),
+    UnicodeEmoji::new("🌱", &["seedling"]),
+    UnicodeEmoji::new("🌲", &["evergreen_tree"]),
+    UnicodeEmoji::new("🌵", &["cactus"]),
+    UnicodeEmoji::new("🍀", &["four_leaf_clover"]),
+    UnicodeEmoji::new("🍁", &["maple_leaf"]),
+    UnicodeEmoji::new("🍄", &["mushroom"]),
+    UnicodeEmoji::new("🌎", &["earth_americas"]),
+    UnicodeEmoji::new("🌍", &["earth_africa"]),
+    UnicodeEmoji::new("🌙", &["crescent_moon"]),
+    UnicodeEmoji::new("⭐", &["star"]),
+    UnicodeEmoji::new("🌟", &["star2"]),
+    UnicodeEmoji::new("✨", &["sparkles"]),
+    UnicodeEmoji::new("⚡", &["zap"]),
+    UnicodeEmoji::new("🔥", &["fire", "flame"]),
+    UnicodeEmoji::new("☀️", &["sunny"]),
+    UnicodeEmoji::new("☁️", &["cloud"]),
+    UnicodeEmoji::new("🌈", &["rainbow"]),
+    UnicodeEmoji::new("❄️", &["snowflake"]),
+    UnicodeEmoji::new("☃️", &["snowman2"]),
+    UnicodeEmoji::new("💧", &["droplet"]),
+    UnicodeEmoji::new("🌊", &["ocean"]),
+];
+
+const FOOD: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("🍎", &["apple"]),
+    UnicodeEmoji::new("🍏", &["green_apple"]),
+    UnicodeEmoji::new("🍐", &["pear"]),
+    UnicodeEmoji::new("🍊", &["tangerine"]),
+    UnicodeEmoji::new("🍋", &["lemon"]),
+    UnicodeEmoji::new("🍌", &["banana"]),
+    UnicodeEmoji::new("🍉", &["watermelon"]),
+    UnicodeEmoji::new("🍇", &["grapes"]),
+    UnicodeEmoji::new("🍓", &["strawberry"]),
+    UnicodeEmoji::new("🍒", &["cherries"]),
+    UnicodeEmoji::new("🍑", &["peach"]),
+    UnicodeEmoji::new("🍍", &["pineapple"]),
+    UnicodeEmoji::new("🥑", &["avocado"]),
+    UnicodeEmoji::new("🍆", &["eggplant"]),
+    UnicodeEmoji::new("🥕", &["carrot"]),
+    UnicodeEmoji::new("🌽", &["corn"]),
+    UnicodeEmoji::new("🌶️", &["hot_pepper"]),
+    UnicodeEmoji::new("🍞", &["bread"]),
+    UnicodeEmoji::new("🧀", &["cheese", "cheese_wedge"]),
+    UnicodeEmoji::new("🥚", &["egg"]),
+    UnicodeEmoji::new("🥓", &["bacon"]),
+    UnicodeEmoji::new("🍔", &["hamburger"]),
+    UnicodeEmoji::new("🍟", &["fries"]),
+    UnicodeEmoji::new("🍕", &["pizza"]),
+    UnicodeEmoji::new("🌭", &["hotdog", "hot_dog"]),
+    UnicodeEmoji::new("🌮", &["taco"]),
+    UnicodeEmoji::new("🌯", &["burrito"]),
+    UnicodeEmoji::new("🍜", &["ramen"]),
+    UnicodeEmoji::new("🍝", &["spaghetti"]),
+    UnicodeEmoji::new("🍣", &["sushi"]),
+    UnicodeEmoji::new("🍚", &["rice"]),
+    UnicodeEmoji::new("🍦", &["icecream"]),
+    UnicodeEmoji::new("🍩", &["doughnut"]),
+    UnicodeEmoji::new("🍪", &["cookie"]),
+    UnicodeEmoji::new("🎂", &["birthday"]),
+    UnicodeEmoji::new("🍰", &["cake"]),
+    UnicodeEmoji::new("🍫", &["chocolate_bar"]),
+    UnicodeEmoji::new("🍬", &["candy"]),
+    UnicodeEmoji::new("🍿", &["popcorn"]),
+    UnicodeEmoji::new("☕", &["coffee"]),
+    UnicodeEmoji::new("🍵", &["tea"]),
+    UnicodeEmoji::new("🍺", &["beer"]),
+    UnicodeEmoji::new("🍻", &["beers"]),
+    UnicodeEmoji::new("🍷", &["wine_glass"]),
+    UnicodeEmoji::new("🍸", &["cocktail"]),
+    UnicodeEmoji::new("🍾", &["champagne", "bottle_with_popping_cork"]),
+];
+
+const ACTIVITIES: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("⚽", &["soccer"]),
+    UnicodeEmoji::new("🏀", &["basketball"]),
+    UnicodeEmoji::new("🏈", &["football"]),
+    UnicodeEmoji::new("⚾", &["baseball"]),
+    UnicodeEmoji::new("🎾", &["tennis"]),
+    UnicodeEmoji::new("🏐", &["volleyball"]),
+    UnicodeEmoji::new("🎱", &["8ball"]),
+    UnicodeEmoji::new("🏓", &["ping_pong", "table_tennis"]),
+    UnicodeEmoji::new("🏆", &["trophy"]),
+    UnicodeEmoji::new("🏅", &["medal", "sports_medal"]),
+    UnicodeEmoji::new("🥇", &["first_place", "first_place_medal"]),
+    UnicodeEmoji::new("🎮", &["video_game"]),
+    UnicodeEmoji::new("🕹️", &["joystick"]),
+    UnicodeEmoji::new("🎲", &["game_die"]),
+    UnicodeEmoji::new("♟️", &["chess_pawn"]),
+    UnicodeEmoji::new("🎯", &["dart"]),
+    UnicodeEmoji::new("🎳", &["bowling"]),
+    UnicodeEmoji::new("🎸", &["guitar"]),
+    UnicodeEmoji::new("🎵", &["musical_note"]),
+    UnicodeEmoji::new("🎶", &["notes"]),
+    UnicodeEmoji::new("🎤", &["microphone"]),
+    UnicodeEmoji::new("🎧", &["headphones"]),
+    UnicodeEmoji::new("🎨", &["art"]),
+    UnicodeEmoji::new("🎭", &["performing_arts"]),
+    UnicodeEmoji::new("🎉", &["tada"]),
+    UnicodeEmoji::new("🎊", &["confetti_ball"]),
+    UnicodeEmoji::new("🎈", &["balloon"]),
+    UnicodeEmoji::new("🎁", &["gift"]),
+    UnicodeEmoji::new("🎄", &["christmas_tree"]),
+    UnicodeEmoji::new("🎃", &["jack_o_lantern"]),
+];
+
+const TRAVEL: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("🚗", &["red_car"]),
+    UnicodeEmoji::new("🚌", &["bus"]),
+    UnicodeEmoji::new("🚲", &["bike"]),
+    UnicodeEmoji::new("✈️", &["airplane"]),
+    UnicodeEmoji::new("🚀", &["rocket"]),
+    UnicodeEmoji::new("🚢", &["ship"]),
+    UnicodeEmoji::new("🏠", &["house"]),
+    UnicodeEmoji::new("🏢", &["office"]),
+    UnicodeEmoji::new("🏥", &["hospital"]),
+    UnicodeEmoji::new("🏫", &["school"]),
+    UnicodeEmoji::new("🗿", &["moyai"]),
+    UnicodeEmoji::new("🗽", &["statue_of_liberty"]),
+    UnicodeEmoji::new("⛰️", &["mountain"]),
+    UnicodeEmoji::new("🏖️", &["beach", "beach_with_umbrella"]),
+    UnicodeEmoji::new("🌃", &["night_with_stars"]),
+];
+
+const OBJECTS: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("⌚", &["watch"]),
+    UnicodeEmoji::new("📱", &["iphone", "mobile_phone"]),
+    UnicodeEmoji::new("💻", &["computer"]),
+    UnicodeEmoji::new("⌨️", &["keyboard"]),
+    UnicodeEmoji::new("🖥️", &["desktop", "desktop_computer"]),
+    UnicodeEmoji::new("🖨️", &["printer"]),
+    UnicodeEmoji::new("💾", &["floppy_disk"]),
+    UnicodeEmoji::new("💿", &["cd"]),
+    UnicodeEmoji::new("📷", &["camera"]),
+    UnicodeEmoji::new("📺", &["tv"]),
+    UnicodeEmoji::new("📻", &["radio"]),
+    UnicodeEmoji::new("⏳", &["hourglass_flowing_sand"]),
+    UnicodeEmoji::new("⏰", &["alarm_clock"]),
+    UnicodeEmoji::new("💡", &["bulb"]),
+    UnicodeEmoji::new("🔦", &["flashlight"]),
+    UnicodeEmoji::new("💸", &["money_with_wings"]),
+    UnicodeEmoji::new("💵", &["dollar"]),
+    UnicodeEmoji::new("💎", &["gem"]),
+    UnicodeEmoji::new("🔧", &["wrench"]),
+    UnicodeEmoji::new("🔨", &["hammer"]),
+    UnicodeEmoji::new("🛠️", &["tools", "hammer_and_wrench"]),
+    UnicodeEmoji::new("⚙️", &["gear"]),
+    UnicodeEmoji::new("⛓️", &["chains"]),
+    UnicodeEmoji::new("💊", &["pill"]),
+    UnicodeEmoji::new("💉", &["syringe"]),
+    UnicodeEmoji::new("🔑", &["key"]),
+    UnicodeEmoji::new("🔒", &["lock"]),
+    UnicodeEmoji::new("🔓", &["unlock"]),
+    UnicodeEmoji::new("🔔", &["bell"]),
+    UnicodeEmoji::new("🔖", &["bookmark"]),
+    UnicodeEmoji::new("📖", &["book", "open_book"]),
+    UnicodeEmoji::new("📚", &["books"]),
+    UnicodeEmoji::new("📝", &["pencil", "memo"]),
+    UnicodeEmoji::new("✏️", &["pencil2"]),
+    UnicodeEmoji::new("🖊️", &["pen_ballpoint", "lower_left_ballpoint_pen"]),
+    UnicodeEmoji::new("📎", &["paperclip"]),
+    UnicodeEmoji::new("📌", &["pushpin"]),
+    UnicodeEmoji::new("✂️", &["scissors"]),
+    UnicodeEmoji::new("📅", &["date"]),
+    UnicodeEmoji::new("📈", &["chart_with_upwards_trend"]),
+    UnicodeEmoji::new("📋", &["clipboard"]),
+    UnicodeEmoji::new("📦", &["package"]),
+    UnicodeEmoji::new("✉️", &["envelope"]),
+    UnicodeEmoji::new("📧", &["e_mail", "email"]),
+    UnicodeEmoji::new("🗑️", &["wastebasket"]),
+    UnicodeEmoji::new("🛒", &["shopping_cart"]),
+    UnicodeEmoji::new("💣", &["bomb"]),
+    UnicodeEmoji::new("🔪", &["knife"]),
+    UnicodeEmoji::new("🛡️", &["shield"]),
+    UnicodeEmoji::new("🔮", &["crystal_ball"]),
+    UnicodeEmoji::new("🧲", &["magnet"]),
+    UnicodeEmoji::new("🔋", &["battery"]),
+];
+
+const SYMBOLS: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("✅", &["white_check_mark"]),
+    UnicodeEmoji::new("✔️", &["heavy_check_mark"]),
+    UnicodeEmoji::new("❌", &["x"]),
+    UnicodeEmoji::new("❎", &["negative_squared_cross_mark"]),
+    UnicodeEmoji::new("❗", &["exclamation"]),
+    UnicodeEmoji::new("❓", &["question"]),
+    UnicodeEmoji::new("‼️", &["bangbang"]),
+    UnicodeEmoji::new("⁉️", &["interrobang"]),
+    UnicodeEmoji::new("⚠️", &["warning"]),
+    UnicodeEmoji::new("⛔", &["no_entry"]),
+    UnicodeEmoji::new("🚫", &["no_entry_sign"]),
+    UnicodeEmoji::new("🛑", &["octagonal_sign", "stop_sign"]),
+    UnicodeEmoji::new("♻️", &["recycle"]),
+    UnicodeEmoji::new("♾️", &["infinity"]),
+    UnicodeEmoji::new("➕", &["heavy_plus_sign"]),
+    UnicodeEmoji::new("➖", &["heavy_minus_sign"]),
+    UnicodeEmoji::new("➗", &["heavy_division_sign"]),
+    UnicodeEmoji::new("✖️", &["heavy_multiplication_x"]),
+    UnicodeEmoji::new("⬆️", &["arrow_up"]),
+    UnicodeEmoji::new("⬇️", &["arrow_down"]),
+    UnicodeEmoji::new("⬅️", &["arrow_left"]),
+    UnicodeEmoji::new("➡️", &["arrow_right"]),
+    UnicodeEmoji::new("🔄", &["arrows_counterclockwise"]),
+    UnicodeEmoji::new("🔁", &["repeat"]),
+    UnicodeEmoji::new("ℹ️", &["information_source"]),
+    UnicodeEmoji::new("©️", &["copyright"]),
+    UnicodeEmoji::new("®️", &["registered"]),
+    UnicodeEmoji::new("™️", &["tm"]),
+    UnicodeEmoji::new("🔴", &["red_circle"]),
+    UnicodeEmoji::new("🟠", &["orange_circle"]),
+    UnicodeEmoji::new("🟡", &["yellow_circle"]),
+    UnicodeEmoji::new("🟢", &["green_circle"]),
+    UnicodeEmoji::new("🔵", &["blue_circle"]),
+    UnicodeEmoji::new("🟣", &["purple_circle"]),
+    UnicodeEmoji::new("⚫", &["black_circle"]),
+    UnicodeEmoji::new("⚪", &["white_circle"]),
+    UnicodeEmoji::new("0️⃣", &["zero"]),
+    UnicodeEmoji::new("1️⃣", &["one"]),
+    UnicodeEmoji::new("2️⃣", &["two"]),
+    UnicodeEmoji::new("3️⃣", &["three"]),
+    UnicodeEmoji::new("4️⃣", &["four"]),
+    UnicodeEmoji::new("5️⃣", &["five"]),
+    UnicodeEmoji::new("6️⃣", &["six"]),
+    UnicodeEmoji::new("7️⃣", &["seven"]),
+    UnicodeEmoji::new("8️⃣", &["eight"]),
+    UnicodeEmoji::new("9️⃣", &["nine"]),
+    UnicodeEmoji::new("🔟", &["keycap_ten"]),
+    UnicodeEmoji::new("#️⃣", &["hash"]),
+    UnicodeEmoji::new("*️⃣", &["asterisk", "keycap_asterisk"]),
+    UnicodeEmoji::new("🆕", &["new"]),
+    UnicodeEmoji::new("🆓", &["free"]),
+    UnicodeEmoji::new("🆙", &["up"]),
+    UnicodeEmoji::new("🆒", &["cool"]),
+    UnicodeEmoji::new("🆗", &["ok"]),
+    UnicodeEmoji::new("🆘", &["sos"]),
+];
+
+const FLAGS: &[UnicodeEmoji] = &[
+    UnicodeEmoji::new("🏁", &["checkered_flag"]),
+    UnicodeEmoji::new("🚩", &["triangular_flag_on_post"]),
+    UnicodeEmoji::new("🏳️", &["flag_white"]),
+    UnicodeEmoji::new("🏴", &["flag_black"]),
+    UnicodeEmoji::new("🏳️‍🌈", &["rainbow_flag", "gay_pride_flag"]),
+    UnicodeEmoji::new("🏴‍☠️", &["pirate_flag"]),
+    UnicodeEmoji::new("🇺🇸", &["flag_us", "us"]),
+    UnicodeEmoji::new("🇬🇧", &["flag_gb", "gb"]),
+    UnicodeEmoji::new("🇫🇷", &["flag_fr", "fr"]),
+    UnicodeEmoji::new("🇩🇪", &["flag_de", "de"]),
+    UnicodeEmoji::new("🇧🇪", &["flag_be", "be"]),
+    UnicodeEmoji::new("🇳🇱", &["flag_nl", "nl"]),
+    UnicodeEmoji::new("🇪🇸", &["flag_es", "es"]),
+    UnicodeEmoji::new("🇮🇹", &["flag_it", "it"]),
+    UnicodeEmoji::new("🇯🇵", &["flag_jp", "jp"]),
+    UnicodeEmoji::new("🇨🇦", &["flag_ca", "ca"]),
+    UnicodeEmoji::new("🇧🇷", &["flag_br", "br"]),
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use twilight_model::{guild::Emoji, id::Id};
+
+    use super::{by_emoji, by_name, unicode_emojis, usable, PickedEmoji, SkinTone};
+
+    #[test]
+    fn names_are_unique() {
+        let mut names = HashSet::new();
+        let mut emojis = HashSet::new();
+
+        for emoji in unicode_emojis() {
+            assert!(
+                emojis.insert(emoji.emoji),
+                "{} is listed twice",
+                emoji.emoji
+            );
+            for name in emoji.names {
+                assert!(names.insert(*name), "{name} is used twice");
+            }
+        }
+    }
+
+    #[test]
+    fn lookups() {
+        assert_eq!(by_name("+1").map(|emoji| emoji.emoji), Some("👍"));
+        assert_eq!(by_emoji("🦀").map(|emoji| emoji.name()), Some("crab"));
+        assert_eq!(by_name("not_an_emoji"), None);
+    }
+
+    #[test]
+    fn skin_tones() {
+        let thumbsup = by_name("thumbsup").unwrap();
+        assert_eq!(thumbsup.with_skin_tone(SkinTone::Default), "👍");
+        assert_eq!(thumbsup.with_skin_tone(SkinTone::Medium), "👍🏽");
+        // the variation selector is replaced by the modifier
+        assert_eq!(by_name("v").unwrap().with_skin_tone(SkinTone::Dark), "✌🏿");
+        assert_eq!(
+            by_name("crab").unwrap().with_skin_tone(SkinTone::Dark),
+            "🦀"
+        );
+    }
+
+    #[test]
+    fn picked_emojis() {
+        let emoji = Emoji {
+            animated: true,
+            available: true,
+            id: Id::new(3),
+            managed: false,
+            name: "ferris".to_owned(),
+            require_colons: true,
+            roles: Vec::new(),
+            user: None,
+        };
+        let picked = PickedEmoji::custom(&emoji);
+        assert_eq!(picked.text(), "<a:ferris:3>");
+        assert_eq!(picked.frecency_key(), "3");
+
+        let unicode = PickedEmoji::Unicode {
+            emoji: "👍🏽".to_owned(),
+            name: "thumbsup",
+        };
+        assert_eq!(unicode.text(), "👍🏽");
+        assert_eq!(unicode.frecency_key(), "thumbsup");
+
+        // animated emojis and the ones of other guilds require Nitro
+        assert!(!usable(&emoji, true, false, &[]));
+        assert!(usable(&emoji, false, true, &[]));
+        let restricted = Emoji {
+            animated: false,
+            roles: vec![Id::new(10)],
+            ..emoji
+        };
+        assert!(!usable(&restricted, true, false, &[Id::new(11)]));
+        assert!(usable(&restricted, true, false, &[Id::new(10)]));
+    }
+}
@@ -0,0 +1,76 @@
+//! Gateway payloads shared by the tests of the stores.
+
+use serde_json::{json, Value};
+use twilight_model::{
+    channel::Message,
+    gateway::{event::DispatchEvent, payload::incoming::Ready},
+};
+
+pub const CURRENT_USER_ID: u64 = 1;
+/// Author of the messages.
+pub const OTHER_USER_ID: u64 = 2;
+pub const GUILD_ID: u64 = 10;
+pub const CHANNEL_ID: u64 = 20;
+
+/// READY payload of the current user, without any guilds.
+pub fn ready() -> DispatchEvent {
+    ready_with(&json!({}))
+}
+
+/// READY payload of the current user, with the fields of `extra` added.
+pub fn ready_with(extra: &Value) -> DispatchEvent {
+    let ready = json!({
+        "guilds": [],
+        "resume_gateway_url": "wss://gateway.discord.gg",
+        "session_id": "session",
+        "user": {
+            "avatar": null,
+            "discriminator": "0",
+            "id": CURRENT_USER_ID.to_string(),
+            "mfa_enabled": true,
+            "username": "me",
+            "verified": true,
+        },
+        "v": 9,
+    });
+    let ready: Ready = serde_json::from_value(with_fields(ready, extra)).unwrap();
+
+    DispatchEvent::Ready(Box::new(ready))
+}
+
+/// Message sent by another user in the channel of the guild, with the
+/// fields of `extra` replacing its own.
+pub fn message(id: u64, extra: &Value) -> Message {
+    let message = json!({
+        "attachments": [],
+        "author": {
+            "avatar": null,
+            "discriminator": "0",
+            "id": OTHER_USER_ID.to_string(),
+            "username": "author",
+        },
+        "channel_id": CHANNEL_ID.to_string(),
+        "content": "hello",
+        "edited_timestamp": null,
+        "embeds": [],
+        "guild_id": GUILD_ID.to_string(),
+        "id": id.to_string(),
+        "mention_everyone": false,
+        "mention_roles": [],
+        "mentions": [],
+        "pinned": false,
+        "timestamp": "2023-11-14T22:13:20+00:00",
+        "tts": false,
+        "type": 0,
+    });
+
+    serde_json::from_value(with_fields(message, extra)).unwrap()
+}
+
+fn with_fields(mut value: Value, extra: &Value) -> Value {
+    for (key, field) in extra.as_object().into_iter().flatten() {
+        value[key] = field.clone();
+    }
+
+    value
+}
@@ -0,0 +1,449 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use twilight_model::id::{marker::GuildMarker, Id};
+
+use crate::{
+    cache::Cache,
+    user_settings::{GuildFolder, GuildFolders},
+};
+
+/// Entry of the guild list, either a guild or a folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuildListKey {
+    Guild(Id<GuildMarker>),
+    /// A folder by its id.
+    Folder(i64),
+}
+
+/// Where a guild or folder was dropped in the guild list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropTarget {
+    Before(GuildListKey),
+    After(GuildListKey),
+    /// On top of an entry, which adds a guild to a folder or creates a new
+    /// folder when it is dropped on another guild.
+    Into(GuildListKey),
+}
+
+/// Folders of the guild list in display order, guilds that aren't in a
+/// folder are in a folder without an id, like Discord stores them.
+///
+/// Guilds of the settings that aren't cached are left out, the cached ones
+/// missing from the settings, such as the ones joined on another client that
+/// didn't update the settings yet, come first.
+pub fn guild_folders(cache: &Cache, settings: Option<&GuildFolders>) -> Vec<GuildFolder> {
+    let mut folders = settings
+        .map(|settings| settings.folders.clone())
+        .unwrap_or_default();
+
+    for folder in &mut folders {
+        folder
+            .guild_ids
+            .retain(|&guild_id| cache.guild(guild_id).is_some());
+    }
+    folders.retain(|folder| !folder.guild_ids.is_empty());
+
+    let missing = cache
+        .guilds()
+        .filter(|guild| {
+            !folders
+                .iter()
+                .any(|folder| folder.guild_ids.contains(&guild.id))
+        })
+        .map(|guild| single_guild(guild.id))
+        .collect::<Vec<_>>();
+    folders.splice(0..0, missing);
+
+    folders
+}
+
+/// Settings with the given folders, in the form they're written back.
+pub fn to_settings(folders: Vec<GuildFolder>, previous: Option<&GuildFolders>) -> GuildFolders {
+    let guild_positions = folders
+        .iter()
+        .flat_map(|folder| folder.guild_ids.iter().copied())
+        .collect();
+
+    GuildFolders {
+        folders,
+        guild_positions,
+        unknown: previous
+            .map(|previous| previous.unknown.clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// Whether a folder is shown as a folder rather than as a single guild.
+pub const fn is_folder(folder: &GuildFolder) -> bool {
+    folder.id.is_some()
+}
+
+/// Moves a guild or folder to where it was dropped.
+pub fn move_entry(folders: &mut Vec<GuildFolder>, dragged: GuildListKey, target: DropTarget) {
+    if dragged == target_key(target) {
+        return;
+    }
+
+    match dragged {
+        GuildListKey::Guild(guild_id) => move_guild(folders, guild_id, target),
+        GuildListKey::Folder(folder_id) => move_folder(folders, folder_id, target),
+    }
+}
+
+/// Replaces a folder with its guilds.
+pub fn remove_folder(folders: &mut Vec<GuildFolder>, folder_id: i64) {
+    let Some(index) = folders
+        .iter()
+        .position(|folder| folder.id == Some(folder_id))
+    else {
+        return;
+    };
+
+    let folder = folders.remove(index);
+    let guilds = folder.guild_ids.into_iter().map(single_guild);
+    folders.splice(index..index, guilds);
+}
+
+fn move_guild(folders: &mut Vec<GuildFolder>, guild_id: Id<GuildMarker>, target: DropTarget) {
+    remove_guild(folders, guild_id);
+
+    let (index, after) = match target {
+        DropTarget::Before(key) => (top_level_index(folders, key), false),
+        DropTarget::After(key) => (top_level_index(folders, key), true),
+        DropTarget::Into(GuildListKey::Folder(folder_id)) => {
+            if let Some(folder) = folders
+                .iter_mut()
+                .find(|folder| folder.id == Some(folder_id))
+            {
+                folder.guild_ids.push(guild_id);
+                return;
+            }
+
+            (None, true)
+        }
+        DropTarget::Into(GuildListKey::Guild(other_id)) => {
+            match top_level_index(folders, GuildListKey::Guild(other_id)) {
+                // dropping a guild on another guild creates a folder of both
+                Some(index) if !is_folder(&folders[index]) => {
+                    folders[index] = GuildFolder {
+                        guild_ids: vec![other_id, guild_id],
+                        id: Some(new_folder_id(folders)),
+                        ..GuildFolder::default()
+                    };
+                    return;
+                }
+                index => (index, true),
+            }
+        }
+    };
+
+    let Some(index) = index else {
+        folders.push(single_guild(guild_id));
+        return;
+    };
+
+    // guilds dropped next to a guild in a folder are added to the folder
+    let target_guild = match target {
+        DropTarget::Before(GuildListKey::Guild(id))
+        | DropTarget::After(GuildListKey::Guild(id))
+        | DropTarget::Into(GuildListKey::Guild(id)) => Some(id),
+        _ => None,
+    };
+    if let Some(target_guild) = target_guild.filter(|_| is_folder(&folders[index])) {
+        let guild_ids = &mut folders[index].guild_ids;
+        let position = guild_ids
+            .iter()
+            .position(|&id| id == target_guild)
+            .map_or(guild_ids.len(), |position| position + usize::from(after));
+        guild_ids.insert(position, guild_id);
+        return;
+    }
+
+    folders.insert(index + usize::from(after), single_guild(guild_id));
+}
+
+fn move_folder(folders: &mut Vec<GuildFolder>, folder_id: i64, target: DropTarget) {
+    let Some(index) = folders
+        .iter()
+        .position(|folder| folder.id == Some(folder_id))
+    else {
+        return;
+    };
+    // dropping a folder on one of its own guilds doesn't move it
+    if top_level_index(folders, target_key(target)) == Some(index) {
+        return;
+    }
+    let folder = folders.remove(index);
+
+    // folders can't be nested, they're moved next to the target instead
+    let index = match target {
+        DropTarget::Before(key) => top_level_index(folders, key),
+        DropTarget::After(key) | DropTarget::Into(key) => {
+            top_level_index(folders, key).map(|index| index + 1)
+        }
+    };
+
+    folders.insert(index.unwrap_or(folders.len()), folder);
+}
+
+const fn target_key(target: DropTarget) -> GuildListKey {
+    match target {
+        DropTarget::Before(key) | DropTarget::After(key) | DropTarget::Into(key) => key,
+    }
+}
+
+/// Removes a guild from its folder, along with the folder if it's empty.
+fn remove_guild(folders: &mut Vec<GuildFolder>, guild_id: Id<GuildMarker>) {
+    for folder in folders.iter_mut() {
+        folder.guild_ids.retain(|&id| id != guild_id);
+    }
+    folders.retain(|folder| !folder.guild_ids.is_empty());
+}
+
+/// Index of the folder that is or contains an entry.
+fn top_level_index(folders: &[GuildFolder], key: GuildListKey) -> Option<usize> {
+    folders.iter().position(|folder| match key {
+        GuildListKey::Guild(guild_id) => folder.guild_ids.contains(&guild_id),
+        GuildListKey::Folder(folder_id) => folder.id == Some(folder_id),
+    })
+}
+
+fn single_guild(guild_id: Id<GuildMarker>) -> GuildFolder {
+    GuildFolder {
+        guild_ids: vec![guild_id],
+        ..GuildFolder::default()
+    }
+}
+
+/// Generates an id for a new folder, folder ids are picked by the client.
+fn new_folder_id(folders: &[GuildFolder]) -> i64 {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    // ids that fit in 32 bits, like the ones the official client generates
+    let mut id = (millis % u128::from(u32::MAX)) as i64;
+    while folders.iter().any(|folder| folder.id == Some(id)) {
+        id += 1;
+    }
+
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::Id;
+
+    use super::{
+        move_entry, remove_folder, DropTarget, GuildFolder,
+        GuildListKey::{self, Folder, Guild},
+    };
+
+    /// Guild 1, folder 100 of guilds 2 and 3, guild 4 and folder 200 of
+    /// guild 5.
+    fn folders() -> Vec<GuildFolder> {
+        [
+            (None, &[1][..]),
+            (Some(100), &[2, 3]),
+            (None, &[4]),
+            (Some(200), &[5]),
+        ]
+        .into_iter()
+        .map(|(id, guild_ids)| GuildFolder {
+            guild_ids: guild_ids.iter().copied().map(Id::new).collect(),
+            id,
+            ..GuildFolder::default()
+        })
+        .collect()
+    }
+
+    fn layout(folders: &[GuildFolder]) -> Vec<(Option<i64>, Vec<u64>)> {
+        folders
+            .iter()
+            .map(|folder| {
+                let guild_ids = folder.guild_ids.iter().map(|id| id.get()).collect();
+                (folder.id, guild_ids)
+            })
+            .collect()
+    }
+
+    fn moved(dragged: GuildListKey, target: DropTarget) -> Vec<(Option<i64>, Vec<u64>)> {
+        let mut folders = folders();
+        move_entry(&mut folders, dragged, target);
+
+        layout(&folders)
+    }
+
+    #[test]
+    fn moved_guilds() {
+        assert_eq!(
+            moved(Guild(Id::new(4)), DropTarget::Before(Guild(Id::new(1)))),
+            [
+                (None, vec![4]),
+                (None, vec![1]),
+                (Some(100), vec![2, 3]),
+                (Some(200), vec![5]),
+            ]
+        );
+        // within a folder
+        assert_eq!(
+            moved(Guild(Id::new(3)), DropTarget::Before(Guild(Id::new(2)))),
+            [
+                (None, vec![1]),
+                (Some(100), vec![3, 2]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+            ]
+        );
+        // between folders
+        assert_eq!(
+            moved(Guild(Id::new(2)), DropTarget::After(Guild(Id::new(5)))),
+            [
+                (None, vec![1]),
+                (Some(100), vec![3]),
+                (None, vec![4]),
+                (Some(200), vec![5, 2]),
+            ]
+        );
+        assert_eq!(
+            moved(Guild(Id::new(1)), DropTarget::Into(Folder(200))),
+            [
+                (Some(100), vec![2, 3]),
+                (None, vec![4]),
+                (Some(200), vec![5, 1]),
+            ]
+        );
+        // next to a folder rather than into it
+        assert_eq!(
+            moved(Guild(Id::new(1)), DropTarget::After(Folder(100))),
+            [
+                (Some(100), vec![2, 3]),
+                (None, vec![1]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+            ]
+        );
+        // out of a folder, which is removed once it's empty
+        assert_eq!(
+            moved(Guild(Id::new(5)), DropTarget::Before(Guild(Id::new(1)))),
+            [
+                (None, vec![5]),
+                (None, vec![1]),
+                (Some(100), vec![2, 3]),
+                (None, vec![4]),
+            ]
+        );
+        assert_eq!(
+            moved(Guild(Id::new(1)), DropTarget::Into(Guild(Id::new(1)))),
+            layout(&folders())
+        );
+    }
+
+    #[test]
+    fn created_folder() {
+        let mut folders = folders();
+        move_entry(
+            &mut folders,
+            Guild(Id::new(1)),
+            DropTarget::Into(Guild(Id::new(4))),
+        );
+
+        let layout = layout(&folders);
+        assert_eq!(layout.len(), 3);
+        assert_eq!(layout[1].1, [4, 1]);
+        let id = layout[1].0.unwrap();
+        assert!(id != 100 && id != 200);
+        assert_eq!(layout[2], (Some(200), vec![5]));
+    }
+
+    #[test]
+    fn moved_folders() {
+        assert_eq!(
+            moved(Folder(200), DropTarget::Before(Guild(Id::new(1)))),
+            [
+                (Some(200), vec![5]),
+                (None, vec![1]),
+                (Some(100), vec![2, 3]),
+                (None, vec![4]),
+            ]
+        );
+        assert_eq!(
+            moved(Folder(100), DropTarget::After(Guild(Id::new(4)))),
+            [
+                (None, vec![1]),
+                (None, vec![4]),
+                (Some(100), vec![2, 3]),
+                (Some(200), vec![5]),
+            ]
+        );
+        // folders aren't nested
+        assert_eq!(
+            moved(Folder(100), DropTarget::Into(Guild(Id::new(5)))),
+            [
+                (None, vec![1]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+                (Some(100), vec![2, 3]),
+            ]
+        );
+        assert_eq!(
+            moved(Folder(100), DropTarget::Before(Guild(Id::new(3)))),
+            layout(&folders())
+        );
+    }
+
+    #[test]
+    fn unknown_entries() {
+        // entries dropped on one that isn't in the list go last
+        assert_eq!(
+            moved(Guild(Id::new(1)), DropTarget::Before(Guild(Id::new(9)))),
+            [
+                (Some(100), vec![2, 3]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+                (None, vec![1]),
+            ]
+        );
+        assert_eq!(
+            moved(Guild(Id::new(1)), DropTarget::Into(Folder(300))),
+            [
+                (Some(100), vec![2, 3]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+                (None, vec![1]),
+            ]
+        );
+        assert_eq!(
+            moved(Folder(100), DropTarget::After(Guild(Id::new(9)))),
+            [
+                (None, vec![1]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+                (Some(100), vec![2, 3]),
+            ]
+        );
+        assert_eq!(
+            moved(Folder(300), DropTarget::Before(Guild(Id::new(1)))),
+            layout(&folders())
+        );
+    }
+
+    #[test]
+    fn removed_folders() {
+        let mut folders = folders();
+        remove_folder(&mut folders, 300);
+        assert_eq!(layout(&folders), layout(&self::folders()));
+
+        remove_folder(&mut folders, 100);
+        assert_eq!(
+            layout(&folders),
+            [
+                (None, vec![1]),
+                (None, vec![2]),
+                (None, vec![3]),
+                (None, vec![4]),
+                (Some(200), vec![5]),
+            ]
+        );
+    }
+}
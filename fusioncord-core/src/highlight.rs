@@ -0,0 +1,750 @@
+//! Syntax highlighting of code, using simple grammars per language that
+//! recognize comments, strings, numbers and keywords rather than parsing the
+//! code.
+
+use std::{collections::HashMap, ops::Range};
+
+use twilight_model::{
+    gateway::event::DispatchEvent,
+    id::{marker::MessageMarker, Id},
+};
+
+/// Code blocks larger than this are shown without highlighting, which would
+/// make scrolling past them stutter.
+pub const MAX_HIGHLIGHTED_LEN: usize = 64 * 1024;
+
+/// What a part of the highlighted code is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    /// Built-in constants like `true` and `null`.
+    Literal,
+    String,
+    Number,
+    Comment,
+    /// Capitalized identifiers in languages where those name types.
+    Type,
+    /// Identifiers that are called, including Rust macros.
+    Function,
+    /// Added and removed lines of a diff.
+    Inserted,
+    Deleted,
+}
+
+/// Part of the highlighted code, by byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub range: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// Rules to highlight the code of a language with.
+#[derive(Debug)]
+pub struct Grammar {
+    pub name: &'static str,
+    /// Extensions of the files written in the language, lowercase.
+    extensions: &'static [&'static str],
+    /// Other tags of markdown code blocks in the language, lowercase.
+    aliases: &'static [&'static str],
+    /// Space separated keywords.
+    keywords: &'static str,
+    /// Space separated built-in constants.
+    literals: &'static str,
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Whether strings can be quoted with three quotes, like in Python.
+    triple_quotes: bool,
+    /// Whether strings quoted with anything but backticks can span lines.
+    multiline_strings: bool,
+    /// Whether a single quote followed by an identifier is a lifetime
+    /// rather than the start of a character, like in Rust.
+    lifetimes: bool,
+    case_insensitive: bool,
+    capitalized_types: bool,
+    /// Whether lines are highlighted as added or removed, ignoring the other
+    /// rules.
+    diff: bool,
+}
+
+const C_LIKE: Grammar = Grammar {
+    name: "",
+    extensions: &[],
+    aliases: &[],
+    keywords: "",
+    literals: "true false null",
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    multiline_strings: false,
+    lifetimes: false,
+    case_insensitive: false,
+    capitalized_types: true,
+    diff: false,
+};
+
+const SCRIPT: Grammar = Grammar {
+    line_comments: &["#"],
+    block_comment: None,
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const RUST: Grammar = Grammar {
+    name: "Rust",
+    extensions: &["rs"],
+    aliases: &["rust"],
+    keywords: "\
+        as async await break const continue crate dyn else enum extern fn for if impl in \
+        let loop match mod move mut pub ref return self Self static struct super trait \
+        type unsafe use where while yield",
+    literals: "true false None Some Ok Err",
+    quotes: &['"', '\''],
+    multiline_strings: true,
+    lifetimes: true,
+    ..C_LIKE
+};
+
+const PYTHON: Grammar = Grammar {
+    name: "Python",
+    extensions: &["py", "pyw", "pyi"],
+    aliases: &["python", "python3", "py3", "gyp"],
+    keywords: "\
+        and as assert async await break class continue def del elif else except finally \
+        for from global if import in is lambda match case nonlocal not or pass raise \
+        return try while with yield self",
+    literals: "True False None",
+    triple_quotes: true,
+    capitalized_types: true,
+    ..SCRIPT
+};
+
+const JAVASCRIPT: Grammar = Grammar {
+    name: "JavaScript",
+    extensions: &["js", "jsx", "mjs", "cjs"],
+    aliases: &["javascript", "node"],
+    keywords: "\
+        async await break case catch class const continue debugger default delete do \
+        else export extends finally for from function if import in instanceof let new of \
+        return static super switch this throw try typeof var void while with yield",
+    literals: "true false null undefined NaN Infinity",
+    quotes: &['"', '\'', '`'],
+    ..C_LIKE
+};
+
+const TYPESCRIPT: Grammar = Grammar {
+    name: "TypeScript",
+    extensions: &["ts", "tsx", "mts", "cts"],
+    aliases: &["typescript"],
+    keywords: "\
+        abstract any as async await boolean break case catch class const continue \
+        declare default delete do else enum export extends finally for from function if \
+        implements import in instanceof interface keyof let namespace never new number \
+        of private protected public readonly return static string super switch this \
+        throw try type typeof unknown var void while yield",
+    ..JAVASCRIPT
+};
+
+const C: Grammar = Grammar {
+    name: "C",
+    extensions: &["c", "h"],
+    keywords: "\
+        auto break case char const continue default do double else enum extern float for \
+        goto if inline int long register restrict return short signed sizeof static \
+        struct switch typedef union unsigned void volatile while #include #define",
+    literals: "true false NULL",
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const CPP: Grammar = Grammar {
+    name: "C++",
+    extensions: &["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+    aliases: &["c++", "h++"],
+    keywords: "\
+        auto bool break case catch char class const constexpr continue default delete do \
+        double else enum explicit extern float for friend if inline int long namespace \
+        new noexcept operator override private protected public return short signed \
+        sizeof static struct switch template this throw try typedef typename union \
+        unsigned using virtual void volatile while #include #define",
+    literals: "true false nullptr NULL",
+    ..C
+};
+
+const CSHARP: Grammar = Grammar {
+    name: "C#",
+    extensions: &["cs"],
+    aliases: &["csharp", "c#"],
+    keywords: "\
+        abstract as async await base bool break case catch class const continue default \
+        delegate do double else enum event false finally float for foreach get if in int \
+        interface internal is long namespace new object out override private protected \
+        public readonly record ref return sealed set static string struct switch this \
+        throw try typeof using var virtual void while",
+    ..C_LIKE
+};
+
+const JAVA: Grammar = Grammar {
+    name: "Java",
+    extensions: &["java"],
+    aliases: &["jsp"],
+    keywords: "\
+        abstract boolean break byte case catch char class const continue default do \
+        double else enum extends final finally float for if implements import instanceof \
+        int interface long new package private protected public record return short \
+        static super switch synchronized this throw throws try var void volatile while",
+    ..C_LIKE
+};
+
+const KOTLIN: Grammar = Grammar {
+    name: "Kotlin",
+    extensions: &["kt", "kts"],
+    aliases: &["kotlin"],
+    keywords: "\
+        as break class companion const continue data do else enum fun for if import in \
+        interface internal is lateinit object open override package private protected \
+        public return sealed super suspend this throw try val var when while",
+    ..C_LIKE
+};
+
+const GO: Grammar = Grammar {
+    name: "Go",
+    extensions: &["go"],
+    aliases: &["golang"],
+    keywords: "\
+        break case chan const continue default defer else fallthrough for func go goto \
+        if import interface map package range return select struct switch type var",
+    literals: "true false nil iota",
+    quotes: &['"', '\'', '`'],
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const LUA: Grammar = Grammar {
+    name: "Lua",
+    extensions: &["lua"],
+    keywords: "\
+        and break do else elseif end for function goto if in local not or repeat return \
+        then until while",
+    literals: "true false nil",
+    line_comments: &["--"],
+    block_comment: Some(("--[[", "]]")),
+    ..SCRIPT
+};
+
+const SHELL: Grammar = Grammar {
+    name: "Shell",
+    extensions: &["sh", "bash", "zsh"],
+    aliases: &["shell", "console", "shellsession"],
+    keywords: "\
+        case do done elif else esac export fi for function if in local return then until \
+        while",
+    literals: "true false",
+    multiline_strings: true,
+    ..SCRIPT
+};
+
+const SQL: Grammar = Grammar {
+    name: "SQL",
+    extensions: &["sql"],
+    keywords: "\
+        add all alter and as asc between by case create delete desc distinct drop else \
+        end exists from group having in index inner insert into is join key left like \
+        limit not on or order primary references right select set table then union \
+        update values when where with",
+    literals: "true false null",
+    line_comments: &["--"],
+    case_insensitive: true,
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const JSON: Grammar = Grammar {
+    name: "JSON",
+    extensions: &["json"],
+    aliases: &["jsonc", "json5"],
+    line_comments: &[],
+    block_comment: None,
+    quotes: &['"'],
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const YAML: Grammar = Grammar {
+    name: "YAML",
+    extensions: &["yml", "yaml"],
+    literals: "true false null yes no",
+    ..SCRIPT
+};
+
+const TOML: Grammar = Grammar {
+    name: "TOML",
+    extensions: &["toml"],
+    aliases: &["ini"],
+    literals: "true false",
+    triple_quotes: true,
+    ..SCRIPT
+};
+
+const CSS: Grammar = Grammar {
+    name: "CSS",
+    extensions: &["css"],
+    keywords: "!important @media @import @keyframes",
+    literals: "",
+    line_comments: &[],
+    capitalized_types: false,
+    ..C_LIKE
+};
+
+const DIFF: Grammar = Grammar {
+    name: "Diff",
+    extensions: &["diff", "patch"],
+    diff: true,
+    ..C_LIKE
+};
+
+/// All languages that can be highlighted.
+pub const GRAMMARS: &[&Grammar] = &[
+    &RUST,
+    &PYTHON,
+    &JAVASCRIPT,
+    &TYPESCRIPT,
+    &C,
+    &CPP,
+    &CSHARP,
+    &JAVA,
+    &KOTLIN,
+    &GO,
+    &LUA,
+    &SHELL,
+    &SQL,
+    &JSON,
+    &YAML,
+    &TOML,
+    &CSS,
+    &DIFF,
+];
+
+/// Grammar of the language a file is written in, judging by its extension.
+pub fn grammar_for_filename(filename: &str) -> Option<&'static Grammar> {
+    let (_, extension) = filename.rsplit_once('.')?;
+    let extension = extension.to_lowercase();
+
+    GRAMMARS
+        .iter()
+        .find(|grammar| grammar.extensions.contains(&extension.as_str()))
+        .copied()
+}
+
+/// Grammar of the language tag of a markdown code block, which can be the
+/// name of the language, one of its aliases or an extension like Discord
+/// accepts.
+pub fn grammar_for_language(tag: &str) -> Option<&'static Grammar> {
+    let tag = tag.trim().to_lowercase();
+
+    GRAMMARS
+        .iter()
+        .find(|grammar| {
+            grammar.name.to_lowercase() == tag
+                || grammar.aliases.contains(&tag.as_str())
+                || grammar.extensions.contains(&tag.as_str())
+        })
+        .copied()
+}
+
+/// Splits code into tokens, which together span all of it.
+pub fn highlight(code: &str, grammar: &Grammar) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut index = 0;
+
+    while index < code.len() {
+        let rest = &code[index..];
+        let (kind, len) = if grammar.diff {
+            diff_line(rest)
+        } else {
+            next_token(rest, grammar)
+        };
+        let range = index..index + len;
+        index += len;
+
+        match tokens.last_mut() {
+            Some(last) if last.kind == kind => last.range.end = range.end,
+            _ => tokens.push(Token { range, kind }),
+        }
+    }
+
+    tokens
+}
+
+fn diff_line(rest: &str) -> (TokenKind, usize) {
+    let len = rest.find('\n').map_or(rest.len(), |index| index + 1);
+    let kind = match rest.as_bytes()[0] {
+        b'+' => TokenKind::Inserted,
+        b'-' => TokenKind::Deleted,
+        b'@' => TokenKind::Keyword,
+        _ => TokenKind::Plain,
+    };
+
+    (kind, len)
+}
+
+fn next_token(rest: &str, grammar: &Grammar) -> (TokenKind, usize) {
+    if let Some((start, end)) = grammar.block_comment {
+        if let Some(comment) = rest.strip_prefix(start) {
+            let len = comment
+                .find(end)
+                .map_or(rest.len(), |index| start.len() + index + end.len());
+            return (TokenKind::Comment, len);
+        }
+    }
+    if grammar
+        .line_comments
+        .iter()
+        .any(|comment| rest.starts_with(comment))
+    {
+        return (TokenKind::Comment, line_len(rest));
+    }
+
+    let Some(first) = rest.chars().next() else {
+        return (TokenKind::Plain, 0);
+    };
+    if first == '\'' && grammar.lifetimes {
+        if let Some(len) = lifetime_len(rest) {
+            return (TokenKind::Type, len);
+        }
+    }
+    if grammar.quotes.contains(&first) {
+        return (TokenKind::String, string_len(rest, first, grammar));
+    }
+    if first.is_ascii_digit() {
+        let len = rest
+            .find(|char: char| !(char.is_ascii_alphanumeric() || char == '_' || char == '.'))
+            .unwrap_or(rest.len());
+        return (TokenKind::Number, len);
+    }
+    // directives like `#include` and at-rules like `@media` are keywords
+    if first.is_alphabetic() || matches!(first, '_' | '#' | '@' | '!') {
+        let len = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, char)| !(char.is_alphanumeric() || *char == '_'))
+            .map_or(rest.len(), |(index, _)| index);
+        if first.is_alphabetic() || first == '_' || len > 1 {
+            let word = &rest[..len];
+            return (classify(word, &rest[len..], grammar), len);
+        }
+    }
+
+    (TokenKind::Plain, first.len_utf8())
+}
+
+fn classify(word: &str, after: &str, grammar: &Grammar) -> TokenKind {
+    let is = |words: &str| {
+        words.split_whitespace().any(|candidate| {
+            candidate == word || (grammar.case_insensitive && candidate.eq_ignore_ascii_case(word))
+        })
+    };
+
+    if is(grammar.keywords) {
+        TokenKind::Keyword
+    } else if is(grammar.literals) {
+        TokenKind::Literal
+    } else if !word.starts_with(char::is_alphabetic) && !word.starts_with('_') {
+        TokenKind::Plain
+    } else if after.trim_start().starts_with('(')
+        || ["!(", "![", "!{"]
+            .iter()
+            .any(|call| after.starts_with(call))
+    {
+        TokenKind::Function
+    } else if grammar.capitalized_types && word.starts_with(char::is_uppercase) {
+        TokenKind::Type
+    } else {
+        TokenKind::Plain
+    }
+}
+
+/// Length of a lifetime at the start of the text, `None` if it's a character
+/// instead.
+fn lifetime_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if !(first.is_alphabetic() || first == '_') {
+        return None;
+    }
+
+    let end = chars
+        .find(|(_, char)| !(char.is_alphanumeric() || *char == '_'))
+        .map_or(rest.len(), |(index, _)| index);
+    // `'a'` is a character
+    (!rest[end..].starts_with('\'')).then_some(end)
+}
+
+/// Length of the string at the start of the text, up to the end of the text
+/// if it's never closed.
+fn string_len(rest: &str, quote: char, grammar: &Grammar) -> usize {
+    if grammar.triple_quotes {
+        let triple = quote.to_string().repeat(3);
+        if let Some(string) = rest.strip_prefix(&triple) {
+            return string.find(&triple).map_or(rest.len(), |index| index + 6);
+        }
+    }
+
+    let multiline = grammar.multiline_strings || quote == '`';
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '\\' => {
+                chars.next();
+            }
+            // unclosed strings end with their line
+            '\n' if !multiline => return index,
+            char if char == quote => return index + char.len_utf8(),
+            _ => (),
+        }
+    }
+
+    rest.len()
+}
+
+fn line_len(rest: &str) -> usize {
+    rest.find('\n').unwrap_or(rest.len())
+}
+
+/// Highlighted code blocks of messages, so that they aren't highlighted again
+/// every frame.
+#[derive(Debug, Default)]
+pub struct HighlightCache {
+    messages: HashMap<Id<MessageMarker>, Vec<HighlightedBlock>>,
+}
+
+#[derive(Debug)]
+struct HighlightedBlock {
+    grammar: &'static str,
+    code: String,
+    tokens: Vec<Token>,
+}
+
+impl HighlightCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => self.messages.clear(),
+            // the code blocks of edited messages are highlighted again
+            DispatchEvent::MessageUpdate(update) => {
+                self.messages.remove(&update.id);
+            }
+            DispatchEvent::MessageDelete(delete) => {
+                self.messages.remove(&delete.id);
+            }
+            DispatchEvent::MessageDeleteBulk(delete) => {
+                for message_id in &delete.ids {
+                    self.messages.remove(message_id);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Tokens of a code block of a message, highlighting it unless that
+    /// already happened.
+    ///
+    /// Returns `None` if the block has no language tag, its language is
+    /// unknown or it's too large, it's then shown as plain text.
+    pub fn highlight(
+        &mut self,
+        message_id: Id<MessageMarker>,
+        language: Option<&str>,
+        code: &str,
+    ) -> Option<&[Token]> {
+        let grammar = grammar_for_language(language?)?;
+        if code.len() > MAX_HIGHLIGHTED_LEN {
+            return None;
+        }
+
+        let blocks = self.messages.entry(message_id).or_default();
+        let index = match blocks
+            .iter()
+            .position(|block| block.grammar == grammar.name && block.code == code)
+        {
+            Some(index) => index,
+            None => {
+                blocks.push(HighlightedBlock {
+                    grammar: grammar.name,
+                    code: code.to_owned(),
+                    tokens: highlight(code, grammar),
+                });
+                blocks.len() - 1
+            }
+        };
+
+        Some(&blocks[index].tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        gateway::{event::DispatchEvent, payload::incoming::MessageDelete},
+        id::Id,
+    };
+
+    use super::{
+        grammar_for_filename, grammar_for_language, highlight, Grammar, HighlightCache, TokenKind,
+        DIFF, MAX_HIGHLIGHTED_LEN, PYTHON, RUST, SQL,
+    };
+
+    /// The tokens of some code along with their text, ignoring whitespace.
+    fn tokens<'a>(code: &'a str, grammar: &Grammar) -> Vec<(&'a str, TokenKind)> {
+        let tokens = highlight(code, grammar);
+
+        // the tokens span all of the code
+        assert_eq!(tokens.first().map(|token| token.range.start), Some(0));
+        assert!(tokens
+            .windows(2)
+            .all(|pair| pair[0].range.end == pair[1].range.start));
+        assert_eq!(tokens.last().map(|token| token.range.end), Some(code.len()));
+
+        tokens
+            .into_iter()
+            .map(|token| (code[token.range].trim(), token.kind))
+            .filter(|(text, _)| !text.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn rust() {
+        let code =
+            "fn main(x: &'a str) {\n    println!(\"{}\\\"\", 'c'); // done\n    Vec::new(0x1f)\n}";
+
+        assert_eq!(
+            tokens(code, &RUST),
+            [
+                ("fn", TokenKind::Keyword),
+                ("main", TokenKind::Function),
+                ("(x: &", TokenKind::Plain),
+                ("'a", TokenKind::Type),
+                ("str) {", TokenKind::Plain),
+                ("println", TokenKind::Function),
+                ("!(", TokenKind::Plain),
+                ("\"{}\\\"\"", TokenKind::String),
+                (",", TokenKind::Plain),
+                ("'c'", TokenKind::String),
+                (");", TokenKind::Plain),
+                ("// done", TokenKind::Comment),
+                ("Vec", TokenKind::Type),
+                ("::", TokenKind::Plain),
+                ("new", TokenKind::Function),
+                ("(", TokenKind::Plain),
+                ("0x1f", TokenKind::Number),
+                (")\n}", TokenKind::Plain),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_languages() {
+        assert_eq!(
+            tokens(
+                "def f():\n    \"\"\"it's\ndocs\"\"\" # note\n    return None",
+                &PYTHON
+            ),
+            [
+                ("def", TokenKind::Keyword),
+                ("f", TokenKind::Function),
+                ("():", TokenKind::Plain),
+                ("\"\"\"it's\ndocs\"\"\"", TokenKind::String),
+                ("# note", TokenKind::Comment),
+                ("return", TokenKind::Keyword),
+                ("None", TokenKind::Literal),
+            ]
+        );
+        assert_eq!(
+            tokens("SELECT name FROM users -- all\nWHERE id = 'it", &SQL),
+            [
+                ("SELECT", TokenKind::Keyword),
+                ("name", TokenKind::Plain),
+                ("FROM", TokenKind::Keyword),
+                ("users", TokenKind::Plain),
+                ("-- all", TokenKind::Comment),
+                ("WHERE", TokenKind::Keyword),
+                ("id =", TokenKind::Plain),
+                ("'it", TokenKind::String),
+            ]
+        );
+        assert_eq!(
+            tokens("@@ -1 +1 @@\n-old\n+new\n same", &DIFF),
+            [
+                ("@@ -1 +1 @@", TokenKind::Keyword),
+                ("-old", TokenKind::Deleted),
+                ("+new", TokenKind::Inserted),
+                ("same", TokenKind::Plain),
+            ]
+        );
+    }
+
+    #[test]
+    fn grammars_by_filename() {
+        assert_eq!(
+            grammar_for_filename("main.RS").map(|g| g.name),
+            Some("Rust")
+        );
+        assert_eq!(
+            grammar_for_filename("a.tar.json").map(|g| g.name),
+            Some("JSON")
+        );
+        assert!(grammar_for_filename("notes.txt").is_none());
+        assert!(grammar_for_filename("Makefile").is_none());
+    }
+
+    #[test]
+    fn grammars_by_language() {
+        let name = |tag| grammar_for_language(tag).map(|grammar| grammar.name);
+
+        assert_eq!(name("rust"), Some("Rust"));
+        assert_eq!(name("rs"), Some("Rust"));
+        assert_eq!(name("C++"), Some("C++"));
+        assert_eq!(name("cs"), Some("C#"));
+        assert_eq!(name("golang"), Some("Go"));
+        assert_eq!(name("py"), Some("Python"));
+        assert_eq!(name("console"), Some("Shell"));
+        assert_eq!(name("ansi"), None);
+        assert_eq!(name(""), None);
+    }
+
+    #[test]
+    fn cache() {
+        let mut cache = HighlightCache::new();
+        let message_id = Id::new(1);
+
+        let tokens = cache
+            .highlight(message_id, Some("rs"), "let x = 1;")
+            .map(<[_]>::to_vec);
+        assert_eq!(tokens.as_deref(), Some(&highlight("let x = 1;", &RUST)[..]));
+        assert_eq!(
+            cache.highlight(message_id, Some("rust"), "let x = 1;"),
+            tokens.as_deref()
+        );
+        assert_eq!(cache.messages[&message_id].len(), 1);
+
+        // unknown languages and large blocks are left plain
+        assert!(cache.highlight(message_id, None, "let x = 1;").is_none());
+        assert!(cache
+            .highlight(message_id, Some("?"), "let x = 1;")
+            .is_none());
+        let large = "x".repeat(MAX_HIGHLIGHTED_LEN + 1);
+        assert!(cache.highlight(message_id, Some("rs"), &large).is_none());
+
+        cache.update(&DispatchEvent::MessageDelete(MessageDelete {
+            channel_id: Id::new(2),
+            guild_id: None,
+            id: message_id,
+        }));
+        assert!(cache.messages.is_empty());
+    }
+}
@@ -0,0 +1,653 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{
+    header,
+    multipart::{Form, Part},
+    Method, RequestBuilder, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use twilight_model::{
+    application::{
+        command::{Command, CommandIndex},
+        interaction::{
+            application_command::CommandData, message_component::MessageComponentInteractionData,
+            modal::ModalInteractionData, InteractionType,
+        },
+    },
+    channel::{
+        message::{AllowedMentions, MessageFlags, MessageReference, ReactionType},
+        thread::ThreadsListing,
+        Message,
+    },
+    gateway::payload::incoming::UserSettingsProtoType,
+    http::attachment::Attachment,
+    id::{
+        marker::{
+            ApplicationMarker, ChannelMarker, CommandVersionMarker, GuildMarker, InteractionMarker,
+            MessageMarker, StickerMarker, UserMarker,
+        },
+        Id,
+    },
+    user::{ProfileResponse, RelationshipType, User},
+    util::{ImageHash, Timestamp},
+};
+
+use crate::command::CommandScope;
+
+const API_URL: &str = "https://discord.com/api/v10";
+
+/// A client for the REST API, authenticated as the current user.
+///
+/// Cheap to clone, clones share the same connection pool.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    token: Arc<str>,
+}
+
+impl HttpClient {
+    pub fn new(token: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            token: token.trim().into(),
+        }
+    }
+
+    /// Acknowledges a channel up to the given message, marking it as read
+    /// on all sessions of the current user.
+    pub async fn ack_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+    ) -> Result<(), HttpError> {
+        let request = self
+            .request(
+                Method::POST,
+                &format!("/channels/{channel_id}/messages/{message_id}/ack"),
+            )
+            .json(&json!({ "token": null }));
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Fetches the most recent messages of a channel, the newest first.
+    pub async fn messages(&self, channel_id: Id<ChannelMarker>) -> Result<Vec<Message>, HttpError> {
+        let request = self
+            .request(Method::GET, &format!("/channels/{channel_id}/messages"))
+            .query(&[("limit", "50")]);
+
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Sends a message, uploading its attachments if there are any.
+    pub async fn create_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message: &CreateMessage,
+    ) -> Result<(), HttpError> {
+        let request = self.request(Method::POST, &format!("/channels/{channel_id}/messages"));
+
+        let request = if message.attachments.is_empty() {
+            request.json(message)
+        } else {
+            let mut form = Form::new().text("payload_json", serde_json::to_string(message)?);
+            for attachment in &message.attachments {
+                let part =
+                    Part::bytes(attachment.file.clone()).file_name(attachment.filename.clone());
+                form = form.part(format!("files[{}]", attachment.id), part);
+            }
+
+            request.multipart(form)
+        };
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Replaces the content of a message sent by the current user.
+    pub async fn update_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        content: &str,
+    ) -> Result<(), HttpError> {
+        let request = self
+            .request(
+                Method::PATCH,
+                &format!("/channels/{channel_id}/messages/{message_id}"),
+            )
+            .json(&json!({ "content": content }));
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Shows the current user as typing in a channel for the next 10 seconds,
+    /// or until they send a message.
+    pub async fn trigger_typing(&self, channel_id: Id<ChannelMarker>) -> Result<(), HttpError> {
+        send(self.request(Method::POST, &format!("/channels/{channel_id}/typing"))).await?;
+
+        Ok(())
+    }
+
+    /// Reacts to a message as the current user.
+    ///
+    /// Burst reactions use up one of the burst credits of the user.
+    pub async fn create_reaction(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+    ) -> Result<(), HttpError> {
+        let emoji = encode_emoji(emoji);
+        let request = self
+            .request(
+                Method::PUT,
+                &format!("/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/@me"),
+            )
+            .query(&[("location", "Message"), ("type", reaction_type(burst))]);
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Removes a reaction of the current user from a message.
+    pub async fn delete_own_reaction(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+    ) -> Result<(), HttpError> {
+        let emoji = encode_emoji(emoji);
+        let kind = reaction_type(burst);
+        let request = self.request(
+            Method::DELETE,
+            &format!("/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/{kind}/@me"),
+        );
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Fetches the first 100 users that reacted to a message with an emoji.
+    pub async fn reactions(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+    ) -> Result<Vec<User>, HttpError> {
+        let emoji = encode_emoji(emoji);
+        let request = self
+            .request(
+                Method::GET,
+                &format!("/channels/{channel_id}/messages/{message_id}/reactions/{emoji}"),
+            )
+            .query(&[("limit", "100"), ("type", reaction_type(burst))]);
+
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Fetches the public archived threads of a channel, most recently
+    /// archived first.
+    ///
+    /// Only threads archived before the given time are returned, to fetch
+    /// the next page.
+    pub async fn archived_threads(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        before: Option<Timestamp>,
+    ) -> Result<ThreadsListing, HttpError> {
+        let mut request = self
+            .request(
+                Method::GET,
+                &format!("/channels/{channel_id}/threads/archived/public"),
+            )
+            .query(&[("limit", "50")]);
+        if let Some(before) = before {
+            request = request.query(&[("before", before.iso_8601().to_string())]);
+        }
+
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Adds the current user to a thread.
+    pub async fn join_thread(&self, thread_id: Id<ChannelMarker>) -> Result<(), HttpError> {
+        send(self.request(
+            Method::PUT,
+            &format!("/channels/{thread_id}/thread-members/@me"),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the current user from a thread.
+    pub async fn leave_thread(&self, thread_id: Id<ChannelMarker>) -> Result<(), HttpError> {
+        send(self.request(
+            Method::DELETE,
+            &format!("/channels/{thread_id}/thread-members/@me"),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sends a friend request to the user with the given username.
+    pub async fn send_friend_request(&self, username: &str) -> Result<(), HttpError> {
+        let request = self
+            .request(Method::POST, "/users/@me/relationships")
+            .json(&json!({ "username": username, "discriminator": null }));
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Accepts the friend request of a user, or sends them one if they
+    /// haven't sent one.
+    pub async fn accept_friend_request(&self, user_id: Id<UserMarker>) -> Result<(), HttpError> {
+        self.put_relationship(user_id, None).await
+    }
+
+    pub async fn block_user(&self, user_id: Id<UserMarker>) -> Result<(), HttpError> {
+        self.put_relationship(user_id, Some(RelationshipType::Blocked))
+            .await
+    }
+
+    /// Removes a friend, declines or cancels a friend request, or unblocks a
+    /// user, depending on the relationship with them.
+    pub async fn remove_relationship(&self, user_id: Id<UserMarker>) -> Result<(), HttpError> {
+        send(self.request(
+            Method::DELETE,
+            &format!("/users/@me/relationships/{user_id}"),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Changes the name, icon or owner of a group DM.
+    ///
+    /// The group DM is updated once the gateway sends the `CHANNEL_UPDATE`
+    /// event.
+    pub async fn update_group_dm(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        update: &UpdateGroupDm,
+    ) -> Result<(), HttpError> {
+        send(
+            self.request(Method::PATCH, &format!("/channels/{channel_id}"))
+                .json(update),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Adds a user to a group DM, the current user must be friends with them.
+    pub async fn add_recipient(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<(), HttpError> {
+        send(self.request(
+            Method::PUT,
+            &format!("/channels/{channel_id}/recipients/{user_id}"),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes a user from a group DM, only the owner can remove other users.
+    pub async fn remove_recipient(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<(), HttpError> {
+        send(self.request(
+            Method::DELETE,
+            &format!("/channels/{channel_id}/recipients/{user_id}"),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetches the base64 encoded protobuf settings of the current user of
+    /// the given type.
+    pub async fn user_settings(&self, kind: UserSettingsProtoType) -> Result<String, HttpError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/users/@me/settings-proto/{}", u8::from(kind)),
+        );
+        let response: SettingsResponse = send(request).await?.json().await?;
+
+        Ok(response.settings)
+    }
+
+    /// Changes the protobuf settings of the current user, given as base64.
+    ///
+    /// Returns the settings after the change, which other sessions receive
+    /// through the `USER_SETTINGS_PROTO_UPDATE` gateway event.
+    pub async fn update_user_settings(
+        &self,
+        kind: UserSettingsProtoType,
+        settings: &str,
+    ) -> Result<String, HttpError> {
+        let request = self
+            .request(
+                Method::PATCH,
+                &format!("/users/@me/settings-proto/{}", u8::from(kind)),
+            )
+            .json(&json!({ "settings": settings }));
+        let response: SettingsResponse = send(request).await?.json().await?;
+
+        Ok(response.settings)
+    }
+
+    /// Fetches the public information of an application, such as the name of
+    /// a game that sets the activity of the current user.
+    pub async fn application_rpc(
+        &self,
+        application_id: Id<ApplicationMarker>,
+    ) -> Result<ApplicationRpc, HttpError> {
+        let request = self.request(Method::GET, &format!("/applications/{application_id}/rpc"));
+
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Fetches the profile of a user along with what they share with the
+    /// current user, as it's shown in the given guild if any.
+    pub async fn user_profile(
+        &self,
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> Result<ProfileResponse, HttpError> {
+        let mut request = self
+            .request(Method::GET, &format!("/users/{user_id}/profile"))
+            .query(&[
+                ("with_mutual_guilds", "true"),
+                ("with_mutual_friends", "true"),
+            ]);
+        if let Some(guild_id) = guild_id {
+            request = request.query(&[("guild_id", guild_id.to_string())]);
+        }
+
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Fetches the private note the current user keeps about a user, empty if
+    /// they have none.
+    pub async fn user_note(&self, user_id: Id<UserMarker>) -> Result<String, HttpError> {
+        let request = self.request(Method::GET, &format!("/users/@me/notes/{user_id}"));
+
+        match send(request).await {
+            Ok(response) => Ok(response.json::<NoteResponse>().await?.note),
+            // users without a note aren't found
+            Err(HttpError::Response { status, .. }) if status == StatusCode::NOT_FOUND => {
+                Ok(String::new())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Changes the private note the current user keeps about a user, an empty
+    /// note removes it.
+    ///
+    /// Other sessions receive the change through the `USER_NOTE_UPDATE`
+    /// gateway event.
+    pub async fn update_user_note(
+        &self,
+        user_id: Id<UserMarker>,
+        note: &str,
+    ) -> Result<(), HttpError> {
+        let request = self
+            .request(Method::PUT, &format!("/users/@me/notes/{user_id}"))
+            .json(&json!({ "note": note }));
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    /// Fetches the commands the current user can use in a scope along with
+    /// the applications they belong to.
+    pub async fn command_index(&self, scope: CommandScope) -> Result<CommandIndex, HttpError> {
+        let route = match scope {
+            CommandScope::Guild(guild_id) => {
+                format!("/guilds/{guild_id}/application-command-index")
+            }
+            CommandScope::Channel(channel_id) => {
+                format!("/channels/{channel_id}/application-command-index")
+            }
+        };
+
+        Ok(send(self.request(Method::GET, &route))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Sends an interaction, such as clicking a button or submitting a modal.
+    ///
+    /// The outcome is reported through the `INTERACTION_SUCCESS` and
+    /// `INTERACTION_FAILURE` gateway events.
+    pub async fn create_interaction(
+        &self,
+        interaction: &CreateInteraction,
+    ) -> Result<(), HttpError> {
+        send(
+            self.request(Method::POST, "/interactions")
+                .json(interaction),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn put_relationship(
+        &self,
+        user_id: Id<UserMarker>,
+        kind: Option<RelationshipType>,
+    ) -> Result<(), HttpError> {
+        let body = match kind {
+            Some(kind) => json!({ "type": u8::from(kind) }),
+            None => json!({}),
+        };
+        let request = self
+            .request(Method::PUT, &format!("/users/@me/relationships/{user_id}"))
+            .json(&body);
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    fn request(&self, method: Method, route: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{API_URL}{route}"))
+            .header(header::AUTHORIZATION, &*self.token)
+    }
+}
+
+/// Body of a message to send with [`HttpClient::create_message`].
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// Files to upload, only their metadata is part of the JSON body.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    pub content: String,
+    /// Echoed back in the resulting MESSAGE_CREATE event, used to match it
+    /// with the message that is shown while sending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Message this message replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_reference: Option<MessageReference>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sticker_ids: Vec<Id<StickerMarker>>,
+}
+
+/// Body of an interaction to send with [`HttpClient::create_interaction`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateInteraction {
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+    pub application_id: Id<ApplicationMarker>,
+    pub channel_id: Id<ChannelMarker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Id<GuildMarker>>,
+    pub data: InteractionData,
+    /// Message the component belongs to, for component interactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<Id<MessageMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_flags: Option<MessageFlags>,
+    /// Echoed back in the events reporting the outcome of the interaction.
+    pub nonce: String,
+    /// ID of the gateway session, the outcome is only sent to that session.
+    pub session_id: String,
+}
+
+/// Changes to a group DM to send with [`HttpClient::update_group_dm`], fields
+/// left to `None` are kept as is.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct UpdateGroupDm {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Data URI of the new icon, see [`image_data_uri`], or `Some(None)` to
+    /// remove the icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    /// User to transfer the ownership of the group DM to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Id<UserMarker>>,
+}
+
+#[derive(Deserialize)]
+struct SettingsResponse {
+    settings: String,
+}
+
+#[derive(Deserialize)]
+struct NoteResponse {
+    note: String,
+}
+
+/// Public information of an application.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApplicationRpc {
+    pub id: Id<ApplicationMarker>,
+    pub name: String,
+    pub icon: Option<ImageHash>,
+}
+
+/// Data of an interaction, depending on its type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum InteractionData {
+    /// Runs a command, or asks for the choices of the option being typed.
+    ApplicationCommand {
+        /// Version of the command the options were entered for.
+        version: Id<CommandVersionMarker>,
+        #[serde(flatten)]
+        data: Box<CommandData>,
+        application_command: Box<Command>,
+    },
+    MessageComponent(MessageComponentInteractionData),
+    ModalSubmit {
+        /// ID of the modal that was opened.
+        id: Id<InteractionMarker>,
+        #[serde(flatten)]
+        data: ModalInteractionData,
+    },
+}
+
+/// Encodes an image as a data URI, the format images are uploaded in when
+/// they're part of a JSON body.
+///
+/// Returns `None` if the image isn't a PNG, JPEG, GIF or WebP image.
+pub fn image_data_uri(image: &[u8]) -> Option<String> {
+    let content_type = match image {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => return None,
+    };
+
+    Some(format!(
+        "data:{content_type};base64,{}",
+        STANDARD.encode(image)
+    ))
+}
+
+/// Formats an emoji for use in a route, `name:id` for custom emojis and the
+/// percent-encoded emoji itself otherwise.
+fn encode_emoji(emoji: &ReactionType) -> String {
+    let emoji = match emoji {
+        ReactionType::Custom { id, name, .. } => {
+            format!("{}:{id}", name.as_deref().unwrap_or("_"))
+        }
+        ReactionType::Unicode { name } => name.clone(),
+    };
+
+    emoji
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+const fn reaction_type(burst: bool) -> &'static str {
+    if burst {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+/// Sends a request, turning unsuccessful status codes into errors.
+async fn send(request: RequestBuilder) -> Result<Response, HttpError> {
+    let response = request.send().await?;
+    let status = response.status();
+
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    Err(HttpError::Response {
+        status,
+        body: response.text().await.unwrap_or_default(),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub enum HttpError {
+    Reqwest(#[from] reqwest::Error),
+    Json(#[from] serde_json::Error),
+    #[error("request failed with status {status}: {body}")]
+    Response {
+        status: StatusCode,
+        body: String,
+    },
+}
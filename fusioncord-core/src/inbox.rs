@@ -0,0 +1,133 @@
+use std::cmp::Reverse;
+
+use twilight_model::{gateway::event::DispatchEvent, user::NotificationCenterItem};
+
+/// Keeps track of the items of the inbox of the current user, received since
+/// the client connected.
+///
+/// Must be fed every dispatch event through [`InboxStore::update`].
+#[derive(Debug, Default)]
+pub struct InboxStore {
+    /// Items sorted from newest to oldest.
+    items: Vec<NotificationCenterItem>,
+}
+
+impl InboxStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::NotificationCenterItemCreate(item) => {
+                self.items.retain(|existing| existing.id != item.id);
+                self.items.push(item.0.clone());
+                self.items.sort_by_key(|item| Reverse(item.id));
+            }
+            DispatchEvent::NotificationCenterItemCompleted(completed) => {
+                for item in &mut self.items {
+                    if item.item_enum == Some(completed.item_enum) {
+                        item.completed = true;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn items(&self) -> &[NotificationCenterItem] {
+        &self.items
+    }
+
+    /// Amount of items the current user hasn't seen yet.
+    pub fn unacked_count(&self) -> usize {
+        self.items.iter().filter(|item| !item.acked).count()
+    }
+
+    /// Marks every item as seen, such as once the inbox is opened.
+    ///
+    /// This is local only, the items aren't acknowledged to Discord and show
+    /// up as unseen again on other clients and after reconnecting.
+    pub fn ack_all(&mut self) {
+        for item in &mut self.items {
+            item.acked = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{NotificationCenterItemCompleted, NotificationCenterItemCreate},
+        },
+        id::Id,
+        user::NotificationCenterItem,
+    };
+
+    use super::InboxStore;
+
+    fn create(id: u64, item_enum: Option<u64>) -> DispatchEvent {
+        DispatchEvent::NotificationCenterItemCreate(Box::new(NotificationCenterItemCreate(
+            NotificationCenterItem {
+                acked: false,
+                body: format!("item {id}"),
+                bundle_id: None,
+                completed: false,
+                deeplink: "https://discord.com/settings".to_owned(),
+                guild_id: None,
+                icon_url: None,
+                id: Id::new(id),
+                item_enum,
+                kind: "go_live_push".to_owned(),
+                message_channel_id: None,
+                message_id: None,
+                other_user: None,
+            },
+        )))
+    }
+
+    fn ids(inbox: &InboxStore) -> Vec<u64> {
+        inbox.items().iter().map(|item| item.id.get()).collect()
+    }
+
+    #[test]
+    fn items() {
+        let mut inbox = InboxStore::new();
+        inbox.update(&create(2, None));
+        inbox.update(&create(3, None));
+        inbox.update(&create(1, None));
+        assert_eq!(ids(&inbox), [3, 2, 1]);
+
+        // items sent again replace the previous ones
+        inbox.update(&create(2, None));
+        assert_eq!(ids(&inbox), [3, 2, 1]);
+        assert_eq!(inbox.unacked_count(), 3);
+
+        inbox.ack_all();
+        assert_eq!(inbox.unacked_count(), 0);
+        inbox.update(&create(4, None));
+        assert_eq!(inbox.unacked_count(), 1);
+    }
+
+    #[test]
+    fn completed() {
+        let mut inbox = InboxStore::new();
+        inbox.update(&create(1, Some(5)));
+        inbox.update(&create(2, Some(6)));
+        inbox.update(&create(3, None));
+
+        inbox.update(&DispatchEvent::NotificationCenterItemCompleted(
+            NotificationCenterItemCompleted { item_enum: 5 },
+        ));
+
+        let completed = inbox
+            .items()
+            .iter()
+            .filter(|item| item.completed)
+            .map(|item| item.id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(completed, [1]);
+    }
+}
@@ -0,0 +1,580 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use twilight_model::{
+    application::interaction::{
+        application_command::CommandData, message_component::MessageComponentInteractionData,
+        modal::ModalInteractionData, InteractionType,
+    },
+    channel::Message,
+    gateway::{event::DispatchEvent, payload::incoming::InteractionModalCreate},
+    id::{
+        marker::{ChannelMarker, GuildMarker, InteractionMarker, MessageMarker},
+        Id,
+    },
+};
+
+use crate::{
+    cache,
+    command::CommandEntry,
+    http::{CreateInteraction, InteractionData},
+};
+
+/// An interaction sent by the current user that wasn't handled yet.
+#[derive(Debug, Clone)]
+pub struct PendingInteraction {
+    pub nonce: String,
+    /// ID assigned by Discord once the interaction was received.
+    pub id: Option<Id<InteractionMarker>>,
+    pub kind: InteractionType,
+    pub channel_id: Id<ChannelMarker>,
+    /// Message of the component that was used, `None` for modal submits and
+    /// commands.
+    pub message_id: Option<Id<MessageMarker>>,
+    /// Custom ID of the component, the title of the modal for modal submits
+    /// and the name of the command for commands.
+    pub custom_id: String,
+    /// Whether the application failed to respond to the interaction.
+    pub failed: bool,
+}
+
+/// Keeps track of the interactions sent by the current user and the modals
+/// opened in response.
+///
+/// Must be fed every dispatch event through [`InteractionStore::update`].
+#[derive(Debug, Default)]
+pub struct InteractionStore {
+    /// Interactions are only reported to the session they were sent from.
+    session_id: Option<String>,
+    pending: Vec<PendingInteraction>,
+    modal: Option<InteractionModalCreate>,
+}
+
+impl InteractionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.session_id = Some(ready.session_id.clone());
+                self.pending.clear();
+                self.modal = None;
+            }
+            DispatchEvent::InteractionQueued(queued) => {
+                if let Some(pending) = self.pending_mut(queued.nonce.as_deref()) {
+                    pending.id = Some(queued.id);
+                }
+            }
+            DispatchEvent::InteractionSuccess(success) => {
+                self.pending
+                    .retain(|pending| Some(&pending.nonce) != success.nonce.as_ref());
+            }
+            DispatchEvent::InteractionFailure(failure) => {
+                if let Some(nonce) = &failure.nonce {
+                    self.fail(nonce);
+                }
+            }
+            // only show modals opened by interactions of this session
+            DispatchEvent::InteractionModalCreate(modal)
+                if self.pending_mut(modal.nonce.as_deref()).is_some() =>
+            {
+                self.modal = Some(modal.clone());
+            }
+            _ => (),
+        }
+    }
+
+    /// Interactions of the components of a message that weren't handled yet,
+    /// or that failed.
+    pub fn pending(
+        &self,
+        message_id: Id<MessageMarker>,
+    ) -> impl Iterator<Item = &PendingInteraction> {
+        self.pending
+            .iter()
+            .filter(move |pending| pending.message_id == Some(message_id))
+    }
+
+    /// Commands run and modals submitted in a channel that weren't handled
+    /// yet, or that failed.
+    pub fn pending_in_channel(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> impl Iterator<Item = &PendingInteraction> {
+        self.pending.iter().filter(move |pending| {
+            matches!(
+                pending.kind,
+                InteractionType::ApplicationCommand | InteractionType::ModalSubmit
+            ) && pending.channel_id == channel_id
+        })
+    }
+
+    /// Takes the modal that was opened since the last call, if any.
+    pub fn take_modal(&mut self) -> Option<InteractionModalCreate> {
+        self.modal.take()
+    }
+
+    /// Starts an interaction with a component of a message.
+    ///
+    /// Returns the request to send, `None` if the gateway session isn't
+    /// known yet.
+    pub fn component_interaction(
+        &mut self,
+        message: &Message,
+        data: MessageComponentInteractionData,
+    ) -> Option<CreateInteraction> {
+        let session_id = self.session_id.clone()?;
+        // a failure is only shown until the component is used again
+        self.pending
+            .retain(|pending| !(pending.failed && pending.message_id == Some(message.id)));
+        let nonce = self.insert_pending(
+            InteractionType::MessageComponent,
+            message.channel_id,
+            Some(message.id),
+            data.custom_id.clone(),
+        );
+
+        Some(CreateInteraction {
+            kind: InteractionType::MessageComponent,
+            application_id: message
+                .application_id
+                .unwrap_or_else(|| message.author.id.cast()),
+            channel_id: message.channel_id,
+            guild_id: message.guild_id,
+            data: InteractionData::MessageComponent(data),
+            message_id: Some(message.id),
+            message_flags: message.flags,
+            nonce,
+            session_id,
+        })
+    }
+
+    /// Starts the submission of a modal.
+    ///
+    /// Returns the request to send, `None` if the gateway session isn't
+    /// known yet.
+    pub fn modal_submit(
+        &mut self,
+        modal: &InteractionModalCreate,
+        guild_id: Option<Id<GuildMarker>>,
+        data: ModalInteractionData,
+    ) -> Option<CreateInteraction> {
+        let session_id = self.session_id.clone()?;
+        let nonce = self.insert_pending(
+            InteractionType::ModalSubmit,
+            modal.channel_id,
+            None,
+            modal.title.clone(),
+        );
+
+        Some(CreateInteraction {
+            kind: InteractionType::ModalSubmit,
+            application_id: modal.application.id,
+            channel_id: modal.channel_id,
+            guild_id,
+            data: InteractionData::ModalSubmit { id: modal.id, data },
+            message_id: None,
+            message_flags: None,
+            nonce,
+            session_id,
+        })
+    }
+
+    /// Starts running a command.
+    ///
+    /// Returns the request to send, `None` if the gateway session isn't
+    /// known yet or the command doesn't belong to an application.
+    pub fn application_command(
+        &mut self,
+        channel_id: Id<ChannelMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        entry: &CommandEntry,
+        data: CommandData,
+    ) -> Option<CreateInteraction> {
+        let session_id = self.session_id.clone()?;
+        let mut interaction = command_interaction(
+            InteractionType::ApplicationCommand,
+            channel_id,
+            guild_id,
+            entry,
+            data,
+            String::new(),
+            session_id,
+        )?;
+        // only commands that are sent wait for a response
+        interaction.nonce = self.insert_pending(
+            InteractionType::ApplicationCommand,
+            channel_id,
+            None,
+            format!("/{}", entry.name()),
+        );
+
+        Some(interaction)
+    }
+
+    /// Asks the application of a command for the choices of the option being
+    /// typed.
+    ///
+    /// The choices are received through the
+    /// `APPLICATION_COMMAND_AUTOCOMPLETE_RESPONSE` gateway event with the
+    /// nonce of the returned request, `None` if the gateway session isn't
+    /// known yet.
+    pub fn autocomplete(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        entry: &CommandEntry,
+        data: CommandData,
+    ) -> Option<CreateInteraction> {
+        let session_id = self.session_id.clone()?;
+
+        command_interaction(
+            InteractionType::ApplicationCommandAutocomplete,
+            channel_id,
+            guild_id,
+            entry,
+            data,
+            new_nonce(),
+            session_id,
+        )
+    }
+
+    /// Forgets an interaction that failed.
+    pub fn dismiss(&mut self, nonce: &str) {
+        self.pending.retain(|pending| pending.nonce != nonce);
+    }
+
+    /// Marks an interaction as failed, for example when sending it failed.
+    pub fn fail(&mut self, nonce: &str) {
+        if let Some(pending) = self.pending_mut(Some(nonce)) {
+            pending.failed = true;
+        }
+    }
+
+    fn insert_pending(
+        &mut self,
+        kind: InteractionType,
+        channel_id: Id<ChannelMarker>,
+        message_id: Option<Id<MessageMarker>>,
+        custom_id: String,
+    ) -> String {
+        let nonce = new_nonce();
+
+        self.pending.push(PendingInteraction {
+            nonce: nonce.clone(),
+            id: None,
+            kind,
+            channel_id,
+            message_id,
+            custom_id,
+            failed: false,
+        });
+
+        nonce
+    }
+
+    fn pending_mut(&mut self, nonce: Option<&str>) -> Option<&mut PendingInteraction> {
+        let nonce = nonce?;
+        self.pending
+            .iter_mut()
+            .find(|pending| pending.nonce == nonce)
+    }
+}
+
+fn new_nonce() -> String {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    cache::nonce(since_epoch)
+}
+
+fn command_interaction(
+    kind: InteractionType,
+    channel_id: Id<ChannelMarker>,
+    guild_id: Option<Id<GuildMarker>>,
+    entry: &CommandEntry,
+    data: CommandData,
+    nonce: String,
+    session_id: String,
+) -> Option<CreateInteraction> {
+    Some(CreateInteraction {
+        kind,
+        application_id: entry.command.application_id?,
+        channel_id,
+        guild_id,
+        data: InteractionData::ApplicationCommand {
+            version: entry.command.version,
+            data: Box::new(data),
+            application_command: Box::new(entry.command.clone()),
+        },
+        message_id: None,
+        message_flags: None,
+        nonce,
+        session_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+    use twilight_model::{
+        application::interaction::{
+            message_component::MessageComponentInteractionData, modal::ModalInteractionData,
+            InteractionType,
+        },
+        channel::{message::component::ComponentType, Message},
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{
+                InteractionFailure, InteractionModalCreate, InteractionQueued, InteractionSuccess,
+                ModalApplication,
+            },
+        },
+        id::Id,
+    };
+
+    use super::InteractionStore;
+    use crate::{
+        command::{self, CommandEntry},
+        fixtures::{self, ready},
+    };
+
+    const MESSAGE_ID: u64 = 30;
+
+    /// Message with components sent by application 2.
+    fn message() -> Message {
+        fixtures::message(
+            MESSAGE_ID,
+            &json!({
+                "application_id": "2",
+                "author": {
+                    "avatar": null,
+                    "bot": true,
+                    "discriminator": "0",
+                    "id": "2",
+                    "username": "bot",
+                },
+                "content": "",
+                "guild_id": null,
+            }),
+        )
+    }
+
+    fn button(custom_id: &str) -> MessageComponentInteractionData {
+        MessageComponentInteractionData {
+            custom_id: custom_id.to_owned(),
+            component_type: ComponentType::Button,
+            values: Vec::new(),
+        }
+    }
+
+    fn modal(nonce: &str) -> DispatchEvent {
+        DispatchEvent::InteractionModalCreate(InteractionModalCreate {
+            application: ModalApplication {
+                icon: None,
+                id: Id::new(2),
+                name: "bot".to_owned(),
+            },
+            channel_id: Id::new(20),
+            components: Vec::new(),
+            custom_id: "modal".to_owned(),
+            id: Id::new(50),
+            nonce: Some(nonce.to_owned()),
+            title: "Modal".to_owned(),
+        })
+    }
+
+    /// Command of application 2, or of no application.
+    fn entry(application_id: Option<&str>) -> CommandEntry {
+        let command = serde_json::from_value(json!({
+            "application_id": application_id,
+            "description": "Pings",
+            "id": "3",
+            "name": "ping",
+            "options": [],
+            "type": 1,
+            "version": "4",
+        }))
+        .unwrap();
+
+        CommandEntry {
+            application_name: None,
+            command,
+            path: Vec::new(),
+            description: "Pings".to_owned(),
+            options: Vec::new(),
+        }
+    }
+
+    /// Custom IDs of the pending interactions of the message, and whether
+    /// they failed.
+    fn pending(store: &InteractionStore) -> Vec<(&str, bool)> {
+        store
+            .pending(Id::new(MESSAGE_ID))
+            .map(|pending| (pending.custom_id.as_str(), pending.failed))
+            .collect()
+    }
+
+    #[test]
+    fn requires_session() {
+        let mut store = InteractionStore::new();
+        assert!(store
+            .component_interaction(&message(), button("a"))
+            .is_none());
+        assert!(pending(&store).is_empty());
+
+        store.update(&ready());
+        let request = store
+            .component_interaction(&message(), button("a"))
+            .unwrap();
+        assert_eq!(request.session_id, "session");
+        assert_eq!(request.application_id, Id::new(2));
+        assert_eq!(request.message_id, Some(Id::new(MESSAGE_ID)));
+    }
+
+    #[test]
+    fn transitions() {
+        let mut store = InteractionStore::new();
+        store.update(&ready());
+        let first = store
+            .component_interaction(&message(), button("a"))
+            .unwrap()
+            .nonce;
+        let second = store
+            .component_interaction(&message(), button("b"))
+            .unwrap()
+            .nonce;
+        assert_ne!(first, second);
+        assert_eq!(pending(&store), [("a", false), ("b", false)]);
+
+        store.update(&DispatchEvent::InteractionQueued(InteractionQueued {
+            id: Id::new(40),
+            nonce: Some(first.clone()),
+        }));
+        let queued = store.pending(Id::new(MESSAGE_ID)).next().unwrap();
+        assert_eq!(queued.id, Some(Id::new(40)));
+
+        store.update(&DispatchEvent::InteractionSuccess(InteractionSuccess {
+            id: Id::new(40),
+            nonce: Some(first),
+        }));
+        assert_eq!(pending(&store), [("b", false)]);
+
+        store.update(&DispatchEvent::InteractionFailure(InteractionFailure {
+            id: Id::new(41),
+            nonce: Some(second.clone()),
+        }));
+        assert_eq!(pending(&store), [("b", true)]);
+
+        // failures are shown until the components are used again
+        let third = store
+            .component_interaction(&message(), button("c"))
+            .unwrap()
+            .nonce;
+        assert_eq!(pending(&store), [("c", false)]);
+
+        // dismissing failures that were cleared already does nothing
+        store.dismiss(&second);
+        store.fail(&third);
+        assert_eq!(pending(&store), [("c", true)]);
+
+        // a new session forgets about the previous interactions
+        store.update(&ready());
+        assert!(pending(&store).is_empty());
+    }
+
+    #[test]
+    fn nonce_matching() {
+        let mut store = InteractionStore::new();
+        store.update(&ready());
+        let nonce = store
+            .component_interaction(&message(), button("a"))
+            .unwrap()
+            .nonce;
+
+        // events of interactions sent by other sessions are ignored
+        for event in [
+            DispatchEvent::InteractionSuccess(InteractionSuccess {
+                id: Id::new(40),
+                nonce: Some("other".to_owned()),
+            }),
+            DispatchEvent::InteractionFailure(InteractionFailure {
+                id: Id::new(40),
+                nonce: None,
+            }),
+            modal("other"),
+        ] {
+            store.update(&event);
+        }
+        assert_eq!(pending(&store), [("a", false)]);
+        assert!(store.take_modal().is_none());
+
+        store.update(&modal(&nonce));
+        let modal = store.take_modal().unwrap();
+        assert_eq!(modal.nonce.as_ref(), Some(&nonce));
+        assert!(store.take_modal().is_none());
+
+        let data = ModalInteractionData {
+            components: Vec::new(),
+            custom_id: modal.custom_id.clone(),
+        };
+        let request = store.modal_submit(&modal, None, data).unwrap();
+        assert_eq!(request.kind, InteractionType::ModalSubmit);
+        assert_ne!(request.nonce, nonce);
+    }
+
+    #[test]
+    fn pending_in_channel() {
+        let pending_in_channel = |store: &InteractionStore| {
+            store
+                .pending_in_channel(Id::new(20))
+                .map(|pending| (pending.custom_id.clone(), pending.failed))
+                .collect::<Vec<_>>()
+        };
+        let mut store = InteractionStore::new();
+        store.update(&ready());
+
+        // commands of no application can't be sent, so they're not pending
+        let orphan = entry(None);
+        let data = command::command_data(&orphan, &HashMap::new()).unwrap();
+        assert!(store
+            .application_command(Id::new(20), None, &orphan, data)
+            .is_none());
+        assert!(pending_in_channel(&store).is_empty());
+
+        let entry = entry(Some("2"));
+        let data = command::command_data(&entry, &HashMap::new()).unwrap();
+        let command = store
+            .application_command(Id::new(20), None, &entry, data)
+            .unwrap()
+            .nonce;
+
+        // component interactions are shown along with their message instead
+        let nonce = store
+            .component_interaction(&message(), button("a"))
+            .unwrap()
+            .nonce;
+        store.update(&modal(&nonce));
+        let modal = store.take_modal().unwrap();
+        let data = ModalInteractionData {
+            components: Vec::new(),
+            custom_id: modal.custom_id.clone(),
+        };
+        let submit = store.modal_submit(&modal, None, data).unwrap().nonce;
+        assert_eq!(
+            pending_in_channel(&store),
+            [("/ping".to_owned(), false), ("Modal".to_owned(), false)]
+        );
+
+        store.update(&DispatchEvent::InteractionSuccess(InteractionSuccess {
+            id: Id::new(40),
+            nonce: Some(command),
+        }));
+        store.fail(&submit);
+        assert_eq!(pending_in_channel(&store), [("Modal".to_owned(), true)]);
+    }
+}
@@ -1,3 +1,35 @@
+pub mod attachment;
+pub mod cache;
+pub mod cdn;
 pub mod client;
+pub mod command;
+pub mod completion;
 pub mod connection;
+#[cfg(target_os = "linux")]
+pub mod desktop_notification;
+pub mod emoji;
+#[cfg(test)]
+mod fixtures;
+pub mod guild_folder;
+pub mod highlight;
+pub mod http;
+pub mod inbox;
+pub mod interaction;
+pub mod lottie;
+pub mod markdown;
 pub mod message;
+pub mod notification;
+pub mod presence;
+pub mod profile;
+pub mod proto;
+pub mod reaction;
+pub mod read_state;
+pub mod relationship;
+pub mod role;
+#[cfg(unix)]
+pub mod rpc;
+pub mod session;
+pub mod thread;
+pub mod typing;
+pub mod user_presence;
+pub mod user_settings;
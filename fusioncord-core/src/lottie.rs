@@ -0,0 +1,972 @@
+//! Renderer for the subset of Lottie animations used by stickers.
+//!
+//! Shape layers with paths, ellipses and rectangles are supported along with
+//! their fills, strokes and transforms, nested groups, parenting and
+//! precompositions. Anything else, like gradients, masks and trim paths, is
+//! skipped. Frames are rendered to flattened paths that can be painted
+//! directly.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Line segments each curve of a path is flattened to.
+const CURVE_STEPS: usize = 8;
+/// Handle length of a cubic bezier approximating a quarter circle.
+const KAPPA: f32 = 0.552_284_8;
+/// Deepest parent chain that's followed, guarding against cycles.
+const MAX_PARENT_DEPTH: usize = 16;
+
+/// Parsed Lottie animation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Animation {
+    #[serde(rename = "w")]
+    pub width: f32,
+    #[serde(rename = "h")]
+    pub height: f32,
+    #[serde(rename = "fr")]
+    pub frame_rate: f32,
+    #[serde(rename = "ip")]
+    pub in_point: f32,
+    #[serde(rename = "op")]
+    pub out_point: f32,
+    #[serde(default)]
+    layers: Vec<Layer>,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+/// Filled or stroked paths of a rendered frame, in the coordinates of the
+/// animation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub paths: Vec<Path>,
+    pub style: Style,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    /// Premultiplied RGBA color the paths are filled with.
+    Fill([u8; 4]),
+    Stroke {
+        color: [u8; 4],
+        width: f32,
+    },
+}
+
+/// Path flattened to line segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    pub points: Vec<[f32; 2]>,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Asset {
+    id: String,
+    /// Only precompositions have layers, images don't.
+    #[serde(default)]
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Layer {
+    #[serde(rename = "ty")]
+    kind: u8,
+    #[serde(rename = "ind")]
+    index: Option<i64>,
+    parent: Option<i64>,
+    #[serde(rename = "ip", default)]
+    in_point: f32,
+    #[serde(rename = "op", default = "forever")]
+    out_point: f32,
+    /// Frame the layer starts at, offsetting the frames of a
+    /// precomposition.
+    #[serde(rename = "st", default)]
+    start_time: f32,
+    #[serde(rename = "ks", default)]
+    transform: Transform,
+    #[serde(default)]
+    shapes: Vec<ShapeItem>,
+    #[serde(rename = "refId")]
+    ref_id: Option<String>,
+    #[serde(rename = "hd", default)]
+    hidden: bool,
+}
+
+const PRECOMPOSITION_LAYER: u8 = 0;
+const SHAPE_LAYER: u8 = 4;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "ty")]
+enum ShapeItem {
+    #[serde(rename = "gr")]
+    Group {
+        #[serde(rename = "it", default)]
+        items: Vec<ShapeItem>,
+        #[serde(rename = "hd", default)]
+        hidden: bool,
+    },
+    #[serde(rename = "sh")]
+    Path {
+        #[serde(rename = "ks")]
+        shape: Property<Bezier>,
+    },
+    #[serde(rename = "el")]
+    Ellipse {
+        #[serde(rename = "p")]
+        position: Property<Values>,
+        #[serde(rename = "s")]
+        size: Property<Values>,
+    },
+    #[serde(rename = "rc")]
+    Rect {
+        #[serde(rename = "p")]
+        position: Property<Values>,
+        #[serde(rename = "s")]
+        size: Property<Values>,
+    },
+    #[serde(rename = "fl")]
+    Fill {
+        #[serde(rename = "c")]
+        color: Property<Values>,
+        #[serde(rename = "o")]
+        opacity: Property<Values>,
+    },
+    #[serde(rename = "st")]
+    Stroke {
+        #[serde(rename = "c")]
+        color: Property<Values>,
+        #[serde(rename = "o")]
+        opacity: Property<Values>,
+        #[serde(rename = "w")]
+        width: Property<Values>,
+    },
+    #[serde(rename = "tr")]
+    Transform(Transform),
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Transform {
+    #[serde(rename = "a")]
+    anchor: Option<Property<Values>>,
+    #[serde(rename = "p")]
+    position: Option<Position>,
+    /// Scale in percent.
+    #[serde(rename = "s")]
+    scale: Option<Property<Values>>,
+    /// Clockwise rotation in degrees.
+    #[serde(rename = "r")]
+    rotation: Option<Property<Values>>,
+    /// Opacity in percent.
+    #[serde(rename = "o")]
+    opacity: Option<Property<Values>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Position {
+    /// Position with its dimensions animated separately.
+    Split {
+        x: Property<Values>,
+        y: Property<Values>,
+    },
+    Combined(Property<Values>),
+}
+
+/// Value that's either static or animated with keyframes.
+#[derive(Debug, Clone, Deserialize)]
+struct Property<T> {
+    #[serde(rename = "k")]
+    value: Value<T>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Value<T> {
+    Animated(Vec<Keyframe<T>>),
+    Static(T),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Keyframe<T> {
+    #[serde(rename = "t")]
+    time: f32,
+    #[serde(rename = "s")]
+    start: Option<T>,
+    /// End value of older animations, newer ones use the start value of the
+    /// next keyframe.
+    #[serde(rename = "e")]
+    end: Option<T>,
+    /// Whether the value jumps to the next keyframe instead of easing to it.
+    #[serde(rename = "h", default)]
+    hold: u8,
+    #[serde(rename = "o")]
+    ease_out: Option<Easing>,
+    #[serde(rename = "i")]
+    ease_in: Option<Easing>,
+}
+
+/// Control point of the cubic bezier easing between two keyframes.
+#[derive(Debug, Clone, Deserialize)]
+struct Easing {
+    x: Values,
+    y: Values,
+}
+
+/// Scalar or multidimensional value, scalars are sometimes wrapped in an
+/// array.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(from = "RawValues")]
+struct Values(Vec<f32>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValues {
+    One(f32),
+    Many(Vec<f32>),
+}
+
+impl From<RawValues> for Values {
+    fn from(values: RawValues) -> Self {
+        match values {
+            RawValues::One(value) => Self(vec![value]),
+            RawValues::Many(values) => Self(values),
+        }
+    }
+}
+
+impl Values {
+    fn get(&self, index: usize, default: f32) -> f32 {
+        self.0.get(index).copied().unwrap_or(default)
+    }
+
+    fn point(&self, default: f32) -> [f32; 2] {
+        [self.get(0, default), self.get(1, default)]
+    }
+}
+
+/// Cubic bezier path, with tangents relative to their vertex. Keyframes wrap
+/// it in an array.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(from = "RawBezier")]
+struct Bezier {
+    closed: bool,
+    vertices: Vec<[f32; 2]>,
+    in_tangents: Vec<[f32; 2]>,
+    out_tangents: Vec<[f32; 2]>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawBezier {
+    One(BezierData),
+    Many(Vec<BezierData>),
+}
+
+#[derive(Default, Deserialize)]
+struct BezierData {
+    #[serde(rename = "c", default)]
+    closed: bool,
+    #[serde(rename = "v", default)]
+    vertices: Vec<[f32; 2]>,
+    #[serde(rename = "i", default)]
+    in_tangents: Vec<[f32; 2]>,
+    #[serde(rename = "o", default)]
+    out_tangents: Vec<[f32; 2]>,
+}
+
+impl From<RawBezier> for Bezier {
+    fn from(bezier: RawBezier) -> Self {
+        let data = match bezier {
+            RawBezier::One(data) => data,
+            RawBezier::Many(data) => data.into_iter().next().unwrap_or_default(),
+        };
+
+        Self {
+            closed: data.closed,
+            vertices: data.vertices,
+            in_tangents: data.in_tangents,
+            out_tangents: data.out_tangents,
+        }
+    }
+}
+
+/// Values that can be eased between keyframes.
+trait Lerp: Clone + Default {
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for Values {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self(
+            self.0
+                .iter()
+                .zip(&other.0)
+                .map(|(from, to)| lerp(*from, *to, t))
+                .collect(),
+        )
+    }
+}
+
+impl Lerp for Bezier {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        // paths can only be morphed into paths with as many vertices
+        if self.vertices.len() != other.vertices.len() {
+            return self.clone();
+        }
+        let points = |from: &[[f32; 2]], to: &[[f32; 2]]| {
+            from.iter()
+                .zip(to)
+                .map(|(from, to)| lerp_point(*from, *to, t))
+                .collect()
+        };
+
+        Self {
+            closed: self.closed,
+            vertices: points(&self.vertices, &other.vertices),
+            in_tangents: points(&self.in_tangents, &other.in_tangents),
+            out_tangents: points(&self.out_tangents, &other.out_tangents),
+        }
+    }
+}
+
+impl<T: Lerp> Property<T> {
+    fn at(&self, frame: f32) -> T {
+        let keyframes = match &self.value {
+            Value::Static(value) => return value.clone(),
+            // malformed animations can have properties without keyframes
+            Value::Animated(keyframes) if keyframes.is_empty() => return T::default(),
+            Value::Animated(keyframes) => keyframes,
+        };
+
+        let index = keyframes
+            .iter()
+            .rposition(|keyframe| keyframe.time <= frame)
+            .unwrap_or(0);
+        let keyframe = &keyframes[index];
+        // the last keyframe of older animations only has the end value of
+        // the previous one
+        let start = keyframe
+            .start
+            .clone()
+            .or_else(|| index.checked_sub(1).and_then(|i| keyframes[i].end.clone()))
+            .unwrap_or_default();
+
+        let Some(next) = keyframes.get(index + 1) else {
+            return start;
+        };
+        let end = keyframe.end.as_ref().or(next.start.as_ref());
+        let Some(end) = end.filter(|_| keyframe.hold == 0 && next.time > keyframe.time) else {
+            return start;
+        };
+
+        let progress = ((frame - keyframe.time) / (next.time - keyframe.time)).clamp(0., 1.);
+        let progress = match (&keyframe.ease_out, &keyframe.ease_in) {
+            (Some(out), Some(ease_in)) => ease(out, ease_in, progress),
+            _ => progress,
+        };
+        start.lerp(end, progress)
+    }
+}
+
+impl Property<Values> {
+    fn scalar(&self, frame: f32, default: f32) -> f32 {
+        self.at(frame).get(0, default)
+    }
+}
+
+/// Eases the progress between two keyframes along a cubic bezier going from
+/// `(0, 0)` to `(1, 1)`.
+fn ease(out: &Easing, ease_in: &Easing, progress: f32) -> f32 {
+    let (x1, y1) = (out.x.get(0, 0.), out.y.get(0, 0.));
+    let (x2, y2) = (ease_in.x.get(0, 1.), ease_in.y.get(0, 1.));
+    let bezier = |a: f32, b: f32, t: f32| {
+        3. * (1. - t) * (1. - t) * t * a + 3. * (1. - t) * t * t * b + t * t * t
+    };
+
+    // x grows monotonically for valid easings, so the t matching the
+    // progress is found by bisection
+    let (mut low, mut high) = (0., 1.);
+    for _ in 0..20 {
+        let middle = (low + high) / 2.;
+        if bezier(x1, x2, middle) < progress {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    bezier(y1, y2, (low + high) / 2.)
+}
+
+/// 2D affine transformation, mapping `(x, y)` to
+/// `(a * x + c * y + e, b * x + d * y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Matrix([f32; 6]);
+
+impl Matrix {
+    const IDENTITY: Self = Self([1., 0., 0., 1., 0., 0.]);
+
+    fn translate([x, y]: [f32; 2]) -> Self {
+        Self([1., 0., 0., 1., x, y])
+    }
+
+    fn scale([x, y]: [f32; 2]) -> Self {
+        Self([x, 0., 0., y, 0., 0.])
+    }
+
+    fn rotate(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self([cos, sin, -sin, cos, 0., 0.])
+    }
+
+    /// Applies `other` before this transformation.
+    fn then(self, other: Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [oa, ob, oc, od, oe, of] = other.0;
+
+        Self([
+            a * oa + c * ob,
+            b * oa + d * ob,
+            a * oc + c * od,
+            b * oc + d * od,
+            a * oe + c * of + e,
+            b * oe + d * of + f,
+        ])
+    }
+
+    fn apply(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let [a, b, c, d, e, f] = self.0;
+        [a * x + c * y + e, b * x + d * y + f]
+    }
+
+    /// Factor the area of a shape is scaled by, used to scale stroke widths.
+    fn scale_factor(&self) -> f32 {
+        let [a, b, c, d, ..] = self.0;
+        (a * d - b * c).abs().sqrt()
+    }
+}
+
+impl Transform {
+    fn matrix(&self, frame: f32) -> Matrix {
+        let anchor = self
+            .anchor
+            .as_ref()
+            .map_or([0., 0.], |anchor| anchor.at(frame).point(0.));
+        let position = match &self.position {
+            Some(Position::Split { x, y }) => [x.scalar(frame, 0.), y.scalar(frame, 0.)],
+            Some(Position::Combined(position)) => position.at(frame).point(0.),
+            None => [0., 0.],
+        };
+        let scale = self
+            .scale
+            .as_ref()
+            .map_or([100., 100.], |scale| scale.at(frame).point(100.));
+        let rotation = self
+            .rotation
+            .as_ref()
+            .map_or(0., |rotation| rotation.scalar(frame, 0.));
+
+        Matrix::translate(position)
+            .then(Matrix::rotate(rotation))
+            .then(Matrix::scale([scale[0] / 100., scale[1] / 100.]))
+            .then(Matrix::translate([-anchor[0], -anchor[1]]))
+    }
+
+    fn opacity(&self, frame: f32) -> f32 {
+        self.opacity
+            .as_ref()
+            .map_or(1., |opacity| opacity.scalar(frame, 100.) / 100.)
+    }
+}
+
+impl Animation {
+    pub fn parse(json: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(json)
+    }
+
+    /// How long the animation takes before it loops.
+    pub fn duration(&self) -> Duration {
+        let frames = self.out_point - self.in_point;
+        if frames <= 0. || self.frame_rate <= 0. {
+            return Duration::ZERO;
+        }
+
+        Duration::from_secs_f32(frames / self.frame_rate)
+    }
+
+    /// The frame shown after the animation has been playing in a loop for
+    /// some time.
+    pub fn frame_at(&self, elapsed: Duration) -> f32 {
+        let frames = self.out_point - self.in_point;
+        if frames <= 0. {
+            return self.in_point;
+        }
+
+        self.in_point + (elapsed.as_secs_f32() * self.frame_rate) % frames
+    }
+
+    /// Renders a frame, with the shapes that are drawn first coming first.
+    pub fn render(&self, frame: f32) -> Vec<Shape> {
+        let mut shapes = Vec::new();
+        self.render_layers(&self.layers, frame, Matrix::IDENTITY, 1., &mut shapes);
+        shapes
+    }
+
+    fn render_layers(
+        &self,
+        layers: &[Layer],
+        frame: f32,
+        matrix: Matrix,
+        opacity: f32,
+        shapes: &mut Vec<Shape>,
+    ) {
+        // the first layer is shown on top
+        for layer in layers.iter().rev() {
+            if layer.hidden || frame < layer.in_point || frame >= layer.out_point {
+                continue;
+            }
+
+            let matrix = matrix.then(layer_matrix(layers, layer, frame, 0));
+            let opacity = opacity * layer.transform.opacity(frame);
+
+            match layer.kind {
+                SHAPE_LAYER => render_items(&layer.shapes, frame, matrix, opacity, shapes),
+                PRECOMPOSITION_LAYER => {
+                    let asset = self
+                        .assets
+                        .iter()
+                        .find(|asset| Some(&asset.id) == layer.ref_id.as_ref());
+                    if let Some(asset) = asset {
+                        let frame = frame - layer.start_time;
+                        self.render_layers(&asset.layers, frame, matrix, opacity, shapes);
+                    }
+                }
+                // null layers only exist to be parented to
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Transformation of a layer, including the ones of its parents.
+fn layer_matrix(layers: &[Layer], layer: &Layer, frame: f32, depth: usize) -> Matrix {
+    let matrix = layer.transform.matrix(frame);
+    let parent = layer
+        .parent
+        .filter(|_| depth < MAX_PARENT_DEPTH)
+        .and_then(|parent| layers.iter().find(|layer| layer.index == Some(parent)));
+
+    match parent {
+        Some(parent) => layer_matrix(layers, parent, frame, depth + 1).then(matrix),
+        None => matrix,
+    }
+}
+
+/// Renders the items of a shape layer or group.
+///
+/// Fills and strokes apply to all paths listed before them, including the
+/// ones in nested groups, while the first items are shown on top.
+fn render_items(
+    items: &[ShapeItem],
+    frame: f32,
+    matrix: Matrix,
+    opacity: f32,
+    shapes: &mut Vec<Shape>,
+) {
+    let (matrix, opacity) = match group_transform(items) {
+        Some(transform) => (
+            matrix.then(transform.matrix(frame)),
+            opacity * transform.opacity(frame),
+        ),
+        None => (matrix, opacity),
+    };
+
+    for (index, item) in items.iter().enumerate().rev() {
+        let style = match item {
+            ShapeItem::Group {
+                items,
+                hidden: false,
+            } => {
+                render_items(items, frame, matrix, opacity, shapes);
+                continue;
+            }
+            ShapeItem::Fill {
+                color,
+                opacity: fill_opacity,
+            } => Style::Fill(color_at(color, fill_opacity, frame, opacity)),
+            ShapeItem::Stroke {
+                color,
+                opacity: stroke_opacity,
+                width,
+            } => Style::Stroke {
+                color: color_at(color, stroke_opacity, frame, opacity),
+                width: width.scalar(frame, 1.) * matrix.scale_factor(),
+            },
+            _ => continue,
+        };
+
+        let mut paths = Vec::new();
+        collect_paths(&items[..index], frame, matrix, &mut paths);
+        if !paths.is_empty() {
+            shapes.push(Shape { paths, style });
+        }
+    }
+}
+
+/// Flattens the paths of a group and its nested groups.
+fn collect_paths(items: &[ShapeItem], frame: f32, matrix: Matrix, paths: &mut Vec<Path>) {
+    for item in items {
+        let bezier = match item {
+            ShapeItem::Group {
+                items,
+                hidden: false,
+            } => {
+                let matrix = match group_transform(items) {
+                    Some(transform) => matrix.then(transform.matrix(frame)),
+                    None => matrix,
+                };
+                collect_paths(items, frame, matrix, paths);
+                continue;
+            }
+            ShapeItem::Path { shape } => shape.at(frame),
+            ShapeItem::Ellipse { position, size } => {
+                ellipse(position.at(frame).point(0.), size.at(frame).point(0.))
+            }
+            ShapeItem::Rect { position, size } => {
+                rect(position.at(frame).point(0.), size.at(frame).point(0.))
+            }
+            _ => continue,
+        };
+
+        if let Some(path) = flatten(&bezier, matrix) {
+            paths.push(path);
+        }
+    }
+}
+
+/// The transform of a group is its last item.
+fn group_transform(items: &[ShapeItem]) -> Option<&Transform> {
+    items.iter().rev().find_map(|item| match item {
+        ShapeItem::Transform(transform) => Some(transform),
+        _ => None,
+    })
+}
+
+fn color_at(
+    color: &Property<Values>,
+    opacity: &Property<Values>,
+    frame: f32,
+    alpha: f32,
+) -> [u8; 4] {
+    let color = color.at(frame);
+    let alpha = (alpha * opacity.scalar(frame, 100.) / 100. * color.get(3, 1.)).clamp(0., 1.);
+    let channel = |index| (color.get(index, 0.).clamp(0., 1.) * alpha * 255.).round() as u8;
+
+    [
+        channel(0),
+        channel(1),
+        channel(2),
+        (alpha * 255.).round() as u8,
+    ]
+}
+
+fn ellipse([x, y]: [f32; 2], [width, height]: [f32; 2]) -> Bezier {
+    let (rx, ry) = (width / 2., height / 2.);
+    let (hx, hy) = (rx * KAPPA, ry * KAPPA);
+
+    Bezier {
+        closed: true,
+        vertices: vec![[x, y - ry], [x + rx, y], [x, y + ry], [x - rx, y]],
+        in_tangents: vec![[-hx, 0.], [0., -hy], [hx, 0.], [0., hy]],
+        out_tangents: vec![[hx, 0.], [0., hy], [-hx, 0.], [0., -hy]],
+    }
+}
+
+fn rect([x, y]: [f32; 2], [width, height]: [f32; 2]) -> Bezier {
+    let (left, right) = (x - width / 2., x + width / 2.);
+    let (top, bottom) = (y - height / 2., y + height / 2.);
+
+    Bezier {
+        closed: true,
+        vertices: vec![[left, top], [right, top], [right, bottom], [left, bottom]],
+        in_tangents: Vec::new(),
+        out_tangents: Vec::new(),
+    }
+}
+
+fn flatten(bezier: &Bezier, matrix: Matrix) -> Option<Path> {
+    let first = *bezier.vertices.first()?;
+    let tangent =
+        |tangents: &[[f32; 2]], index: usize| tangents.get(index).copied().unwrap_or_default();
+
+    let mut points = vec![matrix.apply(first)];
+    let count = bezier.vertices.len();
+    let segments = if bezier.closed { count } else { count - 1 };
+
+    for index in 0..segments {
+        let next = (index + 1) % count;
+        let (from, to) = (bezier.vertices[index], bezier.vertices[next]);
+        let out = tangent(&bezier.out_tangents, index);
+        let into = tangent(&bezier.in_tangents, next);
+
+        if out == [0., 0.] && into == [0., 0.] {
+            points.push(matrix.apply(to));
+            continue;
+        }
+        let control1 = [from[0] + out[0], from[1] + out[1]];
+        let control2 = [to[0] + into[0], to[1] + into[1]];
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            points.push(matrix.apply(cubic(from, control1, control2, to, t)));
+        }
+    }
+
+    // the closing segment ends where the path started
+    if bezier.closed {
+        points.pop();
+    }
+    Some(Path {
+        points,
+        closed: bezier.closed,
+    })
+}
+
+fn cubic(p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], p3: [f32; 2], t: f32) -> [f32; 2] {
+    let inverse = 1. - t;
+    let weights = [
+        inverse * inverse * inverse,
+        3. * inverse * inverse * t,
+        3. * inverse * t * t,
+        t * t * t,
+    ];
+    let axis = |axis: usize| {
+        weights[0] * p0[axis]
+            + weights[1] * p1[axis]
+            + weights[2] * p2[axis]
+            + weights[3] * p3[axis]
+    };
+
+    [axis(0), axis(1)]
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+fn lerp_point(from: [f32; 2], to: [f32; 2], t: f32) -> [f32; 2] {
+    [lerp(from[0], to[0], t), lerp(from[1], to[1], t)]
+}
+
+fn forever() -> f32 {
+    f32::INFINITY
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Animation, Matrix, Path, Property, Style, Transform, Values};
+
+    fn property(json: &str) -> Property<Values> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn assert_close([x, y]: [f32; 2], [expected_x, expected_y]: [f32; 2]) {
+        assert!(
+            (x - expected_x).abs() < 0.001 && (y - expected_y).abs() < 0.001,
+            "expected {:?}, got {:?}",
+            [expected_x, expected_y],
+            [x, y]
+        );
+    }
+
+    /// A red square moving 100 pixels to the right over 10 frames, inside a
+    /// layer parented to a null layer that's moved down.
+    const MOVING_SQUARE: &str = r#"{
+        "v": "5.7.1", "fr": 10, "ip": 0, "op": 20, "w": 200, "h": 200,
+        "layers": [
+            {
+                "ty": 4, "ind": 2, "parent": 1, "ip": 0, "op": 20,
+                "ks": {
+                    "p": {"a": 1, "k": [
+                        {"t": 0, "s": [0, 0, 0]},
+                        {"t": 10, "s": [100, 0, 0]}
+                    ]},
+                    "o": {"a": 0, "k": 50}
+                },
+                "shapes": [{
+                    "ty": "gr",
+                    "it": [
+                        {"ty": "rc", "p": {"a": 0, "k": [0, 0]}, "s": {"a": 0, "k": [20, 20]}, "r": {"a": 0, "k": 0}},
+                        {"ty": "fl", "c": {"a": 0, "k": [1, 0, 0, 1]}, "o": {"a": 0, "k": 100}},
+                        {"ty": "gf"},
+                        {"ty": "tr", "p": {"a": 0, "k": [10, 10]}}
+                    ]
+                }]
+            },
+            {"ty": 3, "ind": 1, "ks": {"p": {"a": 0, "k": [0, 50]}}, "ip": 0, "op": 20}
+        ]
+    }"#;
+
+    #[test]
+    fn animated_layers() {
+        let animation = Animation::parse(MOVING_SQUARE.as_bytes()).unwrap();
+        assert_eq!(animation.duration(), Duration::from_secs(2));
+        assert_eq!(animation.frame_at(Duration::from_millis(2500)), 5.);
+
+        let shapes = animation.render(5.);
+        assert_eq!(shapes.len(), 1);
+        // the layer is half transparent, which the color is premultiplied by
+        assert_eq!(shapes[0].style, Style::Fill([128, 0, 0, 128]));
+        assert_eq!(
+            shapes[0].paths,
+            [Path {
+                points: vec![[50., 50.], [70., 50.], [70., 70.], [50., 70.]],
+                closed: true,
+            }]
+        );
+
+        // keyframes hold their value past the last one
+        let shapes = animation.render(15.);
+        assert_eq!(shapes[0].paths[0].points[0], [100., 50.]);
+    }
+
+    #[test]
+    fn strokes_and_curves() {
+        let json = r#"{
+            "fr": 30, "ip": 0, "op": 30, "w": 100, "h": 100,
+            "layers": [{
+                "ty": 4, "ip": 0, "op": 30,
+                "ks": {"s": {"a": 0, "k": [200, 200]}},
+                "shapes": [
+                    {"ty": "el", "p": {"a": 0, "k": [25, 25]}, "s": {"a": 0, "k": [10, 10]}},
+                    {"ty": "sh", "ks": {"a": 1, "k": [
+                        {"t": 0, "s": [{"c": false, "v": [[0, 0], [10, 0]], "i": [[0, 0], [0, 0]], "o": [[0, 0], [0, 0]]}],
+                         "o": {"x": [0.5], "y": [0]}, "i": {"x": [0.5], "y": [1]}},
+                        {"t": 30, "s": [{"c": false, "v": [[0, 10], [10, 10]], "i": [[0, 0], [0, 0]], "o": [[0, 0], [0, 0]]}]}
+                    ]}},
+                    {"ty": "st", "c": {"a": 0, "k": [0, 0, 1]}, "o": {"a": 0, "k": 100}, "w": {"a": 0, "k": 2}}
+                ]
+            }]
+        }"#;
+        let animation = Animation::parse(json.as_bytes()).unwrap();
+
+        let shapes = animation.render(15.);
+        assert_eq!(
+            shapes[0].style,
+            Style::Stroke {
+                color: [0, 0, 255, 255],
+                width: 4.,
+            }
+        );
+
+        let [ellipse, line] = shapes[0].paths.as_slice() else {
+            panic!("expected an ellipse and a line");
+        };
+        assert!(ellipse.closed);
+        assert_eq!(ellipse.points.len(), 4 * 8);
+        assert_eq!(ellipse.points[0], [50., 40.]);
+        assert!(ellipse
+            .points
+            .iter()
+            .all(|[x, y]| (((x - 50.).powi(2) + (y - 50.).powi(2)).sqrt() - 10.).abs() < 0.1));
+
+        // halfway through a symmetric easing is halfway between the values
+        assert!(!line.closed);
+        assert!((line.points[0][1] - 10.).abs() < 0.01);
+    }
+
+    #[test]
+    fn keyframe_interpolation() {
+        let position = property(
+            r#"{"a": 1, "k": [
+                {"t": 10, "s": [0, 0]},
+                {"t": 20, "s": [100, 50]},
+                {"t": 30, "s": [100, 100]}
+            ]}"#,
+        );
+
+        // the first and last values are held outside the keyframes
+        assert_eq!(position.at(0.).0, [0., 0.]);
+        assert_eq!(position.at(15.).0, [50., 25.]);
+        assert_eq!(position.at(25.).0, [100., 75.]);
+        assert_eq!(position.at(40.).0, [100., 100.]);
+
+        // older animations have the end values in the previous keyframes
+        let opacity = property(
+            r#"{"a": 1, "k": [
+                {"t": 0, "s": [0], "e": [100]},
+                {"t": 10, "s": [100], "e": [20]},
+                {"t": 20}
+            ]}"#,
+        );
+        assert_eq!(opacity.scalar(5., 100.), 50.);
+        assert_eq!(opacity.scalar(15., 100.), 60.);
+        assert_eq!(opacity.scalar(25., 100.), 20.);
+    }
+
+    #[test]
+    fn hold_and_eased_keyframes() {
+        let held = property(
+            r#"{"a": 1, "k": [
+                {"t": 0, "s": [0], "h": 1},
+                {"t": 10, "s": [100]}
+            ]}"#,
+        );
+        assert_eq!(held.scalar(9.9, 0.), 0.);
+        assert_eq!(held.scalar(10., 0.), 100.);
+
+        let eased = property(
+            r#"{"a": 1, "k": [
+                {"t": 0, "s": [0], "o": {"x": [0.42], "y": [0]}, "i": {"x": [0.58], "y": [1]}},
+                {"t": 10, "s": [100]}
+            ]}"#,
+        );
+        // ease in and out is slower than linear at the start, and symmetric
+        assert!(eased.scalar(2.5, 0.) < 25.);
+        assert!((eased.scalar(5., 0.) - 50.).abs() < 0.01);
+        assert!((eased.scalar(2.5, 0.) + eased.scalar(7.5, 0.) - 100.).abs() < 0.01);
+    }
+
+    #[test]
+    fn empty_keyframes() {
+        assert_eq!(property(r#"{"a": 1, "k": []}"#).scalar(5., 100.), 100.);
+
+        let json = MOVING_SQUARE.replace(r#""o": {"a": 0, "k": 50}"#, r#""o": {"a": 1, "k": []}"#);
+        let animation = Animation::parse(json.as_bytes()).unwrap();
+        assert_eq!(animation.render(5.)[0].style, Style::Fill([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn layer_transforms() {
+        let transform: Transform = serde_json::from_str(
+            r#"{
+                "a": {"a": 0, "k": [10, 10]},
+                "p": {"a": 0, "k": [100, 50]},
+                "s": {"a": 0, "k": [200, 50]},
+                "r": {"a": 0, "k": 90},
+                "o": {"a": 0, "k": 25}
+            }"#,
+        )
+        .unwrap();
+        let matrix = transform.matrix(0.);
+
+        // the anchor ends up at the position
+        assert_close(matrix.apply([10., 10.]), [100., 50.]);
+        // scaled to (20, 0) then rotated clockwise to (0, 20)
+        assert_close(matrix.apply([20., 10.]), [100., 70.]);
+        // scaled to (0, 5) then rotated to (-5, 0)
+        assert_close(matrix.apply([10., 20.]), [95., 50.]);
+        assert_eq!(transform.opacity(0.), 0.25);
+
+        let split: Transform = serde_json::from_str(
+            r#"{"p": {"s": true, "x": {"a": 0, "k": 3}, "y": {"a": 0, "k": 4}}}"#,
+        )
+        .unwrap();
+        assert_eq!(split.matrix(0.), Matrix::translate([3., 4.]));
+        assert_eq!(Transform::default().matrix(0.), Matrix::IDENTITY);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{env, error::Error, io, sync::mpsc};
 
 use fusioncord_core::client::Client;
+use tokio::sync::mpsc::unbounded_channel;
 use tracing::{subscriber, Level};
 use tracing_subscriber::FmtSubscriber;
 use twilight_model::gateway::{
@@ -38,13 +39,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // TODO: remove this file
     // tx should be noop to avoid panics, whats even the point of this?
     let (tx, _) = mpsc::channel();
+    let (_, commands) = unbounded_channel();
 
     Ok(client
         .wait_for_hello()
         .await?
         .identify(identify)
         .await?
-        .wait_for_ready(tx)
+        .wait_for_ready(tx, commands)
         .await?
         .run()
         .await?)
@@ -0,0 +1,872 @@
+//! Parser for the markdown flavour used in message contents and embeds.
+
+use twilight_model::id::{
+    marker::{ChannelMarker, EmojiMarker, RoleMarker, UserMarker},
+    Id,
+};
+
+/// Shown instead of spoilers in text without formatting.
+const SPOILER_MASK: &str = "▮▮▮";
+
+/// Block level element of a parsed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// Lines of text, separated by [`Inline::LineBreak`].
+    Paragraph(Vec<Inline>),
+    Heading {
+        /// Level of the heading, from 1 to 3.
+        level: u8,
+        content: Vec<Inline>,
+    },
+    /// Quoted blocks, prefixed with `> ` or following a `>>> `.
+    Quote(Vec<Block>),
+    CodeBlock {
+        /// Language tag after the opening backticks, if any.
+        language: Option<String>,
+        code: String,
+    },
+    ListItem {
+        /// Amount of indentation levels of the item.
+        depth: u8,
+        /// Number of the item in an ordered list, `None` for bullet points.
+        number: Option<u32>,
+        content: Vec<Inline>,
+    },
+}
+
+/// Inline element of a parsed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text {
+        text: String,
+        style: Style,
+    },
+    Code(String),
+    Link {
+        text: String,
+        url: String,
+        style: Style,
+    },
+    /// Content hidden until clicked.
+    Spoiler {
+        /// Position of the spoiler among the ones of the text, which
+        /// identifies it across renders.
+        index: usize,
+        content: Vec<Inline>,
+    },
+    UserMention(Id<UserMarker>),
+    RoleMention(Id<RoleMarker>),
+    ChannelMention(Id<ChannelMarker>),
+    /// `@everyone` or `@here`.
+    EveryoneMention(&'static str),
+    CustomEmoji {
+        id: Id<EmojiMarker>,
+        name: String,
+        animated: bool,
+    },
+    /// Unix timestamp shown in the locale of the reader.
+    Timestamp {
+        secs: i64,
+        /// Formatting style, such as `R` for relative times.
+        style: Option<char>,
+    },
+    LineBreak,
+}
+
+/// Emphasis applied to text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl Style {
+    /// Combines the emphasis of both styles.
+    pub const fn with(self, other: Self) -> Self {
+        Self {
+            bold: self.bold || other.bold,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            strikethrough: self.strikethrough || other.strikethrough,
+        }
+    }
+}
+
+/// Parses a message content or embed text into blocks.
+pub fn parse(content: &str) -> Vec<Block> {
+    let mut blocks = parse_blocks(content);
+    number_spoilers(&mut blocks, &mut 0);
+    blocks
+}
+
+fn parse_blocks(content: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut rest = content;
+
+    // code blocks take precedence over everything else and may start anywhere
+    while let Some(start) = rest.find("```") {
+        let after_open = &rest[start + 3..];
+        let Some(end) = after_open.find("```") else {
+            break;
+        };
+
+        parse_lines(&rest[..start], &mut blocks);
+        blocks.push(code_block(&after_open[..end]));
+        rest = after_open[end + 3..]
+            .strip_prefix('\n')
+            .unwrap_or(&after_open[end + 3..]);
+    }
+
+    parse_lines(rest, &mut blocks);
+    blocks
+}
+
+fn code_block(inner: &str) -> Block {
+    // the language tag is a single word directly after the opening backticks
+    let (language, code) = match inner.split_once('\n') {
+        Some((first_line, code))
+            if !first_line.is_empty() && !first_line.contains(char::is_whitespace) =>
+        {
+            (Some(first_line.to_owned()), code)
+        }
+        _ => (None, inner.strip_prefix('\n').unwrap_or(inner)),
+    };
+
+    Block::CodeBlock {
+        language,
+        code: code.strip_suffix('\n').unwrap_or(code).to_owned(),
+    }
+}
+
+fn parse_lines(text: &str, blocks: &mut Vec<Block>) {
+    let mut paragraph: Vec<Inline> = Vec::new();
+    let mut quote = String::new();
+    let mut lines = text.lines();
+
+    let flush_paragraph = |paragraph: &mut Vec<Inline>, blocks: &mut Vec<Block>| {
+        if paragraph.last() == Some(&Inline::LineBreak) {
+            paragraph.pop();
+        }
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(std::mem::take(paragraph)));
+        }
+    };
+    let flush_quote = |quote: &mut String, blocks: &mut Vec<Block>| {
+        if !quote.is_empty() {
+            blocks.push(Block::Quote(parse_blocks(&std::mem::take(quote))));
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        if let Some(quoted) = line.strip_prefix(">>> ") {
+            // quotes everything up to the end of the text
+            flush_paragraph(&mut paragraph, blocks);
+            quote.push_str(quoted);
+            for line in lines.by_ref() {
+                quote.push('\n');
+                quote.push_str(line);
+            }
+            continue;
+        }
+
+        if let Some(quoted) = line.strip_prefix("> ").or((line == ">").then_some("")) {
+            flush_paragraph(&mut paragraph, blocks);
+            quote.push_str(quoted);
+            quote.push('\n');
+            continue;
+        }
+        flush_quote(&mut quote, blocks);
+
+        if let Some((level, heading)) = heading(line) {
+            flush_paragraph(&mut paragraph, blocks);
+            blocks.push(Block::Heading {
+                level,
+                content: parse_inline(heading, Style::default()),
+            });
+        } else if let Some((depth, number, item)) = list_item(line) {
+            flush_paragraph(&mut paragraph, blocks);
+            blocks.push(Block::ListItem {
+                depth,
+                number,
+                content: parse_inline(item, Style::default()),
+            });
+        } else {
+            paragraph.extend(parse_inline(line, Style::default()));
+            paragraph.push(Inline::LineBreak);
+        }
+    }
+
+    flush_paragraph(&mut paragraph, blocks);
+    flush_quote(&mut quote, blocks);
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.bytes().take_while(|&byte| byte == b'#').count();
+    let heading = line[level..].strip_prefix(' ')?;
+
+    (1..=3)
+        .contains(&level)
+        .then_some((level as u8, heading.trim()))
+        .filter(|(_, heading)| !heading.is_empty())
+}
+
+fn list_item(line: &str) -> Option<(u8, Option<u32>, &str)> {
+    let trimmed = line.trim_start();
+    let depth = ((line.len() - trimmed.len()) / 2).min(u8::MAX as usize) as u8;
+
+    if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        return Some((depth, None, item));
+    }
+
+    let (number, item) = trimmed.split_once(". ")?;
+    let number = number.parse().ok()?;
+    Some((depth, Some(number), item))
+}
+
+fn parse_inline(text: &str, style: Style) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    let mut buffer = String::new();
+    let mut rest = text;
+    let mut previous = None;
+
+    macro_rules! flush {
+        () => {
+            if !buffer.is_empty() {
+                inlines.push(Inline::Text {
+                    text: std::mem::take(&mut buffer),
+                    style,
+                });
+            }
+        };
+    }
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest.strip_prefix('\\').and_then(|r| r.chars().next()) {
+            if escaped.is_ascii_punctuation() {
+                buffer.push(escaped);
+                previous = Some(escaped);
+                rest = &rest[1 + escaped.len_utf8()..];
+                continue;
+            }
+        }
+
+        if let Some((inline, len)) = parse_span(rest, style, previous) {
+            flush!();
+            match inline {
+                Span::Inline(inline) => inlines.push(inline),
+                Span::Nested(nested) => inlines.extend(nested),
+            }
+            previous = rest[..len].chars().last();
+            rest = &rest[len..];
+            continue;
+        }
+
+        buffer.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    flush!();
+    inlines
+}
+
+enum Span {
+    Inline(Inline),
+    Nested(Vec<Inline>),
+}
+
+/// Parses a span starting at the beginning of the text, returning it along
+/// with the amount of bytes it spans.
+///
+/// The character preceding the text is needed as underscores within words
+/// don't start an emphasis.
+fn parse_span(text: &str, style: Style, previous: Option<char>) -> Option<(Span, usize)> {
+    // longest delimiters first, `**` must not be mistaken for `*`
+    const NONE: Style = Style {
+        bold: false,
+        italic: false,
+        underline: false,
+        strikethrough: false,
+    };
+    const EMPHASIS: [(&str, Style); 6] = [
+        (
+            "***",
+            Style {
+                bold: true,
+                italic: true,
+                ..NONE
+            },
+        ),
+        ("**", Style { bold: true, ..NONE }),
+        (
+            "__",
+            Style {
+                underline: true,
+                ..NONE
+            },
+        ),
+        (
+            "~~",
+            Style {
+                strikethrough: true,
+                ..NONE
+            },
+        ),
+        (
+            "*",
+            Style {
+                italic: true,
+                ..NONE
+            },
+        ),
+        (
+            "_",
+            Style {
+                italic: true,
+                ..NONE
+            },
+        ),
+    ];
+
+    if text.starts_with('`') {
+        let ticks = if text.starts_with("``") { 2 } else { 1 };
+        let inner = &text[ticks..];
+        let end = inner.find(&text[..ticks]).filter(|&end| end > 0)?;
+        let code = inner[..end].trim();
+
+        return Some((Span::Inline(Inline::Code(code.to_owned())), end + 2 * ticks));
+    }
+
+    if let Some(inner) = text.strip_prefix("||") {
+        let end = inner.find("||").filter(|&end| end > 0)?;
+        let spoiler = Inline::Spoiler {
+            // numbered once the whole text is parsed
+            index: 0,
+            content: parse_inline(&inner[..end], style),
+        };
+
+        return Some((Span::Inline(spoiler), end + 4));
+    }
+
+    for (delimiter, emphasis) in EMPHASIS {
+        let Some(inner) = text.strip_prefix(delimiter) else {
+            continue;
+        };
+        let Some(end) = closing_delimiter(inner, delimiter) else {
+            continue;
+        };
+        let within_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        if delimiter == "_"
+            && (within_word(previous) || within_word(inner[end + 1..].chars().next()))
+        {
+            continue;
+        }
+
+        return Some((
+            Span::Nested(parse_inline(&inner[..end], style.with(emphasis))),
+            end + 2 * delimiter.len(),
+        ));
+    }
+
+    if text.starts_with('[') {
+        if let Some(link) = masked_link(text, style) {
+            return Some(link);
+        }
+    }
+
+    if text.starts_with("https://") || text.starts_with("http://") {
+        let end = text.find(char::is_whitespace).unwrap_or(text.len());
+        let url = &text[..end];
+
+        return Some((
+            Span::Inline(Inline::Link {
+                text: url.to_owned(),
+                url: url.to_owned(),
+                style,
+            }),
+            end,
+        ));
+    }
+
+    if text.starts_with('<') {
+        return angle_bracket(text, style);
+    }
+
+    for mention in ["@everyone", "@here"] {
+        if text.starts_with(mention) {
+            return Some((
+                Span::Inline(Inline::EveryoneMention(mention)),
+                mention.len(),
+            ));
+        }
+    }
+
+    None
+}
+
+/// Finds the delimiter closing an emphasis, which can't directly follow the
+/// opening one or be part of a longer delimiter.
+fn closing_delimiter(inner: &str, delimiter: &str) -> Option<usize> {
+    let first = delimiter.chars().next()?;
+    let mut offset = 0;
+
+    while let Some(index) = inner[offset..].find(delimiter) {
+        let end = offset + index;
+        let after = &inner[end + delimiter.len()..];
+
+        if end > 0 && !after.starts_with(first) && !inner[..end].ends_with(char::is_whitespace) {
+            return Some(end);
+        }
+        offset = end + delimiter.len();
+    }
+
+    None
+}
+
+/// Parses a `[text](url)` link, the URL can be enclosed in angle brackets
+/// to suppress its embed.
+fn masked_link(text: &str, style: Style) -> Option<(Span, usize)> {
+    let text_end = closing_bracket(text)?;
+    // the URL must directly follow the text
+    let after = text[text_end + 1..].strip_prefix('(')?;
+    let url_len = after.find(')')?;
+    let url = &after[..url_len];
+    let url = url
+        .strip_prefix('<')
+        .and_then(|url| url.strip_suffix('>'))
+        .unwrap_or(url);
+
+    if !is_url(url) {
+        return None;
+    }
+
+    Some((
+        Span::Inline(Inline::Link {
+            text: text[1..text_end].to_owned(),
+            url: url.to_owned(),
+            style,
+        }),
+        text_end + url_len + 3,
+    ))
+}
+
+/// Finds the `]` matching the `[` the text starts with.
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0_usize;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+fn is_url(text: &str) -> bool {
+    (text.starts_with("https://") || text.starts_with("http://"))
+        && !text.contains(char::is_whitespace)
+}
+
+/// Parses mentions, custom emojis, timestamps and links whose embeds are
+/// suppressed, which are all enclosed in angle brackets.
+fn angle_bracket(text: &str, style: Style) -> Option<(Span, usize)> {
+    let end = text.find('>')?;
+    let inner = &text[1..end];
+    let len = end + 1;
+
+    let inline = if is_url(inner) {
+        Inline::Link {
+            text: inner.to_owned(),
+            url: inner.to_owned(),
+            style,
+        }
+    } else if let Some(id) = inner.strip_prefix("@&") {
+        Inline::RoleMention(id.parse().ok()?)
+    } else if let Some(id) = inner.strip_prefix('@') {
+        Inline::UserMention(id.strip_prefix('!').unwrap_or(id).parse().ok()?)
+    } else if let Some(id) = inner.strip_prefix('#') {
+        Inline::ChannelMention(id.parse().ok()?)
+    } else if let Some(timestamp) = inner.strip_prefix("t:") {
+        let (secs, style) = match timestamp.split_once(':') {
+            Some((secs, style)) => (secs, style.chars().next()),
+            None => (timestamp, None),
+        };
+
+        Inline::Timestamp {
+            secs: secs.parse().ok()?,
+            style,
+        }
+    } else {
+        let (animated, emoji) = match inner.strip_prefix("a:") {
+            Some(emoji) => (true, emoji),
+            None => (false, inner.strip_prefix(':')?),
+        };
+        let (name, id) = emoji.split_once(':')?;
+
+        Inline::CustomEmoji {
+            id: id.parse().ok()?,
+            name: name.to_owned(),
+            animated,
+        }
+    };
+
+    Some((Span::Inline(inline), len))
+}
+
+/// Numbers the spoilers of blocks in the order they appear in.
+fn number_spoilers(blocks: &mut [Block], next: &mut usize) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(content)
+            | Block::Heading { content, .. }
+            | Block::ListItem { content, .. } => number_inline_spoilers(content, next),
+            Block::Quote(blocks) => number_spoilers(blocks, next),
+            Block::CodeBlock { .. } => (),
+        }
+    }
+}
+
+fn number_inline_spoilers(inlines: &mut [Inline], next: &mut usize) {
+    for inline in inlines {
+        if let Inline::Spoiler { index, content } = inline {
+            *index = *next;
+            *next += 1;
+            number_inline_spoilers(content, next);
+        }
+    }
+}
+
+/// Text of parsed blocks without any formatting, such as for notifications.
+///
+/// Spoilers are masked. Mentions are named through `mention_name`, which
+/// returns `None` for the ones that aren't known so that a placeholder is
+/// shown instead.
+pub fn plain_text(blocks: &[Block], mention_name: &dyn Fn(&Inline) -> Option<String>) -> String {
+    let mut text = String::new();
+
+    for block in blocks {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+
+        match block {
+            Block::Paragraph(content)
+            | Block::Heading { content, .. }
+            | Block::ListItem { content, .. } => push_plain_text(&mut text, content, mention_name),
+            Block::Quote(blocks) => text.push_str(&plain_text(blocks, mention_name)),
+            Block::CodeBlock { code, .. } => text.push_str(code),
+        }
+    }
+
+    text
+}
+
+/// Text of parsed inline elements without any formatting, with placeholders
+/// for mentions.
+pub fn inline_plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    push_plain_text(&mut text, inlines, &|_| None);
+    text
+}
+
+fn push_plain_text(
+    text: &mut String,
+    inlines: &[Inline],
+    mention_name: &dyn Fn(&Inline) -> Option<String>,
+) {
+    for inline in inlines {
+        match inline {
+            Inline::Text { text: inner, .. } | Inline::Code(inner) => text.push_str(inner),
+            Inline::Link { text: inner, .. } => text.push_str(inner),
+            Inline::Spoiler { .. } => text.push_str(SPOILER_MASK),
+            Inline::UserMention(_) => {
+                let name = mention_name(inline).unwrap_or_else(|| "user".to_owned());
+                text.push_str(&format!("@{name}"));
+            }
+            Inline::RoleMention(_) => {
+                let name = mention_name(inline).unwrap_or_else(|| "role".to_owned());
+                text.push_str(&format!("@{name}"));
+            }
+            Inline::ChannelMention(_) => {
+                let name = mention_name(inline).unwrap_or_else(|| "channel".to_owned());
+                text.push_str(&format!("#{name}"));
+            }
+            Inline::EveryoneMention(mention) => text.push_str(mention),
+            Inline::CustomEmoji { name, .. } => {
+                text.push(':');
+                text.push_str(name);
+                text.push(':');
+            }
+            Inline::Timestamp { secs, .. } => text.push_str(&format!("<t:{secs}>")),
+            Inline::LineBreak => text.push('\n'),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::Id;
+
+    use super::{parse, plain_text, Block, Inline, Style};
+
+    const BOLD: Style = Style {
+        bold: true,
+        italic: false,
+        underline: false,
+        strikethrough: false,
+    };
+
+    fn text(text: &str, style: Style) -> Inline {
+        Inline::Text {
+            text: text.to_owned(),
+            style,
+        }
+    }
+
+    fn plain(value: &str) -> Inline {
+        text(value, Style::default())
+    }
+
+    fn link(text: &str, url: &str) -> Inline {
+        Inline::Link {
+            text: text.to_owned(),
+            url: url.to_owned(),
+            style: Style::default(),
+        }
+    }
+
+    /// Inline elements of text parsed into a single paragraph.
+    fn inline(content: &str) -> Vec<Inline> {
+        match parse(content).as_slice() {
+            [Block::Paragraph(inlines)] => inlines.clone(),
+            blocks => panic!("expected a single paragraph, got {blocks:?}"),
+        }
+    }
+
+    #[test]
+    fn emphasis() {
+        let italic = Style {
+            italic: true,
+            ..Style::default()
+        };
+        let underline = Style {
+            underline: true,
+            ..Style::default()
+        };
+
+        assert_eq!(
+            inline("**bold *both* __all ~~four~~__**"),
+            [
+                text("bold ", BOLD),
+                text("both", BOLD.with(italic)),
+                text(" ", BOLD),
+                text("all ", BOLD.with(underline)),
+                text(
+                    "four",
+                    BOLD.with(underline).with(Style {
+                        strikethrough: true,
+                        ..Style::default()
+                    })
+                ),
+            ]
+        );
+        assert_eq!(inline("***a***"), [text("a", BOLD.with(italic))]);
+        assert_eq!(
+            inline("__a__ _b_"),
+            [text("a", underline), plain(" "), text("b", italic)]
+        );
+        // underscores within words and escaped delimiters are kept
+        assert_eq!(inline("snake_case_name"), [plain("snake_case_name")]);
+        assert_eq!(inline(r"\*not italic\*"), [plain("*not italic*")]);
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            inline("a `b *c*` ``d ` e``"),
+            [
+                plain("a "),
+                Inline::Code("b *c*".to_owned()),
+                plain(" "),
+                Inline::Code("d ` e".to_owned()),
+            ]
+        );
+
+        assert_eq!(
+            parse("before```rs\nfn main() {}\n```after\n```\nplain```"),
+            [
+                Block::Paragraph(vec![plain("before")]),
+                Block::CodeBlock {
+                    language: Some("rs".to_owned()),
+                    code: "fn main() {}".to_owned(),
+                },
+                Block::Paragraph(vec![plain("after")]),
+                Block::CodeBlock {
+                    language: None,
+                    code: "plain".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            parse("```two words\ncode```"),
+            [Block::CodeBlock {
+                language: None,
+                code: "two words\ncode".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn spoilers() {
+        assert_eq!(
+            parse("||a **b**|| c\n> ||d|| ||e||"),
+            [
+                Block::Paragraph(vec![
+                    Inline::Spoiler {
+                        index: 0,
+                        content: vec![plain("a "), text("b", BOLD)],
+                    },
+                    plain(" c"),
+                ]),
+                Block::Quote(vec![Block::Paragraph(vec![
+                    Inline::Spoiler {
+                        index: 1,
+                        content: vec![plain("d")],
+                    },
+                    plain(" "),
+                    Inline::Spoiler {
+                        index: 2,
+                        content: vec![plain("e")],
+                    },
+                ])]),
+            ]
+        );
+        assert_eq!(inline("||||"), [plain("||||")]);
+        assert_eq!(plain_text(&parse("||secret|| text"), &|_| None), "▮▮▮ text");
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            inline("see [the docs](https://x.com/a) now"),
+            [
+                plain("see "),
+                link("the docs", "https://x.com/a"),
+                plain(" now")
+            ]
+        );
+        // the text ends at the first matching bracket
+        assert_eq!(
+            inline("[a] foo [b](https://x.com)"),
+            [plain("[a] foo "), link("b", "https://x.com")]
+        );
+        assert_eq!(
+            inline("[[nested]](https://x.com)"),
+            [link("[nested]", "https://x.com")]
+        );
+        assert_eq!(
+            inline("[a] (https://x.com)"),
+            [plain("[a] ("), link("https://x.com)", "https://x.com)"),]
+        );
+        assert_eq!(
+            inline("[x](javascript:alert(1))"),
+            [plain("[x](javascript:alert(1))")]
+        );
+
+        // angle brackets suppress embeds
+        assert_eq!(
+            inline("<https://example.com> and [x](<https://y.com>)"),
+            [
+                link("https://example.com", "https://example.com"),
+                plain(" and "),
+                link("x", "https://y.com"),
+            ]
+        );
+        assert_eq!(
+            inline("go to https://example.com"),
+            [
+                plain("go to "),
+                link("https://example.com", "https://example.com")
+            ]
+        );
+        assert_eq!(inline("<not a link>"), [plain("<not a link>")]);
+    }
+
+    #[test]
+    fn mentions() {
+        assert_eq!(
+            inline("<@1> <@!2> <@&3> <#4> @everyone <:wave:5><a:dance:6> <t:7:R> <@x>"),
+            [
+                Inline::UserMention(Id::new(1)),
+                plain(" "),
+                Inline::UserMention(Id::new(2)),
+                plain(" "),
+                Inline::RoleMention(Id::new(3)),
+                plain(" "),
+                Inline::ChannelMention(Id::new(4)),
+                plain(" "),
+                Inline::EveryoneMention("@everyone"),
+                plain(" "),
+                Inline::CustomEmoji {
+                    id: Id::new(5),
+                    name: "wave".to_owned(),
+                    animated: false,
+                },
+                Inline::CustomEmoji {
+                    id: Id::new(6),
+                    name: "dance".to_owned(),
+                    animated: true,
+                },
+                plain(" "),
+                Inline::Timestamp {
+                    secs: 7,
+                    style: Some('R'),
+                },
+                plain(" <@x>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            parse("# Title\n- item\n  2. nested\n> quoted\nline\n>>> rest\nof it"),
+            [
+                Block::Heading {
+                    level: 1,
+                    content: vec![plain("Title")],
+                },
+                Block::ListItem {
+                    depth: 0,
+                    number: None,
+                    content: vec![plain("item")],
+                },
+                Block::ListItem {
+                    depth: 1,
+                    number: Some(2),
+                    content: vec![plain("nested")],
+                },
+                Block::Quote(vec![Block::Paragraph(vec![plain("quoted")])]),
+                Block::Paragraph(vec![plain("line")]),
+                Block::Quote(vec![Block::Paragraph(vec![
+                    plain("rest"),
+                    Inline::LineBreak,
+                    plain("of it"),
+                ])]),
+            ]
+        );
+    }
+}
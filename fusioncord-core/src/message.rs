@@ -1,6 +1,106 @@
-use twilight_model::guild::Guild;
+use std::sync::mpsc::Sender;
+
+use twilight_model::{
+    application::command::CommandIndex,
+    channel::{message::ReactionType, thread::ThreadsListing, Message},
+    gateway::{event::DispatchEvent, payload::incoming::UserSettingsProtoType},
+    guild::Guild,
+    id::{
+        marker::{AttachmentMarker, ChannelMarker, GuildMarker, MessageMarker, UserMarker},
+        Id,
+    },
+    user::{ProfileResponse, User},
+};
+
+use crate::{command::CommandScope, presence::ActivityUpdate};
+
+/// Location of a message, such as the one a notification was shown for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageLink {
+    /// Guild of the message, `None` for direct messages.
+    pub guild_id: Option<Id<GuildMarker>>,
+    pub channel_id: Id<ChannelMarker>,
+    pub message_id: Id<MessageMarker>,
+}
 
 #[derive(Debug)]
 pub enum RenderMessage {
-    InitialData { guilds: Vec<Guild> },
+    InitialData {
+        guilds: Vec<Guild>,
+    },
+    /// A dispatch event was received from the gateway.
+    Dispatch(DispatchEvent),
+    /// Recent messages of a channel were fetched, `None` if fetching them
+    /// failed.
+    History {
+        channel_id: Id<ChannelMarker>,
+        messages: Option<Vec<Message>>,
+    },
+    /// Sending the message with the given nonce failed.
+    MessageFailed {
+        channel_id: Id<ChannelMarker>,
+        nonce: String,
+    },
+    /// Sending the interaction with the given nonce failed.
+    InteractionFailed {
+        nonce: String,
+    },
+    /// Reactors of a message were fetched, `None` if fetching them failed.
+    Reactors {
+        message_id: Id<MessageMarker>,
+        emoji: ReactionType,
+        burst: bool,
+        users: Option<Vec<User>>,
+    },
+    /// Sending a friend request to the user with the given username failed.
+    FriendRequestFailed {
+        username: String,
+    },
+    /// A page of archived threads of a channel was fetched, `None` if
+    /// fetching it failed.
+    ArchivedThreads {
+        channel_id: Id<ChannelMarker>,
+        listing: Option<ThreadsListing>,
+    },
+    /// A notification of the given message was clicked.
+    OpenMessage(MessageLink),
+    /// The activity a local application set through rich presence changed.
+    LocalActivity(Box<ActivityUpdate>),
+    /// The profile of a user was fetched, `None` if fetching it failed.
+    Profile {
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        profile: Option<Box<ProfileResponse>>,
+    },
+    /// The note the current user keeps about a user was fetched.
+    UserNote {
+        user_id: Id<UserMarker>,
+        note: String,
+    },
+    /// The commands available in a scope were fetched, `None` if fetching
+    /// them failed.
+    Commands {
+        scope: CommandScope,
+        index: Option<Box<CommandIndex>>,
+    },
+    /// Base64 encoded protobuf settings of the current user were fetched or
+    /// changed over the REST API.
+    UserSettings {
+        kind: UserSettingsProtoType,
+        settings: String,
+    },
+    /// The text of a text file attachment was fetched, `None` if fetching
+    /// it failed.
+    AttachmentText {
+        attachment_id: Id<AttachmentMarker>,
+        text: Option<String>,
+    },
+}
+
+/// Sends a message to the render loop.
+///
+/// The message is dropped if the render loop is gone already, which happens
+/// while the app is shutting down.
+pub fn notify(tx: &Sender<RenderMessage>, message: RenderMessage) {
+    let _ = tx.send(message);
 }
@@ -0,0 +1,530 @@
+use std::collections::HashMap;
+
+use twilight_model::{
+    channel::{message::MessageFlags, Message},
+    gateway::event::DispatchEvent,
+    guild::DefaultMessageNotificationLevel,
+    id::{
+        marker::{ChannelMarker, GuildMarker, UserMarker},
+        Id,
+    },
+    user::{MessageNotificationLevel, UserGuildSettings},
+    util::Timestamp,
+};
+
+use crate::{cache::Cache, read_state::now};
+
+/// How a new message is brought to the attention of the current user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notification {
+    /// The message isn't shown anywhere outside of its channel, such as the
+    /// own messages of the current user and messages in muted channels.
+    Silent,
+    /// The channel is marked as unread, and its mention badge is increased if
+    /// the message mentions the current user, without notifying.
+    Badge,
+    /// A notification is shown in addition to the badge.
+    Notify,
+}
+
+/// Everything about a message and where it was sent that decides whether it
+/// notifies, see [`NotificationContext::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationContext {
+    /// Guild of the message, `None` for direct messages.
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// The channel of the message followed by its parents, from the thread
+    /// to its channel to the category containing it.
+    pub channel_ids: Vec<Id<ChannelMarker>>,
+    /// Level the guild notifies its members with unless they override it.
+    pub default_level: DefaultMessageNotificationLevel,
+    /// Whether the message was sent by the current user.
+    pub own_message: bool,
+    pub mentions_user: bool,
+    /// Whether the message mentions `@everyone` or `@here`.
+    pub mentions_everyone: bool,
+    /// Whether the message mentions one of the roles of the current user.
+    pub mentions_role: bool,
+    /// Whether the message was sent with `@silent`.
+    pub silent: bool,
+}
+
+impl NotificationContext {
+    /// Collects the context of a message from the cache.
+    pub fn new(cache: &Cache, current_user_id: Id<UserMarker>, message: &Message) -> Self {
+        let mut channel_ids = vec![message.channel_id];
+        while let Some(parent_id) = channel_ids
+            .last()
+            .and_then(|&channel_id| cache.channel(channel_id))
+            .and_then(|channel| channel.parent_id)
+        {
+            // a thread is in a channel, which is in a category
+            if channel_ids.contains(&parent_id) || channel_ids.len() >= 3 {
+                break;
+            }
+            channel_ids.push(parent_id);
+        }
+
+        let guild = message.guild_id.and_then(|guild_id| cache.guild(guild_id));
+        let roles = guild
+            .and_then(|guild| {
+                guild
+                    .members
+                    .iter()
+                    .find(|member| member.user.id == current_user_id)
+            })
+            .map(|member| member.roles.as_slice())
+            .unwrap_or_default();
+
+        Self {
+            guild_id: message.guild_id,
+            channel_ids,
+            default_level: guild.map_or(DefaultMessageNotificationLevel::All, |guild| {
+                guild.default_message_notifications
+            }),
+            own_message: message.author.id == current_user_id,
+            mentions_user: message
+                .mentions
+                .iter()
+                .any(|user| user.id == current_user_id),
+            mentions_everyone: message.mention_everyone,
+            mentions_role: message
+                .mention_roles
+                .iter()
+                .any(|role_id| roles.contains(role_id)),
+            silent: message
+                .flags
+                .is_some_and(|flags| flags.contains(MessageFlags::SUPPRESS_NOTIFICATIONS)),
+        }
+    }
+}
+
+/// Keeps the notification settings of the current user per guild and decides
+/// whether new messages notify.
+///
+/// The store is seeded from the `user_guild_settings` of the READY payload
+/// and must be fed every subsequent dispatch event through
+/// [`NotificationStore::update`].
+#[derive(Debug, Default)]
+pub struct NotificationStore {
+    current_user_id: Option<Id<UserMarker>>,
+    /// Notification settings per guild, `None` being the direct messages.
+    settings: HashMap<Option<Id<GuildMarker>>, UserGuildSettings>,
+}
+
+impl NotificationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_user_id = Some(ready.user.id);
+                self.settings = ready
+                    .user_guild_settings
+                    .entries
+                    .iter()
+                    .map(|settings| (settings.guild_id, settings.clone()))
+                    .collect();
+            }
+            DispatchEvent::UserGuildSettingsUpdate(settings) => {
+                self.settings.insert(settings.guild_id, settings.0.clone());
+            }
+            _ => (),
+        }
+    }
+
+    /// Settings of a guild, or of the direct messages for `None`.
+    pub fn settings(&self, guild_id: Option<Id<GuildMarker>>) -> Option<&UserGuildSettings> {
+        self.settings.get(&guild_id)
+    }
+
+    /// Decides how a message that was just received is notified.
+    pub fn resolve(&self, cache: &Cache, message: &Message) -> Notification {
+        let Some(current_user_id) = self.current_user_id else {
+            return Notification::Silent;
+        };
+
+        let context = NotificationContext::new(cache, current_user_id, message);
+        resolve(self.settings(context.guild_id), &context, now())
+    }
+}
+
+/// Decides how a message is notified given the settings of its guild at the
+/// given point in time.
+///
+/// Mentions notify even in muted channels and guilds, unless the user chose
+/// to be notified of nothing. `@silent` messages never notify but still
+/// count as mentions.
+pub fn resolve(
+    settings: Option<&UserGuildSettings>,
+    context: &NotificationContext,
+    now: Timestamp,
+) -> Notification {
+    if context.own_message {
+        return Notification::Silent;
+    }
+
+    let muted = is_muted(settings, context, now);
+    // every message in a direct message counts as a mention, unless muted
+    let mentioned = (context.guild_id.is_none() && !muted)
+        || context.mentions_user
+        || (context.mentions_everyone && !settings.is_some_and(|s| s.suppress_everyone))
+        || (context.mentions_role && !settings.is_some_and(|s| s.suppress_roles));
+    let level = level(settings, context);
+
+    if mentioned {
+        return if level == MessageNotificationLevel::Nothing || context.silent {
+            Notification::Badge
+        } else {
+            Notification::Notify
+        };
+    }
+
+    if muted {
+        Notification::Silent
+    } else if level == MessageNotificationLevel::All && !context.silent {
+        Notification::Notify
+    } else {
+        Notification::Badge
+    }
+}
+
+/// Level of the closest channel overriding it, falling back to the guild
+/// settings and the default of the guild.
+fn level(
+    settings: Option<&UserGuildSettings>,
+    context: &NotificationContext,
+) -> MessageNotificationLevel {
+    let Some(settings) = settings else {
+        return context.default_level.into();
+    };
+
+    context
+        .channel_ids
+        .iter()
+        .filter_map(|&channel_id| settings.channel_override(channel_id))
+        .map(|channel_override| channel_override.message_notifications)
+        .chain([settings.message_notifications])
+        .find(|&level| level != MessageNotificationLevel::Inherit)
+        .unwrap_or_else(|| context.default_level.into())
+}
+
+/// Whether the guild or the channel of a message or any of its parents is
+/// muted.
+fn is_muted(
+    settings: Option<&UserGuildSettings>,
+    context: &NotificationContext,
+    now: Timestamp,
+) -> bool {
+    let Some(settings) = settings else {
+        return false;
+    };
+
+    settings.is_muted(now)
+        || context
+            .channel_ids
+            .iter()
+            .filter_map(|&channel_id| settings.channel_override(channel_id))
+            .any(|channel_override| channel_override.is_muted(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        guild::DefaultMessageNotificationLevel,
+        id::Id,
+        user::{ChannelOverride, MessageNotificationLevel, MuteConfig, UserGuildSettings},
+        util::Timestamp,
+    };
+
+    use super::{resolve, Notification, NotificationContext};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn now() -> Timestamp {
+        Timestamp::from_secs(NOW).unwrap()
+    }
+
+    /// Plain message in channel 2 of category 3 in guild 1.
+    fn context() -> NotificationContext {
+        NotificationContext {
+            guild_id: Some(Id::new(1)),
+            channel_ids: vec![Id::new(2), Id::new(3)],
+            default_level: DefaultMessageNotificationLevel::All,
+            own_message: false,
+            mentions_user: false,
+            mentions_everyone: false,
+            mentions_role: false,
+            silent: false,
+        }
+    }
+
+    fn settings(level: MessageNotificationLevel) -> UserGuildSettings {
+        UserGuildSettings {
+            channel_overrides: Vec::new(),
+            flags: 0,
+            guild_id: Some(Id::new(1)),
+            hide_muted_channels: false,
+            message_notifications: level,
+            mobile_push: true,
+            mute_config: None,
+            mute_scheduled_events: false,
+            muted: false,
+            notify_highlights: 0,
+            suppress_everyone: false,
+            suppress_roles: false,
+            version: 1,
+        }
+    }
+
+    fn channel_override(channel_id: u64, level: MessageNotificationLevel) -> ChannelOverride {
+        ChannelOverride {
+            channel_id: Id::new(channel_id),
+            collapsed: false,
+            flags: 0,
+            message_notifications: level,
+            mute_config: None,
+            muted: false,
+        }
+    }
+
+    fn mute_until(secs: i64) -> Option<MuteConfig> {
+        Some(MuteConfig {
+            end_time: Some(Timestamp::from_secs(secs).unwrap()),
+            selected_time_window: 3600,
+        })
+    }
+
+    #[test]
+    fn own_message() {
+        let context = NotificationContext {
+            own_message: true,
+            mentions_user: true,
+            ..context()
+        };
+
+        assert_eq!(resolve(None, &context, now()), Notification::Silent);
+    }
+
+    #[test]
+    fn guild_default_level() {
+        assert_eq!(resolve(None, &context(), now()), Notification::Notify);
+
+        let context = NotificationContext {
+            default_level: DefaultMessageNotificationLevel::Mentions,
+            ..context()
+        };
+        assert_eq!(resolve(None, &context, now()), Notification::Badge);
+
+        let settings = settings(MessageNotificationLevel::Inherit);
+        assert_eq!(
+            resolve(Some(&settings), &context, now()),
+            Notification::Badge
+        );
+    }
+
+    #[test]
+    fn user_level() {
+        const MAP: &[(MessageNotificationLevel, Notification, Notification)] = &[
+            (
+                MessageNotificationLevel::All,
+                Notification::Notify,
+                Notification::Notify,
+            ),
+            (
+                MessageNotificationLevel::OnlyMentions,
+                Notification::Badge,
+                Notification::Notify,
+            ),
+            (
+                MessageNotificationLevel::Nothing,
+                Notification::Badge,
+                Notification::Badge,
+            ),
+        ];
+        let mention = NotificationContext {
+            mentions_user: true,
+            ..context()
+        };
+
+        for &(level, plain, mentioned) in MAP {
+            let settings = settings(level);
+            assert_eq!(resolve(Some(&settings), &context(), now()), plain);
+            assert_eq!(resolve(Some(&settings), &mention, now()), mentioned);
+        }
+    }
+
+    #[test]
+    fn channel_override_level() {
+        let mut settings = settings(MessageNotificationLevel::Nothing);
+        settings.channel_overrides = vec![
+            channel_override(2, MessageNotificationLevel::Inherit),
+            channel_override(3, MessageNotificationLevel::All),
+        ];
+        // the channel inherits the level of its category
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Notify
+        );
+
+        settings.channel_overrides[0].message_notifications =
+            MessageNotificationLevel::OnlyMentions;
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Badge
+        );
+
+        // other channels use the level of the guild
+        let context = NotificationContext {
+            channel_ids: vec![Id::new(4)],
+            ..context()
+        };
+        assert_eq!(
+            resolve(Some(&settings), &context, now()),
+            Notification::Badge
+        );
+    }
+
+    #[test]
+    fn muted_guild() {
+        let mut settings = settings(MessageNotificationLevel::All);
+        settings.muted = true;
+        let mention = NotificationContext {
+            mentions_user: true,
+            ..context()
+        };
+
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Silent
+        );
+        assert_eq!(
+            resolve(Some(&settings), &mention, now()),
+            Notification::Notify
+        );
+
+        settings.message_notifications = MessageNotificationLevel::Nothing;
+        assert_eq!(
+            resolve(Some(&settings), &mention, now()),
+            Notification::Badge
+        );
+    }
+
+    #[test]
+    fn muted_channel_and_category() {
+        for channel_id in [2, 3] {
+            let mut settings = settings(MessageNotificationLevel::All);
+            let mut channel_override =
+                channel_override(channel_id, MessageNotificationLevel::Inherit);
+            channel_override.muted = true;
+            settings.channel_overrides.push(channel_override);
+
+            assert_eq!(
+                resolve(Some(&settings), &context(), now()),
+                Notification::Silent
+            );
+        }
+    }
+
+    #[test]
+    fn mute_expiry() {
+        let mut settings = settings(MessageNotificationLevel::All);
+        settings.muted = true;
+
+        settings.mute_config = mute_until(NOW + 60);
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Silent
+        );
+
+        settings.mute_config = mute_until(NOW - 60);
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Notify
+        );
+
+        settings.mute_config = None;
+        let mut channel_override = channel_override(2, MessageNotificationLevel::Inherit);
+        channel_override.muted = true;
+        channel_override.mute_config = mute_until(NOW - 60);
+        settings.muted = false;
+        settings.channel_overrides.push(channel_override);
+        assert_eq!(
+            resolve(Some(&settings), &context(), now()),
+            Notification::Notify
+        );
+    }
+
+    #[test]
+    fn silent_message() {
+        let context = NotificationContext {
+            silent: true,
+            ..context()
+        };
+        let mention = NotificationContext {
+            mentions_user: true,
+            ..context.clone()
+        };
+
+        assert_eq!(resolve(None, &context, now()), Notification::Badge);
+        assert_eq!(resolve(None, &mention, now()), Notification::Badge);
+    }
+
+    #[test]
+    fn suppressed_mentions() {
+        let mut settings = settings(MessageNotificationLevel::OnlyMentions);
+        let everyone = NotificationContext {
+            mentions_everyone: true,
+            ..context()
+        };
+        let role = NotificationContext {
+            mentions_role: true,
+            ..context()
+        };
+
+        assert_eq!(
+            resolve(Some(&settings), &everyone, now()),
+            Notification::Notify
+        );
+        assert_eq!(resolve(Some(&settings), &role, now()), Notification::Notify);
+
+        settings.suppress_everyone = true;
+        assert_eq!(
+            resolve(Some(&settings), &everyone, now()),
+            Notification::Badge
+        );
+        assert_eq!(resolve(Some(&settings), &role, now()), Notification::Notify);
+
+        settings.suppress_roles = true;
+        assert_eq!(resolve(Some(&settings), &role, now()), Notification::Badge);
+
+        // suppressing doesn't affect direct mentions
+        let both = NotificationContext {
+            mentions_user: true,
+            ..everyone
+        };
+        assert_eq!(resolve(Some(&settings), &both, now()), Notification::Notify);
+    }
+
+    #[test]
+    fn direct_message() {
+        let context = NotificationContext {
+            guild_id: None,
+            channel_ids: vec![Id::new(2)],
+            ..context()
+        };
+        assert_eq!(resolve(None, &context, now()), Notification::Notify);
+
+        let mut settings = settings(MessageNotificationLevel::All);
+        settings.guild_id = None;
+        let mut channel_override = channel_override(2, MessageNotificationLevel::All);
+        channel_override.muted = true;
+        settings.channel_overrides.push(channel_override);
+        assert_eq!(
+            resolve(Some(&settings), &context, now()),
+            Notification::Silent
+        );
+    }
+}
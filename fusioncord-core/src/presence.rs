@@ -0,0 +1,357 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use twilight_model::gateway::{
+    payload::outgoing::update_presence::UpdatePresencePayload,
+    presence::{Activity, ActivityEmoji, ActivityType, Status},
+};
+
+use crate::user_settings::{CustomStatus, StatusSettings};
+
+/// Name Discord gives the activity of a custom status.
+const CUSTOM_STATUS_NAME: &str = "Custom Status";
+
+/// The activity of a local application changed, `None` once it's cleared or
+/// the application disconnected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityUpdate {
+    /// The connection of the application, unique for the whole process.
+    pub connection: u64,
+    pub activity: Option<Activity>,
+}
+
+/// Presence this client publishes for the current user, built from the
+/// status settings and the activities local applications set.
+///
+/// Must be given the status settings through [`PresenceStore::set_settings`]
+/// whenever they change, changes to publish are taken with
+/// [`PresenceStore::take_changes`].
+#[derive(Debug, Default)]
+pub struct PresenceStore {
+    /// Status chosen by the current user, `None` until the settings are
+    /// known.
+    status: Option<Status>,
+    custom_status: Option<CustomStatus>,
+    /// Whether the activities of local applications are shown.
+    show_current_game: bool,
+    /// Activities of local applications, by the connection that set them.
+    activities: BTreeMap<u64, Activity>,
+    /// Since when the current user is away from this client in milliseconds
+    /// since the unix epoch.
+    idle_since: Option<u64>,
+    /// The presence that was last published.
+    published: Option<UpdatePresencePayload>,
+}
+
+impl PresenceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the status settings of the current user.
+    pub fn set_settings(&mut self, settings: Option<&StatusSettings>) {
+        self.status = Some(
+            settings
+                .and_then(|settings| settings.status.as_deref())
+                .and_then(parse_status)
+                .unwrap_or(Status::Online),
+        );
+        self.custom_status = settings.and_then(|settings| settings.custom_status.clone());
+        self.show_current_game = settings
+            .and_then(|settings| settings.show_current_game)
+            .unwrap_or(true);
+    }
+
+    /// Sets the activity of a local application, `None` to clear it.
+    pub fn set_activity(&mut self, source: u64, activity: Option<Activity>) {
+        match activity {
+            Some(activity) => self.activities.insert(source, activity),
+            None => self.activities.remove(&source),
+        };
+    }
+
+    /// Marks the current user as away from this client since the given time
+    /// in milliseconds since the unix epoch, `None` once they're back.
+    ///
+    /// They're shown as idle while they're away, unless they chose to be
+    /// shown as another status than online.
+    pub fn set_idle(&mut self, idle_since: Option<u64>) {
+        self.idle_since = idle_since;
+    }
+
+    /// Clears the activities of every local application, such as when they
+    /// are no longer shared.
+    pub fn clear_activities(&mut self) {
+        self.activities.clear();
+    }
+
+    /// Status chosen by the current user.
+    pub fn status(&self) -> Status {
+        self.status.unwrap_or(Status::Online)
+    }
+
+    /// Status other users see, idle while the current user is away unless
+    /// they chose do not disturb, idle or invisible.
+    pub fn shown_status(&self) -> Status {
+        match (self.status(), self.idle_since) {
+            (Status::Online, Some(_)) => Status::Idle,
+            (status, _) => status,
+        }
+    }
+
+    /// The custom status of the current user, unless it expired.
+    pub fn custom_status(&self, now_ms: u64) -> Option<&CustomStatus> {
+        self.custom_status
+            .as_ref()
+            .filter(|custom_status| !is_expired(custom_status, now_ms))
+    }
+
+    /// Time until the custom status expires, when the presence changes
+    /// without any event.
+    pub fn expires_in(&self) -> Option<Duration> {
+        let now_ms = now_millis();
+        let custom_status = self.custom_status(now_ms)?;
+
+        (custom_status.expires_at_ms != 0)
+            .then(|| Duration::from_millis(custom_status.expires_at_ms - now_ms))
+    }
+
+    /// The presence to publish.
+    pub fn payload(&self, now_ms: u64) -> UpdatePresencePayload {
+        let custom_status = self.custom_status(now_ms).and_then(custom_status_activity);
+        let games = self
+            .activities
+            .values()
+            .filter(|_| self.show_current_game)
+            .cloned();
+
+        UpdatePresencePayload {
+            activities: custom_status.into_iter().chain(games).collect(),
+            // notifications are sent to mobile while the user is away
+            afk: self.idle_since.is_some(),
+            since: self.idle_since,
+            status: self.shown_status(),
+        }
+    }
+
+    /// The presence to publish if it changed since it was last taken, once
+    /// the settings are known.
+    pub fn take_changes(&mut self) -> Option<UpdatePresencePayload> {
+        self.status?;
+
+        let payload = self.payload(now_millis());
+        if self.published.as_ref() == Some(&payload) {
+            return None;
+        }
+        self.published = Some(payload.clone());
+
+        Some(payload)
+    }
+}
+
+/// The presence sent when identifying, invisible until the status settings
+/// are received so the current user doesn't briefly show up as online.
+pub fn identify_presence() -> UpdatePresencePayload {
+    UpdatePresencePayload {
+        activities: Vec::new(),
+        afk: false,
+        since: None,
+        status: Status::Invisible,
+    }
+}
+
+/// Parses a status as it's stored in the settings.
+pub fn parse_status(status: &str) -> Option<Status> {
+    Some(match status {
+        "online" => Status::Online,
+        "idle" => Status::Idle,
+        "dnd" => Status::DoNotDisturb,
+        "invisible" => Status::Invisible,
+        _ => return None,
+    })
+}
+
+/// A status as it's stored in the settings.
+pub const fn status_setting(status: Status) -> &'static str {
+    match status {
+        Status::Online => "online",
+        Status::Idle => "idle",
+        Status::DoNotDisturb => "dnd",
+        Status::Invisible | Status::Offline => "invisible",
+    }
+}
+
+/// The activity other users see a custom status as, `None` if it's empty.
+pub fn custom_status_activity(custom_status: &CustomStatus) -> Option<Activity> {
+    if custom_status.text.is_empty() && custom_status.emoji_name.is_empty() {
+        return None;
+    }
+
+    let emoji = (!custom_status.emoji_name.is_empty()).then(|| ActivityEmoji {
+        animated: None,
+        name: custom_status.emoji_name.clone(),
+        id: custom_status.emoji_id.map(|id| id.to_string()),
+    });
+
+    Some(Activity {
+        application_id: None,
+        assets: None,
+        buttons: Vec::new(),
+        created_at: None,
+        details: None,
+        emoji,
+        flags: None,
+        id: None,
+        instance: None,
+        kind: ActivityType::Custom,
+        name: CUSTOM_STATUS_NAME.to_owned(),
+        party: None,
+        secrets: None,
+        state: (!custom_status.text.is_empty()).then(|| custom_status.text.clone()),
+        timestamps: None,
+        url: None,
+    })
+}
+
+const fn is_expired(custom_status: &CustomStatus, now_ms: u64) -> bool {
+    custom_status.expires_at_ms != 0 && custom_status.expires_at_ms <= now_ms
+}
+
+/// The current time in milliseconds since the unix epoch, which custom
+/// statuses and emoji uses are timed in.
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::gateway::presence::{Activity, ActivityType, Status};
+
+    use super::PresenceStore;
+    use crate::user_settings::{CustomStatus, StatusSettings};
+
+    fn settings(status: &str, custom_status: Option<CustomStatus>) -> StatusSettings {
+        StatusSettings {
+            status: Some(status.to_owned()),
+            custom_status,
+            ..StatusSettings::default()
+        }
+    }
+
+    fn game(name: &str) -> Activity {
+        Activity {
+            application_id: None,
+            assets: None,
+            buttons: Vec::new(),
+            created_at: None,
+            details: None,
+            emoji: None,
+            flags: None,
+            id: None,
+            instance: None,
+            kind: ActivityType::Playing,
+            name: name.to_owned(),
+            party: None,
+            secrets: None,
+            state: None,
+            timestamps: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn nothing_published_before_settings() {
+        let mut presence = PresenceStore::new();
+        presence.set_activity(1, Some(game("Factorio")));
+
+        assert_eq!(presence.take_changes(), None);
+    }
+
+    #[test]
+    fn changes_are_published_once() {
+        let mut presence = PresenceStore::new();
+        presence.set_settings(Some(&settings("dnd", None)));
+
+        let payload = presence.take_changes().unwrap();
+        assert_eq!(payload.status, Status::DoNotDisturb);
+        assert!(payload.activities.is_empty());
+        assert_eq!(presence.take_changes(), None);
+
+        presence.set_activity(1, Some(game("Factorio")));
+        assert_eq!(presence.take_changes().unwrap().activities.len(), 1);
+
+        presence.set_activity(1, None);
+        assert!(presence.take_changes().unwrap().activities.is_empty());
+    }
+
+    #[test]
+    fn custom_status_comes_first_until_it_expires() {
+        let custom_status = CustomStatus {
+            text: "brb".to_owned(),
+            emoji_name: "☕".to_owned(),
+            expires_at_ms: 2_000,
+            ..CustomStatus::default()
+        };
+        let mut presence = PresenceStore::new();
+        presence.set_settings(Some(&settings("online", Some(custom_status))));
+        presence.set_activity(1, Some(game("Factorio")));
+
+        let activities = presence.payload(1_000).activities;
+        assert_eq!(activities[0].kind, ActivityType::Custom);
+        assert_eq!(activities[0].state.as_deref(), Some("brb"));
+        assert_eq!(activities[0].emoji.as_ref().unwrap().name, "☕");
+        assert_eq!(activities[1].name, "Factorio");
+
+        let activities = presence.payload(2_000).activities;
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].name, "Factorio");
+    }
+
+    #[test]
+    fn idle_while_away() {
+        let mut presence = PresenceStore::new();
+        presence.set_settings(Some(&settings("online", None)));
+        presence.set_idle(Some(1_000));
+
+        let payload = presence.payload(2_000);
+        assert_eq!(payload.status, Status::Idle);
+        assert!(payload.afk);
+        assert_eq!(payload.since, Some(1_000));
+
+        presence.set_idle(None);
+        let payload = presence.payload(2_000);
+        assert_eq!(payload.status, Status::Online);
+        assert!(!payload.afk);
+        assert_eq!(payload.since, None);
+    }
+
+    #[test]
+    fn chosen_status_kept_while_away() {
+        for status in ["dnd", "invisible"] {
+            let mut presence = PresenceStore::new();
+            presence.set_settings(Some(&settings(status, None)));
+            let chosen = presence.status();
+            presence.set_idle(Some(1_000));
+
+            let payload = presence.payload(2_000);
+            assert_eq!(payload.status, chosen);
+            assert!(payload.afk);
+        }
+    }
+
+    #[test]
+    fn games_hidden_unless_shown() {
+        let mut presence = PresenceStore::new();
+        presence.set_settings(Some(&StatusSettings {
+            show_current_game: Some(false),
+            ..settings("idle", None)
+        }));
+        presence.set_activity(1, Some(game("Factorio")));
+
+        assert!(presence.payload(0).activities.is_empty());
+    }
+}
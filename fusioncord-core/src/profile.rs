@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use twilight_model::{
+    gateway::event::DispatchEvent,
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+    user::{ConnectedAccount, PremiumType, ProfileResponse, UserFlags},
+};
+
+/// Profile of a user, fetched on demand.
+#[derive(Debug, Clone)]
+pub enum Profile {
+    Loading,
+    Loaded(Box<ProfileResponse>),
+    Failed,
+}
+
+/// Identifies the profile of a user as it's shown in a guild, or outside of
+/// guilds.
+type ProfileKey = (Id<UserMarker>, Option<Id<GuildMarker>>);
+
+/// Keeps track of the profiles of users that were opened, along with the
+/// private notes the current user keeps about them.
+///
+/// Must be fed every dispatch event through [`ProfileStore::update`].
+#[derive(Debug, Default)]
+pub struct ProfileStore {
+    profiles: HashMap<ProfileKey, Profile>,
+    notes: HashMap<Id<UserMarker>, String>,
+}
+
+impl ProfileStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => {
+                self.profiles.clear();
+                self.notes.clear();
+            }
+            // the fetched profiles are outdated, they are fetched again when needed
+            DispatchEvent::GuildMemberUpdate(update) => {
+                self.profiles
+                    .remove(&(update.user.id, Some(update.guild_id)));
+            }
+            DispatchEvent::GuildMemberRemove(remove) => {
+                self.profiles
+                    .remove(&(remove.user.id, Some(remove.guild_id)));
+            }
+            DispatchEvent::UserUpdate(user) => {
+                self.profiles
+                    .retain(|(user_id, _), _| *user_id != user.0.id);
+            }
+            DispatchEvent::UserNoteUpdate(update) => {
+                self.notes.insert(update.id, update.note.clone());
+            }
+            _ => (),
+        }
+    }
+
+    pub fn profile(
+        &self,
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> Option<&Profile> {
+        self.profiles.get(&(user_id, guild_id))
+    }
+
+    /// Marks the profile of a user as being fetched.
+    ///
+    /// Returns whether it must be fetched, `false` if it already was.
+    pub fn start_fetch(
+        &mut self,
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> bool {
+        let key = (user_id, guild_id);
+        if self.profiles.contains_key(&key) {
+            return false;
+        }
+        self.profiles.insert(key, Profile::Loading);
+        true
+    }
+
+    /// Inserts a fetched profile, `None` if fetching it failed.
+    pub fn insert_profile(
+        &mut self,
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        profile: Option<Box<ProfileResponse>>,
+    ) {
+        let profile = profile.map_or(Profile::Failed, Profile::Loaded);
+        self.profiles.insert((user_id, guild_id), profile);
+    }
+
+    /// The note the current user keeps about a user, `None` until it's
+    /// fetched.
+    pub fn note(&self, user_id: Id<UserMarker>) -> Option<&str> {
+        self.notes.get(&user_id).map(String::as_str)
+    }
+
+    pub fn set_note(&mut self, user_id: Id<UserMarker>, note: String) {
+        self.notes.insert(user_id, note);
+    }
+}
+
+/// Badge shown on a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Badge {
+    Staff,
+    Partner,
+    ModeratorProgramsAlumni,
+    HypeSquadEvents,
+    HypeSquadBravery,
+    HypeSquadBrilliance,
+    HypeSquadBalance,
+    BugHunter,
+    GoldBugHunter,
+    ActiveDeveloper,
+    VerifiedDeveloper,
+    EarlySupporter,
+    Nitro,
+}
+
+impl Badge {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Staff => "Discord Staff",
+            Self::Partner => "Partnered Server Owner",
+            Self::ModeratorProgramsAlumni => "Moderator Programs Alumni",
+            Self::HypeSquadEvents => "HypeSquad Events",
+            Self::HypeSquadBravery => "HypeSquad Bravery",
+            Self::HypeSquadBrilliance => "HypeSquad Brilliance",
+            Self::HypeSquadBalance => "HypeSquad Balance",
+            Self::BugHunter => "Discord Bug Hunter",
+            Self::GoldBugHunter => "Discord Gold Bug Hunter",
+            Self::ActiveDeveloper => "Active Developer",
+            Self::VerifiedDeveloper => "Early Verified Bot Developer",
+            Self::EarlySupporter => "Early Supporter",
+            Self::Nitro => "Nitro Subscriber",
+        }
+    }
+}
+
+/// Badges of a user, in the order Discord shows them.
+pub fn badges(flags: UserFlags, premium_type: Option<PremiumType>) -> Vec<Badge> {
+    let mut badges = [
+        (UserFlags::STAFF, Badge::Staff),
+        (UserFlags::PARTNER, Badge::Partner),
+        (
+            UserFlags::MODERATOR_PROGRAMS_ALUMNI,
+            Badge::ModeratorProgramsAlumni,
+        ),
+        (UserFlags::HYPESQUAD, Badge::HypeSquadEvents),
+        (UserFlags::HYPESQUAD_ONLINE_HOUSE_1, Badge::HypeSquadBravery),
+        (
+            UserFlags::HYPESQUAD_ONLINE_HOUSE_2,
+            Badge::HypeSquadBrilliance,
+        ),
+        (UserFlags::HYPESQUAD_ONLINE_HOUSE_3, Badge::HypeSquadBalance),
+        (UserFlags::BUG_HUNTER_LEVEL_1, Badge::BugHunter),
+        (UserFlags::BUG_HUNTER_LEVEL_2, Badge::GoldBugHunter),
+        (UserFlags::ACTIVE_DEVELOPER, Badge::ActiveDeveloper),
+        (UserFlags::VERIFIED_DEVELOPER, Badge::VerifiedDeveloper),
+        (UserFlags::PREMIUM_EARLY_SUPPORTER, Badge::EarlySupporter),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags.contains(*flag))
+    .map(|(_, badge)| badge)
+    .collect::<Vec<_>>();
+
+    if premium_type.is_some_and(|premium_type| premium_type != PremiumType::None) {
+        badges.push(Badge::Nitro);
+    }
+
+    badges
+}
+
+/// Page of a connected account on its service, `None` for the services that
+/// don't have one.
+pub fn connection_url(account: &ConnectedAccount) -> Option<String> {
+    let ConnectedAccount { id, name, .. } = account;
+
+    Some(match account.kind.as_str() {
+        "domain" => format!("https://{name}"),
+        "ebay" => format!("https://www.ebay.com/usr/{name}"),
+        "github" => format!("https://github.com/{name}"),
+        "instagram" => format!("https://www.instagram.com/{name}"),
+        "reddit" => format!("https://www.reddit.com/u/{name}"),
+        "spotify" => format!("https://open.spotify.com/user/{id}"),
+        "steam" => format!("https://steamcommunity.com/profiles/{id}"),
+        "tiktok" => format!("https://www.tiktok.com/@{name}"),
+        "twitch" => format!("https://www.twitch.tv/{name}"),
+        "twitter" => format!("https://twitter.com/{name}"),
+        "youtube" => format!("https://www.youtube.com/channel/{id}"),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{MemberUpdate, UserNoteUpdate},
+        },
+        id::Id,
+        user::{ConnectedAccount, PremiumType, User, UserFlags},
+        util::Timestamp,
+    };
+
+    use super::{badges, connection_url, Badge, Profile, ProfileStore};
+
+    fn user(id: u64) -> User {
+        User {
+            accent_color: None,
+            avatar: None,
+            avatar_decoration: None,
+            banner: None,
+            bot: false,
+            discriminator: 0,
+            email: None,
+            flags: None,
+            global_name: None,
+            id: Id::new(id),
+            locale: None,
+            mfa_enabled: None,
+            name: "user".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }
+    }
+
+    #[test]
+    fn fetched_once() {
+        let mut profiles = ProfileStore::new();
+
+        assert!(profiles.start_fetch(Id::new(1), None));
+        assert!(!profiles.start_fetch(Id::new(1), None));
+        assert!(matches!(
+            profiles.profile(Id::new(1), None),
+            Some(Profile::Loading)
+        ));
+
+        profiles.insert_profile(Id::new(1), None, None);
+        assert!(matches!(
+            profiles.profile(Id::new(1), None),
+            Some(Profile::Failed)
+        ));
+        // the profile in a guild is fetched separately
+        assert!(profiles.start_fetch(Id::new(1), Some(Id::new(2))));
+    }
+
+    #[test]
+    fn member_update_invalidates_guild_profile() {
+        let mut profiles = ProfileStore::new();
+        profiles.start_fetch(Id::new(1), None);
+        profiles.start_fetch(Id::new(1), Some(Id::new(2)));
+
+        profiles.update(&DispatchEvent::GuildMemberUpdate(Box::new(MemberUpdate {
+            avatar: None,
+            communication_disabled_until: None,
+            guild_id: Id::new(2),
+            deaf: None,
+            joined_at: Timestamp::from_secs(1_600_000_000).unwrap(),
+            mute: None,
+            nick: Some("nick".to_owned()),
+            pending: false,
+            premium_since: None,
+            roles: Vec::new(),
+            user: user(1),
+        })));
+
+        assert!(profiles.profile(Id::new(1), None).is_some());
+        assert!(profiles.profile(Id::new(1), Some(Id::new(2))).is_none());
+    }
+
+    #[test]
+    fn notes_follow_updates() {
+        let mut profiles = ProfileStore::new();
+        assert_eq!(profiles.note(Id::new(1)), None);
+
+        profiles.set_note(Id::new(1), String::new());
+        assert_eq!(profiles.note(Id::new(1)), Some(""));
+
+        profiles.update(&DispatchEvent::UserNoteUpdate(UserNoteUpdate {
+            id: Id::new(1),
+            note: "owes me lunch".to_owned(),
+        }));
+        assert_eq!(profiles.note(Id::new(1)), Some("owes me lunch"));
+    }
+
+    #[test]
+    fn badges_from_flags() {
+        let flags = UserFlags::ACTIVE_DEVELOPER | UserFlags::HYPESQUAD_ONLINE_HOUSE_2;
+
+        assert_eq!(
+            badges(flags, Some(PremiumType::Nitro)),
+            [
+                Badge::HypeSquadBrilliance,
+                Badge::ActiveDeveloper,
+                Badge::Nitro
+            ]
+        );
+        assert!(badges(UserFlags::empty(), Some(PremiumType::None)).is_empty());
+    }
+
+    #[test]
+    fn connection_urls() {
+        let account = |kind: &str| ConnectedAccount {
+            id: "76561197960287930".to_owned(),
+            kind: kind.to_owned(),
+            name: "ferris".to_owned(),
+            verified: true,
+        };
+
+        assert_eq!(
+            connection_url(&account("github")).as_deref(),
+            Some("https://github.com/ferris")
+        );
+        assert_eq!(
+            connection_url(&account("steam")).as_deref(),
+            Some("https://steamcommunity.com/profiles/76561197960287930")
+        );
+        assert_eq!(connection_url(&account("battlenet")), None);
+    }
+}
@@ -0,0 +1,559 @@
+//! Minimal protobuf wire format codec, enough to read and write the settings
+//! Discord sends as protobuf.
+//!
+//! Fields a message doesn't know are kept as raw bytes and written back as is,
+//! so settings can be changed without dropping the ones added by newer
+//! clients.
+
+/// Type of the value of a field, as encoded in its tag.
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const LENGTH_DELIMITED: u8 = 2;
+const FIXED32: u8 = 5;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("unexpected end of the message")]
+    UnexpectedEof,
+    #[error("varint is longer than 10 bytes")]
+    VarintOverflow,
+    #[error("unsupported wire type {0}")]
+    WireType(u8),
+    #[error("field {0} has an unexpected wire type")]
+    UnexpectedWireType(u32),
+    #[error("field {0} is not valid UTF-8")]
+    InvalidUtf8(u32),
+}
+
+/// Value of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    LengthDelimited(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// A field of a message.
+#[derive(Debug, Clone, Copy)]
+pub struct Field<'a> {
+    pub number: u32,
+    pub value: Value<'a>,
+    /// The encoded field including its tag, written back for unknown fields.
+    raw: &'a [u8],
+}
+
+impl<'a> Field<'a> {
+    pub fn as_u64(&self) -> Result<u64, DecodeError> {
+        match self.value {
+            Value::Varint(value) | Value::Fixed64(value) => Ok(value),
+            Value::Fixed32(value) => Ok(value.into()),
+            Value::LengthDelimited(_) => Err(DecodeError::UnexpectedWireType(self.number)),
+        }
+    }
+
+    /// Value of an `int32` or `uint32` field, which are truncated like
+    /// other protobuf implementations do.
+    pub fn as_u32(&self) -> Result<u32, DecodeError> {
+        self.as_u64().map(|value| value as u32)
+    }
+
+    pub fn as_i32(&self) -> Result<i32, DecodeError> {
+        self.as_u64().map(|value| value as i32)
+    }
+
+    pub fn as_i64(&self) -> Result<i64, DecodeError> {
+        self.as_u64().map(|value| value as i64)
+    }
+
+    pub fn as_bool(&self) -> Result<bool, DecodeError> {
+        self.as_u64().map(|value| value != 0)
+    }
+
+    pub fn as_bytes(&self) -> Result<&'a [u8], DecodeError> {
+        match self.value {
+            Value::LengthDelimited(bytes) => Ok(bytes),
+            _ => Err(DecodeError::UnexpectedWireType(self.number)),
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&'a str, DecodeError> {
+        std::str::from_utf8(self.as_bytes()?).map_err(|_| DecodeError::InvalidUtf8(self.number))
+    }
+
+    /// Values of a repeated `fixed64` field, which are either packed in a
+    /// single field or sent as one field per value.
+    pub fn as_packed_fixed64(&self) -> Result<Vec<u64>, DecodeError> {
+        match self.value {
+            Value::LengthDelimited(bytes) => {
+                if bytes.len() % 8 != 0 {
+                    return Err(DecodeError::UnexpectedEof);
+                }
+
+                Ok(bytes
+                    .chunks_exact(8)
+                    .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("chunk of 8 bytes")))
+                    .collect())
+            }
+            _ => self.as_u64().map(|value| vec![value]),
+        }
+    }
+
+    /// Values of a repeated varint field, which are either packed in a
+    /// single field or sent as one field per value.
+    pub fn as_packed_varint(&self) -> Result<Vec<u64>, DecodeError> {
+        match self.value {
+            Value::LengthDelimited(mut bytes) => {
+                let mut values = Vec::new();
+                while !bytes.is_empty() {
+                    values.push(read_varint(&mut bytes)?);
+                }
+
+                Ok(values)
+            }
+            _ => self.as_u64().map(|value| vec![value]),
+        }
+    }
+
+    /// Merges an embedded message field into an existing message.
+    pub fn merge_into<M: Message>(&self, message: &mut M) -> Result<(), DecodeError> {
+        message.merge(self.as_bytes()?)
+    }
+
+    /// Value of a `google.protobuf` wrapper message such as `StringValue`,
+    /// which wrap their value in the field with number 1.
+    pub fn wrapped(&self) -> Result<Option<Field<'a>>, DecodeError> {
+        let mut value = None;
+        for field in Reader::new(self.as_bytes()?) {
+            let field = field?;
+            if field.number == 1 {
+                value = Some(field);
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+/// Iterator over the fields of an encoded message.
+pub struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn read_field(&mut self) -> Result<Field<'a>, DecodeError> {
+        let start = self.buf;
+        let tag = read_varint(&mut self.buf)?;
+        let number = (tag >> 3) as u32;
+
+        let value = match (tag & 0b111) as u8 {
+            VARINT => Value::Varint(read_varint(&mut self.buf)?),
+            FIXED64 => Value::Fixed64(u64::from_le_bytes(self.take_array()?)),
+            LENGTH_DELIMITED => {
+                let len = read_varint(&mut self.buf)?;
+                let len = usize::try_from(len).map_err(|_| DecodeError::UnexpectedEof)?;
+                Value::LengthDelimited(self.take(len)?)
+            }
+            FIXED32 => Value::Fixed32(u32::from_le_bytes(self.take_array()?)),
+            wire_type => return Err(DecodeError::WireType(wire_type)),
+        };
+
+        let raw = &start[..start.len() - self.buf.len()];
+        Ok(Field { number, value, raw })
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.buf.len() < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        let (taken, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().expect("slice of N bytes"))
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Field<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let field = self.read_field();
+        if field.is_err() {
+            // the rest of the message can't be read after a malformed field
+            self.buf = &[];
+        }
+
+        Some(field)
+    }
+}
+
+/// Builds an encoded message, fields are written in the order they're added.
+#[derive(Debug, Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub fn varint(&mut self, number: u32, value: u64) {
+        self.tag(number, VARINT);
+        write_varint(&mut self.buf, value);
+    }
+
+    pub fn bool(&mut self, number: u32, value: bool) {
+        self.varint(number, value.into());
+    }
+
+    pub fn fixed64(&mut self, number: u32, value: u64) {
+        self.tag(number, FIXED64);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, number: u32, value: &[u8]) {
+        self.tag(number, LENGTH_DELIMITED);
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    pub fn string(&mut self, number: u32, value: &str) {
+        self.bytes(number, value.as_bytes());
+    }
+
+    pub fn message<M: Message>(&mut self, number: u32, message: &M) {
+        self.bytes(number, &message.encode());
+    }
+
+    /// Writes a repeated `fixed64` field in its packed form, nothing if there
+    /// are no values.
+    pub fn packed_fixed64(&mut self, number: u32, values: impl IntoIterator<Item = u64>) {
+        let packed = values
+            .into_iter()
+            .flat_map(u64::to_le_bytes)
+            .collect::<Vec<_>>();
+        if !packed.is_empty() {
+            self.bytes(number, &packed);
+        }
+    }
+
+    /// Writes a repeated varint field in its packed form, nothing if there
+    /// are no values.
+    pub fn packed_varint(&mut self, number: u32, values: impl IntoIterator<Item = u64>) {
+        let mut packed = Vec::new();
+        for value in values {
+            write_varint(&mut packed, value);
+        }
+        if !packed.is_empty() {
+            self.bytes(number, &packed);
+        }
+    }
+
+    /// Writes a `google.protobuf` wrapper message holding a value written by
+    /// the given closure as field 1.
+    pub fn wrapped(&mut self, number: u32, write: impl FnOnce(&mut Self)) {
+        let mut wrapper = Self::new();
+        write(&mut wrapper);
+        self.bytes(number, &wrapper.buf);
+    }
+
+    /// Writes fields that were read but not understood.
+    pub fn unknown(&mut self, unknown: &UnknownFields) {
+        self.buf.extend_from_slice(&unknown.0);
+    }
+
+    fn tag(&mut self, number: u32, wire_type: u8) {
+        write_varint(&mut self.buf, u64::from(number) << 3 | u64::from(wire_type));
+    }
+}
+
+/// Encoded fields of a message that it doesn't know.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UnknownFields(Vec<u8>);
+
+impl UnknownFields {
+    pub fn push(&mut self, field: &Field<'_>) {
+        self.0.extend_from_slice(field.raw);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A protobuf message.
+pub trait Message: Default {
+    /// Decodes a field of the message, returning `false` if the field is
+    /// unknown.
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError>;
+
+    /// Writes the known fields of the message.
+    fn write_fields(&self, writer: &mut Writer);
+
+    fn unknown_fields(&self) -> &UnknownFields;
+
+    fn unknown_fields_mut(&mut self) -> &mut UnknownFields;
+
+    /// Merges an encoded message into this one, following the protobuf
+    /// rules: scalar fields are overwritten, embedded messages are merged
+    /// and repeated fields are appended to.
+    fn merge(&mut self, buf: &[u8]) -> Result<(), DecodeError> {
+        for field in Reader::new(buf) {
+            let field = field?;
+            if !self.merge_field(&field)? {
+                self.unknown_fields_mut().push(&field);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut message = Self::default();
+        message.merge(buf)?;
+
+        Ok(message)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::new();
+        self.write_fields(&mut writer);
+        writer.unknown(self.unknown_fields());
+
+        writer.into_bytes()
+    }
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or(DecodeError::UnexpectedEof)?;
+        *buf = rest;
+
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(DecodeError::VarintOverflow)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodeError, Field, Message, Reader, UnknownFields, Value, Writer};
+
+    /// Message knowing a varint, a `fixed64` and a string field.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct Sample {
+        count: u64,
+        id: u64,
+        name: String,
+        unknown: UnknownFields,
+    }
+
+    impl Message for Sample {
+        fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+            match field.number {
+                1 => self.count = field.as_u64()?,
+                2 => self.id = field.as_u64()?,
+                3 => self.name = field.as_str()?.to_owned(),
+                _ => return Ok(false),
+            }
+
+            Ok(true)
+        }
+
+        fn write_fields(&self, writer: &mut Writer) {
+            writer.varint(1, self.count);
+            writer.fixed64(2, self.id);
+            writer.string(3, &self.name);
+        }
+
+        fn unknown_fields(&self) -> &UnknownFields {
+            &self.unknown
+        }
+
+        fn unknown_fields_mut(&mut self) -> &mut UnknownFields {
+            &mut self.unknown
+        }
+    }
+
+    fn values(buf: &[u8]) -> Result<Vec<(u32, Value<'_>)>, DecodeError> {
+        Reader::new(buf)
+            .map(|field| field.map(|field| (field.number, field.value)))
+            .collect()
+    }
+
+    #[test]
+    fn varint() {
+        let mut writer = Writer::new();
+        writer.varint(1, 150);
+        writer.varint(2, u64::MAX);
+        // negative int32 values are sign extended to 10 bytes
+        writer.varint(3, -2_i64 as u64);
+        writer.bool(16, true);
+        let buf = writer.into_bytes();
+
+        assert_eq!(buf[..3], [0x08, 0x96, 0x01]);
+        assert_eq!(buf[buf.len() - 3..], [0x80, 0x01, 0x01]);
+        assert_eq!(
+            values(&buf).unwrap(),
+            [
+                (1, Value::Varint(150)),
+                (2, Value::Varint(u64::MAX)),
+                (3, Value::Varint(-2_i64 as u64)),
+                (16, Value::Varint(1)),
+            ]
+        );
+
+        let fields = Reader::new(&buf).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(fields[2].as_i32(), Ok(-2));
+        assert_eq!(fields[3].as_bool(), Ok(true));
+        assert_eq!(
+            fields[0].as_bytes(),
+            Err(DecodeError::UnexpectedWireType(1))
+        );
+
+        assert_eq!(values(&[0x08, 0x96]), Err(DecodeError::UnexpectedEof));
+        assert_eq!(
+            values(&[0x08, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]),
+            Err(DecodeError::VarintOverflow)
+        );
+    }
+
+    #[test]
+    fn fixed64() {
+        let mut writer = Writer::new();
+        writer.fixed64(1, 81_384_788_765_712_384);
+        writer.packed_fixed64(2, [1, u64::MAX]);
+        writer.packed_fixed64(3, []);
+        let buf = writer.into_bytes();
+
+        assert_eq!(buf[0], 0x09);
+        let fields = Reader::new(&buf).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].value, Value::Fixed64(81_384_788_765_712_384));
+        assert_eq!(
+            fields[0].as_packed_fixed64(),
+            Ok(vec![81_384_788_765_712_384])
+        );
+        assert_eq!(fields[1].as_packed_fixed64(), Ok(vec![1, u64::MAX]));
+
+        assert_eq!(values(&buf[..5]), Err(DecodeError::UnexpectedEof));
+    }
+
+    #[test]
+    fn length_delimited() {
+        let nested = Sample {
+            count: 3,
+            id: 4,
+            name: "nested".to_owned(),
+            ..Sample::default()
+        };
+
+        let mut writer = Writer::new();
+        writer.string(1, "crab 🦀");
+        writer.message(2, &nested);
+        writer.packed_varint(3, [1, 300, u64::MAX]);
+        writer.wrapped(4, |wrapper| wrapper.string(1, "wrapped"));
+        let buf = writer.into_bytes();
+
+        let fields = Reader::new(&buf).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(fields[0].as_str(), Ok("crab 🦀"));
+        assert_eq!(Sample::decode(fields[1].as_bytes().unwrap()), Ok(nested));
+        assert_eq!(fields[2].as_packed_varint(), Ok(vec![1, 300, u64::MAX]));
+        assert_eq!(
+            fields[3].wrapped().unwrap().unwrap().as_str(),
+            Ok("wrapped")
+        );
+
+        // the length is longer than the rest of the message
+        assert_eq!(values(&[0x0A, 0x05, b'a']), Err(DecodeError::UnexpectedEof));
+        let invalid = [0x0A, 0x01, 0xFF];
+        let field = Reader::new(&invalid).next().unwrap().unwrap();
+        assert_eq!(field.as_str(), Err(DecodeError::InvalidUtf8(1)));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let mut writer = Writer::new();
+        writer.varint(1, 7);
+        writer.varint(10, 1);
+        writer.fixed64(11, 2);
+        writer.string(3, "known");
+        writer.string(12, "unknown");
+        writer.packed_varint(13, [1, 2, 3]);
+        let mut buf = writer.into_bytes();
+        // fixed32 fields aren't written by the client, but must be kept
+        buf.extend_from_slice(&[0x75, 0x01, 0x02, 0x03, 0x04]);
+
+        let sample = Sample::decode(&buf).unwrap();
+        assert_eq!((sample.count, sample.name.as_str()), (7, "known"));
+        assert!(!sample.unknown.is_empty());
+
+        // known fields are written first, followed by the unknown ones
+        let encoded = sample.encode();
+        assert_eq!(
+            values(&encoded).unwrap(),
+            [
+                (1, Value::Varint(7)),
+                (2, Value::Fixed64(0)),
+                (3, Value::LengthDelimited(b"known")),
+                (10, Value::Varint(1)),
+                (11, Value::Fixed64(2)),
+                (12, Value::LengthDelimited(b"unknown")),
+                (13, Value::LengthDelimited(&[1, 2, 3])),
+                (14, Value::Fixed32(0x0403_0201)),
+            ]
+        );
+        assert_eq!(Sample::decode(&encoded), Ok(sample));
+
+        // unsupported wire types can't be skipped
+        assert_eq!(Sample::decode(&[0x0B]), Err(DecodeError::WireType(3)));
+    }
+
+    #[test]
+    fn merge() {
+        let mut sample = Sample {
+            count: 1,
+            name: "first".to_owned(),
+            ..Sample::default()
+        };
+
+        let mut writer = Writer::new();
+        writer.varint(1, 2);
+        writer.varint(10, 1);
+        sample.merge(&writer.into_bytes()).unwrap();
+
+        // fields missing from the merged message are kept
+        assert_eq!((sample.count, sample.name.as_str()), (2, "first"));
+        assert!(!sample.unknown.is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use twilight_model::{
+    channel::message::ReactionType,
+    gateway::{event::DispatchEvent, payload::incoming::BurstCreditBalanceUpdate},
+    id::{marker::MessageMarker, Id},
+    user::User,
+};
+
+/// Users that reacted with an emoji, fetched on demand.
+#[derive(Debug, Clone)]
+pub enum Reactors {
+    Loading,
+    Loaded(Vec<User>),
+    Failed,
+}
+
+/// Identifies the reactions of one kind with one emoji to a message.
+type ReactionKey = (Id<MessageMarker>, String, bool);
+
+/// Keeps track of the burst reaction credits of the current user and the
+/// users that reacted to messages.
+///
+/// Must be fed every dispatch event through [`ReactionStore::update`].
+#[derive(Debug, Default)]
+pub struct ReactionStore {
+    burst_credits: Option<BurstCreditBalanceUpdate>,
+    reactors: HashMap<ReactionKey, Reactors>,
+}
+
+impl ReactionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => {
+                self.burst_credits = None;
+                self.reactors.clear();
+            }
+            DispatchEvent::BurstCreditBalanceUpdate(balance) => {
+                self.burst_credits = Some(balance.clone());
+            }
+            // the fetched reactors are outdated, they are fetched again when needed
+            DispatchEvent::ReactionAdd(reaction) => {
+                self.reactors
+                    .remove(&key(reaction.message_id, &reaction.emoji, reaction.burst));
+            }
+            DispatchEvent::ReactionRemove(reaction) => {
+                self.reactors
+                    .remove(&key(reaction.message_id, &reaction.emoji, reaction.burst));
+            }
+            DispatchEvent::ReactionRemoveAll(remove) => {
+                self.reactors
+                    .retain(|(message_id, ..), _| *message_id != remove.message_id);
+            }
+            DispatchEvent::ReactionRemoveEmoji(remove) => {
+                let emoji = emoji_key(&remove.emoji);
+                self.reactors.retain(|(message_id, cached_emoji, _), _| {
+                    *message_id != remove.message_id || *cached_emoji != emoji
+                });
+            }
+            _ => (),
+        }
+    }
+
+    /// Amount of burst reactions the current user can send, if known.
+    pub fn burst_credits(&self) -> Option<u32> {
+        self.burst_credits.as_ref().map(|balance| balance.amount)
+    }
+
+    pub fn reactors(
+        &self,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+    ) -> Option<&Reactors> {
+        self.reactors.get(&key(message_id, emoji, burst))
+    }
+
+    /// Marks the reactors of an emoji as being fetched.
+    ///
+    /// Returns whether they must be fetched, `false` if they already were.
+    pub fn start_fetch(
+        &mut self,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+    ) -> bool {
+        let key = key(message_id, emoji, burst);
+        if self.reactors.contains_key(&key) {
+            return false;
+        }
+
+        self.reactors.insert(key, Reactors::Loading);
+        true
+    }
+
+    /// Forgets the fetched reactors of an emoji, for reactions the current
+    /// user is changing before the gateway confirms it.
+    pub fn evict(&mut self, message_id: Id<MessageMarker>, emoji: &ReactionType, burst: bool) {
+        self.reactors.remove(&key(message_id, emoji, burst));
+    }
+
+    /// Inserts fetched reactors, `None` if fetching them failed.
+    pub fn insert_reactors(
+        &mut self,
+        message_id: Id<MessageMarker>,
+        emoji: &ReactionType,
+        burst: bool,
+        users: Option<Vec<User>>,
+    ) {
+        let reactors = users.map_or(Reactors::Failed, Reactors::Loaded);
+        self.reactors
+            .insert(key(message_id, emoji, burst), reactors);
+    }
+}
+
+/// Whether two reactions use the same emoji.
+///
+/// Custom emojis are compared by ID only, their name isn't always sent.
+pub fn same_emoji(a: &ReactionType, b: &ReactionType) -> bool {
+    match (a, b) {
+        (ReactionType::Custom { id: a, .. }, ReactionType::Custom { id: b, .. }) => a == b,
+        (ReactionType::Unicode { name: a }, ReactionType::Unicode { name: b }) => a == b,
+        _ => false,
+    }
+}
+
+fn key(message_id: Id<MessageMarker>, emoji: &ReactionType, burst: bool) -> ReactionKey {
+    (message_id, emoji_key(emoji), burst)
+}
+
+/// Identifies an emoji the same way as [`same_emoji`] compares them.
+fn emoji_key(emoji: &ReactionType) -> String {
+    match emoji {
+        ReactionType::Custom { id, .. } => id.to_string(),
+        ReactionType::Unicode { name } => name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::{
+        channel::message::ReactionType,
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{
+                BurstCreditBalanceUpdate, ReactionAdd, ReactionRemove, ReactionRemoveAll,
+                ReactionRemoveEmoji,
+            },
+            GatewayReaction,
+        },
+        id::Id,
+        util::Timestamp,
+    };
+
+    use super::{ReactionStore, Reactors};
+
+    const CHANNEL_ID: u64 = 20;
+    const GUILD_ID: u64 = 10;
+
+    fn crab() -> ReactionType {
+        ReactionType::Unicode {
+            name: "🦀".to_owned(),
+        }
+    }
+
+    /// A custom emoji, whose name isn't always sent.
+    fn ferris(name: Option<&str>) -> ReactionType {
+        ReactionType::Custom {
+            animated: false,
+            id: Id::new(30),
+            name: name.map(ToOwned::to_owned),
+        }
+    }
+
+    fn reaction(message_id: u64, emoji: &ReactionType, burst: bool) -> GatewayReaction {
+        serde_json::from_value(json!({
+            "burst": burst,
+            "channel_id": CHANNEL_ID.to_string(),
+            "emoji": emoji,
+            "guild_id": GUILD_ID.to_string(),
+            "member": null,
+            "message_id": message_id.to_string(),
+            "user_id": "2",
+        }))
+        .unwrap()
+    }
+
+    /// Store with the reactors of both emojis fetched for messages 100 and
+    /// 101, normal and burst ones.
+    fn store() -> ReactionStore {
+        let mut store = ReactionStore::new();
+        for message_id in [100, 101] {
+            for emoji in [crab(), ferris(Some("ferris"))] {
+                for burst in [false, true] {
+                    assert!(store.start_fetch(Id::new(message_id), &emoji, burst));
+                    store.insert_reactors(Id::new(message_id), &emoji, burst, Some(Vec::new()));
+                }
+            }
+        }
+
+        store
+    }
+
+    fn fetched(store: &ReactionStore, message_id: u64, emoji: &ReactionType, burst: bool) -> bool {
+        store.reactors(Id::new(message_id), emoji, burst).is_some()
+    }
+
+    #[test]
+    fn fetches() {
+        let mut store = ReactionStore::new();
+        assert!(store.reactors(Id::new(100), &crab(), false).is_none());
+        assert!(store.start_fetch(Id::new(100), &crab(), false));
+        assert!(matches!(
+            store.reactors(Id::new(100), &crab(), false),
+            Some(Reactors::Loading)
+        ));
+        // they're only fetched once
+        assert!(!store.start_fetch(Id::new(100), &crab(), false));
+        assert!(store.start_fetch(Id::new(100), &crab(), true));
+
+        store.insert_reactors(Id::new(100), &crab(), false, Some(Vec::new()));
+        store.insert_reactors(Id::new(100), &crab(), true, None);
+        assert!(matches!(
+            store.reactors(Id::new(100), &crab(), false),
+            Some(Reactors::Loaded(users)) if users.is_empty()
+        ));
+        assert!(matches!(
+            store.reactors(Id::new(100), &crab(), true),
+            Some(Reactors::Failed)
+        ));
+
+        // custom emojis are identified by their id
+        store.insert_reactors(Id::new(100), &ferris(Some("ferris")), false, None);
+        assert!(fetched(&store, 100, &ferris(None), false));
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let mut store = store();
+        let add = ReactionAdd(reaction(100, &crab(), false));
+        store.update(&DispatchEvent::ReactionAdd(Box::new(add)));
+        assert!(!fetched(&store, 100, &crab(), false));
+        assert!(fetched(&store, 100, &crab(), true));
+        assert!(fetched(&store, 101, &crab(), false));
+
+        let remove = ReactionRemove(reaction(100, &ferris(None), true));
+        store.update(&DispatchEvent::ReactionRemove(Box::new(remove)));
+        assert!(!fetched(&store, 100, &ferris(None), true));
+        assert!(fetched(&store, 100, &ferris(None), false));
+
+        store.evict(Id::new(101), &crab(), true);
+        assert!(!fetched(&store, 101, &crab(), true));
+        assert!(fetched(&store, 101, &crab(), false));
+    }
+
+    #[test]
+    fn removed_all() {
+        let mut store = store();
+        store.update(&DispatchEvent::ReactionRemoveEmoji(ReactionRemoveEmoji {
+            channel_id: Id::new(CHANNEL_ID),
+            emoji: ferris(None),
+            guild_id: Id::new(GUILD_ID),
+            message_id: Id::new(100),
+        }));
+        assert!(!fetched(&store, 100, &ferris(None), false));
+        assert!(!fetched(&store, 100, &ferris(None), true));
+        assert!(fetched(&store, 100, &crab(), false));
+        assert!(fetched(&store, 101, &ferris(None), false));
+
+        store.update(&DispatchEvent::ReactionRemoveAll(ReactionRemoveAll {
+            channel_id: Id::new(CHANNEL_ID),
+            message_id: Id::new(100),
+            guild_id: Some(Id::new(GUILD_ID)),
+        }));
+        assert!(!fetched(&store, 100, &crab(), false));
+        assert!(!fetched(&store, 100, &crab(), true));
+        assert!(fetched(&store, 101, &crab(), true));
+    }
+
+    #[test]
+    fn burst_credits() {
+        let mut store = ReactionStore::new();
+        assert_eq!(store.burst_credits(), None);
+
+        store.update(&DispatchEvent::BurstCreditBalanceUpdate(
+            BurstCreditBalanceUpdate {
+                amount: 2,
+                next_replenish_at: Timestamp::from_secs(1_700_000_000).unwrap(),
+                replenished_today: true,
+            },
+        ));
+        assert_eq!(store.burst_credits(), Some(2));
+    }
+}
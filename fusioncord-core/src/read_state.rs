@@ -0,0 +1,554 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use twilight_model::{
+    channel::{Channel, Message},
+    gateway::{
+        event::DispatchEvent,
+        payload::incoming::{ChannelUnreadUpdate, MessageAck},
+    },
+    guild::Guild,
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
+        Id,
+    },
+    user::{ReadState, UserGuildSettings},
+    util::Timestamp,
+};
+
+/// Read state of a single channel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelReadState {
+    /// Last message the current user has acknowledged.
+    pub last_acked_id: Option<Id<MessageMarker>>,
+    /// Last message that was sent in the channel.
+    pub last_message_id: Option<Id<MessageMarker>>,
+    /// Amount of unacknowledged mentions of the current user.
+    pub mention_count: u32,
+}
+
+impl ChannelReadState {
+    pub fn is_unread(&self) -> bool {
+        match (self.last_message_id, self.last_acked_id) {
+            (Some(last_message_id), Some(last_acked_id)) => last_message_id > last_acked_id,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Read state of a guild, aggregated over its channels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GuildReadState {
+    /// Whether any channel that isn't muted contains unread messages.
+    pub unread: bool,
+    /// Amount of unacknowledged mentions over all channels, muted or not.
+    pub mention_count: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChannelLocation {
+    guild_id: Id<GuildMarker>,
+    parent_id: Option<Id<ChannelMarker>>,
+}
+
+/// Keeps track of which channels contain unread messages and mentions.
+///
+/// The store is seeded from the `read_state` of the READY payload and must be
+/// fed every subsequent dispatch event through [`ReadStateStore::update`].
+#[derive(Debug, Default)]
+pub struct ReadStateStore {
+    current_user_id: Option<Id<UserMarker>>,
+    channels: HashMap<Id<ChannelMarker>, ChannelReadState>,
+    locations: HashMap<Id<ChannelMarker>, ChannelLocation>,
+    /// Notification settings per guild, `None` being the direct messages.
+    guild_settings: HashMap<Option<Id<GuildMarker>>, UserGuildSettings>,
+}
+
+impl ReadStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_user_id = Some(ready.user.id);
+                self.channels.clear();
+                self.guild_settings.clear();
+
+                for read_state in &ready.read_state.entries {
+                    self.insert_read_state(read_state);
+                }
+                for settings in &ready.user_guild_settings.entries {
+                    self.guild_settings
+                        .insert(settings.guild_id, settings.clone());
+                }
+            }
+            DispatchEvent::GuildCreate(guild) => self.insert_guild(guild),
+            DispatchEvent::GuildDelete(guild) if !guild.unavailable => {
+                self.locations
+                    .retain(|_, location| location.guild_id != guild.id);
+            }
+            DispatchEvent::ChannelCreate(channel) => self.insert_guild_channel(channel),
+            DispatchEvent::ChannelUpdate(channel) => self.insert_guild_channel(channel),
+            DispatchEvent::ThreadCreate(thread) => self.insert_guild_channel(thread),
+            DispatchEvent::ChannelDelete(channel) => {
+                self.channels.remove(&channel.id);
+                self.locations.remove(&channel.id);
+            }
+            DispatchEvent::MessageCreate(message) => self.message_create(message),
+            DispatchEvent::MessageAck(ack) => self.message_ack(ack),
+            DispatchEvent::ChannelUnreadUpdate(update) => self.channel_unread_update(update),
+            DispatchEvent::UserGuildSettingsUpdate(settings) => {
+                self.guild_settings
+                    .insert(settings.guild_id, settings.0.clone());
+            }
+            _ => (),
+        }
+    }
+
+    /// Inserts the channels of a guild that wasn't received through a dispatch
+    /// event, such as the ones sent along with the READY payload.
+    pub fn insert_guild(&mut self, guild: &Guild) {
+        for channel in guild.channels.iter().chain(&guild.threads) {
+            self.insert_channel(guild.id, channel);
+        }
+    }
+
+    /// Marks a channel as read up to the given message.
+    ///
+    /// This only updates the local state, the acknowledgement must still be
+    /// sent with [`HttpClient::ack_message`].
+    ///
+    /// [`HttpClient::ack_message`]: crate::http::HttpClient::ack_message
+    pub fn ack(&mut self, channel_id: Id<ChannelMarker>, message_id: Id<MessageMarker>) {
+        let state = self.channels.entry(channel_id).or_default();
+        state.last_acked_id = Some(message_id);
+        state.mention_count = 0;
+    }
+
+    /// Marks a channel as read up to its last message.
+    ///
+    /// Returns the message to send the acknowledgement for, `None` if the
+    /// channel was read already.
+    pub fn ack_latest(&mut self, channel_id: Id<ChannelMarker>) -> Option<Id<MessageMarker>> {
+        let state = self.channel(channel_id);
+        let message_id = state
+            .last_message_id
+            .filter(|_| state.is_unread() || state.mention_count > 0)?;
+
+        self.ack(channel_id, message_id);
+        Some(message_id)
+    }
+
+    pub fn channel(&self, channel_id: Id<ChannelMarker>) -> ChannelReadState {
+        self.channels.get(&channel_id).copied().unwrap_or_default()
+    }
+
+    pub fn guild(&self, guild_id: Id<GuildMarker>) -> GuildReadState {
+        let now = now();
+        let guild_muted = self
+            .guild_settings
+            .get(&Some(guild_id))
+            .is_some_and(|settings| settings.is_muted(now));
+
+        self.locations
+            .iter()
+            .filter(|(_, location)| location.guild_id == guild_id)
+            .fold(GuildReadState::default(), |mut acc, (&channel_id, _)| {
+                let state = self.channel(channel_id);
+                acc.mention_count += state.mention_count;
+                acc.unread |=
+                    !guild_muted && state.is_unread() && !self.is_channel_muted_at(channel_id, now);
+                acc
+            })
+    }
+
+    /// Whether a guild channel, or the category containing it, is muted.
+    pub fn is_channel_muted(&self, channel_id: Id<ChannelMarker>) -> bool {
+        self.is_channel_muted_at(channel_id, now())
+    }
+
+    fn is_channel_muted_at(&self, channel_id: Id<ChannelMarker>, now: Timestamp) -> bool {
+        let Some(location) = self.locations.get(&channel_id) else {
+            return false;
+        };
+        let Some(settings) = self.guild_settings.get(&Some(location.guild_id)) else {
+            return false;
+        };
+
+        [Some(channel_id), location.parent_id]
+            .into_iter()
+            .flatten()
+            .filter_map(|id| settings.channel_override(id))
+            .any(|channel_override| channel_override.is_muted(now))
+    }
+
+    fn insert_read_state(&mut self, read_state: &ReadState) {
+        // other types don't refer to channels
+        if read_state.read_state_type != 0 {
+            return;
+        }
+
+        let state = self.channels.entry(read_state.id).or_default();
+        state.last_acked_id = read_state.last_message_id;
+        state.mention_count = read_state.mention_count;
+    }
+
+    fn insert_guild_channel(&mut self, channel: &Channel) {
+        if let Some(guild_id) = channel.guild_id {
+            self.insert_channel(guild_id, channel);
+        }
+    }
+
+    fn insert_channel(&mut self, guild_id: Id<GuildMarker>, channel: &Channel) {
+        self.locations.insert(
+            channel.id,
+            ChannelLocation {
+                guild_id,
+                parent_id: channel.parent_id,
+            },
+        );
+
+        if let Some(last_message_id) = channel.last_message_id {
+            let state = self.channels.entry(channel.id).or_default();
+            state.last_message_id = state.last_message_id.max(Some(last_message_id.cast()));
+        }
+    }
+
+    fn message_create(&mut self, message: &Message) {
+        if let Some(guild_id) = message.guild_id {
+            self.locations
+                .entry(message.channel_id)
+                .or_insert(ChannelLocation {
+                    guild_id,
+                    parent_id: None,
+                });
+        }
+
+        let own_message = Some(message.author.id) == self.current_user_id;
+        let mentioned = self.mentions_current_user(message);
+
+        let state = self.channels.entry(message.channel_id).or_default();
+        state.last_message_id = state.last_message_id.max(Some(message.id));
+
+        if own_message {
+            // sending a message implicitly acknowledges the channel
+            state.last_acked_id = Some(message.id);
+            state.mention_count = 0;
+        } else if mentioned {
+            state.mention_count += 1;
+        }
+    }
+
+    fn message_ack(&mut self, ack: &MessageAck) {
+        let state = self.channels.entry(ack.channel_id).or_default();
+        state.last_acked_id = Some(ack.message_id);
+        // only manual acknowledgements (marking as unread) keep mentions
+        state.mention_count = ack.mention_count.unwrap_or(0);
+    }
+
+    fn channel_unread_update(&mut self, update: &ChannelUnreadUpdate) {
+        for unread in &update.channel_unread_updates {
+            self.locations.entry(unread.id).or_insert(ChannelLocation {
+                guild_id: update.guild_id,
+                parent_id: None,
+            });
+
+            let state = self.channels.entry(unread.id).or_default();
+            state.last_message_id = state.last_message_id.max(unread.last_message_id);
+        }
+    }
+
+    /// Whether a message counts as a mention of the current user.
+    ///
+    /// Every message in a direct message channel counts as a mention. Role
+    /// mentions aren't taken into account as the roles of the current user
+    /// aren't tracked here.
+    fn mentions_current_user(&self, message: &Message) -> bool {
+        let Some(current_user_id) = self.current_user_id else {
+            return false;
+        };
+        if message.guild_id.is_none() {
+            return true;
+        }
+
+        let suppress_everyone = self
+            .guild_settings
+            .get(&message.guild_id)
+            .is_some_and(|settings| settings.suppress_everyone);
+
+        message
+            .mentions
+            .iter()
+            .any(|user| user.id == current_user_id)
+            || (message.mention_everyone && !suppress_everyone)
+    }
+}
+
+pub(crate) fn now() -> Timestamp {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    Timestamp::from_secs(secs as i64).expect("system time out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::{
+        channel::Channel,
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{ChannelCreate, ChannelUnreadUpdate, MessageAck, MessageCreate},
+        },
+        id::Id,
+    };
+
+    use super::{ChannelReadState, GuildReadState, ReadStateStore};
+    use crate::fixtures::{self, CURRENT_USER_ID, GUILD_ID};
+
+    /// READY payload with read states for channels 20 and 21, and settings
+    /// muting channel 22 in the guild.
+    fn ready(guild_muted: bool) -> DispatchEvent {
+        fixtures::ready_with(&json!({
+            "read_state": {
+                "entries": [
+                    { "id": "20", "last_message_id": "100", "mention_count": 2 },
+                    { "id": "21", "last_message_id": "200", "mention_count": 0 },
+                    // notification center, its id isn't a channel
+                    { "id": "22", "last_message_id": "5", "mention_count": 7, "read_state_type": 2 },
+                ],
+                "partial": false,
+                "version": 1,
+            },
+            "user_guild_settings": {
+                "entries": [{
+                    "channel_overrides": [{
+                        "channel_id": "22",
+                        "message_notifications": 3,
+                        "mute_config": null,
+                        "muted": true,
+                    }],
+                    "guild_id": GUILD_ID.to_string(),
+                    "message_notifications": 3,
+                    "mute_config": null,
+                    "muted": guild_muted,
+                    "suppress_everyone": true,
+                }],
+                "partial": false,
+                "version": 1,
+            },
+        }))
+    }
+
+    fn message(id: u64, channel_id: u64, author_id: u64, extra: &Value) -> DispatchEvent {
+        let mut fields = json!({
+            "author": {
+                "avatar": null,
+                "discriminator": "0",
+                "id": author_id.to_string(),
+                "username": "author",
+            },
+            "channel_id": channel_id.to_string(),
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            fields[key] = value.clone();
+        }
+
+        let message = fixtures::message(id, &fields);
+        DispatchEvent::MessageCreate(Box::new(MessageCreate(message)))
+    }
+
+    fn mention(user_id: u64) -> Value {
+        json!({
+            "mentions": [{
+                "avatar": null,
+                "discriminator": "0",
+                "id": user_id.to_string(),
+                "public_flags": 0,
+                "username": "user",
+            }],
+        })
+    }
+
+    fn ack(channel_id: u64, message_id: u64, mention_count: Option<u32>) -> DispatchEvent {
+        DispatchEvent::MessageAck(MessageAck {
+            channel_id: Id::new(channel_id),
+            flags: None,
+            last_viewed: None,
+            manual: mention_count.is_some(),
+            mention_count,
+            message_id: Id::new(message_id),
+            version: 1,
+        })
+    }
+
+    /// Latest messages of channels of the guild.
+    fn unread_update(channels: &[(u64, u64)]) -> DispatchEvent {
+        let update: ChannelUnreadUpdate = serde_json::from_value(json!({
+            "channel_unread_updates": channels
+                .iter()
+                .map(|(id, last_message_id)| json!({
+                    "id": id.to_string(),
+                    "last_message_id": last_message_id.to_string(),
+                }))
+                .collect::<Vec<_>>(),
+            "guild_id": GUILD_ID.to_string(),
+        }))
+        .unwrap();
+
+        DispatchEvent::ChannelUnreadUpdate(update)
+    }
+
+    fn channel_create(id: u64, parent_id: u64) -> DispatchEvent {
+        let channel: Channel = serde_json::from_value(json!({
+            "guild_id": GUILD_ID.to_string(),
+            "id": id.to_string(),
+            "name": "channel",
+            "parent_id": parent_id.to_string(),
+            "type": 0,
+        }))
+        .unwrap();
+
+        DispatchEvent::ChannelCreate(Box::new(ChannelCreate(channel)))
+    }
+
+    fn state(
+        last_acked_id: Option<u64>,
+        last_message_id: Option<u64>,
+        mention_count: u32,
+    ) -> ChannelReadState {
+        ChannelReadState {
+            last_acked_id: last_acked_id.map(Id::new),
+            last_message_id: last_message_id.map(Id::new),
+            mention_count,
+        }
+    }
+
+    #[test]
+    fn ready_seeds_read_states() {
+        let mut store = ReadStateStore::new();
+        store.update(&ready(false));
+
+        assert_eq!(store.channel(Id::new(20)), state(Some(100), None, 2));
+        assert_eq!(store.channel(Id::new(21)), state(Some(200), None, 0));
+        assert_eq!(store.channel(Id::new(22)), ChannelReadState::default());
+
+        store.update(&unread_update(&[(20, 100), (21, 250)]));
+        assert!(!store.channel(Id::new(20)).is_unread());
+        assert!(store.channel(Id::new(21)).is_unread());
+
+        // a new session starts over
+        store.update(&ready(false));
+        assert_eq!(store.channel(Id::new(21)), state(Some(200), None, 0));
+    }
+
+    #[test]
+    fn message_create() {
+        let mut store = ReadStateStore::new();
+        store.update(&ready(false));
+
+        store.update(&message(300, 21, 2, &json!({})));
+        assert_eq!(store.channel(Id::new(21)), state(Some(200), Some(300), 0));
+
+        store.update(&message(301, 21, 2, &mention(CURRENT_USER_ID)));
+        store.update(&message(302, 21, 2, &mention(3)));
+        // the guild suppresses @everyone
+        store.update(&message(303, 21, 2, &json!({ "mention_everyone": true })));
+        assert_eq!(store.channel(Id::new(21)), state(Some(200), Some(303), 1));
+
+        // every direct message is a mention
+        store.update(&message(304, 30, 2, &json!({ "guild_id": null })));
+        assert_eq!(store.channel(Id::new(30)), state(None, Some(304), 1));
+
+        // sending a message acknowledges the channel
+        store.update(&message(305, 21, CURRENT_USER_ID, &json!({})));
+        assert_eq!(store.channel(Id::new(21)), state(Some(305), Some(305), 0));
+        assert!(!store.channel(Id::new(21)).is_unread());
+    }
+
+    #[test]
+    fn message_ack() {
+        let mut store = ReadStateStore::new();
+        store.update(&ready(false));
+        store.update(&unread_update(&[(20, 150)]));
+        assert!(store.channel(Id::new(20)).is_unread());
+
+        store.update(&ack(20, 150, None));
+        assert_eq!(store.channel(Id::new(20)), state(Some(150), Some(150), 0));
+
+        // marking as unread keeps the mentions that were sent along
+        store.update(&ack(20, 120, Some(1)));
+        assert_eq!(store.channel(Id::new(20)), state(Some(120), Some(150), 1));
+        assert!(store.channel(Id::new(20)).is_unread());
+
+        store.ack(Id::new(20), Id::new(150));
+        assert_eq!(store.channel(Id::new(20)), state(Some(150), Some(150), 0));
+    }
+
+    #[test]
+    fn acked_latest() {
+        let mut store = ReadStateStore::new();
+        store.update(&ready(false));
+        assert_eq!(store.ack_latest(Id::new(21)), None);
+
+        store.update(&message(300, 21, 2, &mention(CURRENT_USER_ID)));
+        store.update(&message(301, 21, 2, &json!({})));
+        assert_eq!(store.ack_latest(Id::new(21)), Some(Id::new(301)));
+        assert_eq!(store.channel(Id::new(21)), state(Some(301), Some(301), 0));
+
+        // the acknowledgement is only sent once
+        assert_eq!(store.ack_latest(Id::new(21)), None);
+    }
+
+    #[test]
+    fn guild_aggregation() {
+        let mut store = ReadStateStore::new();
+        store.update(&ready(false));
+        assert_eq!(store.guild(Id::new(GUILD_ID)), GuildReadState::default());
+
+        // channel 22 is muted
+        store.update(&unread_update(&[(20, 100), (22, 400)]));
+        store.update(&message(401, 22, 2, &mention(CURRENT_USER_ID)));
+        assert!(store.is_channel_muted(Id::new(22)));
+        assert_eq!(
+            store.guild(Id::new(GUILD_ID)),
+            GuildReadState {
+                unread: false,
+                mention_count: 3,
+            }
+        );
+
+        // so are the channels of the category 22
+        store.update(&channel_create(23, 22));
+        store.update(&message(500, 23, 2, &json!({})));
+        assert!(store.is_channel_muted(Id::new(23)));
+        assert!(!store.guild(Id::new(GUILD_ID)).unread);
+
+        store.update(&unread_update(&[(21, 201)]));
+        assert_eq!(
+            store.guild(Id::new(GUILD_ID)),
+            GuildReadState {
+                unread: true,
+                mention_count: 3,
+            }
+        );
+        assert_eq!(store.guild(Id::new(11)), GuildReadState::default());
+
+        // muted guilds are never unread, but still count their mentions
+        store.update(&ready(true));
+        store.update(&unread_update(&[(20, 101), (21, 201)]));
+        assert_eq!(
+            store.guild(Id::new(GUILD_ID)),
+            GuildReadState {
+                unread: false,
+                mention_count: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use twilight_model::{
+    gateway::{event::DispatchEvent, presence::UserOrId},
+    id::{marker::UserMarker, Id},
+    user::{Relationship, RelationshipType},
+};
+
+/// Keeps track of the friends, blocked users and friend requests of the
+/// current user.
+///
+/// Presences of friends are kept by the
+/// [`UserPresenceStore`](crate::user_presence::UserPresenceStore).
+///
+/// Must be fed every dispatch event through [`RelationshipStore::update`].
+#[derive(Debug, Default)]
+pub struct RelationshipStore {
+    relationships: HashMap<Id<UserMarker>, Relationship>,
+}
+
+impl RelationshipStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.relationships = ready
+                    .relationships
+                    .iter()
+                    .map(|relationship| (relationship.id, relationship.clone()))
+                    .collect();
+            }
+            DispatchEvent::RelationshipAdd(add) => {
+                self.relationships
+                    .insert(add.relationship.id, add.relationship.clone());
+            }
+            DispatchEvent::RelationshipRemove(remove) => {
+                self.relationships.remove(&remove.id);
+            }
+            // presence updates include the new profile of the user when it
+            // changed
+            DispatchEvent::PresenceUpdate(presence) => {
+                if let (UserOrId::User(user), Some(relationship)) = (
+                    &presence.user,
+                    self.relationships.get_mut(&presence.user.id()),
+                ) {
+                    relationship.user = user.clone();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn relationship(&self, user_id: Id<UserMarker>) -> Option<&Relationship> {
+        self.relationships.get(&user_id)
+    }
+
+    /// Relationships of the given types, ordered by the name of the other
+    /// user.
+    pub fn relationships(&self, kinds: &[RelationshipType]) -> Vec<&Relationship> {
+        let mut relationships = self
+            .relationships
+            .values()
+            .filter(|relationship| kinds.contains(&relationship.kind))
+            .collect::<Vec<_>>();
+        relationships.sort_by_cached_key(|relationship| display_name(relationship).to_lowercase());
+
+        relationships
+    }
+
+    /// Amount of friend requests the current user received and hasn't
+    /// answered yet.
+    pub fn incoming_request_count(&self) -> usize {
+        self.relationships
+            .values()
+            .filter(|relationship| relationship.kind == RelationshipType::PendingIncoming)
+            .count()
+    }
+}
+
+/// Name the current user sees for the other user of a relationship,
+/// preferring the nickname they gave them.
+pub fn display_name(relationship: &Relationship) -> &str {
+    relationship
+        .nickname
+        .as_deref()
+        .or(relationship.user.global_name.as_deref())
+        .unwrap_or(&relationship.user.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::{
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{PresenceUpdate, RelationshipAdd, RelationshipRemove},
+        },
+        id::Id,
+        user::RelationshipType,
+    };
+
+    use super::{display_name, RelationshipStore};
+    use crate::fixtures;
+
+    fn user(id: u64, username: &str) -> Value {
+        json!({
+            "avatar": null,
+            "discriminator": "0",
+            "global_name": null,
+            "id": id.to_string(),
+            "username": username,
+        })
+    }
+
+    fn relationship(id: u64, kind: RelationshipType, username: &str) -> Value {
+        json!({
+            "id": id.to_string(),
+            "nickname": null,
+            "type": u8::from(kind),
+            "user": user(id, username),
+        })
+    }
+
+    /// READY payload with two friends, two incoming and one outgoing friend
+    /// requests and a blocked user.
+    fn ready() -> DispatchEvent {
+        let mut bob = relationship(2, RelationshipType::Friend, "bob");
+        bob["nickname"] = json!("Bobby");
+
+        fixtures::ready_with(&json!({
+            "relationships": [
+                bob,
+                relationship(3, RelationshipType::Friend, "alice"),
+                relationship(4, RelationshipType::PendingIncoming, "carol"),
+                relationship(5, RelationshipType::PendingIncoming, "dave"),
+                relationship(6, RelationshipType::PendingOutgoing, "erin"),
+                relationship(7, RelationshipType::Blocked, "frank"),
+            ],
+        }))
+    }
+
+    fn add(id: u64, kind: RelationshipType, username: &str) -> DispatchEvent {
+        let add: RelationshipAdd =
+            serde_json::from_value(relationship(id, kind, username)).unwrap();
+
+        DispatchEvent::RelationshipAdd(Box::new(add))
+    }
+
+    fn ids(store: &RelationshipStore, kinds: &[RelationshipType]) -> Vec<u64> {
+        store
+            .relationships(kinds)
+            .into_iter()
+            .map(|relationship| relationship.id.get())
+            .collect()
+    }
+
+    #[test]
+    fn ready_relationships() {
+        let mut store = RelationshipStore::new();
+        store.update(&ready());
+
+        // ordered by the name shown, nicknames first
+        assert_eq!(ids(&store, &[RelationshipType::Friend]), [3, 2]);
+        assert_eq!(
+            display_name(store.relationship(Id::new(2)).unwrap()),
+            "Bobby"
+        );
+        assert_eq!(ids(&store, &[RelationshipType::PendingIncoming]), [4, 5]);
+        assert_eq!(ids(&store, &[RelationshipType::PendingOutgoing]), [6]);
+        assert_eq!(
+            ids(
+                &store,
+                &[
+                    RelationshipType::PendingIncoming,
+                    RelationshipType::PendingOutgoing
+                ]
+            ),
+            [4, 5, 6]
+        );
+        assert_eq!(ids(&store, &[RelationshipType::Blocked]), [7]);
+        assert_eq!(store.incoming_request_count(), 2);
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let mut store = RelationshipStore::new();
+        store.update(&ready());
+
+        // accepted friend requests replace the pending ones
+        store.update(&add(4, RelationshipType::Friend, "carol"));
+        store.update(&add(8, RelationshipType::PendingOutgoing, "grace"));
+        assert_eq!(ids(&store, &[RelationshipType::Friend]), [3, 2, 4]);
+        assert_eq!(ids(&store, &[RelationshipType::PendingIncoming]), [5]);
+        assert_eq!(ids(&store, &[RelationshipType::PendingOutgoing]), [6, 8]);
+        assert_eq!(store.incoming_request_count(), 1);
+
+        for (id, kind) in [
+            (5, RelationshipType::PendingIncoming),
+            (6, RelationshipType::PendingOutgoing),
+            // unknown relationships are ignored
+            (9, RelationshipType::Friend),
+        ] {
+            store.update(&DispatchEvent::RelationshipRemove(RelationshipRemove {
+                id: Id::new(id),
+                kind,
+                nickname: None,
+            }));
+        }
+        assert!(store.relationship(Id::new(5)).is_none());
+        assert_eq!(store.incoming_request_count(), 0);
+        assert_eq!(ids(&store, &[RelationshipType::PendingOutgoing]), [8]);
+
+        store.update(&ready());
+        assert!(store.relationship(Id::new(8)).is_none());
+        assert_eq!(store.incoming_request_count(), 2);
+    }
+
+    #[test]
+    fn updated_users() {
+        let mut store = RelationshipStore::new();
+        store.update(&ready());
+
+        let presence = |user: Value| {
+            let presence: PresenceUpdate = serde_json::from_value(json!({
+                "activities": [],
+                "client_status": { "desktop": "online" },
+                "status": "online",
+                "user": user,
+            }))
+            .unwrap();
+
+            DispatchEvent::PresenceUpdate(Box::new(presence))
+        };
+        let mut alice = user(3, "alice");
+        alice["global_name"] = json!("Alice");
+        store.update(&presence(alice));
+        // presences without changes to the profile only include the id
+        store.update(&presence(json!({ "id": "2" })));
+        store.update(&presence(user(9, "heidi")));
+
+        assert_eq!(
+            display_name(store.relationship(Id::new(3)).unwrap()),
+            "Alice"
+        );
+        assert_eq!(
+            display_name(store.relationship(Id::new(2)).unwrap()),
+            "Bobby"
+        );
+        assert!(store.relationship(Id::new(9)).is_none());
+    }
+}
@@ -0,0 +1,638 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use twilight_model::{
+    channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    gateway::event::DispatchEvent,
+    guild::{Guild, Permissions, Role},
+    id::{
+        marker::{GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    util::ImageHash,
+};
+
+/// How a member is shown, derived from their roles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemberAppearance {
+    /// Color of their name as an integer representation of a hexadecimal
+    /// color code, `None` to use the default color.
+    pub color: Option<u32>,
+    /// Their highest hoisted role, they're listed under it in the member list.
+    pub hoist: Option<Id<RoleMarker>>,
+    /// Icon shown next to their name.
+    pub icon: Option<RoleIcon>,
+    /// Whether they're a bot, bots get a role of their own when they're
+    /// added.
+    pub bot: bool,
+    /// Whether they boost the guild, which gives them its booster role.
+    pub booster: bool,
+}
+
+/// Icon of a role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoleIcon {
+    Image {
+        role_id: Id<RoleMarker>,
+        icon: ImageHash,
+    },
+    Emoji(String),
+}
+
+/// Roles of a guild and the appearance of its members.
+#[derive(Debug, Default)]
+struct GuildRoles {
+    owner_id: Option<Id<UserMarker>>,
+    roles: HashMap<Id<RoleMarker>, Role>,
+    /// Roles of the members, as they were last received.
+    members: HashMap<Id<UserMarker>, Vec<Id<RoleMarker>>>,
+    /// Appearance of the members, resolved from their roles whenever either
+    /// of them changes rather than while rendering.
+    appearances: HashMap<Id<UserMarker>, MemberAppearance>,
+}
+
+impl GuildRoles {
+    fn set_member(&mut self, user_id: Id<UserMarker>, roles: &[Id<RoleMarker>]) {
+        self.appearances
+            .insert(user_id, resolve(&self.roles, roles));
+        self.members.insert(user_id, roles.to_vec());
+    }
+
+    /// Resolves the appearance of every member again after the roles
+    /// changed.
+    fn invalidate(&mut self) {
+        self.appearances = self
+            .members
+            .iter()
+            .map(|(user_id, roles)| (*user_id, resolve(&self.roles, roles)))
+            .collect();
+    }
+}
+
+/// Keeps track of the roles of every guild and resolves how their members
+/// are shown from them.
+///
+/// Must be fed every dispatch event through [`RoleStore::update`].
+#[derive(Debug, Default)]
+pub struct RoleStore {
+    guilds: HashMap<Id<GuildMarker>, GuildRoles>,
+}
+
+impl RoleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => self.guilds.clear(),
+            DispatchEvent::GuildCreate(guild) => self.insert_guild(guild),
+            DispatchEvent::GuildUpdate(guild) => {
+                let guild_roles = self.guilds.entry(guild.id).or_default();
+                guild_roles.owner_id = Some(guild.owner_id);
+                guild_roles.roles = roles_by_id(&guild.roles);
+                guild_roles.invalidate();
+            }
+            DispatchEvent::GuildDelete(guild) if !guild.unavailable => {
+                self.guilds.remove(&guild.id);
+            }
+            DispatchEvent::GuildRoleCreate(create) => {
+                self.upsert_role(create.guild_id, &create.role);
+            }
+            DispatchEvent::GuildRoleUpdate(update) => {
+                self.upsert_role(update.guild_id, &update.role);
+            }
+            DispatchEvent::GuildRoleDelete(delete) => {
+                if let Some(guild_roles) = self.guilds.get_mut(&delete.guild_id) {
+                    guild_roles.roles.remove(&delete.role_id);
+                    guild_roles.invalidate();
+                }
+            }
+            DispatchEvent::GuildMemberAdd(add) => {
+                self.set_member(add.guild_id, add.member.user.id, &add.member.roles);
+            }
+            DispatchEvent::GuildMemberUpdate(update) => {
+                self.set_member(update.guild_id, update.user.id, &update.roles);
+            }
+            DispatchEvent::GuildMembersChunk(chunk) => {
+                for member in &chunk.members {
+                    self.set_member(chunk.guild_id, member.user.id, &member.roles);
+                }
+            }
+            DispatchEvent::GuildMemberRemove(remove) => {
+                if let Some(guild_roles) = self.guilds.get_mut(&remove.guild_id) {
+                    guild_roles.members.remove(&remove.user.id);
+                    guild_roles.appearances.remove(&remove.user.id);
+                }
+            }
+            // messages carry the roles of their author
+            DispatchEvent::MessageCreate(message) => {
+                if let (Some(guild_id), Some(member)) = (message.guild_id, &message.member) {
+                    self.set_member(guild_id, message.author.id, &member.roles);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Inserts the roles and members of a guild that wasn't received through
+    /// a dispatch event.
+    pub fn insert_guild(&mut self, guild: &Guild) {
+        let mut guild_roles = GuildRoles {
+            owner_id: Some(guild.owner_id),
+            roles: roles_by_id(&guild.roles),
+            ..GuildRoles::default()
+        };
+        for member in &guild.members {
+            guild_roles.set_member(member.user.id, &member.roles);
+        }
+
+        self.guilds.insert(guild.id, guild_roles);
+    }
+
+    pub fn role(&self, guild_id: Id<GuildMarker>, role_id: Id<RoleMarker>) -> Option<&Role> {
+        self.guilds.get(&guild_id)?.roles.get(&role_id)
+    }
+
+    /// All roles of a guild, the highest first.
+    pub fn guild_roles(&self, guild_id: Id<GuildMarker>) -> Vec<&Role> {
+        let mut roles = self
+            .guilds
+            .get(&guild_id)
+            .map(|guild_roles| guild_roles.roles.values().collect::<Vec<_>>())
+            .unwrap_or_default();
+        roles.sort_unstable_by_key(|role| Reverse(*role));
+
+        roles
+    }
+
+    /// Roles of a guild among the given ones, the highest first.
+    pub fn sorted_roles(
+        &self,
+        guild_id: Id<GuildMarker>,
+        role_ids: &[Id<RoleMarker>],
+    ) -> Vec<&Role> {
+        let Some(guild_roles) = self.guilds.get(&guild_id) else {
+            return Vec::new();
+        };
+        let mut roles = role_ids
+            .iter()
+            .filter_map(|role_id| guild_roles.roles.get(role_id))
+            .collect::<Vec<_>>();
+        roles.sort_unstable_by_key(|role| Reverse(*role));
+
+        roles
+    }
+
+    /// Appearance of a member whose roles are known.
+    pub fn appearance(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<&MemberAppearance> {
+        self.guilds.get(&guild_id)?.appearances.get(&user_id)
+    }
+
+    /// Appearance of a member with the given roles, for members whose roles
+    /// weren't received through the gateway.
+    pub fn resolve(
+        &self,
+        guild_id: Id<GuildMarker>,
+        role_ids: &[Id<RoleMarker>],
+    ) -> MemberAppearance {
+        self.guilds
+            .get(&guild_id)
+            .map(|guild_roles| resolve(&guild_roles.roles, role_ids))
+            .unwrap_or_default()
+    }
+
+    /// Roles of a member whose roles are known.
+    pub fn member_roles(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<&[Id<RoleMarker>]> {
+        self.guilds
+            .get(&guild_id)?
+            .members
+            .get(&user_id)
+            .map(Vec::as_slice)
+    }
+
+    /// Permissions of a member in a guild, narrowed down by the permission
+    /// overwrites of a channel.
+    ///
+    /// Pass no overwrites for the permissions in the guild itself. Members
+    /// whose roles aren't known only get the permissions of `@everyone`.
+    pub fn permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        overwrites: &[PermissionOverwrite],
+    ) -> Permissions {
+        let Some(guild_roles) = self.guilds.get(&guild_id) else {
+            return Permissions::empty();
+        };
+        if guild_roles.owner_id == Some(user_id) {
+            return Permissions::all();
+        }
+
+        let member_roles = guild_roles
+            .members
+            .get(&user_id)
+            .map_or(&[][..], Vec::as_slice);
+        // the id of the @everyone role is the one of its guild
+        let everyone = guild_roles
+            .roles
+            .get(&guild_id.cast())
+            .map_or(Permissions::empty(), |role| role.permissions);
+        let mut permissions = member_roles
+            .iter()
+            .filter_map(|role_id| guild_roles.roles.get(role_id))
+            .fold(everyone, |permissions, role| permissions | role.permissions);
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        // the overwrite of @everyone applies first, then the ones of the
+        // member's roles together, then the one of the member
+        let overwrite_of = |kind, id| {
+            overwrites
+                .iter()
+                .find(|overwrite| overwrite.kind == kind && overwrite.id == id)
+        };
+        if let Some(overwrite) = overwrite_of(PermissionOverwriteType::Role, guild_id.cast()) {
+            permissions = (permissions - overwrite.deny) | overwrite.allow;
+        }
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|overwrite| {
+                overwrite.kind == PermissionOverwriteType::Role
+                    && member_roles.contains(&overwrite.id.cast())
+            })
+            .fold(
+                (Permissions::empty(), Permissions::empty()),
+                |(allow, deny), overwrite| (allow | overwrite.allow, deny | overwrite.deny),
+            );
+        permissions = (permissions - deny) | allow;
+        if let Some(overwrite) = overwrite_of(PermissionOverwriteType::Member, user_id.cast()) {
+            permissions = (permissions - overwrite.deny) | overwrite.allow;
+        }
+
+        permissions
+    }
+
+    fn upsert_role(&mut self, guild_id: Id<GuildMarker>, role: &Role) {
+        let guild_roles = self.guilds.entry(guild_id).or_default();
+        guild_roles.roles.insert(role.id, role.clone());
+        guild_roles.invalidate();
+    }
+
+    fn set_member(
+        &mut self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        roles: &[Id<RoleMarker>],
+    ) {
+        self.guilds
+            .entry(guild_id)
+            .or_default()
+            .set_member(user_id, roles);
+    }
+}
+
+/// Resolves the appearance of a member from the highest of their roles that
+/// has a color, is hoisted or has an icon, and from the tags of their roles.
+fn resolve(roles: &HashMap<Id<RoleMarker>, Role>, role_ids: &[Id<RoleMarker>]) -> MemberAppearance {
+    let mut member_roles = role_ids
+        .iter()
+        .filter_map(|role_id| roles.get(role_id))
+        .collect::<Vec<_>>();
+    member_roles.sort_unstable_by_key(|role| Reverse(*role));
+
+    MemberAppearance {
+        // roles without a color don't change the color of their members
+        color: member_roles
+            .iter()
+            .map(|role| role.color)
+            .find(|color| *color != 0),
+        hoist: member_roles
+            .iter()
+            .find(|role| role.hoist)
+            .map(|role| role.id),
+        icon: member_roles.iter().find_map(|role| role_icon(role)),
+        bot: member_roles
+            .iter()
+            .any(|role| role.tags.as_ref().is_some_and(|tags| tags.bot_id.is_some())),
+        booster: member_roles.iter().any(|role| {
+            role.tags
+                .as_ref()
+                .is_some_and(|tags| tags.premium_subscriber)
+        }),
+    }
+}
+
+/// Icon of a role, preferring its image over its emoji.
+pub fn role_icon(role: &Role) -> Option<RoleIcon> {
+    match (&role.icon, &role.unicode_emoji) {
+        (Some(icon), _) => Some(RoleIcon::Image {
+            role_id: role.id,
+            icon: *icon,
+        }),
+        (None, Some(emoji)) => Some(RoleIcon::Emoji(emoji.clone())),
+        (None, None) => None,
+    }
+}
+
+fn roles_by_id(roles: &[Role]) -> HashMap<Id<RoleMarker>, Role> {
+    roles.iter().map(|role| (role.id, role.clone())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{MemberUpdate, RoleCreate, RoleDelete, RoleUpdate},
+        },
+        guild::{Permissions, Role, RoleFlags, RoleTags},
+        id::{
+            marker::{GuildMarker, RoleMarker},
+            Id,
+        },
+        user::User,
+        util::Timestamp,
+    };
+
+    use super::{MemberAppearance, RoleIcon, RoleStore};
+
+    const GUILD_ID: Id<GuildMarker> = Id::new(1);
+
+    fn role(id: u64, position: i64, color: u32, hoist: bool) -> Role {
+        Role {
+            color,
+            hoist,
+            icon: None,
+            id: Id::new(id),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions: Permissions::empty(),
+            position,
+            flags: RoleFlags::empty(),
+            tags: None,
+            unicode_emoji: None,
+        }
+    }
+
+    fn member_update(user_id: u64, roles: Vec<Id<RoleMarker>>) -> DispatchEvent {
+        DispatchEvent::GuildMemberUpdate(Box::new(MemberUpdate {
+            avatar: None,
+            communication_disabled_until: None,
+            guild_id: GUILD_ID,
+            deaf: None,
+            joined_at: Timestamp::from_secs(1_600_000_000).unwrap(),
+            mute: None,
+            nick: None,
+            pending: false,
+            premium_since: None,
+            roles,
+            user: User {
+                accent_color: None,
+                avatar: None,
+                avatar_decoration: None,
+                banner: None,
+                bot: false,
+                discriminator: 0,
+                email: None,
+                flags: None,
+                global_name: None,
+                id: Id::new(user_id),
+                locale: None,
+                mfa_enabled: None,
+                name: "user".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            },
+        }))
+    }
+
+    fn store(roles: impl IntoIterator<Item = Role>) -> RoleStore {
+        let mut store = RoleStore::new();
+        for role in roles {
+            store.update(&DispatchEvent::GuildRoleCreate(RoleCreate {
+                guild_id: GUILD_ID,
+                role,
+            }));
+        }
+
+        store
+    }
+
+    #[test]
+    fn highest_colored_role_wins() {
+        let mut roles = store([
+            role(10, 1, 0xff0000, true),
+            role(11, 2, 0x00ff00, false),
+            // roles without a color are skipped
+            role(12, 3, 0, false),
+        ]);
+        roles.update(&member_update(
+            5,
+            vec![Id::new(10), Id::new(11), Id::new(12)],
+        ));
+
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)),
+            Some(&MemberAppearance {
+                color: Some(0x00ff00),
+                hoist: Some(Id::new(10)),
+                icon: None,
+                ..MemberAppearance::default()
+            })
+        );
+        assert_eq!(roles.appearance(GUILD_ID, Id::new(6)), None);
+        assert_eq!(
+            roles.resolve(GUILD_ID, &[Id::new(10)]).color,
+            Some(0xff0000)
+        );
+        assert_eq!(
+            roles
+                .sorted_roles(GUILD_ID, &[Id::new(10), Id::new(12), Id::new(11)])
+                .iter()
+                .map(|role| role.id.get())
+                .collect::<Vec<_>>(),
+            [12, 11, 10]
+        );
+    }
+
+    #[test]
+    fn icon_of_highest_role() {
+        let mut roles = store([
+            Role {
+                unicode_emoji: Some("🦀".to_owned()),
+                ..role(10, 1, 0, false)
+            },
+            Role {
+                unicode_emoji: Some("🐍".to_owned()),
+                ..role(11, 2, 0, false)
+            },
+        ]);
+        roles.update(&member_update(5, vec![Id::new(10), Id::new(11)]));
+
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)).unwrap().icon,
+            Some(RoleIcon::Emoji("🐍".to_owned()))
+        );
+    }
+
+    #[test]
+    fn tagged_roles() {
+        let tags = RoleTags {
+            available_for_purchase: false,
+            bot_id: None,
+            guild_connections: false,
+            integration_id: None,
+            premium_subscriber: false,
+            subscription_listing_id: None,
+        };
+        let mut roles = store([
+            Role {
+                managed: true,
+                tags: Some(RoleTags {
+                    bot_id: Some(Id::new(5)),
+                    integration_id: Some(Id::new(20)),
+                    ..tags.clone()
+                }),
+                ..role(10, 1, 0, false)
+            },
+            Role {
+                managed: true,
+                tags: Some(RoleTags {
+                    premium_subscriber: true,
+                    ..tags
+                }),
+                ..role(11, 2, 0xf47fff, false)
+            },
+            role(12, 3, 0, false),
+        ]);
+        roles.update(&member_update(5, vec![Id::new(10), Id::new(12)]));
+        roles.update(&member_update(6, vec![Id::new(11), Id::new(12)]));
+
+        let bot = roles.appearance(GUILD_ID, Id::new(5)).unwrap();
+        assert!(bot.bot && !bot.booster);
+        let booster = roles.appearance(GUILD_ID, Id::new(6)).unwrap();
+        assert!(!booster.bot && booster.booster);
+        assert_eq!(booster.color, Some(0xf47fff));
+
+        roles.update(&member_update(6, vec![Id::new(12)]));
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(6)),
+            Some(&MemberAppearance::default())
+        );
+    }
+
+    #[test]
+    fn invalidated_on_role_changes() {
+        let mut roles = store([role(10, 1, 0xff0000, false), role(11, 2, 0, false)]);
+        roles.update(&member_update(5, vec![Id::new(10), Id::new(11)]));
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)).unwrap().color,
+            Some(0xff0000)
+        );
+
+        roles.update(&DispatchEvent::GuildRoleUpdate(RoleUpdate {
+            guild_id: GUILD_ID,
+            role: role(11, 2, 0x0000ff, true),
+        }));
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)),
+            Some(&MemberAppearance {
+                color: Some(0x0000ff),
+                hoist: Some(Id::new(11)),
+                icon: None,
+                ..MemberAppearance::default()
+            })
+        );
+
+        roles.update(&DispatchEvent::GuildRoleDelete(RoleDelete {
+            guild_id: GUILD_ID,
+            role_id: Id::new(11),
+        }));
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)).unwrap().color,
+            Some(0xff0000)
+        );
+
+        roles.update(&member_update(5, Vec::new()));
+        assert_eq!(
+            roles.appearance(GUILD_ID, Id::new(5)),
+            Some(&MemberAppearance::default())
+        );
+    }
+
+    #[test]
+    fn permissions_with_overwrites() {
+        let mut roles = store([
+            Role {
+                permissions: Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+                ..role(GUILD_ID.get(), 0, 0, false)
+            },
+            Role {
+                permissions: Permissions::MENTION_EVERYONE,
+                ..role(10, 1, 0, false)
+            },
+            Role {
+                permissions: Permissions::ADMINISTRATOR,
+                ..role(11, 2, 0, false)
+            },
+        ]);
+        roles.update(&member_update(5, vec![Id::new(10)]));
+        roles.update(&member_update(6, vec![Id::new(11)]));
+
+        assert_eq!(
+            roles.permissions(GUILD_ID, Id::new(5), &[]),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MENTION_EVERYONE
+        );
+        // unknown members only get the permissions of @everyone
+        assert_eq!(
+            roles.permissions(GUILD_ID, Id::new(7), &[]),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+
+        let overwrites = [
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                id: GUILD_ID.cast(),
+                kind: PermissionOverwriteType::Role,
+            },
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL,
+                deny: Permissions::SEND_MESSAGES,
+                id: Id::new(10),
+                kind: PermissionOverwriteType::Role,
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                id: Id::new(5),
+                kind: PermissionOverwriteType::Member,
+            },
+        ];
+        assert_eq!(
+            roles.permissions(GUILD_ID, Id::new(5), &overwrites),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MENTION_EVERYONE
+        );
+        assert!(!roles
+            .permissions(GUILD_ID, Id::new(7), &overwrites)
+            .contains(Permissions::VIEW_CHANNEL));
+        // administrators bypass the overwrites
+        assert_eq!(
+            roles.permissions(GUILD_ID, Id::new(6), &overwrites),
+            Permissions::all()
+        );
+    }
+}
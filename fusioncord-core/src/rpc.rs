@@ -0,0 +1,506 @@
+use std::{
+    env, fs,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+    time::sleep,
+};
+use tracing::{debug, info, warn};
+use twilight_model::{
+    gateway::presence::{
+        Activity, ActivityAssets, ActivityButton, ActivityButtonText, ActivityParty,
+        ActivitySecrets, ActivityTimestamps, ActivityType,
+    },
+    id::{marker::ApplicationMarker, Id},
+    user::CurrentUser,
+};
+
+use crate::presence::ActivityUpdate;
+
+/// Amount of sockets Discord clients try to connect to, a local server takes
+/// the first free one.
+const SOCKET_COUNT: u8 = 10;
+/// Largest frame that is accepted, activities are a lot smaller.
+const MAX_FRAME_LEN: u32 = 64 * 1024;
+/// Wait after failing to accept a connection, doubled after every failure in
+/// a row.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(10);
+/// Failures in a row after which the server stops, as accepting won't
+/// succeed anymore.
+const MAX_ACCEPT_FAILURES: u32 = 10;
+/// Name of an activity if the name of its application can't be fetched.
+const FALLBACK_NAME: &str = "a game";
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+const OP_PING: u32 = 3;
+const OP_PONG: u32 = 4;
+
+/// RPC error code of commands that aren't supported.
+const INVALID_COMMAND: u32 = 4002;
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A local server of the Discord RPC protocol over a Unix socket, which games
+/// and other applications use to set the activity of the current user.
+///
+/// Only the commands to set an activity are supported.
+#[derive(Debug)]
+pub struct RpcServer {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl RpcServer {
+    /// Listens on the first free socket Discord clients connect to.
+    pub fn bind() -> io::Result<Self> {
+        let directory = socket_directory();
+        let mut last_error = None;
+
+        for index in 0..SOCKET_COUNT {
+            match Self::bind_at(directory.join(format!("discord-ipc-{index}"))) {
+                Ok(server) => return Ok(server),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| io::ErrorKind::AddrInUse.into()))
+    }
+
+    /// Listens on the given socket, replacing it if no server listens on it
+    /// anymore.
+    pub fn bind_at(path: PathBuf) -> io::Result<Self> {
+        let listener = match UnixListener::bind(&path) {
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse && is_stale(&path) => {
+                fs::remove_file(&path)?;
+                UnixListener::bind(&path)?
+            }
+            result => result?,
+        };
+
+        Ok(Self { listener, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Accepts applications until the server is dropped, or accepting keeps
+    /// failing.
+    ///
+    /// The names of the applications are looked up through `application_name`
+    /// once they connect.
+    pub async fn run<N, F>(
+        self,
+        user: CurrentUser,
+        application_name: N,
+        on_update: impl Fn(ActivityUpdate) + Clone + Send + Sync + 'static,
+    ) where
+        N: Fn(Id<ApplicationMarker>) -> F + Clone + Send + 'static,
+        F: Future<Output = Option<String>> + Send,
+    {
+        info!("Listening for rich presence on {}", self.path.display());
+
+        let mut failures = 0;
+        loop {
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => {
+                    failures = 0;
+                    stream
+                }
+                Err(e) => {
+                    warn!("Failed to accept rich presence connection: {e}");
+                    failures += 1;
+                    if failures == MAX_ACCEPT_FAILURES {
+                        warn!("Stopping the rich presence server after {failures} failures");
+                        return;
+                    }
+
+                    // errors such as running out of file descriptors last a while
+                    sleep((ACCEPT_BACKOFF * 2_u32.pow(failures - 1)).min(MAX_ACCEPT_BACKOFF)).await;
+                    continue;
+                }
+            };
+
+            let connection = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+            let user = user.clone();
+            let application_name = application_name.clone();
+            let on_update = on_update.clone();
+            tokio::spawn(async move {
+                let result =
+                    handle_connection(stream, connection, &user, application_name, &on_update)
+                        .await;
+                if let Err(e) = result {
+                    debug!("Rich presence connection closed: {e}");
+                }
+
+                // activities are cleared once their application is gone
+                on_update(ActivityUpdate {
+                    connection,
+                    activity: None,
+                });
+            });
+        }
+    }
+}
+
+impl Drop for RpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub enum RpcError {
+    Io(#[from] io::Error),
+    Json(#[from] serde_json::Error),
+    #[error("unexpected opcode {0}")]
+    UnexpectedOpcode(u32),
+    #[error("frame of {0} bytes is too large")]
+    FrameTooLarge(u32),
+    #[error("application closed the connection")]
+    Closed,
+}
+
+#[derive(Deserialize)]
+struct Handshake {
+    client_id: Id<ApplicationMarker>,
+}
+
+#[derive(Deserialize)]
+struct Command {
+    cmd: String,
+    #[serde(default)]
+    args: Value,
+    #[serde(default)]
+    nonce: Value,
+}
+
+#[derive(Deserialize)]
+struct SetActivityArgs {
+    activity: Option<RpcActivity>,
+}
+
+/// An activity as applications send it, without the name that is the one of
+/// their application.
+#[derive(Deserialize)]
+struct RpcActivity {
+    #[serde(default, rename = "type")]
+    kind: Option<u8>,
+    state: Option<String>,
+    details: Option<String>,
+    timestamps: Option<ActivityTimestamps>,
+    assets: Option<ActivityAssets>,
+    party: Option<ActivityParty>,
+    secrets: Option<ActivitySecrets>,
+    #[serde(default)]
+    buttons: Vec<RpcButton>,
+    instance: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct RpcButton {
+    label: String,
+}
+
+async fn handle_connection<N, F>(
+    mut stream: UnixStream,
+    connection: u64,
+    user: &CurrentUser,
+    application_name: N,
+    on_update: &impl Fn(ActivityUpdate),
+) -> Result<(), RpcError>
+where
+    N: Fn(Id<ApplicationMarker>) -> F,
+    F: Future<Output = Option<String>>,
+{
+    let (opcode, payload) = read_frame(&mut stream).await?;
+    if opcode != OP_HANDSHAKE {
+        return Err(RpcError::UnexpectedOpcode(opcode));
+    }
+    let handshake = serde_json::from_slice::<Handshake>(&payload)?;
+    let application_id = handshake.client_id;
+    let name = application_name(application_id)
+        .await
+        .unwrap_or_else(|| FALLBACK_NAME.to_owned());
+    debug!("{name} connected for rich presence");
+
+    write_frame(&mut stream, OP_FRAME, &ready_payload(user)).await?;
+
+    loop {
+        let (opcode, payload) = read_frame(&mut stream).await?;
+        match opcode {
+            OP_FRAME => {
+                let command = serde_json::from_slice::<Command>(&payload)?;
+                let response = match command.cmd.as_str() {
+                    "SET_ACTIVITY" => {
+                        let args = serde_json::from_value::<SetActivityArgs>(command.args)?;
+                        let activity = args
+                            .activity
+                            .map(|activity| to_activity(activity, application_id, &name));
+                        let data = serde_json::to_value(&activity)?;
+                        on_update(ActivityUpdate {
+                            connection,
+                            activity,
+                        });
+
+                        json!({ "cmd": command.cmd, "data": data, "evt": null, "nonce": command.nonce })
+                    }
+                    _ => json!({
+                        "cmd": command.cmd,
+                        "data": { "code": INVALID_COMMAND, "message": "Unsupported command" },
+                        "evt": "ERROR",
+                        "nonce": command.nonce,
+                    }),
+                };
+                write_frame(&mut stream, OP_FRAME, &response).await?;
+            }
+            OP_PING => {
+                let payload = serde_json::from_slice::<Value>(&payload)?;
+                write_frame(&mut stream, OP_PONG, &payload).await?;
+            }
+            OP_CLOSE => return Err(RpcError::Closed),
+            opcode => return Err(RpcError::UnexpectedOpcode(opcode)),
+        }
+    }
+}
+
+/// The event applications wait for after the handshake.
+fn ready_payload(user: &CurrentUser) -> Value {
+    json!({
+        "cmd": "DISPATCH",
+        "evt": "READY",
+        "nonce": null,
+        "data": {
+            "v": 1,
+            "config": {
+                "cdn_host": "cdn.discordapp.com",
+                "api_endpoint": "//discord.com/api",
+                "environment": "production",
+            },
+            "user": {
+                "id": user.id,
+                "username": user.name,
+                "discriminator": user.discriminator().to_string(),
+                "avatar": user.avatar,
+                "bot": false,
+            },
+        },
+    })
+}
+
+fn to_activity(
+    activity: RpcActivity,
+    application_id: Id<ApplicationMarker>,
+    name: &str,
+) -> Activity {
+    // only playing, listening, watching and competing can be set by applications
+    let kind = match activity.kind.map(ActivityType::from) {
+        Some(
+            kind @ (ActivityType::Listening | ActivityType::Watching | ActivityType::Competing),
+        ) => kind,
+        _ => ActivityType::Playing,
+    };
+    let timestamps = activity.timestamps.map(|timestamps| ActivityTimestamps {
+        end: timestamps.end.map(to_millis),
+        start: timestamps.start.map(to_millis),
+    });
+
+    Activity {
+        application_id: Some(application_id),
+        assets: activity.assets,
+        // the gateway only takes the labels of buttons from users
+        buttons: activity
+            .buttons
+            .into_iter()
+            .map(|button| {
+                ActivityButton::Text(ActivityButtonText {
+                    label: button.label,
+                })
+            })
+            .collect(),
+        created_at: None,
+        details: activity.details,
+        emoji: None,
+        flags: None,
+        id: None,
+        instance: activity.instance,
+        kind,
+        name: name.to_owned(),
+        party: activity.party,
+        secrets: activity.secrets,
+        state: activity.state,
+        timestamps,
+        url: None,
+    }
+}
+
+/// Converts a timestamp to milliseconds, applications often send seconds.
+const fn to_millis(timestamp: u64) -> u64 {
+    // milliseconds since 2001 are larger than any timestamp in seconds
+    if timestamp < 1_000_000_000_000 {
+        timestamp * 1000
+    } else {
+        timestamp
+    }
+}
+
+async fn read_frame(stream: &mut UnixStream) -> Result<(u32, Vec<u8>), RpcError> {
+    let mut header = [0; 8];
+    stream.read_exact(&mut header).await?;
+    let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if len > MAX_FRAME_LEN {
+        return Err(RpcError::FrameTooLarge(len));
+    }
+
+    let mut payload = vec![0; len as usize];
+    stream.read_exact(&mut payload).await?;
+
+    Ok((opcode, payload))
+}
+
+async fn write_frame(
+    stream: &mut UnixStream,
+    opcode: u32,
+    payload: &Value,
+) -> Result<(), RpcError> {
+    let payload = serde_json::to_vec(payload)?;
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+
+    Ok(stream.write_all(&frame).await?)
+}
+
+/// Directory of the sockets, the same one Discord clients look in.
+fn socket_directory() -> PathBuf {
+    ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .into_iter()
+        .find_map(env::var_os)
+        .map_or_else(|| PathBuf::from("/tmp"), PathBuf::from)
+}
+
+/// Whether a socket was left behind by a server that is gone.
+fn is_stale(path: &Path) -> bool {
+    matches!(
+        std::os::unix::net::UnixStream::connect(path),
+        Err(e) if e.kind() == io::ErrorKind::ConnectionRefused
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, future, process};
+
+    use serde_json::{json, Value};
+    use tokio::{net::UnixStream, sync::mpsc};
+    use twilight_model::{gateway::presence::ActivityType, id::Id, user::CurrentUser};
+
+    use super::{read_frame, to_millis, write_frame, RpcServer, OP_FRAME, OP_HANDSHAKE};
+    use crate::presence::ActivityUpdate;
+
+    fn current_user() -> CurrentUser {
+        serde_json::from_value(json!({
+            "avatar": null,
+            "bot": false,
+            "discriminator": "0",
+            "id": "1",
+            "mfa_enabled": false,
+            "username": "test",
+        }))
+        .unwrap()
+    }
+
+    async fn command(stream: &mut UnixStream, command: Value) -> Value {
+        write_frame(stream, OP_FRAME, &command).await.unwrap();
+        let (_, payload) = read_frame(stream).await.unwrap();
+
+        serde_json::from_slice(&payload).unwrap()
+    }
+
+    #[test]
+    fn seconds_become_millis() {
+        assert_eq!(to_millis(1_700_000_000), 1_700_000_000_000);
+        assert_eq!(to_millis(1_700_000_000_000), 1_700_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn set_activity() {
+        let path = env::temp_dir().join(format!("fusioncord-rpc-test-{}", process::id()));
+        let server = RpcServer::bind_at(path.clone()).unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(server.run(
+            current_user(),
+            |_| future::ready(Some("Factorio".to_owned())),
+            move |update: ActivityUpdate| tx.send(update).unwrap(),
+        ));
+
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+        write_frame(
+            &mut stream,
+            OP_HANDSHAKE,
+            &json!({ "v": 1, "client_id": "42" }),
+        )
+        .await
+        .unwrap();
+        let (_, ready) = read_frame(&mut stream).await.unwrap();
+        let ready = serde_json::from_slice::<Value>(&ready).unwrap();
+        assert_eq!(ready["evt"], "READY");
+        assert_eq!(ready["data"]["user"]["username"], "test");
+
+        let response = command(
+            &mut stream,
+            json!({
+                "cmd": "SET_ACTIVITY",
+                "args": {
+                    "pid": 1234,
+                    "activity": {
+                        "state": "Building a factory",
+                        "timestamps": { "start": 1_700_000_000 },
+                        "buttons": [{ "label": "Join", "url": "https://example.com" }],
+                    },
+                },
+                "nonce": "1",
+            }),
+        )
+        .await;
+        assert_eq!(response["nonce"], "1");
+        assert_eq!(response["data"]["name"], "Factorio");
+
+        let update = rx.recv().await.unwrap();
+        let connection = update.connection;
+        let activity = update.activity.unwrap();
+        assert_eq!(activity.application_id, Some(Id::new(42)));
+        assert_eq!(activity.kind, ActivityType::Playing);
+        assert_eq!(activity.state.as_deref(), Some("Building a factory"));
+        assert_eq!(activity.timestamps.unwrap().start, Some(1_700_000_000_000));
+        assert_eq!(activity.buttons.len(), 1);
+
+        let response = command(
+            &mut stream,
+            json!({ "cmd": "SUBSCRIBE", "args": {}, "nonce": "2" }),
+        )
+        .await;
+        assert_eq!(response["evt"], "ERROR");
+
+        // disconnecting clears the activity
+        drop(stream);
+        let update = rx.recv().await.unwrap();
+        assert_eq!(update.connection, connection);
+        assert_eq!(update.activity, None);
+    }
+}
@@ -0,0 +1,226 @@
+use twilight_model::gateway::{
+    event::DispatchEvent,
+    presence::{Activity, ClientStatus, Session, Status},
+};
+
+use crate::user_presence::UserPresence;
+
+/// ID of the session Discord sends with the presence aggregated over every
+/// session of the user.
+const AGGREGATED_SESSION_ID: &str = "all";
+
+/// Keeps track of the gateway sessions of the current user, one per client
+/// they're logged in on.
+///
+/// Must be fed every dispatch event through [`SessionStore::update`].
+#[derive(Debug, Default)]
+pub struct SessionStore {
+    current_session_id: Option<String>,
+    sessions: Vec<Session>,
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_session_id = Some(ready.session_id.clone());
+                self.sessions = ready.sessions.clone();
+            }
+            DispatchEvent::SessionsReplace(sessions) => {
+                self.sessions = sessions.0.clone();
+            }
+            _ => (),
+        }
+    }
+
+    /// The session of this client, once Discord sent it.
+    pub fn current(&self) -> Option<&Session> {
+        let current_session_id = self.current_session_id.as_deref()?;
+
+        self.sessions
+            .iter()
+            .find(|session| session.session_id == current_session_id)
+    }
+
+    /// Sessions of the current user on other clients.
+    pub fn other_sessions(&self) -> impl Iterator<Item = &Session> {
+        self.sessions.iter().filter(|session| {
+            session.session_id != AGGREGATED_SESSION_ID
+                && Some(session.session_id.as_str()) != self.current_session_id.as_deref()
+        })
+    }
+
+    /// Presence other users see for the current user, aggregated over every
+    /// session of the user.
+    pub fn presence(&self) -> UserPresence {
+        aggregate(
+            self.sessions
+                .iter()
+                .filter(|session| session.session_id != AGGREGATED_SESSION_ID),
+        )
+    }
+}
+
+/// Aggregates the presences of several sessions of a user like Discord does:
+/// the status is the one of the session that is the most present, the
+/// activities of the active session come first and every client shows the
+/// status of its most present session.
+pub fn aggregate<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> UserPresence {
+    let mut sessions = sessions.into_iter().collect::<Vec<_>>();
+    // stable, so sessions keep their order otherwise
+    sessions.sort_by_key(|session| !session.active);
+
+    let status = sessions
+        .iter()
+        .map(|session| session.status)
+        .max_by_key(|&status| status_priority(status))
+        .unwrap_or(Status::Offline);
+
+    let mut client_status = ClientStatus {
+        desktop: None,
+        mobile: None,
+        web: None,
+    };
+    let mut activities = Vec::<Activity>::new();
+    for session in &sessions {
+        let client = match session.client_info.client.as_str() {
+            "desktop" => Some(&mut client_status.desktop),
+            "mobile" => Some(&mut client_status.mobile),
+            "web" => Some(&mut client_status.web),
+            _ => None,
+        };
+        if let Some(client) = client {
+            if client
+                .is_none_or(|existing| status_priority(session.status) > status_priority(existing))
+            {
+                *client = Some(session.status);
+            }
+        }
+
+        for activity in &session.activities {
+            if !activities.contains(activity) {
+                activities.push(activity.clone());
+            }
+        }
+    }
+
+    UserPresence {
+        status,
+        client_status,
+        activities,
+    }
+}
+
+/// How present a status is, a user is shown with the status of their most
+/// present session.
+const fn status_priority(status: Status) -> u8 {
+    match status {
+        Status::DoNotDisturb => 4,
+        Status::Online => 3,
+        Status::Idle => 2,
+        Status::Invisible => 1,
+        Status::Offline => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::gateway::presence::{Activity, ActivityType, ClientInfo, Session, Status};
+
+    use super::aggregate;
+
+    fn session(session_id: &str, client: &str, status: Status) -> Session {
+        Session {
+            active: false,
+            activities: Vec::new(),
+            client_info: ClientInfo {
+                client: client.to_owned(),
+                os: "linux".to_owned(),
+                version: 0,
+            },
+            session_id: session_id.to_owned(),
+            status,
+        }
+    }
+
+    fn activity(name: &str) -> Activity {
+        Activity {
+            application_id: None,
+            assets: None,
+            buttons: Vec::new(),
+            created_at: None,
+            details: None,
+            emoji: None,
+            flags: None,
+            id: None,
+            instance: None,
+            kind: ActivityType::Playing,
+            name: name.to_owned(),
+            party: None,
+            secrets: None,
+            state: None,
+            timestamps: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn no_sessions_is_offline() {
+        let presence = aggregate([]);
+
+        assert_eq!(presence.status, Status::Offline);
+        assert!(presence.activities.is_empty());
+    }
+
+    #[test]
+    fn most_present_status_wins() {
+        let sessions = [
+            session("a", "desktop", Status::Idle),
+            session("b", "mobile", Status::Online),
+            session("c", "web", Status::Invisible),
+        ];
+        assert_eq!(aggregate(&sessions).status, Status::Online);
+
+        let sessions = [
+            session("a", "desktop", Status::Online),
+            session("b", "mobile", Status::DoNotDisturb),
+        ];
+        assert_eq!(aggregate(&sessions).status, Status::DoNotDisturb);
+    }
+
+    #[test]
+    fn status_per_client() {
+        let sessions = [
+            session("a", "desktop", Status::Idle),
+            session("b", "desktop", Status::Online),
+            session("c", "mobile", Status::Idle),
+            session("d", "unknown", Status::DoNotDisturb),
+        ];
+        let presence = aggregate(&sessions);
+
+        assert_eq!(presence.client_status.desktop, Some(Status::Online));
+        assert_eq!(presence.client_status.mobile, Some(Status::Idle));
+        assert_eq!(presence.client_status.web, None);
+    }
+
+    #[test]
+    fn activities_of_active_session_first() {
+        let mut desktop = session("a", "desktop", Status::Online);
+        desktop.activities = vec![activity("Factorio"), activity("Spotify")];
+        let mut mobile = session("b", "mobile", Status::Online);
+        mobile.active = true;
+        mobile.activities = vec![activity("Spotify")];
+
+        let names = aggregate(&[desktop, mobile])
+            .activities
+            .into_iter()
+            .map(|activity| activity.name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["Spotify", "Factorio"]);
+    }
+}
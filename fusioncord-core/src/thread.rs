@@ -0,0 +1,430 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+use twilight_model::{
+    channel::{
+        forum::ForumSortOrder,
+        thread::{ThreadMember, ThreadsListing},
+        Channel, ChannelFlags, ChannelType,
+    },
+    gateway::event::DispatchEvent,
+    guild::Guild,
+    id::{
+        marker::{ChannelMarker, TagMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+
+use crate::cache::{is_archived, Cache};
+
+/// State of the archived threads of a channel fetched so far.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ArchivedThreads {
+    /// Whether a page is being fetched.
+    pub loading: bool,
+    /// Whether fetching the last page failed.
+    pub failed: bool,
+    /// Whether all archived threads were fetched.
+    pub complete: bool,
+    /// Archive time of the oldest thread fetched so far, the next page starts
+    /// before it.
+    pub before: Option<Timestamp>,
+}
+
+impl ArchivedThreads {
+    /// Whether another page can be fetched.
+    pub const fn can_fetch(&self) -> bool {
+        !self.loading && !self.complete
+    }
+}
+
+/// Keeps track of the threads the current user joined and the archived
+/// threads fetched per channel.
+///
+/// The threads themselves are kept in the [`Cache`].
+///
+/// Must be fed every dispatch event through [`ThreadStore::update`].
+#[derive(Debug, Default)]
+pub struct ThreadStore {
+    current_user_id: Option<Id<UserMarker>>,
+    joined: HashSet<Id<ChannelMarker>>,
+    archived: HashMap<Id<ChannelMarker>, ArchivedThreads>,
+}
+
+impl ThreadStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_user_id = Some(ready.user.id);
+                self.joined.clear();
+                self.archived.clear();
+            }
+            DispatchEvent::GuildCreate(guild) => self.insert_guild(guild),
+            // only threads the current user is a member of include their member
+            DispatchEvent::ThreadCreate(thread) if thread.member.is_some() => {
+                self.joined.insert(thread.id);
+            }
+            DispatchEvent::ThreadDelete(thread) => {
+                self.joined.remove(&thread.id);
+            }
+            DispatchEvent::ThreadListSync(sync) => self.insert_members(&sync.members),
+            // only sent for the thread member of the current user
+            DispatchEvent::ThreadMemberUpdate(update) => {
+                if let Some(thread_id) = update.member.id {
+                    self.joined.insert(thread_id);
+                }
+            }
+            DispatchEvent::ThreadMembersUpdate(update) => {
+                let Some(current_user_id) = self.current_user_id else {
+                    return;
+                };
+
+                if update.removed_member_ids.contains(&current_user_id) {
+                    self.joined.remove(&update.id);
+                } else if update
+                    .added_members
+                    .iter()
+                    .any(|member| member.user_id == Some(current_user_id))
+                {
+                    self.joined.insert(update.id);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Inserts the threads joined in a guild that wasn't received through a
+    /// dispatch event, such as the ones sent along with the READY payload.
+    pub fn insert_guild(&mut self, guild: &Guild) {
+        let joined = guild
+            .threads
+            .iter()
+            .filter(|thread| thread.member.is_some())
+            .map(|thread| thread.id);
+
+        self.joined.extend(joined);
+    }
+
+    pub fn is_joined(&self, thread_id: Id<ChannelMarker>) -> bool {
+        self.joined.contains(&thread_id)
+    }
+
+    pub fn archived(&self, channel_id: Id<ChannelMarker>) -> ArchivedThreads {
+        self.archived.get(&channel_id).copied().unwrap_or_default()
+    }
+
+    /// Marks the next page of archived threads of a channel as being fetched.
+    ///
+    /// Returns the time the page starts before, `None` if no page must be
+    /// fetched.
+    pub fn start_fetch(&mut self, channel_id: Id<ChannelMarker>) -> Option<Option<Timestamp>> {
+        let archived = self.archived.entry(channel_id).or_default();
+        if !archived.can_fetch() {
+            return None;
+        }
+
+        archived.loading = true;
+        archived.failed = false;
+        Some(archived.before)
+    }
+
+    /// Inserts a fetched page of archived threads, `None` if fetching it
+    /// failed.
+    ///
+    /// The threads themselves must be inserted with [`Cache::insert_threads`].
+    pub fn insert_archived(
+        &mut self,
+        channel_id: Id<ChannelMarker>,
+        listing: Option<&ThreadsListing>,
+    ) {
+        let archived = self.archived.entry(channel_id).or_default();
+        archived.loading = false;
+
+        let Some(listing) = listing else {
+            archived.failed = true;
+            return;
+        };
+
+        archived.complete = !listing.has_more.unwrap_or(false);
+        archived.before = listing
+            .threads
+            .iter()
+            .filter_map(|thread| thread.thread_metadata.as_ref())
+            .map(|metadata| metadata.archive_timestamp)
+            .min_by_key(|timestamp| timestamp.as_micros())
+            .or(archived.before);
+
+        self.insert_members(&listing.members);
+    }
+
+    fn insert_members(&mut self, members: &[ThreadMember]) {
+        self.joined
+            .extend(members.iter().filter_map(|member| member.id));
+    }
+}
+
+/// Whether a channel lists its threads as posts.
+pub fn is_forum(channel: &Channel) -> bool {
+    channel.kind == ChannelType::GuildForum
+}
+
+/// Posts of a forum that have any of the given tags, or all posts if no tags
+/// are given.
+///
+/// Pinned posts come first, followed by the active posts and then the
+/// archived ones, each sorted by the given order.
+pub fn forum_posts<'a>(
+    cache: &'a Cache,
+    forum_id: Id<ChannelMarker>,
+    sort_order: ForumSortOrder,
+    tags: &HashSet<Id<TagMarker>>,
+) -> Vec<&'a Channel> {
+    let mut posts = cache
+        .threads(forum_id)
+        .filter(|post| {
+            tags.is_empty()
+                || post
+                    .applied_tags
+                    .iter()
+                    .flatten()
+                    .any(|tag_id| tags.contains(tag_id))
+        })
+        .collect::<Vec<_>>();
+
+    posts.sort_by_key(|post| {
+        let pinned = post
+            .flags
+            .is_some_and(|flags| flags.contains(ChannelFlags::PINNED));
+        // snowflakes are ordered by the time they were created at
+        let order = match sort_order {
+            ForumSortOrder::CreationDate => post.id.get(),
+            _ => post.last_message_id.map_or(post.id.get(), |id| id.get()),
+        };
+
+        (!pinned, is_archived(post), Reverse(order))
+    });
+
+    posts
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::{json, Value};
+    use twilight_model::{
+        channel::{forum::ForumSortOrder, thread::ThreadsListing, Channel, ChannelType},
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{ThreadCreate, ThreadDelete, ThreadListSync, ThreadMemberUpdate},
+        },
+        id::Id,
+        util::Timestamp,
+    };
+
+    use super::{forum_posts, ThreadStore};
+    use crate::{
+        cache::Cache,
+        fixtures::{ready, CURRENT_USER_ID, GUILD_ID},
+    };
+
+    const FORUM_ID: u64 = 20;
+
+    /// Post of the forum, archived at the given time if any.
+    fn post(id: u64, archived_at: Option<i64>, extra: &Value) -> Channel {
+        let archive_timestamp = Timestamp::from_secs(archived_at.unwrap_or(1_700_000_000)).unwrap();
+        let mut post = json!({
+            "guild_id": GUILD_ID.to_string(),
+            "id": id.to_string(),
+            "name": "post",
+            "parent_id": FORUM_ID.to_string(),
+            "thread_metadata": {
+                "archive_timestamp": archive_timestamp.iso_8601().to_string(),
+                "archived": archived_at.is_some(),
+                "auto_archive_duration": 1440,
+                "locked": false,
+            },
+            "type": 11,
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            post[key] = value.clone();
+        }
+
+        serde_json::from_value(post).unwrap()
+    }
+
+    fn thread_member(thread_id: u64, user_id: u64) -> Value {
+        json!({
+            "flags": 1,
+            "id": thread_id.to_string(),
+            "join_timestamp": "2023-11-14T22:13:20+00:00",
+            "user_id": user_id.to_string(),
+        })
+    }
+
+    fn members_update(thread_id: u64, added: &[u64], removed: &[u64]) -> DispatchEvent {
+        let added_members = added
+            .iter()
+            .map(|&user_id| thread_member(thread_id, user_id))
+            .collect::<Vec<_>>();
+        let update = serde_json::from_value(json!({
+            "added_members": added_members,
+            "guild_id": GUILD_ID.to_string(),
+            "id": thread_id.to_string(),
+            "member_count": 2,
+            "removed_member_ids": removed.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        }))
+        .unwrap();
+
+        DispatchEvent::ThreadMembersUpdate(update)
+    }
+
+    #[test]
+    fn joined_threads() {
+        let mut store = ThreadStore::new();
+        store.update(&ready());
+
+        let joined = post(
+            30,
+            None,
+            &json!({ "member": thread_member(30, CURRENT_USER_ID) }),
+        );
+        store.update(&DispatchEvent::ThreadCreate(Box::new(ThreadCreate(joined))));
+        let other = post(31, None, &json!({}));
+        store.update(&DispatchEvent::ThreadCreate(Box::new(ThreadCreate(other))));
+        assert!(store.is_joined(Id::new(30)));
+        assert!(!store.is_joined(Id::new(31)));
+
+        let update = serde_json::from_value::<ThreadMemberUpdate>(json!({
+            "guild_id": GUILD_ID.to_string(),
+            "flags": 1,
+            "id": "31",
+            "join_timestamp": "2023-11-14T22:13:20+00:00",
+            "user_id": CURRENT_USER_ID.to_string(),
+        }))
+        .unwrap();
+        store.update(&DispatchEvent::ThreadMemberUpdate(Box::new(update)));
+        assert!(store.is_joined(Id::new(31)));
+
+        // other members joining or leaving don't change anything
+        store.update(&members_update(31, &[], &[2]));
+        store.update(&members_update(32, &[2], &[]));
+        assert!(store.is_joined(Id::new(31)));
+        assert!(!store.is_joined(Id::new(32)));
+
+        store.update(&members_update(31, &[], &[CURRENT_USER_ID]));
+        store.update(&members_update(32, &[CURRENT_USER_ID], &[]));
+        assert!(!store.is_joined(Id::new(31)));
+        assert!(store.is_joined(Id::new(32)));
+
+        store.update(&DispatchEvent::ThreadDelete(ThreadDelete {
+            guild_id: Id::new(GUILD_ID),
+            id: Id::new(30),
+            kind: ChannelType::PublicThread,
+            parent_id: Id::new(FORUM_ID),
+        }));
+        assert!(!store.is_joined(Id::new(30)));
+
+        store.update(&DispatchEvent::ThreadListSync(ThreadListSync {
+            channel_ids: Vec::new(),
+            guild_id: Id::new(GUILD_ID),
+            members: vec![serde_json::from_value(thread_member(33, CURRENT_USER_ID)).unwrap()],
+            threads: vec![post(33, None, &json!({}))],
+        }));
+        assert!(store.is_joined(Id::new(33)));
+
+        store.update(&ready());
+        assert!(!store.is_joined(Id::new(32)));
+    }
+
+    #[test]
+    fn archived_pages() {
+        let mut store = ThreadStore::new();
+        let channel_id = Id::new(FORUM_ID);
+        assert_eq!(store.start_fetch(channel_id), Some(None));
+        // one page is fetched at a time
+        assert_eq!(store.start_fetch(channel_id), None);
+
+        store.insert_archived(channel_id, None);
+        assert!(store.archived(channel_id).failed);
+        assert_eq!(store.start_fetch(channel_id), Some(None));
+        assert!(!store.archived(channel_id).failed);
+
+        let listing = ThreadsListing {
+            has_more: Some(true),
+            members: vec![serde_json::from_value(thread_member(31, CURRENT_USER_ID)).unwrap()],
+            threads: vec![
+                post(30, Some(1_700_000_200), &json!({})),
+                post(31, Some(1_700_000_100), &json!({})),
+            ],
+        };
+        store.insert_archived(channel_id, Some(&listing));
+        assert!(store.is_joined(Id::new(31)));
+        let before = Timestamp::from_secs(1_700_000_100).unwrap();
+        assert_eq!(store.start_fetch(channel_id), Some(Some(before)));
+
+        let listing = ThreadsListing {
+            has_more: Some(false),
+            members: Vec::new(),
+            threads: Vec::new(),
+        };
+        store.insert_archived(channel_id, Some(&listing));
+        let archived = store.archived(channel_id);
+        assert!(archived.complete && !archived.loading);
+        assert_eq!(archived.before, Some(before));
+        assert_eq!(store.start_fetch(channel_id), None);
+    }
+
+    #[test]
+    fn sorted_forum_posts() {
+        let mut cache = Cache::new();
+        cache.update(&ready());
+        cache.insert_threads([
+            post(
+                30,
+                None,
+                &json!({ "applied_tags": ["60"], "last_message_id": "300" }),
+            ),
+            post(
+                31,
+                None,
+                &json!({ "applied_tags": ["61"], "last_message_id": "100" }),
+            ),
+            // pinned
+            post(32, None, &json!({ "flags": 2, "last_message_id": "50" })),
+            post(
+                33,
+                Some(1_700_000_000),
+                &json!({ "applied_tags": ["60"], "last_message_id": "400" }),
+            ),
+            // a post of another forum
+            Channel {
+                parent_id: Some(Id::new(21)),
+                ..post(34, None, &json!({}))
+            },
+        ]);
+        let posts = |sort_order, tags: &[u64]| {
+            let tags = tags.iter().copied().map(Id::new).collect::<HashSet<_>>();
+            forum_posts(&cache, Id::new(FORUM_ID), sort_order, &tags)
+                .into_iter()
+                .map(|post| post.id.get())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(posts(ForumSortOrder::LatestActivity, &[]), [32, 30, 31, 33]);
+        assert_eq!(posts(ForumSortOrder::CreationDate, &[]), [32, 31, 30, 33]);
+        assert_eq!(posts(ForumSortOrder::LatestActivity, &[61]), [31]);
+        assert_eq!(
+            posts(ForumSortOrder::LatestActivity, &[60, 61]),
+            [30, 31, 33]
+        );
+        assert!(posts(ForumSortOrder::CreationDate, &[62]).is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use twilight_model::{
+    gateway::{event::DispatchEvent, payload::incoming::TypingStart},
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+};
+
+/// How long a user is shown as typing after a TYPING_START event.
+const TYPING_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+struct TypingUser {
+    user_id: Id<UserMarker>,
+    name: String,
+    since: Instant,
+}
+
+/// Keeps track of the users that are typing in every channel.
+///
+/// Must be fed every dispatch event through [`TypingStore::update`].
+#[derive(Debug, Default)]
+pub struct TypingStore {
+    current_user_id: Option<Id<UserMarker>>,
+    channels: HashMap<Id<ChannelMarker>, Vec<TypingUser>>,
+}
+
+impl TypingStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.current_user_id = Some(ready.user.id);
+                self.channels.clear();
+            }
+            DispatchEvent::TypingStart(typing) => self.typing_start(typing),
+            // users stop typing once their message is sent
+            DispatchEvent::MessageCreate(message) => {
+                self.remove(message.channel_id, message.author.id);
+            }
+            _ => (),
+        }
+    }
+
+    /// Names of the users currently typing in a channel, excluding the
+    /// current user.
+    pub fn typing_users(&self, channel_id: Id<ChannelMarker>) -> Vec<&str> {
+        self.channels
+            .get(&channel_id)
+            .into_iter()
+            .flatten()
+            .filter(|user| user.since.elapsed() < TYPING_DURATION)
+            .map(|user| user.name.as_str())
+            .collect()
+    }
+
+    fn typing_start(&mut self, typing: &TypingStart) {
+        if Some(typing.user_id) == self.current_user_id {
+            return;
+        }
+
+        // the user is only known when typing in a guild
+        let name = typing.member.as_ref().map_or_else(
+            || "Someone".to_owned(),
+            |member| {
+                member
+                    .nick
+                    .as_ref()
+                    .or(member.user.global_name.as_ref())
+                    .unwrap_or(&member.user.name)
+                    .clone()
+            },
+        );
+
+        self.remove(typing.channel_id, typing.user_id);
+        let users = self.channels.entry(typing.channel_id).or_default();
+        users.retain(|user| user.since.elapsed() < TYPING_DURATION);
+        users.push(TypingUser {
+            user_id: typing.user_id,
+            name,
+            since: Instant::now(),
+        });
+    }
+
+    fn remove(&mut self, channel_id: Id<ChannelMarker>, user_id: Id<UserMarker>) {
+        if let Some(users) = self.channels.get_mut(&channel_id) {
+            users.retain(|user| user.user_id != user_id);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use twilight_model::{
+    gateway::{
+        event::DispatchEvent,
+        payload::incoming::{MergedPresence, PresenceUpdate},
+        presence::{Activity, ActivityType, ClientStatus, Presence, Status},
+    },
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+
+/// Presence of another user.
+#[derive(Debug, Clone)]
+pub struct UserPresence {
+    pub status: Status,
+    /// Status of the user per platform they're connected on.
+    pub client_status: ClientStatus,
+    pub activities: Vec<Activity>,
+}
+
+/// Keeps track of the presences of other users, per guild they're shared in.
+///
+/// Presences of friends aren't shared in a guild and are stored without one.
+///
+/// Must be fed every dispatch event through [`UserPresenceStore::update`].
+#[derive(Debug, Default)]
+pub struct UserPresenceStore {
+    presences: HashMap<(Option<Id<GuildMarker>>, Id<UserMarker>), UserPresence>,
+}
+
+impl UserPresenceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(_) => self.presences.clear(),
+            DispatchEvent::ReadySupplemental(supplemental) => {
+                let merged = &supplemental.merged_presences;
+                for presence in &merged.friends {
+                    self.insert_merged(None, presence);
+                }
+                for (guild, presences) in supplemental.guilds.iter().zip(&merged.guilds) {
+                    for presence in presences {
+                        self.insert_merged(Some(guild.id), presence);
+                    }
+                }
+            }
+            DispatchEvent::GuildCreate(guild) => {
+                self.remove_guild(guild.id);
+                self.insert_presences(&guild.presences);
+            }
+            DispatchEvent::GuildDelete(guild) => self.remove_guild(guild.id),
+            DispatchEvent::GuildMembersChunk(chunk) => self.insert_presences(&chunk.presences),
+            DispatchEvent::GuildMemberRemove(remove) => {
+                self.presences
+                    .remove(&(Some(remove.guild_id), remove.user.id));
+            }
+            DispatchEvent::PresenceUpdate(presence) => self.update_presence(presence),
+            _ => (),
+        }
+    }
+
+    /// Presence of a user as it's shown in a guild, or as a friend outside of
+    /// guilds.
+    ///
+    /// Falls back to their presence as a friend, which Discord doesn't repeat
+    /// for every guild the current user shares with them.
+    pub fn presence(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+        user_id: Id<UserMarker>,
+    ) -> Option<&UserPresence> {
+        guild_id
+            .and_then(|guild_id| self.presences.get(&(Some(guild_id), user_id)))
+            .or_else(|| self.presences.get(&(None, user_id)))
+    }
+
+    /// Status of a user, offline if their presence is unknown.
+    pub fn status(&self, guild_id: Option<Id<GuildMarker>>, user_id: Id<UserMarker>) -> Status {
+        self.presence(guild_id, user_id)
+            .map_or(Status::Offline, |presence| presence.status)
+    }
+
+    fn insert_merged(&mut self, guild_id: Option<Id<GuildMarker>>, presence: &MergedPresence) {
+        self.insert(
+            guild_id,
+            presence.user_id,
+            UserPresence {
+                status: presence.status,
+                client_status: presence.client_status.clone(),
+                activities: presence.activities.clone(),
+            },
+        );
+    }
+
+    fn insert_presences(&mut self, presences: &[Presence]) {
+        for presence in presences {
+            self.insert(
+                Some(presence.guild_id),
+                presence.user.id(),
+                UserPresence {
+                    status: presence.status,
+                    client_status: presence.client_status.clone(),
+                    activities: presence.activities.clone(),
+                },
+            );
+        }
+    }
+
+    fn update_presence(&mut self, presence: &PresenceUpdate) {
+        self.insert(
+            presence.guild_id,
+            presence.user.id(),
+            UserPresence {
+                status: presence.status,
+                client_status: presence.client_status.clone(),
+                activities: presence.activities.clone(),
+            },
+        );
+    }
+
+    /// Stores a presence, forgetting users that went offline.
+    fn insert(
+        &mut self,
+        guild_id: Option<Id<GuildMarker>>,
+        user_id: Id<UserMarker>,
+        presence: UserPresence,
+    ) {
+        if presence.status == Status::Offline {
+            self.presences.remove(&(guild_id, user_id));
+        } else {
+            self.presences.insert((guild_id, user_id), presence);
+        }
+    }
+
+    fn remove_guild(&mut self, guild_id: Id<GuildMarker>) {
+        self.presences
+            .retain(|(presence_guild_id, _), _| *presence_guild_id != Some(guild_id));
+    }
+}
+
+/// The custom status among the activities of a user.
+pub fn custom_status(activities: &[Activity]) -> Option<&Activity> {
+    activities
+        .iter()
+        .find(|activity| activity.kind == ActivityType::Custom)
+}
+
+/// Activities of a user other than their custom status, shown as cards.
+pub fn shown_activities(activities: &[Activity]) -> impl Iterator<Item = &Activity> {
+    activities
+        .iter()
+        .filter(|activity| activity.kind != ActivityType::Custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{
+                MemberRemove, MergedPresence, MergedPresences, PresenceUpdate, ReadySupplemental,
+                SupplementalGuild,
+            },
+            presence::{ClientStatus, Status, UserOrId},
+        },
+        id::Id,
+        user::User,
+    };
+
+    use super::UserPresenceStore;
+
+    fn merged(user_id: u64, status: Status) -> MergedPresence {
+        MergedPresence {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: Some(status),
+                mobile: None,
+                web: None,
+            },
+            status,
+            user_id: Id::new(user_id),
+        }
+    }
+
+    fn presence_update(guild_id: Option<u64>, user_id: u64, status: Status) -> DispatchEvent {
+        DispatchEvent::PresenceUpdate(Box::new(PresenceUpdate {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: None,
+                mobile: Some(status),
+                web: None,
+            },
+            guild_id: guild_id.map(Id::new),
+            status,
+            user: UserOrId::UserId {
+                id: Id::new(user_id),
+            },
+        }))
+    }
+
+    fn supplemental() -> DispatchEvent {
+        DispatchEvent::ReadySupplemental(ReadySupplemental {
+            guilds: vec![
+                SupplementalGuild { id: Id::new(10) },
+                SupplementalGuild { id: Id::new(20) },
+            ],
+            merged_presences: MergedPresences {
+                friends: vec![merged(1, Status::Online)],
+                guilds: vec![
+                    vec![merged(2, Status::Idle)],
+                    vec![merged(3, Status::DoNotDisturb)],
+                ],
+            },
+        })
+    }
+
+    #[test]
+    fn supplemental_presences_per_guild() {
+        let mut presences = UserPresenceStore::new();
+        presences.update(&supplemental());
+
+        assert_eq!(presences.status(None, Id::new(1)), Status::Online);
+        assert_eq!(
+            presences.status(Some(Id::new(10)), Id::new(2)),
+            Status::Idle
+        );
+        assert_eq!(
+            presences.status(Some(Id::new(20)), Id::new(3)),
+            Status::DoNotDisturb
+        );
+        // presences are only shared in the guild they're sent for
+        assert_eq!(
+            presences.status(Some(Id::new(20)), Id::new(2)),
+            Status::Offline
+        );
+        // friends are shown with their presence in every guild
+        assert_eq!(
+            presences.status(Some(Id::new(10)), Id::new(1)),
+            Status::Online
+        );
+    }
+
+    #[test]
+    fn updates_and_removals() {
+        let mut presences = UserPresenceStore::new();
+        presences.update(&supplemental());
+
+        presences.update(&presence_update(Some(10), 2, Status::Online));
+        let presence = presences.presence(Some(Id::new(10)), Id::new(2)).unwrap();
+        assert_eq!(presence.client_status.mobile, Some(Status::Online));
+
+        presences.update(&presence_update(None, 1, Status::Offline));
+        assert!(presences.presence(None, Id::new(1)).is_none());
+
+        presences.update(&DispatchEvent::GuildMemberRemove(MemberRemove {
+            guild_id: Id::new(10),
+            user: User {
+                accent_color: None,
+                avatar: None,
+                avatar_decoration: None,
+                banner: None,
+                bot: false,
+                discriminator: 0,
+                email: None,
+                flags: None,
+                global_name: None,
+                id: Id::new(2),
+                locale: None,
+                mfa_enabled: None,
+                name: "two".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            },
+        }));
+        assert_eq!(
+            presences.status(Some(Id::new(10)), Id::new(2)),
+            Status::Offline
+        );
+    }
+}
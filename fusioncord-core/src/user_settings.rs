@@ -0,0 +1,1222 @@
+//! Settings of the current user that are synced between clients, sent as
+//! base64 encoded protobuf messages.
+//!
+//! Only the settings the client uses are modelled, the others are kept
+//! encoded so they survive when settings are written back.
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tracing::warn;
+use twilight_model::{
+    gateway::{event::DispatchEvent, payload::incoming::UserSettingsProtoType},
+    id::{
+        marker::{ChannelMarker, EmojiMarker, GuildMarker, StickerMarker},
+        Id,
+    },
+};
+
+use crate::proto::{DecodeError, Field, Message, Reader, UnknownFields, Writer};
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub enum SettingsError {
+    Base64(#[from] base64::DecodeError),
+    Proto(#[from] DecodeError),
+}
+
+/// Decodes base64 encoded settings, as sent by the gateway and the REST API.
+pub fn decode_settings<M: Message>(proto: &str) -> Result<M, SettingsError> {
+    Ok(M::decode(&STANDARD.decode(proto)?)?)
+}
+
+/// Encodes settings in the base64 form the REST API expects.
+pub fn encode_settings<M: Message>(settings: &M) -> String {
+    STANDARD.encode(settings.encode())
+}
+
+/// Keeps track of the protobuf settings of the current user.
+///
+/// The frecency settings aren't sent by the gateway until they change, they
+/// must be fetched and inserted through [`UserSettingsStore::insert`]. Emoji
+/// uses are recorded locally and only written back in batches, the settings
+/// can be saved locally in the meantime and restored through
+/// [`UserSettingsStore::restore_frecency`].
+///
+/// Must be fed every dispatch event through [`UserSettingsStore::update`].
+#[derive(Debug, Default)]
+pub struct UserSettingsStore {
+    preloaded: PreloadedUserSettings,
+    frecency: FrecencyUserSettings,
+    /// Whether the complete frecency settings were received, they can't be
+    /// written back before.
+    frecency_loaded: bool,
+    /// When the first use that wasn't written back yet was recorded.
+    frecency_changed_at: Option<u64>,
+}
+
+impl UserSettingsStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &DispatchEvent) {
+        match event {
+            DispatchEvent::Ready(ready) => {
+                self.preloaded = PreloadedUserSettings::default();
+                // the frecency settings are kept until they're fetched again,
+                // as they may contain uses that weren't written back
+                self.frecency_loaded = false;
+
+                if let Some(proto) = &ready.user_settings_proto {
+                    self.insert(UserSettingsProtoType::PreloadedUserSettings, proto, false);
+                }
+            }
+            DispatchEvent::UserSettingsProtoUpdate(update) => {
+                let settings = &update.settings;
+                self.insert(settings.kind, &settings.proto, update.partial);
+            }
+            _ => (),
+        }
+    }
+
+    /// Inserts base64 encoded settings, merging them into the current ones
+    /// if only the changed fields are sent.
+    pub fn insert(&mut self, kind: UserSettingsProtoType, proto: &str, partial: bool) {
+        let result = match kind {
+            UserSettingsProtoType::PreloadedUserSettings => {
+                insert(&mut self.preloaded, proto, partial)
+            }
+            UserSettingsProtoType::FrecencyUserSettings => {
+                let local = (!partial).then(|| self.frecency.clone());
+                let result = insert(&mut self.frecency, proto, partial);
+
+                if let (Ok(()), Some(local)) = (&result, local) {
+                    self.frecency_loaded = true;
+                    if self.frecency.merge_recent_uses(&local) {
+                        // the fetched settings lack them, they are written back right away
+                        self.frecency_changed_at.get_or_insert(0);
+                    }
+                }
+                result
+            }
+            _ => return,
+        };
+
+        if let Err(e) = result {
+            warn!("Failed to decode the {} user settings: {e}", kind.name());
+        }
+    }
+
+    pub fn preloaded(&self) -> &PreloadedUserSettings {
+        &self.preloaded
+    }
+
+    /// Replaces the preloaded settings with changed ones that are being
+    /// written back, so the change is shown right away.
+    pub fn set_preloaded(&mut self, settings: PreloadedUserSettings) {
+        self.preloaded = settings;
+    }
+
+    pub fn frecency(&self) -> &FrecencyUserSettings {
+        &self.frecency
+    }
+
+    /// Restores frecency settings that were saved locally, they're used until
+    /// the ones of the account are fetched and merged into them.
+    pub fn restore_frecency(&mut self, proto: &str) {
+        match decode_settings(proto) {
+            Ok(frecency) => self.frecency = frecency,
+            Err(e) => warn!("Failed to restore the frecency user settings: {e}"),
+        }
+    }
+
+    /// Records that an emoji was used in a message, or as reaction, at a time
+    /// in milliseconds since the unix epoch.
+    ///
+    /// The changed settings are written back once they're taken through
+    /// [`UserSettingsStore::take_frecency_changes`].
+    pub fn record_emoji_use(&mut self, key: String, reaction: bool, now: u64) {
+        let frecency = if reaction {
+            &mut self.frecency.emoji_reaction_frecency
+        } else {
+            &mut self.frecency.emoji_frecency
+        };
+        frecency
+            .get_or_insert_with(Default::default)
+            .record_use(key, now);
+        self.frecency_changed_at.get_or_insert(now);
+    }
+
+    /// The frecency settings to write back, once the complete settings were
+    /// received and uses were recorded for a while.
+    ///
+    /// Uses are batched, so that the settings aren't written back on every
+    /// emoji that is picked.
+    pub fn take_frecency_changes(&mut self, now: u64) -> Option<&FrecencyUserSettings> {
+        let changed_at = self.frecency_changed_at?;
+        if !self.frecency_loaded || now < changed_at + FRECENCY_WRITE_DELAY_MS {
+            return None;
+        }
+
+        self.frecency_changed_at = None;
+        Some(&self.frecency)
+    }
+}
+
+fn insert<M: Message>(settings: &mut M, proto: &str, partial: bool) -> Result<(), SettingsError> {
+    let bytes = STANDARD.decode(proto)?;
+
+    if partial {
+        settings.merge(&bytes)?;
+    } else {
+        *settings = M::decode(&bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Implements the accessors of the unknown fields of a message.
+macro_rules! unknown_fields {
+    () => {
+        fn unknown_fields(&self) -> &UnknownFields {
+            &self.unknown
+        }
+
+        fn unknown_fields_mut(&mut self) -> &mut UnknownFields {
+            &mut self.unknown
+        }
+    };
+}
+
+/// Time recorded emoji uses are batched for before they're written back.
+const FRECENCY_WRITE_DELAY_MS: u64 = 5 * 60 * 1000;
+
+/// Settings sent along with the READY payload.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PreloadedUserSettings {
+    pub versions: Option<Versions>,
+    pub guilds: Option<AllGuildSettings>,
+    pub status: Option<StatusSettings>,
+    pub localization: Option<LocalizationSettings>,
+    pub appearance: Option<AppearanceSettings>,
+    pub guild_folders: Option<GuildFolders>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for PreloadedUserSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => field.merge_into(self.versions.get_or_insert_with(Default::default))?,
+            3 => field.merge_into(self.guilds.get_or_insert_with(Default::default))?,
+            11 => field.merge_into(self.status.get_or_insert_with(Default::default))?,
+            12 => field.merge_into(self.localization.get_or_insert_with(Default::default))?,
+            13 => field.merge_into(self.appearance.get_or_insert_with(Default::default))?,
+            14 => field.merge_into(self.guild_folders.get_or_insert_with(Default::default))?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        if let Some(versions) = &self.versions {
+            writer.message(1, versions);
+        }
+        if let Some(guilds) = &self.guilds {
+            writer.message(3, guilds);
+        }
+        if let Some(status) = &self.status {
+            writer.message(11, status);
+        }
+        if let Some(localization) = &self.localization {
+            writer.message(12, localization);
+        }
+        if let Some(appearance) = &self.appearance {
+            writer.message(13, appearance);
+        }
+        if let Some(guild_folders) = &self.guild_folders {
+            writer.message(14, guild_folders);
+        }
+    }
+
+    unknown_fields!();
+}
+
+/// Versions of the settings, used to detect conflicting changes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Versions {
+    pub client_version: u32,
+    pub server_version: u32,
+    pub data_version: u32,
+    pub unknown: UnknownFields,
+}
+
+impl Message for Versions {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.client_version = field.as_u32()?,
+            2 => self.server_version = field.as_u32()?,
+            3 => self.data_version = field.as_u32()?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        write_u64(writer, 1, self.client_version.into());
+        write_u64(writer, 2, self.server_version.into());
+        write_u64(writer, 3, self.data_version.into());
+    }
+
+    unknown_fields!();
+}
+
+/// Settings of the current user per guild.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AllGuildSettings {
+    pub guilds: BTreeMap<Id<GuildMarker>, GuildSettings>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for AllGuildSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => {
+                let (guild_id, settings) = id_entry(field)?;
+                if let Some(guild_id) = guild_id {
+                    self.guilds.insert(guild_id, settings);
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for (guild_id, settings) in &self.guilds {
+            write_id_entry(writer, 1, guild_id.get(), settings);
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GuildSettings {
+    pub channels: BTreeMap<Id<ChannelMarker>, ChannelSettings>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for GuildSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => {
+                let (channel_id, settings) = id_entry(field)?;
+                if let Some(channel_id) = channel_id {
+                    self.channels.insert(channel_id, settings);
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for (channel_id, settings) in &self.channels {
+            write_id_entry(writer, 1, channel_id.get(), settings);
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChannelSettings {
+    /// Whether the channel is collapsed in the inbox.
+    pub collapsed_in_inbox: bool,
+    pub unknown: UnknownFields,
+}
+
+impl Message for ChannelSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.collapsed_in_inbox = field.as_bool()?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        write_u64(writer, 1, self.collapsed_in_inbox.into());
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatusSettings {
+    /// Status of the current user, such as `online` or `dnd`.
+    pub status: Option<String>,
+    pub custom_status: Option<CustomStatus>,
+    /// Whether the game the current user is playing is shown.
+    pub show_current_game: Option<bool>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for StatusSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.status = wrapped_string(field)?,
+            2 => field.merge_into(self.custom_status.get_or_insert_with(Default::default))?,
+            3 => self.show_current_game = wrapped_u64(field)?.map(|value| value != 0),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        if let Some(status) = &self.status {
+            writer.wrapped(1, |writer| write_string(writer, 1, status));
+        }
+        if let Some(custom_status) = &self.custom_status {
+            writer.message(2, custom_status);
+        }
+        if let Some(show_current_game) = self.show_current_game {
+            writer.wrapped(3, |writer| write_u64(writer, 1, show_current_game.into()));
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CustomStatus {
+    pub text: String,
+    /// Custom emoji of the status.
+    pub emoji_id: Option<Id<EmojiMarker>>,
+    /// Name of the emoji of the status, the emoji itself for unicode emojis.
+    pub emoji_name: String,
+    /// When the status is cleared in milliseconds since the unix epoch, `0`
+    /// if it doesn't expire.
+    pub expires_at_ms: u64,
+    pub created_at_ms: u64,
+    pub unknown: UnknownFields,
+}
+
+impl Message for CustomStatus {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.text = field.as_str()?.to_owned(),
+            2 => self.emoji_id = Id::new_checked(field.as_u64()?),
+            3 => self.emoji_name = field.as_str()?.to_owned(),
+            4 => self.expires_at_ms = field.as_u64()?,
+            5 => self.created_at_ms = field.as_u64()?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        write_string(writer, 1, &self.text);
+        if let Some(emoji_id) = self.emoji_id {
+            writer.fixed64(2, emoji_id.get());
+        }
+        write_string(writer, 3, &self.emoji_name);
+        if self.expires_at_ms != 0 {
+            writer.fixed64(4, self.expires_at_ms);
+        }
+        if self.created_at_ms != 0 {
+            writer.fixed64(5, self.created_at_ms);
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LocalizationSettings {
+    /// Language of the client, such as `en-US`.
+    pub locale: Option<String>,
+    /// Offset of the timezone of the current user from UTC in minutes.
+    pub timezone_offset: Option<i32>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for LocalizationSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.locale = wrapped_string(field)?,
+            2 => self.timezone_offset = wrapped_u64(field)?.map(|value| value as i32),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        if let Some(locale) = &self.locale {
+            writer.wrapped(1, |writer| write_string(writer, 1, locale));
+        }
+        if let Some(timezone_offset) = self.timezone_offset {
+            // negative int32 values are sign extended to 64 bits
+            writer.wrapped(2, |writer| {
+                write_u64(writer, 1, i64::from(timezone_offset) as u64)
+            });
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppearanceSettings {
+    pub theme: Theme,
+    pub developer_mode: bool,
+    pub unknown: UnknownFields,
+}
+
+impl Message for AppearanceSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.theme = Theme::from(field.as_u64()?),
+            2 => self.developer_mode = field.as_bool()?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        write_u64(writer, 1, self.theme.into());
+        write_u64(writer, 2, self.developer_mode.into());
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Unset,
+    Dark,
+    Light,
+    Darker,
+    Midnight,
+    Unknown(u64),
+}
+
+impl From<u64> for Theme {
+    fn from(value: u64) -> Self {
+        match value {
+            0 => Self::Unset,
+            1 => Self::Dark,
+            2 => Self::Light,
+            3 => Self::Darker,
+            4 => Self::Midnight,
+            unknown => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<Theme> for u64 {
+    fn from(value: Theme) -> Self {
+        match value {
+            Theme::Unset => 0,
+            Theme::Dark => 1,
+            Theme::Light => 2,
+            Theme::Darker => 3,
+            Theme::Midnight => 4,
+            Theme::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+/// Order of the guilds in the guild list and the folders they're in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GuildFolders {
+    pub folders: Vec<GuildFolder>,
+    /// Order of the guilds, guilds that aren't in it come first.
+    pub guild_positions: Vec<Id<GuildMarker>>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for GuildFolders {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.folders.push(GuildFolder::decode(field.as_bytes()?)?),
+            2 => self.guild_positions.extend(ids(field)?),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for folder in &self.folders {
+            writer.message(1, folder);
+        }
+        writer.packed_fixed64(2, self.guild_positions.iter().map(|id| id.get()));
+    }
+
+    unknown_fields!();
+}
+
+/// Folder of guilds, guilds that aren't in a folder are in one without an id.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GuildFolder {
+    pub guild_ids: Vec<Id<GuildMarker>>,
+    pub id: Option<i64>,
+    pub name: Option<String>,
+    /// Color of the folder as RGB.
+    pub color: Option<u64>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for GuildFolder {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.guild_ids.extend(ids(field)?),
+            2 => self.id = wrapped_u64(field)?.map(|value| value as i64),
+            3 => self.name = wrapped_string(field)?,
+            4 => self.color = wrapped_u64(field)?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        writer.packed_fixed64(1, self.guild_ids.iter().map(|id| id.get()));
+        if let Some(id) = self.id {
+            writer.wrapped(2, |writer| write_u64(writer, 1, id as u64));
+        }
+        if let Some(name) = &self.name {
+            writer.wrapped(3, |writer| write_string(writer, 1, name));
+        }
+        if let Some(color) = self.color {
+            writer.wrapped(4, |writer| write_u64(writer, 1, color));
+        }
+    }
+
+    unknown_fields!();
+}
+
+/// Favorite and frequently used emojis and stickers, not sent along with the
+/// READY payload.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FrecencyUserSettings {
+    pub versions: Option<Versions>,
+    pub sticker_frecency: Option<StickerFrecency>,
+    pub favorite_emojis: Option<FavoriteEmojis>,
+    pub emoji_frecency: Option<EmojiFrecency>,
+    /// Emojis frequently used as reaction.
+    pub emoji_reaction_frecency: Option<EmojiFrecency>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for FrecencyUserSettings {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => field.merge_into(self.versions.get_or_insert_with(Default::default))?,
+            4 => field.merge_into(self.sticker_frecency.get_or_insert_with(Default::default))?,
+            5 => field.merge_into(self.favorite_emojis.get_or_insert_with(Default::default))?,
+            6 => field.merge_into(self.emoji_frecency.get_or_insert_with(Default::default))?,
+            13 => field.merge_into(
+                self.emoji_reaction_frecency
+                    .get_or_insert_with(Default::default),
+            )?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        if let Some(versions) = &self.versions {
+            writer.message(1, versions);
+        }
+        if let Some(sticker_frecency) = &self.sticker_frecency {
+            writer.message(4, sticker_frecency);
+        }
+        if let Some(favorite_emojis) = &self.favorite_emojis {
+            writer.message(5, favorite_emojis);
+        }
+        if let Some(emoji_frecency) = &self.emoji_frecency {
+            writer.message(6, emoji_frecency);
+        }
+        if let Some(emoji_reaction_frecency) = &self.emoji_reaction_frecency {
+            writer.message(13, emoji_reaction_frecency);
+        }
+    }
+
+    unknown_fields!();
+}
+
+impl FrecencyUserSettings {
+    /// Merges the emoji uses of other settings that are more recent than the
+    /// ones of these, returning whether any were.
+    fn merge_recent_uses(&mut self, other: &Self) -> bool {
+        let mut merged = false;
+
+        for (frecency, other) in [
+            (&mut self.emoji_frecency, &other.emoji_frecency),
+            (
+                &mut self.emoji_reaction_frecency,
+                &other.emoji_reaction_frecency,
+            ),
+        ] {
+            let Some(other) = other else {
+                continue;
+            };
+
+            let emojis = &mut frecency.get_or_insert_with(Default::default).emojis;
+            for (key, item) in &other.emojis {
+                let last_use = |item: &FrecencyItem| item.recent_uses.last().copied();
+                if emojis
+                    .get(key)
+                    .is_none_or(|current| last_use(current) < last_use(item))
+                {
+                    emojis.insert(key.clone(), item.clone());
+                    merged = true;
+                }
+            }
+        }
+
+        merged
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FavoriteEmojis {
+    /// Names of the unicode emojis and ids of the custom emojis.
+    pub emojis: Vec<String>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for FavoriteEmojis {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.emojis.push(field.as_str()?.to_owned()),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for emoji in &self.emojis {
+            writer.string(1, emoji);
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EmojiFrecency {
+    /// Usage per emoji, keyed by the name of unicode emojis and the id of
+    /// custom emojis.
+    pub emojis: BTreeMap<String, FrecencyItem>,
+    pub unknown: UnknownFields,
+}
+
+impl EmojiFrecency {
+    pub fn record_use(&mut self, key: String, now: u64) {
+        self.emojis.entry(key).or_default().record_use(now);
+    }
+
+    /// Keys of the used emojis, the most frecent first.
+    pub fn ranked(&self, now: u64) -> Vec<&str> {
+        let mut ranked = self
+            .emojis
+            .iter()
+            .map(|(key, item)| (item.score_at(now), key.as_str()))
+            .collect::<Vec<_>>();
+        ranked.sort_unstable_by(|(a, a_key), (b, b_key)| b.cmp(a).then(a_key.cmp(b_key)));
+
+        ranked.into_iter().map(|(_, key)| key).collect()
+    }
+}
+
+impl Message for EmojiFrecency {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => {
+                let mut key = String::new();
+                let mut item = FrecencyItem::default();
+                for entry in Reader::new(field.as_bytes()?) {
+                    let entry = entry?;
+                    match entry.number {
+                        1 => key = entry.as_str()?.to_owned(),
+                        2 => entry.merge_into(&mut item)?,
+                        _ => (),
+                    }
+                }
+                self.emojis.insert(key, item);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for (emoji, item) in &self.emojis {
+            let mut entry = Writer::new();
+            entry.string(1, emoji);
+            entry.message(2, item);
+            writer.bytes(1, &entry.into_bytes());
+        }
+    }
+
+    unknown_fields!();
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StickerFrecency {
+    pub stickers: BTreeMap<Id<StickerMarker>, FrecencyItem>,
+    pub unknown: UnknownFields,
+}
+
+impl Message for StickerFrecency {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => {
+                let (sticker_id, item) = id_entry(field)?;
+                if let Some(sticker_id) = sticker_id {
+                    self.stickers.insert(sticker_id, item);
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        for (sticker_id, item) in &self.stickers {
+            write_id_entry(writer, 1, sticker_id.get(), item);
+        }
+    }
+
+    unknown_fields!();
+}
+
+/// How often and how recently an emoji or sticker was used.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FrecencyItem {
+    pub total_uses: u32,
+    /// When it was last used in milliseconds since the unix epoch, oldest
+    /// first.
+    pub recent_uses: Vec<u64>,
+    pub frecency: i32,
+    pub score: i32,
+    pub unknown: UnknownFields,
+}
+
+/// Uses that are remembered per emoji or sticker.
+const MAX_RECENT_USES: usize = 10;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+impl FrecencyItem {
+    /// Records a use at a time in milliseconds since the unix epoch.
+    pub fn record_use(&mut self, now: u64) {
+        self.total_uses += 1;
+        self.recent_uses.push(now);
+        if self.recent_uses.len() > MAX_RECENT_USES {
+            self.recent_uses.remove(0);
+        }
+
+        let score = i32::try_from(self.score_at(now)).unwrap_or(i32::MAX);
+        self.frecency = score;
+        self.score = score;
+    }
+
+    /// How often it was used weighed by how recently, the way the official
+    /// client does.
+    pub fn score_at(&self, now: u64) -> u64 {
+        if self.recent_uses.is_empty() {
+            return 0;
+        }
+
+        let weights = self
+            .recent_uses
+            .iter()
+            .map(|used| match now.saturating_sub(*used) / DAY_MS {
+                0..=3 => 100,
+                4..=15 => 70,
+                16..=30 => 50,
+                31..=45 => 30,
+                46..=80 => 10,
+                _ => 0,
+            })
+            .sum::<u64>();
+
+        u64::from(self.total_uses) * weights / self.recent_uses.len() as u64
+    }
+}
+
+impl Message for FrecencyItem {
+    fn merge_field(&mut self, field: &Field<'_>) -> Result<bool, DecodeError> {
+        match field.number {
+            1 => self.total_uses = field.as_u32()?,
+            2 => self.recent_uses.extend(field.as_packed_varint()?),
+            3 => self.frecency = field.as_i32()?,
+            4 => self.score = field.as_i32()?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_fields(&self, writer: &mut Writer) {
+        write_u64(writer, 1, self.total_uses.into());
+        writer.packed_varint(2, self.recent_uses.iter().copied());
+        write_u64(writer, 3, i64::from(self.frecency) as u64);
+        write_u64(writer, 4, i64::from(self.score) as u64);
+    }
+
+    unknown_fields!();
+}
+
+/// Writes a varint field, nothing for the default value like proto3 does.
+fn write_u64(writer: &mut Writer, number: u32, value: u64) {
+    if value != 0 {
+        writer.varint(number, value);
+    }
+}
+
+/// Writes a string field, nothing for the default value like proto3 does.
+fn write_string(writer: &mut Writer, number: u32, value: &str) {
+    if !value.is_empty() {
+        writer.string(number, value);
+    }
+}
+
+fn wrapped_string(field: &Field<'_>) -> Result<Option<String>, DecodeError> {
+    Ok(match field.wrapped()? {
+        Some(value) => Some(value.as_str()?.to_owned()),
+        // wrappers of the default value are sent empty
+        None => Some(String::new()),
+    })
+}
+
+fn wrapped_u64(field: &Field<'_>) -> Result<Option<u64>, DecodeError> {
+    Ok(Some(match field.wrapped()? {
+        Some(value) => value.as_u64()?,
+        None => 0,
+    }))
+}
+
+/// IDs of a repeated `fixed64` field, skipping the invalid ones.
+fn ids<T>(field: &Field<'_>) -> Result<impl Iterator<Item = Id<T>>, DecodeError> {
+    Ok(field
+        .as_packed_fixed64()?
+        .into_iter()
+        .filter_map(Id::new_checked))
+}
+
+/// Entry of a map keyed by IDs, `None` if the key isn't a valid ID.
+fn id_entry<T, M: Message>(field: &Field<'_>) -> Result<(Option<Id<T>>, M), DecodeError> {
+    let mut key = None;
+    let mut value = M::default();
+
+    for entry in Reader::new(field.as_bytes()?) {
+        let entry = entry?;
+        match entry.number {
+            1 => key = Id::new_checked(entry.as_u64()?),
+            2 => entry.merge_into(&mut value)?,
+            _ => (),
+        }
+    }
+
+    Ok((key, value))
+}
+
+fn write_id_entry<M: Message>(writer: &mut Writer, number: u32, key: u64, value: &M) {
+    let mut entry = Writer::new();
+    entry.fixed64(1, key);
+    entry.message(2, value);
+    writer.bytes(number, &entry.into_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::{
+        gateway::{
+            event::DispatchEvent,
+            payload::incoming::{UserSettingsProtoType, UserSettingsProtoUpdate},
+        },
+        id::Id,
+    };
+
+    use super::{
+        decode_settings, encode_settings, FrecencyUserSettings, PreloadedUserSettings, Theme,
+        UserSettingsStore, DAY_MS,
+    };
+    use crate::fixtures;
+
+    /// `user_settings_proto` of a READY payload, with the guilds in two
+    /// folders, and settings that aren't modelled such as the inbox, text and
+    /// images and privacy ones.
+    const PRELOADED: &str = "CgUIFRjXJRIECAEQARorChoJFQAEnmBTgwgSDwoNCRQABBoIvKwJEgIIAQoNCQAggMAIIyEBEgIQASIHCgUAIAgAQDINCgMKATISAggBYgIIAUIMGAEKCAYAgLBqBbwCWi4KBgoEaWRsZRIgCgZjb2RpbmcaBPCfpoAhAGDY6o8BAAApADJF6I8BAAAaAggBYhYKBwoFZW4tR0ISCwiQ/v////////8BagoIARABGgQSAggEclAKKAoQFQAEnmBTgwgGAICwagW8AhIGCPff+v4IGgUKA0RldiIFCPLL4QIKCgoIACCAwAgjIQESGBUABJ5gU4MIBgCAsGoFvAIAIIDACCMhAQ==";
+    /// USER_SETTINGS_PROTO_UPDATE sent when the status is changed on
+    /// another client.
+    const STATUS_UPDATE: &str =
+        r#"{"settings":{"proto":"CgUIFRjYJVoKCggKBm9ubGluZQ==","type":1},"partial":true}"#;
+    /// Frecency settings fetched from `/users/@me/settings-proto/2`, with
+    /// favorite and recently used emojis and stickers.
+    const FRECENCY: &str = "CgUIFRijCRoKCghHAIDMPi1lCiIcChoJRwCAzD4tZQoSDwgDEgaArPuY/jEYZCCsAiofCgh0aHVtYnN1cAoTMTEwMjI4NTIzNjM1NzQxOTAzODJLCiEKCHRodW1ic3VwEhUIKhIMgKrdvv4xgIe5wP4xGGQg6CAKJgoTMTEwMjI4NTIzNjM1NzQxOTAzOBIPCAcSBoCkm9LxMRgeINIBOjsKOQomMTA5NjYxNzQyNTgzOTgyOTAwMzo0MzI2MTAyOTIzNDI1ODczOTISDwgCEgaA5JTC/jEYZCDIAWoaChgKBWhlYXJ0Eg8ICRIGoI+Rwv4xGGQghAc=";
+
+    const DISCORD_DEVELOPERS: u64 = 613_425_648_685_547_541;
+    const DISCORD_TESTERS: u64 = 197_038_439_483_310_086;
+    const DISCORD_API: u64 = 81_384_788_765_712_384;
+    /// When "thumbsup" was last used in the frecency settings.
+    const LAST_USED: u64 = 1_717_585_200_000;
+
+    fn ready() -> DispatchEvent {
+        fixtures::ready_with(&json!({ "user_settings_proto": PRELOADED }))
+    }
+
+    fn status_update() -> DispatchEvent {
+        DispatchEvent::UserSettingsProtoUpdate(
+            serde_json::from_str::<UserSettingsProtoUpdate>(STATUS_UPDATE).unwrap(),
+        )
+    }
+
+    #[test]
+    fn preloaded_user_settings() {
+        let mut store = UserSettingsStore::new();
+        store.update(&ready());
+        let settings = store.preloaded();
+
+        let versions = settings.versions.as_ref().unwrap();
+        assert_eq!(versions.client_version, 21);
+        assert_eq!(versions.data_version, 4823);
+
+        let status = settings.status.as_ref().unwrap();
+        assert_eq!(status.status.as_deref(), Some("idle"));
+        assert_eq!(status.show_current_game, Some(true));
+        let custom_status = status.custom_status.as_ref().unwrap();
+        assert_eq!(custom_status.text, "coding");
+        assert_eq!(custom_status.emoji_name, "🦀");
+        assert_eq!(custom_status.emoji_id, None);
+        assert_eq!(custom_status.expires_at_ms, 1_717_632_000_000);
+        assert_eq!(custom_status.created_at_ms, 1_717_588_800_000);
+
+        let localization = settings.localization.as_ref().unwrap();
+        assert_eq!(localization.locale.as_deref(), Some("en-GB"));
+        assert_eq!(localization.timezone_offset, Some(-240));
+
+        let appearance = settings.appearance.as_ref().unwrap();
+        assert_eq!(appearance.theme, Theme::Dark);
+        assert!(appearance.developer_mode);
+
+        let guild_folders = settings.guild_folders.as_ref().unwrap();
+        assert_eq!(guild_folders.folders.len(), 2);
+        let folder = &guild_folders.folders[0];
+        assert_eq!(
+            folder.guild_ids,
+            [Id::new(DISCORD_DEVELOPERS), Id::new(DISCORD_TESTERS)]
+        );
+        assert_eq!(folder.id, Some(2_413_735_927));
+        assert_eq!(folder.name.as_deref(), Some("Dev"));
+        assert_eq!(folder.color, Some(0x58_65_F2));
+        // guilds outside of folders are sent as folders without id
+        let folder = &guild_folders.folders[1];
+        assert_eq!(folder.guild_ids, [Id::new(DISCORD_API)]);
+        assert_eq!((folder.id, folder.name.as_deref()), (None, None));
+        assert_eq!(
+            guild_folders.guild_positions,
+            [
+                Id::new(DISCORD_DEVELOPERS),
+                Id::new(DISCORD_TESTERS),
+                Id::new(DISCORD_API)
+            ]
+        );
+
+        let guilds = &settings.guilds.as_ref().unwrap().guilds;
+        let channels = &guilds[&Id::new(DISCORD_DEVELOPERS)].channels;
+        assert!(channels[&Id::new(697_138_785_317_814_292)].collapsed_in_inbox);
+        assert!(guilds[&Id::new(DISCORD_API)].channels.is_empty());
+        assert!(!guilds[&Id::new(DISCORD_API)].unknown.is_empty());
+
+        // the inbox, text and images and privacy settings aren't modelled
+        assert!(!settings.unknown.is_empty());
+    }
+
+    #[test]
+    fn preloaded_user_settings_round_trip() {
+        let settings = decode_settings::<PreloadedUserSettings>(PRELOADED).unwrap();
+        let encoded = encode_settings(&settings);
+
+        assert_eq!(
+            decode_settings::<PreloadedUserSettings>(&encoded).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    fn frecency_user_settings() {
+        let settings = decode_settings::<FrecencyUserSettings>(FRECENCY).unwrap();
+
+        assert_eq!(settings.versions.as_ref().unwrap().data_version, 1187);
+        assert_eq!(
+            settings.favorite_emojis.as_ref().unwrap().emojis,
+            ["thumbsup", "1102285236357419038"]
+        );
+
+        let emojis = &settings.emoji_frecency.as_ref().unwrap().emojis;
+        let item = &emojis["thumbsup"];
+        assert_eq!(item.total_uses, 42);
+        assert_eq!(item.recent_uses, [LAST_USED - 3_600_000, LAST_USED]);
+        assert_eq!(item.frecency, 100);
+        assert_eq!(item.score, 4200);
+        assert_eq!(emojis["1102285236357419038"].total_uses, 7);
+
+        let stickers = &settings.sticker_frecency.as_ref().unwrap().stickers;
+        assert_eq!(stickers[&Id::new(749_054_660_769_218_631)].total_uses, 3);
+        assert_eq!(
+            settings
+                .emoji_reaction_frecency
+                .as_ref()
+                .unwrap()
+                .ranked(LAST_USED),
+            ["heart"]
+        );
+        // favorite stickers and command frecency aren't modelled
+        assert!(!settings.unknown.is_empty());
+
+        let encoded = encode_settings(&settings);
+        assert_eq!(
+            decode_settings::<FrecencyUserSettings>(&encoded).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    fn partial_update() {
+        let mut store = UserSettingsStore::new();
+        store.update(&ready());
+        store.update(&status_update());
+
+        let settings = store.preloaded();
+        assert_eq!(settings.versions.as_ref().unwrap().data_version, 4824);
+        let status = settings.status.as_ref().unwrap();
+        assert_eq!(status.status.as_deref(), Some("online"));
+        // fields missing from the update are kept
+        assert!(status.custom_status.is_some());
+        assert_eq!(settings.appearance.as_ref().unwrap().theme, Theme::Dark);
+    }
+
+    #[test]
+    fn full_update() {
+        let mut store = UserSettingsStore::new();
+        store.update(&ready());
+
+        let DispatchEvent::UserSettingsProtoUpdate(update) = status_update() else {
+            unreachable!();
+        };
+        store.insert(
+            UserSettingsProtoType::PreloadedUserSettings,
+            &update.settings.proto,
+            false,
+        );
+
+        let settings = store.preloaded();
+        assert_eq!(
+            settings.status.as_ref().unwrap().status.as_deref(),
+            Some("online")
+        );
+        assert!(settings.appearance.is_none());
+        assert!(settings.guild_folders.is_none());
+    }
+
+    #[test]
+    fn malformed_settings() {
+        // the first field claims to be longer than the message
+        assert!(decode_settings::<PreloadedUserSettings>("CgUIFQ==").is_err());
+        assert!(decode_settings::<PreloadedUserSettings>("not base64!").is_err());
+    }
+
+    #[test]
+    fn recorded_emoji_uses() {
+        // three months after "thumbsup" was last used
+        const NOW: u64 = LAST_USED + 90 * DAY_MS;
+
+        let mut store = UserSettingsStore::new();
+        store.record_emoji_use("crab".to_owned(), false, NOW);
+        // nothing is written back before the settings are received, which
+        // the use is merged into
+        assert!(store.take_frecency_changes(NOW + DAY_MS).is_none());
+
+        store.insert(UserSettingsProtoType::FrecencyUserSettings, FRECENCY, false);
+        store.record_emoji_use("crab".to_owned(), false, NOW);
+        store.record_emoji_use("crab".to_owned(), false, NOW);
+        store.record_emoji_use("heart".to_owned(), true, NOW + 1000);
+        // uses are written back in batches
+        assert!(store.take_frecency_changes(NOW + 1000).is_none());
+        let settings = store.take_frecency_changes(NOW + DAY_MS).unwrap();
+
+        let emoji_frecency = settings.emoji_frecency.as_ref().unwrap();
+        assert_eq!(emoji_frecency.emojis["crab"].total_uses, 3);
+        // "thumbsup" was used more often, but long ago
+        assert_eq!(
+            emoji_frecency.ranked(NOW),
+            ["crab", "1102285236357419038", "thumbsup"]
+        );
+        assert_eq!(
+            emoji_frecency.ranked(LAST_USED),
+            ["thumbsup", "crab", "1102285236357419038"]
+        );
+        let reactions = settings.emoji_reaction_frecency.as_ref().unwrap();
+        assert_eq!(reactions.emojis["heart"].total_uses, 10);
+        assert!(store.take_frecency_changes(NOW + 2 * DAY_MS).is_none());
+    }
+
+    #[test]
+    fn restored_frecency() {
+        const NOW: u64 = LAST_USED + DAY_MS;
+
+        // settings saved by a previous run, with uses that weren't written back
+        let mut store = UserSettingsStore::new();
+        store.insert(UserSettingsProtoType::FrecencyUserSettings, FRECENCY, false);
+        store.record_emoji_use("crab".to_owned(), false, NOW);
+        store.record_emoji_use("thumbsup".to_owned(), false, NOW);
+        let saved = encode_settings(store.frecency());
+
+        let mut store = UserSettingsStore::new();
+        store.restore_frecency(&saved);
+        store.update(&ready());
+        let ranked = |store: &UserSettingsStore| {
+            let frecency = store.frecency().emoji_frecency.as_ref().unwrap();
+            frecency.ranked(NOW).join(" ")
+        };
+        // the saved settings are used until the fetched ones are received
+        assert_eq!(ranked(&store), "thumbsup 1102285236357419038 crab");
+        assert!(store.take_frecency_changes(NOW).is_none());
+
+        store.insert(UserSettingsProtoType::FrecencyUserSettings, FRECENCY, false);
+        assert_eq!(ranked(&store), "thumbsup 1102285236357419038 crab");
+        assert_eq!(
+            store.frecency().emoji_frecency.as_ref().unwrap().emojis["thumbsup"].total_uses,
+            43
+        );
+        // the fetched settings lacked the uses, they're written back
+        assert!(store.take_frecency_changes(NOW).is_some());
+
+        // fetching settings that are up to date doesn't write them back
+        let mut store = UserSettingsStore::new();
+        store.restore_frecency(FRECENCY);
+        store.insert(UserSettingsProtoType::FrecencyUserSettings, FRECENCY, false);
+        assert!(store.take_frecency_changes(NOW).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use egui::{vec2, Align, Color32, Context, Layout, RichText, ScrollArea, Sense, Ui, Window};
+use fusioncord_core::{
+    cache::Cache,
+    presence::{now_millis, PresenceStore},
+    session::SessionStore,
+};
+use twilight_model::gateway::presence::{Session, Status};
+
+use crate::{
+    friends::{activity_text, status_color, status_name},
+    images::ImageCache,
+    presence::{capitalize, client_icon, client_summary},
+    renderer::{avatar_url, full_uv},
+    state::UiState,
+    status::{StatusAction, StatusPicker},
+};
+
+const AVATAR_SIZE: f32 = 32.;
+
+/// Shows the current user at the bottom of the channel list, along with the
+/// buttons that change their status and open their account.
+pub fn show_user_panel(
+    ui: &mut Ui,
+    cache: &Cache,
+    sessions: &SessionStore,
+    presence: &PresenceStore,
+    status_picker: &mut StatusPicker,
+    images: &mut ImageCache,
+    state: &mut UiState,
+) -> Option<StatusAction> {
+    let user = cache.current_user()?;
+    let status = presence.shown_status();
+    let custom_status = presence.custom_status(now_millis());
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(vec2(AVATAR_SIZE, AVATAR_SIZE), Sense::hover());
+        let url = avatar_url(user.id, user.avatar, user.discriminator);
+        if let Some(texture) = images.get(&url) {
+            ui.painter()
+                .image(texture.id(), rect, full_uv(), Color32::WHITE);
+        }
+        let center = rect.right_bottom() - vec2(4., 4.);
+        ui.painter()
+            .circle_filled(center, 6., ui.visuals().panel_fill);
+        ui.painter().circle_filled(center, 4., status_color(status));
+
+        ui.vertical(|ui| {
+            ui.label(RichText::new(user.name.as_str()).strong());
+            let subtitle = match custom_status {
+                Some(custom_status) => {
+                    format!("{} {}", custom_status.emoji_name, custom_status.text)
+                }
+                None => sessions
+                    .presence()
+                    .activities
+                    .iter()
+                    .find_map(activity_text)
+                    .unwrap_or_else(|| status_name(status).to_owned()),
+            };
+            ui.weak(subtitle.trim());
+        });
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if ui.button("⚙").on_hover_text("My Account").clicked() {
+                state.account_open = !state.account_open;
+            }
+            action = status_picker.show_menu(ui, presence);
+        });
+    });
+
+    action
+}
+
+/// Shows the account of the current user if it's open, with the presence
+/// other users see and the clients they're logged in on.
+pub fn show_account(ctx: &Context, sessions: &SessionStore, state: &mut UiState) {
+    let rpc_enabled = &mut state.rpc_enabled;
+    Window::new("My Account")
+        .open(&mut state.account_open)
+        .collapsible(false)
+        .default_size([360., 400.])
+        .show(ctx, |ui| {
+            let presence = sessions.presence();
+
+            ui.label(RichText::new("PRESENCE").small().strong());
+            ui.horizontal(|ui| {
+                status_dot(ui, presence.status);
+                ui.label(status_name(presence.status));
+                if let Some(activity) = presence.activities.iter().find_map(activity_text) {
+                    ui.weak(activity);
+                }
+            });
+            ui.weak(client_summary(&presence.client_status));
+            ui.checkbox(rpc_enabled, "Share the activity of local applications")
+                .on_hover_text("Lets games set your activity through rich presence");
+
+            ui.separator();
+            ui.label(RichText::new("THIS DEVICE").small().strong());
+            match sessions.current() {
+                Some(session) => show_session(ui, session),
+                None => {
+                    ui.weak("Connecting...");
+                }
+            }
+
+            ui.separator();
+            ui.label(RichText::new("OTHER SESSIONS").small().strong());
+            ScrollArea::vertical().show(ui, |ui| {
+                let mut empty = true;
+                for session in sessions.other_sessions() {
+                    show_session(ui, session);
+                    empty = false;
+                }
+                if empty {
+                    ui.weak("You're not logged in anywhere else.");
+                }
+            });
+        });
+}
+
+fn show_session(ui: &mut Ui, session: &Session) {
+    ui.horizontal(|ui| {
+        ui.label(client_icon(&session.client_info.client));
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(capitalize(&session.client_info.client)).strong());
+                ui.weak(capitalize(&session.client_info.os));
+                if session.active {
+                    ui.weak("· active");
+                }
+            });
+            ui.horizontal(|ui| {
+                status_dot(ui, session.status);
+                ui.label(status_name(session.status));
+                if let Some(activity) = session.activities.iter().find_map(activity_text) {
+                    ui.weak(activity);
+                }
+            });
+        });
+    });
+}
+
+fn status_dot(ui: &mut Ui, status: Status) {
+    let (rect, _) = ui.allocate_exact_size(vec2(10., 10.), Sense::hover());
+    ui.painter()
+        .circle_filled(rect.center(), 4., status_color(status));
+}
@@ -1,30 +1,688 @@
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, Sender};
 
-use eframe::{CreationContext, Frame};
+use eframe::{CreationContext, Frame, Storage};
 use egui::Context;
-use fusioncord_core::message::RenderMessage;
+use fusioncord_core::{
+    attachment::AttachmentStore,
+    cache::Cache,
+    client::GatewayCommand,
+    command::CommandStore,
+    guild_folder,
+    highlight::HighlightCache,
+    http::HttpClient,
+    inbox::InboxStore,
+    interaction::InteractionStore,
+    message::{MessageLink, RenderMessage},
+    notification::{Notification, NotificationStore},
+    presence::{now_millis, status_setting, PresenceStore},
+    profile::ProfileStore,
+    reaction::ReactionStore,
+    read_state::ReadStateStore,
+    relationship::RelationshipStore,
+    role::RoleStore,
+    session::SessionStore,
+    thread::{is_forum, ThreadStore},
+    typing::TypingStore,
+    user_presence::UserPresenceStore,
+    user_settings::{encode_settings, UserSettingsStore},
+};
+use tokio::{runtime::Handle, sync::mpsc::UnboundedSender};
+use twilight_model::{
+    gateway::{event::DispatchEvent, payload::incoming::UserSettingsProtoType},
+    id::{marker::ChannelMarker, Id},
+};
 
-use crate::renderer::Renderer;
+use crate::{
+    account,
+    attachment::AttachmentClient,
+    composer::{Composer, ComposerStores},
+    emoji_picker::{PickerStores, ReactionPicker},
+    friends::FriendsView,
+    guild_list::{GuildList, GuildListAction},
+    history::HistoryClient,
+    idle::IdleDetector,
+    images::ImageCache,
+    inbox,
+    interaction::InteractionClient,
+    notification::NotificationClient,
+    private_channel::GroupDmSettings,
+    profile::ProfilePopout,
+    reaction::{ReactionAction, ReactionClient},
+    read_state::ReadStateClient,
+    renderer::{MessageAction, MessageStores, Renderer},
+    rpc::RpcClient,
+    settings::SettingsClient,
+    state::{Reply, UiState},
+    status::{StatusAction, StatusPicker},
+    thread::{ThreadAction, ThreadClient},
+};
+
+/// Key of the frecency user settings in the storage, they keep the uses that
+/// weren't written back yet.
+const FRECENCY_KEY: &str = "frecency";
 
 pub struct Application {
     renderer: Renderer,
-    #[allow(unused)] // TODO
     rx: Receiver<RenderMessage>,
+    /// Commands to send over the gateway.
+    gateway: UnboundedSender<GatewayCommand>,
+    cache: Cache,
+    read_states: ReadStateStore,
+    notifications: NotificationStore,
+    typing: TypingStore,
+    interactions: InteractionStore,
+    commands: CommandStore,
+    reactions: ReactionStore,
+    threads: ThreadStore,
+    attachments: AttachmentStore,
+    relationships: RelationshipStore,
+    sessions: SessionStore,
+    presence: PresenceStore,
+    /// Presences of the other users.
+    presences: UserPresenceStore,
+    profiles: ProfileStore,
+    roles: RoleStore,
+    user_settings: UserSettingsStore,
+    inbox: InboxStore,
+    images: ImageCache,
+    highlights: HighlightCache,
+    composer: Composer,
+    guild_list: GuildList,
+    status_picker: StatusPicker,
+    idle: IdleDetector,
+    interaction_client: InteractionClient,
+    reaction_client: ReactionClient,
+    history_client: HistoryClient,
+    read_state_client: ReadStateClient,
+    thread_client: ThreadClient,
+    attachment_client: AttachmentClient,
+    settings_client: SettingsClient,
+    notification_client: NotificationClient,
+    rpc_client: RpcClient,
+    friends: FriendsView,
+    group_dm_settings: GroupDmSettings,
+    profile: ProfilePopout,
+    reaction_picker: ReactionPicker,
+    /// Channel that was open during the last frame, to notice when another
+    /// one is opened.
+    opened_channel: Option<Id<ChannelMarker>>,
+    state: UiState,
 }
 
 impl Application {
-    pub fn new(cc: &CreationContext<'_>, rx: Receiver<RenderMessage>) -> Self {
+    pub fn new(
+        cc: &CreationContext<'_>,
+        tx: Sender<RenderMessage>,
+        rx: Receiver<RenderMessage>,
+        gateway: UnboundedSender<GatewayCommand>,
+        runtime: Handle,
+        http: HttpClient,
+    ) -> Self {
+        let state = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+        let mut user_settings = UserSettingsStore::new();
+        if let Some(frecency) = cc
+            .storage
+            .and_then(|storage| storage.get_string(FRECENCY_KEY))
+        {
+            user_settings.restore_frecency(&frecency);
+        }
+
         Self {
             renderer: Renderer::new(cc.egui_ctx.clone()),
             rx,
+            gateway,
+            cache: Cache::new(),
+            read_states: ReadStateStore::new(),
+            notifications: NotificationStore::new(),
+            typing: TypingStore::new(),
+            interactions: InteractionStore::new(),
+            commands: CommandStore::new(),
+            reactions: ReactionStore::new(),
+            threads: ThreadStore::new(),
+            attachments: AttachmentStore::new(),
+            relationships: RelationshipStore::new(),
+            sessions: SessionStore::new(),
+            presence: PresenceStore::new(),
+            presences: UserPresenceStore::new(),
+            profiles: ProfileStore::new(),
+            roles: RoleStore::new(),
+            user_settings,
+            inbox: InboxStore::new(),
+            images: ImageCache::new(cc.egui_ctx.clone(), runtime.clone()),
+            highlights: HighlightCache::new(),
+            guild_list: GuildList::new(),
+            status_picker: StatusPicker::new(),
+            idle: IdleDetector::new(),
+            composer: Composer::new(http.clone(), runtime.clone(), tx.clone()),
+            interaction_client: InteractionClient::new(http.clone(), runtime.clone(), tx.clone()),
+            reaction_client: ReactionClient::new(http.clone(), runtime.clone(), tx.clone()),
+            history_client: HistoryClient::new(http.clone(), runtime.clone(), tx.clone()),
+            read_state_client: ReadStateClient::new(http.clone(), runtime.clone()),
+            thread_client: ThreadClient::new(http.clone(), runtime.clone(), tx.clone()),
+            attachment_client: AttachmentClient::new(runtime.clone(), tx.clone()),
+            settings_client: SettingsClient::new(http.clone(), runtime.clone(), tx.clone()),
+            notification_client: NotificationClient::new(runtime.clone(), tx.clone()),
+            rpc_client: RpcClient::new(http.clone(), runtime.clone(), tx.clone()),
+            profile: ProfilePopout::new(http.clone(), runtime.clone(), tx.clone()),
+            friends: FriendsView::new(http.clone(), runtime.clone(), tx),
+            group_dm_settings: GroupDmSettings::new(http, runtime),
+            reaction_picker: ReactionPicker::default(),
+            opened_channel: None,
+            state,
+        }
+    }
+
+    /// Applies all messages the client sent since the last frame.
+    fn process_messages(&mut self, ctx: &Context) {
+        while let Ok(message) = self.rx.try_recv() {
+            match message {
+                RenderMessage::Dispatch(event) => {
+                    self.cache.update(&event);
+                    self.read_states.update(&event);
+                    self.notifications.update(&event);
+                    self.typing.update(&event);
+                    self.interactions.update(&event);
+                    self.commands.update(&event);
+                    self.reactions.update(&event);
+                    self.threads.update(&event);
+                    self.relationships.update(&event);
+                    self.sessions.update(&event);
+                    self.presences.update(&event);
+                    self.profiles.update(&event);
+                    self.roles.update(&event);
+                    self.user_settings.update(&event);
+                    self.inbox.update(&event);
+                    self.highlights.update(&event);
+
+                    if let DispatchEvent::MessageCreate(message) = &event {
+                        // messages of the open channel are seen while the window has focus
+                        let seen = ctx.input(|input| input.focused)
+                            && self.state.selected_channel() == Some(message.channel_id);
+                        let notification = self.notifications.resolve(&self.cache, message);
+
+                        if notification == Notification::Notify && !seen {
+                            self.notification_client.notify(&self.cache, message);
+                        }
+                    }
+
+                    if matches!(event, DispatchEvent::Ready(_)) {
+                        self.settings_client
+                            .fetch(UserSettingsProtoType::FrecencyUserSettings);
+                        // the cache forgot the history of the open channel
+                        self.opened_channel = None;
+                    }
+                    if matches!(
+                        event,
+                        DispatchEvent::Ready(_) | DispatchEvent::UserSettingsProtoUpdate(_)
+                    ) {
+                        self.sync_presence();
+                    }
+                }
+                RenderMessage::InitialData { guilds } => {
+                    for guild in &guilds {
+                        self.read_states.insert_guild(guild);
+                        self.threads.insert_guild(guild);
+                        self.roles.insert_guild(guild);
+                    }
+                    self.cache.insert_guilds(guilds);
+                }
+                RenderMessage::History {
+                    channel_id,
+                    messages,
+                } => self.cache.insert_history(channel_id, messages),
+                RenderMessage::MessageFailed { channel_id, nonce } => {
+                    self.cache.fail_pending_message(channel_id, &nonce);
+                }
+                RenderMessage::InteractionFailed { nonce } => self.interactions.fail(&nonce),
+                RenderMessage::Reactors {
+                    message_id,
+                    emoji,
+                    burst,
+                    users,
+                } => {
+                    self.reactions
+                        .insert_reactors(message_id, &emoji, burst, users);
+                }
+                RenderMessage::FriendRequestFailed { username } => {
+                    self.friends.fail_friend_request(&username);
+                }
+                RenderMessage::OpenMessage(link) => self.open_message(link),
+                RenderMessage::Profile {
+                    user_id,
+                    guild_id,
+                    profile,
+                } => self.profiles.insert_profile(user_id, guild_id, profile),
+                RenderMessage::UserNote { user_id, note } => self.profiles.set_note(user_id, note),
+                RenderMessage::Commands { scope, index } => {
+                    self.commands.insert_commands(scope, index);
+                }
+                RenderMessage::UserSettings { kind, settings } => {
+                    self.user_settings.insert(kind, &settings, false);
+                    self.sync_presence();
+                }
+                // activities are only shared while rich presence is enabled
+                RenderMessage::LocalActivity(update) if self.state.rpc_enabled => {
+                    self.presence
+                        .set_activity(update.connection, update.activity);
+                }
+                RenderMessage::LocalActivity(_) => (),
+                RenderMessage::AttachmentText {
+                    attachment_id,
+                    text,
+                } => self.attachments.insert_text(attachment_id, text),
+                RenderMessage::ArchivedThreads {
+                    channel_id,
+                    listing,
+                } => {
+                    self.threads.insert_archived(channel_id, listing.as_ref());
+                    if let Some(listing) = listing {
+                        self.cache.insert_threads(listing.threads);
+                    }
+                }
+            }
+
+            ctx.request_repaint();
+        }
+    }
+
+    fn handle_message_action(&mut self, action: MessageAction) {
+        let Some(channel_id) = self.state.selected_channel() else {
+            return;
+        };
+
+        match action {
+            MessageAction::Reply(message_id) => {
+                let Some(message) = self.cache.message(channel_id, message_id) else {
+                    return;
+                };
+                let author_name = message
+                    .member
+                    .as_ref()
+                    .and_then(|member| member.nick.clone())
+                    .or_else(|| message.author.global_name.clone())
+                    .unwrap_or_else(|| message.author.name.clone());
+
+                let draft = self.state.drafts.entry(channel_id).or_default();
+                draft.editing = None;
+                draft.reply = Some(Reply {
+                    message_id,
+                    author_name,
+                    mention: true,
+                });
+            }
+            MessageAction::Edit(message_id) => {
+                let Some(message) = self.cache.message(channel_id, message_id) else {
+                    return;
+                };
+
+                let draft = self.state.drafts.entry(channel_id).or_default();
+                draft.reply = None;
+                draft.editing = Some(message_id);
+                draft.text = message.content.clone();
+            }
+            MessageAction::DismissPending(nonce) => {
+                self.cache.remove_pending_message(channel_id, &nonce);
+            }
+            MessageAction::Component { message_id, data } => {
+                let Some(message) = self.cache.message(channel_id, message_id) else {
+                    return;
+                };
+
+                self.interaction_client
+                    .send_component(&mut self.interactions, message, data);
+            }
+            MessageAction::Reaction { message_id, action } => match action {
+                ReactionAction::Toggle { emoji, burst } => {
+                    if let Some(message) = self.cache.message(channel_id, message_id) {
+                        self.reaction_client
+                            .toggle(&mut self.reactions, message, emoji, burst);
+                    }
+                }
+                ReactionAction::FetchReactors { emoji, burst } => {
+                    self.reaction_client.fetch_reactors(
+                        &mut self.reactions,
+                        channel_id,
+                        message_id,
+                        emoji,
+                        burst,
+                    );
+                }
+                ReactionAction::OpenPicker => self.reaction_picker.open(message_id),
+            },
+            MessageAction::Thread(action) => self.handle_thread_action(action),
+            MessageAction::GroupDmSettings => {
+                if let Some(channel) = self.cache.channel(channel_id) {
+                    self.group_dm_settings.open(channel);
+                }
+            }
+            MessageAction::OpenProfile(message_id) => {
+                let Some(message) = self.cache.message(channel_id, message_id) else {
+                    return;
+                };
+                let nick = message
+                    .member
+                    .as_ref()
+                    .and_then(|member| member.nick.clone());
+
+                self.profile
+                    .open(self.state.selected_guild, message.author.clone(), nick);
+            }
+            MessageAction::FetchAttachmentText { attachment_id, url } => {
+                self.attachment_client
+                    .fetch_text(&mut self.attachments, attachment_id, url);
+            }
+            MessageAction::MarkAsRead => {
+                self.read_state_client
+                    .ack(&mut self.read_states, channel_id);
+            }
+        }
+    }
+
+    fn show_reaction_picker(&mut self, ctx: &Context) {
+        let stores = PickerStores {
+            cache: &self.cache,
+            roles: &self.roles,
+            user_settings: &self.user_settings,
+        };
+        let picked = self.reaction_picker.show(
+            ctx,
+            &stores,
+            self.state.selected_guild,
+            &mut self.state.skin_tone,
+            &mut self.images,
+        );
+
+        if let Some((message_id, emoji)) = picked {
+            self.user_settings
+                .record_emoji_use(emoji.frecency_key(), true, now_millis());
+
+            let message = self
+                .state
+                .selected_channel()
+                .and_then(|channel_id| self.cache.message(channel_id, message_id));
+            if let Some(message) = message {
+                self.reaction_client.add(
+                    &mut self.reactions,
+                    message,
+                    emoji.reaction_type(),
+                    false,
+                );
+            }
+        }
+
+        // emojis picked from the composer or as a reaction, written back in
+        // batches
+        if let Some(settings) = self.user_settings.take_frecency_changes(now_millis()) {
+            self.settings_client
+                .update(UserSettingsProtoType::FrecencyUserSettings, settings);
+        }
+    }
+
+    fn open_message(&mut self, link: MessageLink) {
+        self.state.open_message(link);
+        self.notification_client.clear(link.channel_id);
+    }
+
+    /// Applies the status settings to the presence this client publishes.
+    fn sync_presence(&mut self) {
+        self.presence
+            .set_settings(self.user_settings.preloaded().status.as_ref());
+    }
+
+    /// Publishes the presence of the current user if it changed, such as
+    /// when they become idle or come back.
+    fn publish_presence(&mut self, ctx: &Context) {
+        self.presence.set_idle(self.idle.update(ctx));
+        if !self.state.rpc_enabled {
+            self.presence.clear_activities();
+        }
+        self.rpc_client
+            .set_enabled(self.state.rpc_enabled, self.cache.current_user());
+
+        if let Some(payload) = self.presence.take_changes() {
+            // the client may already be gone
+            let _ = self.gateway.send(GatewayCommand::UpdatePresence(payload));
+        }
+        // the custom status is cleared from the presence once it expires
+        if let Some(expires_in) = self.presence.expires_in() {
+            ctx.request_repaint_after(expires_in);
+        }
+    }
+
+    fn handle_status_action(&mut self, action: StatusAction) {
+        let mut settings = self.user_settings.preloaded().clone();
+        let status_settings = settings.status.get_or_insert_with(Default::default);
+
+        match action {
+            StatusAction::SetStatus(status) => {
+                status_settings.status = Some(status_setting(status).to_owned());
+            }
+            StatusAction::SetCustomStatus(custom_status) => {
+                status_settings.custom_status = custom_status;
+            }
+        }
+
+        self.settings_client
+            .update(UserSettingsProtoType::PreloadedUserSettings, &settings);
+        self.user_settings.set_preloaded(settings);
+        self.sync_presence();
+    }
+
+    /// Applies a change to the guild list and writes it back to the settings.
+    fn handle_guild_list_action(&mut self, action: GuildListAction) {
+        let mut settings = self.user_settings.preloaded().clone();
+        let mut folders = guild_folder::guild_folders(&self.cache, settings.guild_folders.as_ref());
+
+        match action {
+            GuildListAction::Move { dragged, target } => {
+                guild_folder::move_entry(&mut folders, dragged, target);
+            }
+            GuildListAction::UpdateFolder {
+                folder_id,
+                name,
+                color,
+            } => {
+                let Some(folder) = folders
+                    .iter_mut()
+                    .find(|folder| folder.id == Some(folder_id))
+                else {
+                    return;
+                };
+                folder.name = name;
+                folder.color = color;
+            }
+            GuildListAction::RemoveFolder(folder_id) => {
+                guild_folder::remove_folder(&mut folders, folder_id);
+                self.state.expanded_folders.remove(&folder_id);
+            }
+        }
+
+        settings.guild_folders = Some(guild_folder::to_settings(
+            folders,
+            settings.guild_folders.as_ref(),
+        ));
+        self.settings_client
+            .update(UserSettingsProtoType::PreloadedUserSettings, &settings);
+        self.user_settings.set_preloaded(settings);
+    }
+
+    fn handle_thread_action(&mut self, action: ThreadAction) {
+        match action {
+            ThreadAction::Open(channel_id) => {
+                if let Some(guild_id) = self
+                    .cache
+                    .channel(channel_id)
+                    .and_then(|channel| channel.guild_id)
+                {
+                    self.state.select_channel(guild_id, channel_id);
+                }
+            }
+            ThreadAction::FetchArchived(channel_id) => {
+                self.thread_client
+                    .fetch_archived(&mut self.threads, channel_id);
+            }
+            ThreadAction::Join(thread_id) => self.thread_client.set_joined(thread_id, true),
+            ThreadAction::Leave(thread_id) => self.thread_client.set_joined(thread_id, false),
         }
     }
 }
 
 impl eframe::App for Application {
-    fn update(&mut self, _ctx: &Context, _frame: &mut Frame) {
-        self.renderer.render_server_list(3);
-        self.renderer.render_channels(5);
-        self.renderer.render_messages(1000);
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        self.process_messages(ctx);
+        self.publish_presence(ctx);
+        self.images.poll();
+        let selected_channel = self.state.selected_channel();
+
+        let guild_list_action = self.renderer.render_server_list(
+            &self.cache,
+            &self.read_states,
+            &self.relationships,
+            &self.user_settings,
+            &self.inbox,
+            &mut self.guild_list,
+            &mut self.images,
+            &mut self.state,
+        );
+        if let Some(action) = guild_list_action {
+            self.handle_guild_list_action(action);
+        }
+        let status_action = self.renderer.render_channels(
+            &self.cache,
+            &self.read_states,
+            &self.threads,
+            &self.relationships,
+            &self.sessions,
+            &self.presence,
+            &mut self.status_picker,
+            &mut self.images,
+            &mut self.state,
+        );
+        if let Some(action) = status_action {
+            self.handle_status_action(action);
+        }
+
+        let forum_open = self
+            .state
+            .selected_channel()
+            .and_then(|channel_id| self.cache.channel(channel_id))
+            .is_some_and(is_forum);
+        // messages sent before a channel was opened are fetched once the
+        // session is ready, forums have none of their own
+        let opened_channel = self
+            .state
+            .selected_channel()
+            .filter(|_| self.cache.current_user().is_some());
+        if opened_channel != self.opened_channel {
+            self.opened_channel = opened_channel;
+            if let Some(channel_id) = opened_channel.filter(|_| !forum_open) {
+                self.history_client.fetch(&mut self.cache, channel_id);
+            }
+        }
+        // forums use the whole width for their posts
+        if !forum_open {
+            self.renderer.render_member_list(
+                &self.cache,
+                &self.presences,
+                &self.roles,
+                &mut self.profile,
+                &mut self.images,
+                &self.state,
+            );
+        }
+        let stores = ComposerStores {
+            cache: &mut self.cache,
+            typing: &self.typing,
+            roles: &self.roles,
+            commands: &mut self.commands,
+            interactions: &mut self.interactions,
+            user_settings: &mut self.user_settings,
+        };
+        let interaction = self.renderer.render_composer(
+            &mut self.composer,
+            stores,
+            &mut self.images,
+            &mut self.state,
+        );
+        if let Some(interaction) = interaction {
+            self.interaction_client.send(interaction);
+        }
+        if self.state.selected_guild.is_none() && self.state.selected_private_channel.is_none() {
+            self.renderer.render_friends(
+                &mut self.friends,
+                &self.relationships,
+                &self.presences,
+                &mut self.images,
+                &mut self.state,
+            );
+        } else if forum_open {
+            let action = self.renderer.render_forum(
+                &self.cache,
+                &self.threads,
+                &mut self.images,
+                &mut self.state,
+            );
+
+            if let Some(action) = action {
+                self.handle_thread_action(action);
+            }
+        } else {
+            let stores = MessageStores {
+                attachments: &self.attachments,
+                cache: &self.cache,
+                interactions: &self.interactions,
+                reactions: &self.reactions,
+                roles: &self.roles,
+                threads: &self.threads,
+            };
+            let action = self.renderer.render_messages(
+                &stores,
+                &mut self.images,
+                &mut self.highlights,
+                &self.state,
+            );
+
+            if let Some(action) = action {
+                self.handle_message_action(action);
+            }
+        }
+
+        self.interaction_client
+            .show_modal(ctx, &mut self.interactions, &self.cache);
+        self.group_dm_settings
+            .show(ctx, &self.cache, &self.relationships, &mut self.images);
+        self.profile.show(
+            ctx,
+            &self.cache,
+            &self.presences,
+            &self.roles,
+            &mut self.profiles,
+            &mut self.images,
+        );
+        self.show_reaction_picker(ctx);
+        account::show_account(ctx, &self.sessions, &mut self.state);
+        if let Some(action) = self.status_picker.show_editor(ctx) {
+            self.handle_status_action(action);
+        }
+        if let Some(link) =
+            inbox::show_inbox(ctx, &mut self.inbox, &mut self.images, &mut self.state)
+        {
+            self.open_message(link);
+        }
+
+        // the notification of a channel is dismissed once it's opened
+        if let Some(channel_id) = self.state.selected_channel() {
+            if Some(channel_id) != selected_channel {
+                self.notification_client.clear(channel_id);
+            }
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.state);
+        storage.set_string(FRECENCY_KEY, encode_settings(self.user_settings.frecency()));
     }
 }
@@ -0,0 +1,361 @@
+use std::sync::mpsc::Sender;
+
+use egui::{
+    output::OpenUrl, pos2, vec2, Align2, Color32, FontId, Frame, Rect, Response, RichText,
+    ScrollArea, Sense, Ui, Vec2,
+};
+use fusioncord_core::{
+    attachment::{self, AttachmentKind, AttachmentStore, TextPreview},
+    highlight::grammar_for_filename,
+    message::{notify, RenderMessage},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::Attachment,
+    id::{marker::AttachmentMarker, Id},
+};
+
+use crate::{
+    embed::{dimensions, scale_to_fit},
+    highlight,
+    images::ImageCache,
+    presence::format_duration,
+};
+
+const MAX_IMAGE_SIZE: Vec2 = Vec2::new(400., 300.);
+const MAX_VIDEO_SIZE: Vec2 = Vec2::new(400., 225.);
+/// Spoilers show a thumbnail this small scaled up, which blurs it.
+const SPOILER_THUMBNAIL_SIZE: u32 = 16;
+const WAVEFORM_BARS: usize = 48;
+const WAVEFORM_SIZE: Vec2 = Vec2::new(192., 24.);
+/// Lines of a text file shown until its preview is expanded.
+const COLLAPSED_LINES: usize = 6;
+const EXPANDED_HEIGHT: f32 = 400.;
+
+/// Renders the attachments of a message.
+///
+/// Returns the attachment whose text must be fetched to preview it, along
+/// with its URL.
+pub fn show_attachments(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    store: &AttachmentStore,
+    attachments: &[Attachment],
+) -> Option<(Id<AttachmentMarker>, String)> {
+    let mut fetch = None;
+
+    for attachment in attachments {
+        match attachment::kind(attachment) {
+            AttachmentKind::Image => show_image(ui, images, attachment),
+            AttachmentKind::Video => show_video(ui, images, attachment),
+            AttachmentKind::VoiceMessage => show_voice_message(ui, attachment),
+            AttachmentKind::Text if attachment::has_text_preview(attachment) => {
+                if let Some(url) = show_text(ui, store, attachment) {
+                    fetch = Some((attachment.id, url));
+                }
+            }
+            _ => show_file(ui, attachment),
+        }
+    }
+
+    fetch
+}
+
+fn show_image(ui: &mut Ui, images: &mut ImageCache, attachment: &Attachment) {
+    let (response, hidden) = show_media(ui, images, attachment, "", MAX_IMAGE_SIZE);
+
+    if !hidden && response.clicked() {
+        open(ui, &attachment.url);
+    }
+    response.on_hover_text(&attachment.filename);
+}
+
+/// Shows the poster frame of a video, which is opened externally when
+/// clicked.
+fn show_video(ui: &mut Ui, images: &mut ImageCache, attachment: &Attachment) {
+    let (response, hidden) = show_media(ui, images, attachment, "format=jpeg", MAX_VIDEO_SIZE);
+    if hidden {
+        return;
+    }
+
+    let rect = response.rect;
+    ui.painter()
+        .circle_filled(rect.center(), 24., Color32::from_black_alpha(180));
+    ui.painter().text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        "▶",
+        FontId::proportional(24.),
+        Color32::WHITE,
+    );
+
+    if response.clicked() {
+        open(ui, &attachment.url);
+    }
+    response.on_hover_text(format!("{} (opens externally)", attachment.filename));
+}
+
+/// Shows an image through the media proxy, scaled to the size of the
+/// attachment.
+///
+/// Spoilers are blurred until they are clicked, returns whether the media is
+/// still hidden.
+fn show_media(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    attachment: &Attachment,
+    query: &str,
+    max_size: Vec2,
+) -> (Response, bool) {
+    let revealed_id = ui.id().with(("spoiler", attachment.id));
+    let hidden = attachment::is_spoiler(attachment)
+        && !ui
+            .data(|d| d.get_temp::<bool>(revealed_id))
+            .unwrap_or(false);
+
+    let size = dimensions(attachment.width, attachment.height);
+    let display_size = size.map_or(max_size, |size| scale_to_fit(size, max_size));
+    let query = if hidden {
+        let size = SPOILER_THUMBNAIL_SIZE;
+        format!("{query}&width={size}&height={size}")
+    } else if size.is_some() {
+        // the proxy scales the image down, there's no need to download more
+        let (width, height) = (display_size.x.round(), display_size.y.round());
+        format!("{query}&width={width}&height={height}")
+    } else {
+        query.to_owned()
+    };
+    let url = with_query(&attachment.proxy_url, &query);
+
+    let texture = images
+        .get(&url)
+        .map(|texture| (texture.id(), texture.size_vec2()));
+    let display_size = match (size, texture) {
+        (None, Some((_, texture_size))) if !hidden => scale_to_fit(texture_size, max_size),
+        _ => display_size,
+    };
+
+    ui.add_space(4.);
+    let (rect, response) = ui.allocate_exact_size(display_size, Sense::click());
+    let painter = ui.painter();
+    match texture {
+        Some((texture_id, _)) => {
+            let uv = Rect::from_min_max(pos2(0., 0.), pos2(1., 1.));
+            painter.image(texture_id, rect, uv, Color32::WHITE);
+        }
+        None => {
+            painter.rect_filled(rect, 4., ui.visuals().extreme_bg_color);
+        }
+    }
+
+    if hidden {
+        painter.rect_filled(rect, 4., Color32::from_black_alpha(120));
+        let pill = Rect::from_center_size(rect.center(), vec2(76., 24.));
+        painter.rect_filled(pill, 12., Color32::from_black_alpha(200));
+        painter.text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            "SPOILER",
+            FontId::proportional(12.),
+            Color32::WHITE,
+        );
+
+        if response.clicked() {
+            ui.data_mut(|d| d.insert_temp(revealed_id, true));
+        }
+    }
+
+    (response, hidden)
+}
+
+/// Shows the waveform and duration of a voice message, it's played
+/// externally.
+fn show_voice_message(ui: &mut Ui, attachment: &Attachment) {
+    Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            if ui
+                .button("▶")
+                .on_hover_text("Play (opens externally)")
+                .clicked()
+            {
+                open(ui, &attachment.url);
+            }
+
+            let (rect, _) = ui.allocate_exact_size(WAVEFORM_SIZE, Sense::hover());
+            let bar_width = rect.width() / WAVEFORM_BARS as f32;
+            let color = ui.visuals().text_color();
+            for (index, height) in attachment::waveform(attachment, WAVEFORM_BARS)
+                .into_iter()
+                .enumerate()
+            {
+                // silent parts still show a dot
+                let height = (height * rect.height()).max(2.);
+                let center = pos2(
+                    rect.left() + (index as f32 + 0.5) * bar_width,
+                    rect.center().y,
+                );
+                let bar = Rect::from_center_size(center, vec2(bar_width * 0.6, height));
+                ui.painter().rect_filled(bar, 1., color);
+            }
+
+            if let Some(duration) = attachment.duration_secs {
+                ui.weak(format_duration((duration * 1000.) as u64));
+            }
+        });
+    });
+}
+
+/// Shows the preview of a text file, returning its URL if its text must be
+/// fetched.
+fn show_text(ui: &mut Ui, store: &AttachmentStore, attachment: &Attachment) -> Option<String> {
+    let mut fetch = None;
+
+    Frame::group(ui.style()).show(ui, |ui| {
+        ui.set_max_width(MAX_IMAGE_SIZE.x * 1.5);
+        file_header(ui, attachment);
+
+        match store.text(attachment.id) {
+            None => {
+                fetch = Some(attachment.url.clone());
+                ui.spinner();
+            }
+            Some(TextPreview::Loading) => {
+                ui.spinner();
+            }
+            Some(TextPreview::Failed) => {
+                ui.weak("Couldn't load the preview of this file.");
+            }
+            Some(TextPreview::Loaded(text)) => show_text_preview(ui, attachment, text),
+        }
+    });
+
+    fetch
+}
+
+fn show_text_preview(ui: &mut Ui, attachment: &Attachment, text: &str) {
+    let expanded_id = ui.id().with(("expanded", attachment.id));
+    let expanded = ui
+        .data(|d| d.get_temp::<bool>(expanded_id))
+        .unwrap_or(false);
+
+    let lines = text.lines().count();
+    let shown = if expanded {
+        text
+    } else {
+        // up to the end of the last line that's shown
+        let end = text
+            .match_indices('\n')
+            .nth(COLLAPSED_LINES - 1)
+            .map_or(text.len(), |(index, _)| index);
+        &text[..end]
+    };
+
+    let job = highlight::layout_job(ui, shown, grammar_for_filename(&attachment.filename));
+    Frame::none()
+        .fill(ui.visuals().extreme_bg_color)
+        .inner_margin(4.)
+        .show(ui, |ui| {
+            ScrollArea::both()
+                .id_source(("text", attachment.id))
+                .max_height(EXPANDED_HEIGHT)
+                .auto_shrink([false, true])
+                .show(ui, |ui| ui.label(job));
+        });
+
+    if lines > COLLAPSED_LINES {
+        let label = if expanded {
+            "Collapse".to_owned()
+        } else {
+            format!("Expand ({lines} lines)")
+        };
+        if ui.small_button(label).clicked() {
+            ui.data_mut(|d| d.insert_temp(expanded_id, !expanded));
+        }
+    }
+}
+
+/// Shows a file that can't be previewed, with a link to download it.
+fn show_file(ui: &mut Ui, attachment: &Attachment) {
+    Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            let icon = match attachment::kind(attachment) {
+                AttachmentKind::Audio => "🎵",
+                _ => "📄",
+            };
+            ui.label(RichText::new(icon).size(24.));
+            ui.vertical(|ui| file_header(ui, attachment));
+        });
+    });
+}
+
+/// Shows the name of a file, linking to it, along with its size.
+fn file_header(ui: &mut Ui, attachment: &Attachment) {
+    ui.horizontal(|ui| {
+        ui.hyperlink_to(&attachment.filename, &attachment.url);
+        ui.weak(attachment::format_size(attachment.size));
+    });
+}
+
+fn open(ui: &Ui, url: &str) {
+    ui.ctx()
+        .output_mut(|o| o.open_url = Some(OpenUrl::new_tab(url)));
+}
+
+/// Adds query parameters to a URL, which may already have some.
+fn with_query(url: &str, query: &str) -> String {
+    let query = query.trim_start_matches('&');
+    if query.is_empty() {
+        url.to_owned()
+    } else if url.contains('?') {
+        format!("{url}&{query}")
+    } else {
+        format!("{url}?{query}")
+    }
+}
+
+/// Fetches the text of text files to preview them.
+pub struct AttachmentClient {
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+}
+
+impl AttachmentClient {
+    pub fn new(runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self { runtime, tx }
+    }
+
+    /// Fetches the text of a file, unless it already was.
+    pub fn fetch_text(
+        &self,
+        attachments: &mut AttachmentStore,
+        attachment_id: Id<AttachmentMarker>,
+        url: String,
+    ) {
+        if !attachments.start_fetch(attachment_id) {
+            return;
+        }
+
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            let text = fetch_text(&url)
+                .await
+                .inspect_err(|e| warn!("Failed to fetch the text of {url}: {e}"))
+                .ok();
+
+            notify(
+                &tx,
+                RenderMessage::AttachmentText {
+                    attachment_id,
+                    text,
+                },
+            );
+        });
+    }
+}
+
+async fn fetch_text(url: &str) -> Result<String, reqwest::Error> {
+    let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+    // files can be in any encoding, invalid characters are replaced
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
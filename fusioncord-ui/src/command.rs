@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use egui::{Color32, ComboBox, RichText, Spinner, TextEdit, Ui};
+use fusioncord_core::{
+    cache::Cache,
+    command::{
+        choice_value, command_data, parse_option, CommandEntry, CommandScope, CommandStore,
+        Commands, OptionError, OptionInput,
+    },
+};
+use twilight_model::{
+    application::command::{CommandOption, CommandOptionType, CommandOptionValue as BoundValue},
+    id::{
+        marker::{ChannelMarker, GenericMarker, GuildMarker},
+        Id,
+    },
+};
+
+use crate::state::CommandDraft;
+
+/// Commands shown at once in the picker.
+const PICKER_LIMIT: usize = 10;
+/// Users, roles and channels listed at once in the pickers of options.
+const OPTION_PICKER_LIMIT: usize = 100;
+const ERROR_COLOR: Color32 = Color32::from_rgb(237, 66, 69);
+
+/// What the user did with the options of the picked command.
+pub enum CommandAction {
+    Cancel,
+    Submit,
+    /// The option with the given name was typed in, its choices must be
+    /// fetched.
+    Autocomplete(String),
+}
+
+/// Shows the commands matching what is typed after the `/`.
+///
+/// Returns the command that was clicked, or the first one when `pick_first`
+/// is set.
+pub fn show_picker(
+    ui: &mut Ui,
+    commands: &CommandStore,
+    scope: CommandScope,
+    query: &str,
+    pick_first: bool,
+) -> Option<CommandEntry> {
+    match commands.commands(scope) {
+        Some(Commands::Loaded(_)) => (),
+        Some(Commands::Failed) => {
+            ui.weak("Failed to load the commands.");
+            return None;
+        }
+        _ => {
+            ui.add(Spinner::new());
+            return None;
+        }
+    }
+
+    let mut entries = commands.search(scope, query);
+    if entries.is_empty() {
+        ui.weak("No matching commands");
+        return None;
+    }
+    entries.truncate(PICKER_LIMIT);
+
+    let mut picked = None;
+    ui.group(|ui| {
+        for (index, entry) in entries.iter().enumerate() {
+            let clicked = ui
+                .horizontal(|ui| {
+                    let clicked = ui
+                        .selectable_label(index == 0, RichText::new(format!("/{}", entry.name())))
+                        .clicked();
+                    ui.weak(entry.description.as_str());
+                    if let Some(application_name) = &entry.application_name {
+                        ui.label(RichText::new(application_name).small().weak());
+                    }
+                    clicked
+                })
+                .inner;
+            if clicked || (pick_first && index == 0) {
+                picked = Some(index);
+            }
+        }
+    });
+
+    picked.map(|index| entries.swap_remove(index))
+}
+
+/// Shows the options of the picked command to fill in.
+pub fn show_form(
+    ui: &mut Ui,
+    cache: &Cache,
+    commands: &CommandStore,
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    draft: &mut CommandDraft,
+) -> Option<CommandAction> {
+    let CommandDraft {
+        entry,
+        inputs,
+        focused,
+    } = draft;
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(format!("/{}", entry.name())).strong());
+        ui.weak(entry.description.as_str());
+        if ui.small_button("✖").on_hover_text("Cancel").clicked() {
+            action = Some(CommandAction::Cancel);
+        }
+    });
+
+    for option in &entry.options {
+        ui.horizontal(|ui| {
+            let name = if option.required.unwrap_or_default() {
+                format!("{} *", option.name)
+            } else {
+                option.name.clone()
+            };
+            ui.label(RichText::new(name).strong());
+            ui.label(RichText::new(option.description.as_str()).small().weak());
+        });
+
+        let typed = show_input(ui, cache, guild_id, channel_id, option, inputs);
+        if typed && option.autocomplete.unwrap_or_default() {
+            *focused = Some(option.name.clone());
+            action = Some(CommandAction::Autocomplete(option.name.clone()));
+        }
+        if focused.as_deref() == Some(option.name.as_str()) {
+            show_autocomplete_choices(ui, commands, option, inputs, focused);
+        }
+
+        match parse_option(option, inputs.get(&option.name)) {
+            Err(OptionError::Missing) | Ok(_) => (),
+            Err(e) => {
+                ui.colored_label(ERROR_COLOR, e.to_string());
+            }
+        }
+    }
+
+    let valid = command_data(entry, inputs).is_some();
+    if ui.add_enabled(valid, egui::Button::new("Send")).clicked() {
+        action = Some(CommandAction::Submit);
+    }
+
+    action
+}
+
+/// Shows the input of an option, returning whether it was typed in.
+fn show_input(
+    ui: &mut Ui,
+    cache: &Cache,
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    option: &CommandOption,
+    inputs: &mut HashMap<String, OptionInput>,
+) -> bool {
+    let input_id = ui.id().with(("command_option", option.name.as_str()));
+
+    if let Some(choices) = &option.choices {
+        let selected = match inputs.get(&option.name) {
+            Some(OptionInput::Text(value)) => choices
+                .iter()
+                .find(|choice| choice_value(&choice.value) == *value)
+                .map_or("", |choice| choice.name.as_str()),
+            _ => "",
+        };
+        ComboBox::from_id_source(input_id)
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for choice in choices {
+                    let value = choice_value(&choice.value);
+                    if ui
+                        .selectable_label(selected == choice.name, choice.name.as_str())
+                        .clicked()
+                    {
+                        inputs.insert(option.name.clone(), OptionInput::Text(value));
+                    }
+                }
+            });
+        return false;
+    }
+
+    match option.kind {
+        CommandOptionType::String | CommandOptionType::Integer | CommandOptionType::Number => {
+            let input = inputs
+                .entry(option.name.clone())
+                .or_insert_with(|| OptionInput::Text(String::new()));
+            let OptionInput::Text(text) = input else {
+                return false;
+            };
+            let response = ui.add(
+                TextEdit::singleline(text)
+                    .id(input_id)
+                    .hint_text(hint(option))
+                    .desired_width(f32::INFINITY),
+            );
+            response.changed() || response.gained_focus()
+        }
+        CommandOptionType::Boolean => {
+            let selected = match inputs.get(&option.name) {
+                Some(OptionInput::Boolean(true)) => "True",
+                Some(OptionInput::Boolean(false)) => "False",
+                _ => "",
+            };
+            ComboBox::from_id_source(input_id)
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (label, value) in [("True", true), ("False", false)] {
+                        if ui.selectable_label(selected == label, label).clicked() {
+                            inputs.insert(option.name.clone(), OptionInput::Boolean(value));
+                        }
+                    }
+                });
+            false
+        }
+        CommandOptionType::User
+        | CommandOptionType::Channel
+        | CommandOptionType::Role
+        | CommandOptionType::Mentionable => {
+            let candidates = candidates(cache, guild_id, channel_id, option);
+            let selected = match inputs.get(&option.name) {
+                Some(OptionInput::Id(id)) => candidates
+                    .iter()
+                    .find(|(candidate_id, _)| candidate_id == id)
+                    .map_or(String::new(), |(_, name)| name.clone()),
+                _ => String::new(),
+            };
+            ComboBox::from_id_source(input_id)
+                .selected_text(selected.as_str())
+                .show_ui(ui, |ui| {
+                    for (candidate_id, name) in candidates.iter().take(OPTION_PICKER_LIMIT) {
+                        if ui.selectable_label(selected == *name, name).clicked() {
+                            inputs.insert(option.name.clone(), OptionInput::Id(*candidate_id));
+                        }
+                    }
+                });
+            false
+        }
+        _ => {
+            ui.weak("Options of this type can't be filled in yet.");
+            false
+        }
+    }
+}
+
+/// Shows the choices the application suggested for the option being typed.
+fn show_autocomplete_choices(
+    ui: &mut Ui,
+    commands: &CommandStore,
+    option: &CommandOption,
+    inputs: &mut HashMap<String, OptionInput>,
+    focused: &mut Option<String>,
+) {
+    let Some(choices) = commands.autocomplete_choices() else {
+        ui.add(Spinner::new());
+        return;
+    };
+
+    ui.group(|ui| {
+        for choice in choices {
+            if ui.selectable_label(false, choice.name.as_str()).clicked() {
+                let value = choice_value(&choice.value);
+                inputs.insert(option.name.clone(), OptionInput::Text(value));
+                *focused = None;
+            }
+        }
+    });
+}
+
+/// Users, roles or channels that can be picked for an option along with
+/// their names.
+fn candidates(
+    cache: &Cache,
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    option: &CommandOption,
+) -> Vec<(Id<GenericMarker>, String)> {
+    let guild = guild_id.and_then(|guild_id| cache.guild(guild_id));
+    let mut candidates = Vec::new();
+
+    if matches!(
+        option.kind,
+        CommandOptionType::User | CommandOptionType::Mentionable
+    ) {
+        match guild {
+            Some(guild) => candidates.extend(guild.members.iter().map(|member| {
+                let name = member
+                    .nick
+                    .as_deref()
+                    .or(member.user.global_name.as_deref())
+                    .unwrap_or(&member.user.name);
+                (member.user.id.cast(), name.to_owned())
+            })),
+            None => candidates.extend(
+                cache
+                    .channel(channel_id)
+                    .and_then(|channel| channel.recipients.as_ref())
+                    .into_iter()
+                    .flatten()
+                    .map(|user| {
+                        let name = user.global_name.as_deref().unwrap_or(&user.name);
+                        (user.id.cast(), name.to_owned())
+                    }),
+            ),
+        }
+    }
+
+    if matches!(
+        option.kind,
+        CommandOptionType::Role | CommandOptionType::Mentionable
+    ) {
+        if let Some(guild) = guild {
+            let mut roles = guild.roles.iter().collect::<Vec<_>>();
+            roles.sort_unstable_by(|a, b| b.cmp(a));
+            candidates.extend(
+                roles
+                    .into_iter()
+                    .map(|role| (role.id.cast(), format!("@{}", role.name))),
+            );
+        }
+    }
+
+    if option.kind == CommandOptionType::Channel {
+        if let Some(guild_id) = guild_id {
+            let channel_types = option.channel_types.as_deref().unwrap_or_default();
+            candidates.extend(
+                cache
+                    .channel_groups(guild_id)
+                    .into_iter()
+                    .flat_map(|group| group.category.into_iter().chain(group.channels))
+                    .filter(|channel| {
+                        channel_types.is_empty() || channel_types.contains(&channel.kind)
+                    })
+                    .map(|channel| {
+                        let name = channel.name.as_deref().unwrap_or_default();
+                        (channel.id.cast(), format!("#{name}"))
+                    }),
+            );
+        }
+    }
+
+    candidates
+}
+
+/// Hint of a text input, describing the values it accepts.
+fn hint(option: &CommandOption) -> String {
+    let bound = |value| match value {
+        BoundValue::Integer(integer) => integer.to_string(),
+        BoundValue::Number(number) => number.to_string(),
+    };
+
+    match (option.kind, option.min_value, option.max_value) {
+        (CommandOptionType::String, ..) => match (option.min_length, option.max_length) {
+            (Some(min), Some(max)) => format!("{min} to {max} characters"),
+            (None, Some(max)) => format!("Up to {max} characters"),
+            _ => String::new(),
+        },
+        (_, Some(min), Some(max)) => format!("{} to {}", bound(min), bound(max)),
+        (_, Some(min), None) => format!("At least {}", bound(min)),
+        (_, None, Some(max)) => format!("At most {}", bound(max)),
+        (CommandOptionType::Integer, None, None) => "A whole number".to_owned(),
+        _ => "A number".to_owned(),
+    }
+}
@@ -0,0 +1,87 @@
+use egui::{vec2, RichText, Ui};
+use fusioncord_core::{cdn, completion::Suggestion};
+
+use crate::{images::ImageCache, profile};
+
+const EMOJI_SIZE: f32 = 20.;
+
+/// Shows the suggestions for the word being completed, highlighting the
+/// selected one.
+///
+/// Returns the index of the suggestion that was clicked.
+pub fn show_suggestions(
+    ui: &mut Ui,
+    suggestions: &[Suggestion],
+    selected: usize,
+    images: &mut ImageCache,
+) -> Option<usize> {
+    let mut clicked = None;
+
+    ui.group(|ui| {
+        for (index, suggestion) in suggestions.iter().enumerate() {
+            let response = ui
+                .horizontal(|ui| {
+                    show_icon(ui, suggestion, images);
+                    let response = ui.selectable_label(index == selected, label(suggestion));
+                    if let Some(detail) = detail(suggestion) {
+                        ui.weak(detail);
+                    }
+                    response
+                })
+                .inner;
+            if response.clicked() {
+                clicked = Some(index);
+            }
+        }
+    });
+
+    clicked
+}
+
+fn show_icon(ui: &mut Ui, suggestion: &Suggestion, images: &mut ImageCache) {
+    match suggestion {
+        Suggestion::CustomEmoji {
+            emoji_id, animated, ..
+        } => {
+            if let Some(texture) = images.get(&cdn::emoji(*emoji_id, *animated, 48)) {
+                ui.image(texture.id(), vec2(EMOJI_SIZE, EMOJI_SIZE));
+            } else {
+                ui.add_space(EMOJI_SIZE);
+            }
+        }
+        Suggestion::UnicodeEmoji(emoji) => {
+            ui.label(RichText::new(emoji.emoji).size(EMOJI_SIZE - 4.));
+        }
+        _ => (),
+    }
+}
+
+fn label(suggestion: &Suggestion) -> RichText {
+    match suggestion {
+        Suggestion::Member { .. } => RichText::new(suggestion.name()),
+        // roles without a color are shown in the default color
+        Suggestion::Role { name, color, .. } if *color != 0 => {
+            RichText::new(format!("@{name}")).color(profile::to_color(*color))
+        }
+        Suggestion::Role { name, .. } => RichText::new(format!("@{name}")),
+        Suggestion::Everyone | Suggestion::Here => RichText::new(format!("@{}", suggestion.name())),
+        Suggestion::Channel { name, .. } => RichText::new(format!("#{name}")),
+        Suggestion::CustomEmoji { .. } | Suggestion::UnicodeEmoji(_) => {
+            RichText::new(format!(":{}:", suggestion.name()))
+        }
+    }
+}
+
+/// Text shown next to a suggestion to tell it apart from similar ones.
+fn detail(suggestion: &Suggestion) -> Option<&str> {
+    match suggestion {
+        Suggestion::Member {
+            display_name: Some(_),
+            username,
+            ..
+        } => Some(username),
+        Suggestion::Everyone => Some("Notify everyone who has permission to view this channel."),
+        Suggestion::Here => Some("Notify everyone online who has permission to view this channel."),
+        _ => None,
+    }
+}
@@ -0,0 +1,211 @@
+use egui::{vec2, Button, Color32, ComboBox, RichText, Spinner, Ui};
+use fusioncord_core::{cdn, interaction::InteractionStore};
+use twilight_model::{
+    application::interaction::message_component::MessageComponentInteractionData,
+    channel::message::{
+        component::{self, ButtonStyle, ComponentType, SelectMenu},
+        Component, Message, ReactionType,
+    },
+};
+
+use crate::images::ImageCache;
+
+const PRIMARY_COLOR: Color32 = Color32::from_rgb(88, 101, 242);
+const SECONDARY_COLOR: Color32 = Color32::from_rgb(78, 80, 88);
+const SUCCESS_COLOR: Color32 = Color32::from_rgb(36, 128, 70);
+const DANGER_COLOR: Color32 = Color32::from_rgb(218, 55, 60);
+
+/// Renders the buttons and select menus of a message.
+///
+/// Returns the interaction to send when the user used one of them.
+pub fn show_components(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    message: &Message,
+    interactions: &InteractionStore,
+) -> Option<MessageComponentInteractionData> {
+    let mut interaction = None;
+
+    for component in &message.components {
+        let Component::ActionRow(row) = component else {
+            continue;
+        };
+
+        ui.horizontal_wrapped(|ui| {
+            for component in &row.components {
+                let used = match component {
+                    Component::Button(button) => {
+                        show_button(ui, images, message, button, interactions)
+                    }
+                    Component::SelectMenu(menu) => {
+                        show_select_menu(ui, message, menu).map(|values| {
+                            MessageComponentInteractionData {
+                                custom_id: menu.custom_id.clone(),
+                                component_type: ComponentType::SelectMenu,
+                                values,
+                            }
+                        })
+                    }
+                    _ => None,
+                };
+
+                if used.is_some() {
+                    interaction = used;
+                }
+            }
+        });
+    }
+
+    if interactions
+        .pending(message.id)
+        .any(|pending| pending.failed)
+    {
+        ui.colored_label(DANGER_COLOR, "This interaction failed");
+    }
+
+    interaction
+}
+
+/// Renders a button, returning the interaction to send when it was clicked.
+///
+/// Link buttons open their URL instead.
+fn show_button(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    message: &Message,
+    button: &component::Button,
+    interactions: &InteractionStore,
+) -> Option<MessageComponentInteractionData> {
+    let loading = button.custom_id.as_ref().is_some_and(|custom_id| {
+        interactions
+            .pending(message.id)
+            .any(|pending| !pending.failed && pending.custom_id == *custom_id)
+    });
+    if loading {
+        ui.add(Spinner::new());
+        return None;
+    }
+
+    let label = button.label.as_deref().unwrap_or_default();
+    let text = match &button.emoji {
+        Some(ReactionType::Unicode { name }) if label.is_empty() => name.clone(),
+        Some(ReactionType::Unicode { name }) => format!("{name} {label}"),
+        _ => label.to_owned(),
+    };
+    let (fill, text) = match button.style {
+        ButtonStyle::Primary => (PRIMARY_COLOR, text),
+        ButtonStyle::Success => (SUCCESS_COLOR, text),
+        ButtonStyle::Danger => (DANGER_COLOR, text),
+        ButtonStyle::Link => (SECONDARY_COLOR, format!("{text} ↗")),
+        _ => (SECONDARY_COLOR, text),
+    };
+
+    if let Some(ReactionType::Custom { animated, id, .. }) = &button.emoji {
+        if let Some(texture) = images.get(&cdn::emoji(*id, *animated, 48)) {
+            ui.image(texture.id(), vec2(18., 18.));
+        }
+    }
+
+    let clicked = ui
+        .add_enabled(
+            !button.disabled,
+            Button::new(RichText::new(text).color(Color32::WHITE))
+                .fill(fill)
+                .min_size(vec2(60., 32.)),
+        )
+        .clicked();
+    if !clicked {
+        return None;
+    }
+
+    if let Some(url) = &button.url {
+        ui.ctx().output_mut(|output| {
+            output.open_url = Some(egui::output::OpenUrl::new_tab(url));
+        });
+        return None;
+    }
+
+    Some(MessageComponentInteractionData {
+        custom_id: button.custom_id.clone()?,
+        component_type: ComponentType::Button,
+        values: Vec::new(),
+    })
+}
+
+/// Renders a select menu, returning the selected values once the user made
+/// their choice.
+///
+/// Menus accepting multiple values keep the selection until it is submitted.
+fn show_select_menu(ui: &mut Ui, message: &Message, menu: &SelectMenu) -> Option<Vec<String>> {
+    let id = ui.id().with((message.id, &menu.custom_id));
+    let mut selected = ui
+        .data(|data| data.get_temp::<Vec<String>>(id))
+        .unwrap_or_else(|| {
+            menu.options
+                .iter()
+                .filter(|option| option.default)
+                .map(|option| option.value.clone())
+                .collect()
+        });
+    let multiple = menu.max_values.unwrap_or(1) > 1;
+    let mut chosen = None;
+
+    let selected_text = menu
+        .options
+        .iter()
+        .filter(|option| selected.contains(&option.value))
+        .map(|option| option.label.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let selected_text = if selected_text.is_empty() {
+        menu.placeholder.as_deref().unwrap_or("Make a selection")
+    } else {
+        &selected_text
+    };
+
+    ui.add_enabled_ui(!menu.disabled, |ui| {
+        ComboBox::from_id_source(id)
+            .width(300.)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for option in &menu.options {
+                    let is_selected = selected.contains(&option.value);
+                    let label = match &option.emoji {
+                        Some(ReactionType::Unicode { name }) => format!("{name} {}", option.label),
+                        _ => option.label.clone(),
+                    };
+
+                    let response = ui.selectable_label(is_selected, label);
+                    let response = match &option.description {
+                        Some(description) => response.on_hover_text(description.as_str()),
+                        None => response,
+                    };
+                    if !response.clicked() {
+                        continue;
+                    }
+
+                    if !multiple {
+                        selected = vec![option.value.clone()];
+                        chosen = Some(selected.clone());
+                    } else if is_selected {
+                        selected.retain(|value| *value != option.value);
+                    } else if selected.len() < usize::from(menu.max_values.unwrap_or(1)) {
+                        selected.push(option.value.clone());
+                    }
+                }
+            });
+
+        let min_values = usize::from(menu.min_values.unwrap_or(1));
+        if multiple
+            && ui
+                .add_enabled(selected.len() >= min_values, Button::new("Submit"))
+                .clicked()
+        {
+            chosen = Some(selected.clone());
+        }
+    });
+
+    ui.data_mut(|data| data.insert_temp(id, selected));
+
+    chosen
+}
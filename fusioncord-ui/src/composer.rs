@@ -0,0 +1,641 @@
+use std::{
+    collections::HashMap,
+    fs,
+    ops::Range,
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+use egui::{
+    text::CCursor,
+    text_edit::{CCursorRange, TextEditState},
+    Color32, Key, Modifiers, RichText, Spinner, TextEdit, Ui,
+};
+use fusioncord_core::{
+    cache::Cache,
+    command::{autocomplete_data, command_data, CommandScope, CommandStore},
+    completion::{allowed_mentions, complete, completion_query, permissions_in},
+    emoji::PickedEmoji,
+    http::{CreateInteraction, CreateMessage, HttpClient},
+    interaction::InteractionStore,
+    message::{notify, RenderMessage},
+    presence::now_millis,
+    role::RoleStore,
+    typing::TypingStore,
+    user_settings::UserSettingsStore,
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::message::MessageReference,
+    guild::Permissions,
+    http::attachment::Attachment,
+    id::{
+        marker::{ChannelMarker, GuildMarker, StickerMarker},
+        Id,
+    },
+};
+
+use crate::{
+    command::{self, CommandAction},
+    completion,
+    emoji_picker::{EmojiPicker, PickerStores},
+    images::ImageCache,
+    state::{CommandDraft, Draft, UiState},
+    sticker::StickerPicker,
+};
+
+/// Minimum time between two typing notifications for the same channel, the
+/// notification lasts 10 seconds.
+const TYPING_INTERVAL: Duration = Duration::from_secs(8);
+const ERROR_COLOR: Color32 = Color32::from_rgb(237, 66, 69);
+
+/// Stores read and updated by the composer.
+pub struct ComposerStores<'a> {
+    pub cache: &'a mut Cache,
+    pub typing: &'a TypingStore,
+    pub roles: &'a RoleStore,
+    pub commands: &'a mut CommandStore,
+    pub interactions: &'a mut InteractionStore,
+    pub user_settings: &'a mut UserSettingsStore,
+}
+
+/// Text input at the bottom of a channel used to send and edit messages.
+pub struct Composer {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+    /// When the last typing notification was sent, per channel.
+    last_typing: HashMap<Id<ChannelMarker>, Instant>,
+    /// The word that was last completed, and the suggestion selected for it.
+    completed_word: String,
+    selected_suggestion: usize,
+    emoji_picker: EmojiPicker,
+    sticker_picker: StickerPicker,
+    open_picker: Option<Picker>,
+}
+
+/// Picker shown above the text input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Picker {
+    Emoji,
+    Sticker,
+}
+
+impl Composer {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self {
+            http,
+            runtime,
+            tx,
+            last_typing: HashMap::new(),
+            completed_word: String::new(),
+            selected_suggestion: 0,
+            emoji_picker: EmojiPicker::default(),
+            sticker_picker: StickerPicker::default(),
+            open_picker: None,
+        }
+    }
+
+    /// Shows the composer of a channel.
+    ///
+    /// Returns the interaction to send when a command was run or an option
+    /// of a command was typed in.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        stores: ComposerStores<'_>,
+        guild_id: Option<Id<GuildMarker>>,
+        channel_id: Id<ChannelMarker>,
+        state: &mut UiState,
+        images: &mut ImageCache,
+    ) -> Option<CreateInteraction> {
+        let ComposerStores {
+            cache,
+            typing,
+            roles,
+            commands,
+            interactions,
+            user_settings,
+        } = stores;
+        let draft = state.drafts.entry(channel_id).or_default();
+        Self::show_typing(ui, typing, channel_id);
+        Self::show_pending_interactions(ui, interactions, channel_id);
+
+        if let Some(command) = &mut draft.command {
+            let escape = ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape));
+            let action = command::show_form(ui, cache, commands, guild_id, channel_id, command);
+
+            let interaction = match action {
+                Some(CommandAction::Submit) => command_data(&command.entry, &command.inputs)
+                    .and_then(|data| {
+                        interactions.application_command(channel_id, guild_id, &command.entry, data)
+                    }),
+                Some(CommandAction::Autocomplete(focused)) => {
+                    let interaction = autocomplete_data(&command.entry, &command.inputs, &focused)
+                        .and_then(|data| {
+                            interactions.autocomplete(channel_id, guild_id, &command.entry, data)
+                        });
+                    if let Some(interaction) = &interaction {
+                        commands.start_autocomplete(interaction.nonce.clone());
+                    }
+                    return interaction;
+                }
+                Some(CommandAction::Cancel) => None,
+                None if escape => None,
+                None => return None,
+            };
+
+            draft.command = None;
+            commands.clear_autocomplete();
+            return interaction;
+        }
+
+        Self::show_context(ui, draft);
+        Self::collect_dropped_files(ui, draft);
+
+        let id = ui.id().with(("composer", channel_id));
+        let focused = ui.memory(|memory| memory.has_focus(id));
+
+        // the word at the cursor is completed with members, roles, channels
+        // or emojis
+        let completion = Self::cursor(ui, id, &draft.text)
+            .and_then(|cursor| completion_query(&draft.text, cursor))
+            .map(|query| {
+                let suggestions = complete(cache, roles, channel_id, &query);
+                if query.query != self.completed_word {
+                    self.completed_word = query.query.to_owned();
+                    self.selected_suggestion = 0;
+                }
+                (query.range, suggestions)
+            })
+            .filter(|(_, suggestions)| !suggestions.is_empty());
+        let mut accepted = match &completion {
+            Some((_, suggestions)) if focused => self.navigate_suggestions(ui, suggestions.len()),
+            _ => None,
+        };
+
+        // these keys must be consumed before the text edit handles them
+        let submit =
+            focused && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Enter));
+        if focused && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape)) {
+            draft.reply = None;
+            if draft.editing.take().is_some() {
+                draft.text.clear();
+            }
+        }
+        if focused
+            && draft.text.is_empty()
+            && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowUp))
+        {
+            Self::edit_last_message(cache, channel_id, draft);
+        }
+
+        // commands are picked from what is typed after a `/`
+        let query = draft
+            .text
+            .strip_prefix('/')
+            .filter(|query| draft.editing.is_none() && !query.contains('\n'));
+        if let Some(query) = query {
+            let scope = CommandScope::new(guild_id, channel_id);
+            if commands.start_fetch(scope) {
+                self.fetch_commands(scope);
+            }
+
+            if let Some(entry) = command::show_picker(ui, commands, scope, query, submit) {
+                draft.text.clear();
+                draft.command = Some(CommandDraft::new(entry));
+                return None;
+            }
+        }
+
+        if let Some((_, suggestions)) = &completion {
+            let selected = self.selected_suggestion;
+            if let Some(index) = completion::show_suggestions(ui, suggestions, selected, images) {
+                accepted = Some(index);
+            }
+        }
+        if let (Some(index), Some((range, suggestions))) = (accepted, completion) {
+            let suggestion = &suggestions[index];
+            if let Some(emoji) = suggestion.emoji() {
+                user_settings.record_emoji_use(emoji.frecency_key(), false, now_millis());
+            }
+            let replacement = format!("{} ", suggestion.replacement());
+            Self::insert(ui, id, &mut draft.text, range, &replacement);
+        }
+
+        match self.open_picker {
+            Some(Picker::Emoji) => {
+                let stores = PickerStores {
+                    cache,
+                    roles,
+                    user_settings,
+                };
+                let picked = self.emoji_picker.show(
+                    ui,
+                    &stores,
+                    guild_id,
+                    false,
+                    &mut state.skin_tone,
+                    images,
+                );
+                if let Some(emoji) = picked {
+                    self.open_picker = None;
+                    user_settings.record_emoji_use(emoji.frecency_key(), false, now_millis());
+                    Self::insert_emoji(ui, id, &mut draft.text, &emoji);
+                }
+            }
+            Some(Picker::Sticker) => {
+                if let Some(sticker_id) = self.sticker_picker.show(ui, cache, guild_id, images) {
+                    self.open_picker = None;
+                    self.send_sticker(guild_id, channel_id, draft, sticker_id);
+                }
+            }
+            None => (),
+        }
+
+        let hint = if draft.editing.is_some() {
+            "Edit message"
+        } else {
+            "Message"
+        };
+        let response = ui
+            .horizontal(|ui| {
+                self.picker_button(ui, Picker::Emoji, "😀", "Select emoji");
+                // stickers can't be added to a message that was already sent
+                if draft.editing.is_none() {
+                    self.picker_button(ui, Picker::Sticker, "🖼", "Send a sticker");
+                }
+
+                ui.add(
+                    TextEdit::multiline(&mut draft.text)
+                        .id(id)
+                        .hint_text(hint)
+                        .desired_rows(1)
+                        .desired_width(f32::INFINITY),
+                )
+            })
+            .inner;
+
+        if response.changed() && !draft.text.is_empty() && draft.editing.is_none() {
+            self.trigger_typing(channel_id);
+        }
+
+        if submit {
+            let mention_everyone = cache
+                .channel(channel_id)
+                .and_then(|channel| permissions_in(cache, roles, channel))
+                .is_some_and(|permissions| permissions.contains(Permissions::MENTION_EVERYONE));
+            self.submit(cache, guild_id, channel_id, draft, mention_everyone);
+        }
+
+        None
+    }
+
+    /// Byte offset of the cursor in the text of the composer, `None` when
+    /// text is selected.
+    fn cursor(ui: &Ui, id: egui::Id, text: &str) -> Option<usize> {
+        let range = TextEditState::load(ui.ctx(), id)?.ccursor_range()?;
+        if range.primary.index != range.secondary.index {
+            return None;
+        }
+
+        // egui counts characters rather than bytes
+        Some(
+            text.char_indices()
+                .nth(range.primary.index)
+                .map_or(text.len(), |(index, _)| index),
+        )
+    }
+
+    /// Moves through the suggestions with the arrow keys.
+    ///
+    /// Returns the index of the suggestion that was accepted with tab or
+    /// enter.
+    fn navigate_suggestions(&mut self, ui: &mut Ui, len: usize) -> Option<usize> {
+        let (up, down, accept) = ui.input_mut(|input| {
+            (
+                input.consume_key(Modifiers::NONE, Key::ArrowUp),
+                input.consume_key(Modifiers::NONE, Key::ArrowDown),
+                input.consume_key(Modifiers::NONE, Key::Tab)
+                    || input.consume_key(Modifiers::NONE, Key::Enter),
+            )
+        });
+
+        let selected = self.selected_suggestion.min(len - 1);
+        self.selected_suggestion = match (up, down) {
+            (true, false) => selected.checked_sub(1).unwrap_or(len - 1),
+            (false, true) => (selected + 1) % len,
+            _ => selected,
+        };
+
+        accept.then_some(self.selected_suggestion)
+    }
+
+    fn picker_button(&mut self, ui: &mut Ui, picker: Picker, icon: &str, hover_text: &str) {
+        let open = self.open_picker == Some(picker);
+        if ui
+            .selectable_label(open, icon)
+            .on_hover_text(hover_text)
+            .clicked()
+        {
+            self.open_picker = (!open).then_some(picker);
+        }
+    }
+
+    /// Inserts an emoji from the picker at the cursor, or at the end when
+    /// the composer wasn't focused yet.
+    fn insert_emoji(ui: &Ui, id: egui::Id, text: &mut String, emoji: &PickedEmoji) {
+        let cursor = Self::cursor(ui, id, text).unwrap_or(text.len());
+        // custom emojis are only parsed when they're not glued to a word
+        let separator = match text[..cursor].chars().next_back() {
+            Some(char) if !char.is_whitespace() => " ",
+            _ => "",
+        };
+
+        let insertion = format!("{separator}{}", emoji.text());
+        Self::insert(ui, id, text, cursor..cursor, &insertion);
+    }
+
+    /// Replaces a range of the text, placing the cursor after the
+    /// replacement.
+    fn insert(ui: &Ui, id: egui::Id, text: &mut String, range: Range<usize>, replacement: &str) {
+        let cursor = text[..range.start].chars().count() + replacement.chars().count();
+        text.replace_range(range, replacement);
+
+        let mut state = TextEditState::load(ui.ctx(), id).unwrap_or_default();
+        state.set_ccursor_range(Some(CCursorRange::one(CCursor::new(cursor))));
+        state.store(ui.ctx(), id);
+    }
+
+    fn show_typing(ui: &mut Ui, typing: &TypingStore, channel_id: Id<ChannelMarker>) {
+        let text = match typing.typing_users(channel_id).as_slice() {
+            [] => return,
+            [user] => format!("{user} is typing..."),
+            [first, second] => format!("{first} and {second} are typing..."),
+            [first, second, third] => format!("{first}, {second} and {third} are typing..."),
+            _ => "Several people are typing...".to_owned(),
+        };
+
+        ui.label(RichText::new(text).small());
+        // typing expires without any event to trigger a repaint
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+
+    /// Shows the commands run and modals submitted in a channel that the
+    /// application didn't respond to yet, or that failed.
+    fn show_pending_interactions(
+        ui: &mut Ui,
+        interactions: &mut InteractionStore,
+        channel_id: Id<ChannelMarker>,
+    ) {
+        let mut dismissed = None;
+
+        for pending in interactions.pending_in_channel(channel_id) {
+            ui.horizontal(|ui| {
+                if pending.failed {
+                    ui.colored_label(
+                        ERROR_COLOR,
+                        format!(
+                            "{} failed, the application didn't respond",
+                            pending.custom_id
+                        ),
+                    );
+                    if ui.small_button("✖").clicked() {
+                        dismissed = Some(pending.nonce.clone());
+                    }
+                } else {
+                    ui.add(Spinner::new());
+                    ui.label(RichText::new(format!("Sending {}...", pending.custom_id)).small());
+                }
+            });
+        }
+
+        if let Some(nonce) = dismissed {
+            interactions.dismiss(&nonce);
+        }
+    }
+
+    /// Shows what is being replied to or edited, and the files to upload.
+    fn show_context(ui: &mut Ui, draft: &mut Draft) {
+        if let Some(reply) = &mut draft.reply {
+            let mut cancel = false;
+
+            ui.horizontal(|ui| {
+                ui.label(format!("Replying to {}", reply.author_name));
+                let mention = if reply.mention { "@ ON" } else { "@ OFF" };
+                if ui.small_button(mention).clicked() {
+                    reply.mention = !reply.mention;
+                }
+                cancel = ui.small_button("✖").clicked();
+            });
+
+            if cancel {
+                draft.reply = None;
+            }
+        }
+
+        if draft.editing.is_some() {
+            ui.label(RichText::new("Editing message, escape to cancel").small());
+        }
+
+        let mut removed = None;
+        ui.horizontal_wrapped(|ui| {
+            for (index, attachment) in draft.attachments.iter().enumerate() {
+                ui.label(attachment.filename.as_str());
+                if ui.small_button("✖").clicked() {
+                    removed = Some(index);
+                }
+            }
+        });
+
+        if let Some(index) = removed {
+            draft.attachments.remove(index);
+        }
+    }
+
+    /// Adds the files dropped onto the window as attachments.
+    fn collect_dropped_files(ui: &mut Ui, draft: &mut Draft) {
+        let dropped_files = ui.input(|input| input.raw.dropped_files.clone());
+
+        for file in dropped_files {
+            let bytes = match (&file.bytes, &file.path) {
+                (Some(bytes), _) => bytes.to_vec(),
+                (None, Some(path)) => match fs::read(path) {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        warn!("Failed to read dropped file {}: {e}", path.display());
+                        continue;
+                    }
+                },
+                (None, None) => continue,
+            };
+
+            let filename = match &file.path {
+                Some(path) if file.name.is_empty() => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                _ => file.name.clone(),
+            };
+
+            // attachments may have been removed, ids must stay unique
+            let id = draft
+                .attachments
+                .iter()
+                .map(|attachment| attachment.id + 1)
+                .max()
+                .unwrap_or(0);
+            draft
+                .attachments
+                .push(Attachment::from_bytes(filename, bytes, id));
+        }
+    }
+
+    /// Starts editing the last message the current user sent in a channel.
+    fn edit_last_message(cache: &Cache, channel_id: Id<ChannelMarker>, draft: &mut Draft) {
+        let Some(current_user) = cache.current_user() else {
+            return;
+        };
+
+        let last_message = cache
+            .messages(channel_id)
+            .filter(|message| message.author.id == current_user.id)
+            .last();
+
+        if let Some(message) = last_message {
+            draft.editing = Some(message.id);
+            draft.reply = None;
+            draft.text = message.content.clone();
+        }
+    }
+
+    fn fetch_commands(&self, scope: CommandScope) {
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let index = http
+                .command_index(scope)
+                .await
+                .inspect_err(|e| warn!("Failed to fetch commands: {e}"))
+                .ok()
+                .map(Box::new);
+            notify(&tx, RenderMessage::Commands { scope, index });
+        });
+    }
+
+    fn trigger_typing(&mut self, channel_id: Id<ChannelMarker>) {
+        let now = Instant::now();
+        if self
+            .last_typing
+            .get(&channel_id)
+            .is_some_and(|last| now.duration_since(*last) < TYPING_INTERVAL)
+        {
+            return;
+        }
+        self.last_typing.insert(channel_id, now);
+
+        let http = self.http.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.trigger_typing(channel_id).await {
+                warn!("Failed to send typing notification: {e}");
+            }
+        });
+    }
+
+    fn submit(
+        &mut self,
+        cache: &mut Cache,
+        guild_id: Option<Id<GuildMarker>>,
+        channel_id: Id<ChannelMarker>,
+        draft: &mut Draft,
+        mention_everyone: bool,
+    ) {
+        let content = draft.text.trim().to_owned();
+        if content.is_empty() && draft.attachments.is_empty() {
+            return;
+        }
+
+        let http = self.http.clone();
+
+        if let Some(message_id) = draft.editing {
+            self.runtime.spawn(async move {
+                if let Err(e) = http.update_message(channel_id, message_id, &content).await {
+                    warn!("Failed to edit message: {e}");
+                }
+            });
+
+            draft.clear();
+            return;
+        }
+
+        let nonce = cache.insert_pending_message(channel_id, content.clone());
+        let message = CreateMessage {
+            allowed_mentions: Some(allowed_mentions(
+                draft.reply.as_ref().is_some_and(|reply| reply.mention),
+                mention_everyone,
+            )),
+            attachments: draft.attachments.drain(..).collect(),
+            content,
+            nonce: Some(nonce.clone()),
+            message_reference: reference(guild_id, channel_id, draft),
+            sticker_ids: Vec::new(),
+        };
+        draft.clear();
+        self.last_typing.remove(&channel_id);
+
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.create_message(channel_id, &message).await {
+                warn!("Failed to send message: {e}");
+                notify(&tx, RenderMessage::MessageFailed { channel_id, nonce });
+            }
+        });
+    }
+
+    /// Sends a sticker on its own, replying to the same message as the
+    /// draft and keeping its text.
+    fn send_sticker(
+        &mut self,
+        guild_id: Option<Id<GuildMarker>>,
+        channel_id: Id<ChannelMarker>,
+        draft: &mut Draft,
+        sticker_id: Id<StickerMarker>,
+    ) {
+        let message = CreateMessage {
+            allowed_mentions: Some(allowed_mentions(
+                draft.reply.as_ref().is_some_and(|reply| reply.mention),
+                false,
+            )),
+            attachments: Vec::new(),
+            content: String::new(),
+            nonce: None,
+            message_reference: reference(guild_id, channel_id, draft),
+            sticker_ids: vec![sticker_id],
+        };
+        draft.reply = None;
+        self.last_typing.remove(&channel_id);
+
+        let http = self.http.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.create_message(channel_id, &message).await {
+                warn!("Failed to send sticker: {e}");
+            }
+        });
+    }
+}
+
+/// Reference to the message the draft replies to.
+fn reference(
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    draft: &Draft,
+) -> Option<MessageReference> {
+    draft.reply.as_ref().map(|reply| MessageReference {
+        channel_id: Some(channel_id),
+        guild_id,
+        message_id: Some(reply.message_id),
+        fail_if_not_exists: Some(false),
+    })
+}
@@ -0,0 +1,312 @@
+use egui::{pos2, vec2, Color32, Frame, Margin, Rect, Response, RichText, Sense, Ui, Vec2};
+use twilight_model::channel::message::{
+    embed::{EmbedField, EmbedImage},
+    Embed,
+};
+
+use crate::{
+    images::ImageCache,
+    markdown::{self, MentionContext},
+    renderer::format_timestamp,
+};
+
+const MAX_EMBED_WIDTH: f32 = 520.;
+const THUMBNAIL_SIZE: Vec2 = Vec2::splat(80.);
+const DEFAULT_COLOR: Color32 = Color32::from_gray(80);
+const LINK_COLOR: Color32 = Color32::from_rgb(0, 168, 252);
+
+/// Renders the embeds of a message.
+///
+/// Consecutive embeds sharing the same URL form a gallery, they are shown as
+/// a single embed with the images of all of them.
+pub fn show_embeds(
+    ui: &mut Ui,
+    embeds: &[Embed],
+    images: &mut ImageCache,
+    mentions: &MentionContext<'_>,
+) {
+    for (embed, gallery) in gallery_groups(embeds) {
+        match embed.kind.as_str() {
+            // plain media links are shown without the embed around them
+            "image" if embed.title.is_none() && embed.description.is_none() => {
+                if let Some(thumbnail) = &embed.thumbnail {
+                    let url = thumbnail.proxy_url.as_ref().unwrap_or(&thumbnail.url);
+                    let size = dimensions(thumbnail.width, thumbnail.height);
+                    show_image(ui, images, url, size, vec2(400., 300.));
+                }
+            }
+            "gifv" => show_video_placeholder(ui, images, embed),
+            _ => show_embed(ui, embed, &gallery, images, mentions),
+        }
+    }
+}
+
+/// Groups consecutive embeds with the same URL, collecting their images.
+fn gallery_groups(embeds: &[Embed]) -> Vec<(&Embed, Vec<&EmbedImage>)> {
+    let mut groups: Vec<(&Embed, Vec<&EmbedImage>)> = Vec::new();
+
+    for embed in embeds {
+        match groups.last_mut() {
+            Some((first, gallery)) if embed.url.is_some() && first.url == embed.url => {
+                gallery.extend(&embed.image);
+            }
+            _ => groups.push((embed, embed.image.iter().collect())),
+        }
+    }
+
+    groups
+}
+
+fn show_embed(
+    ui: &mut Ui,
+    embed: &Embed,
+    gallery: &[&EmbedImage],
+    images: &mut ImageCache,
+    mentions: &MentionContext<'_>,
+) {
+    let color = embed.color.map_or(DEFAULT_COLOR, |color| {
+        let [_, r, g, b] = color.to_be_bytes();
+        Color32::from_rgb(r, g, b)
+    });
+
+    let response = Frame::none()
+        .fill(ui.visuals().faint_bg_color)
+        .rounding(4.)
+        .inner_margin(Margin {
+            left: 16.,
+            right: 12.,
+            top: 8.,
+            bottom: 12.,
+        })
+        .show(ui, |ui| {
+            ui.set_max_width(MAX_EMBED_WIDTH);
+
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| show_text(ui, embed, images, mentions));
+
+                // the thumbnail of a video is shown as its placeholder instead
+                if let Some(thumbnail) = embed.thumbnail.as_ref().filter(|_| embed.video.is_none())
+                {
+                    let url = thumbnail.proxy_url.as_ref().unwrap_or(&thumbnail.url);
+                    let size = dimensions(thumbnail.width, thumbnail.height);
+                    show_image(ui, images, url, size, THUMBNAIL_SIZE);
+                }
+            });
+
+            show_gallery(ui, gallery, images);
+
+            if embed.video.is_some() && embed.image.is_none() {
+                show_video_placeholder(ui, images, embed);
+            }
+
+            show_footer(ui, embed, images);
+        })
+        .response;
+
+    // colour bar on the left side
+    let rect = response.rect;
+    ui.painter().rect_filled(
+        Rect::from_min_max(rect.left_top(), pos2(rect.left() + 4., rect.bottom())),
+        2.,
+        color,
+    );
+}
+
+/// Renders the provider, author, title, description and fields.
+fn show_text(ui: &mut Ui, embed: &Embed, images: &mut ImageCache, mentions: &MentionContext<'_>) {
+    if let Some(name) = embed
+        .provider
+        .as_ref()
+        .and_then(|provider| provider.name.as_ref())
+    {
+        ui.label(RichText::new(name.as_str()).small().weak());
+    }
+
+    if let Some(author) = &embed.author {
+        ui.horizontal(|ui| {
+            if let Some(url) = author.proxy_icon_url.as_ref().or(author.icon_url.as_ref()) {
+                show_image(ui, images, url, None, vec2(20., 20.));
+            }
+
+            let name = RichText::new(author.name.as_str()).small().strong();
+            match &author.url {
+                Some(url) => ui.hyperlink_to(name, url),
+                None => ui.label(name),
+            };
+        });
+    }
+
+    if let Some(title) = &embed.title {
+        let text = RichText::new(title.as_str()).strong();
+        match &embed.url {
+            Some(url) => ui.hyperlink_to(text.color(LINK_COLOR), url),
+            None => ui.label(text),
+        };
+    }
+
+    if let Some(description) = &embed.description {
+        markdown::show(ui, description, images, mentions, None);
+    }
+
+    show_fields(ui, embed, images, mentions);
+}
+
+/// Lays out fields in rows, with up to three inline fields next to each
+/// other and non-inline fields on their own row.
+fn show_fields(ui: &mut Ui, embed: &Embed, images: &mut ImageCache, mentions: &MentionContext<'_>) {
+    let mut rows: Vec<Vec<&EmbedField>> = Vec::new();
+
+    for field in &embed.fields {
+        match rows.last_mut() {
+            Some(row) if field.inline && row.len() < 3 && row.iter().all(|f| f.inline) => {
+                row.push(field);
+            }
+            _ => rows.push(vec![field]),
+        }
+    }
+
+    for row in rows {
+        ui.add_space(4.);
+        ui.columns(row.len(), |columns| {
+            for (ui, field) in columns.iter_mut().zip(row) {
+                ui.label(RichText::new(field.name.as_str()).strong());
+                markdown::show(ui, &field.value, images, mentions, None);
+            }
+        });
+    }
+}
+
+/// Renders the image of an embed, or a grid of the images of a gallery.
+fn show_gallery(ui: &mut Ui, gallery: &[&EmbedImage], images: &mut ImageCache) {
+    match gallery {
+        [] => (),
+        [image] => {
+            ui.add_space(8.);
+            let url = image.proxy_url.as_ref().unwrap_or(&image.url);
+            let size = dimensions(image.width, image.height);
+            show_image(ui, images, url, size, vec2(MAX_EMBED_WIDTH - 28., 300.));
+        }
+        gallery => {
+            ui.add_space(8.);
+            let cell = vec2((MAX_EMBED_WIDTH - 32.) / 2., 150.);
+
+            for row in gallery.chunks(2) {
+                ui.horizontal(|ui| {
+                    for image in row {
+                        let url = image.proxy_url.as_ref().unwrap_or(&image.url);
+                        show_image(ui, images, url, Some(cell), cell);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Videos can't be played, their thumbnail links to the video instead.
+fn show_video_placeholder(ui: &mut Ui, images: &mut ImageCache, embed: &Embed) {
+    let Some(video_url) = embed
+        .video
+        .as_ref()
+        .and_then(|video| video.url.as_ref())
+        .or(embed.url.as_ref())
+    else {
+        return;
+    };
+
+    ui.add_space(8.);
+    let response = match &embed.thumbnail {
+        Some(thumbnail) => {
+            let url = thumbnail.proxy_url.as_ref().unwrap_or(&thumbnail.url);
+            let size = dimensions(thumbnail.width, thumbnail.height);
+            show_image(ui, images, url, size, vec2(400., 225.))
+        }
+        None => ui.allocate_response(vec2(400., 225.), Sense::click()),
+    };
+
+    let rect = response.rect;
+    ui.painter()
+        .circle_filled(rect.center(), 24., Color32::from_black_alpha(180));
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        "▶",
+        egui::FontId::proportional(24.),
+        Color32::WHITE,
+    );
+
+    let response = ui
+        .interact(rect, response.id.with("play"), Sense::click())
+        .on_hover_text(video_url.as_str());
+    if response.clicked() {
+        ui.ctx().output_mut(|output| {
+            output.open_url = Some(egui::output::OpenUrl::new_tab(video_url));
+        });
+    }
+}
+
+fn show_footer(ui: &mut Ui, embed: &Embed, images: &mut ImageCache) {
+    let text = match (&embed.footer, embed.timestamp) {
+        (None, None) => return,
+        (Some(footer), None) => footer.text.clone(),
+        (None, Some(timestamp)) => format_timestamp(timestamp),
+        (Some(footer), Some(timestamp)) => {
+            format!("{} • {}", footer.text, format_timestamp(timestamp))
+        }
+    };
+
+    ui.add_space(8.);
+    ui.horizontal(|ui| {
+        let icon_url = embed
+            .footer
+            .as_ref()
+            .and_then(|footer| footer.proxy_icon_url.as_ref().or(footer.icon_url.as_ref()));
+        if let Some(url) = icon_url {
+            show_image(ui, images, url, None, vec2(20., 20.));
+        }
+
+        ui.label(RichText::new(text).small());
+    });
+}
+
+/// Renders an image scaled down to fit in the given size, keeping its aspect
+/// ratio.
+///
+/// The size of the image, if known up front, is used to reserve space while
+/// it is loading.
+fn show_image(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    url: &str,
+    size: Option<Vec2>,
+    max_size: Vec2,
+) -> Response {
+    match images.get(url) {
+        Some(texture) => {
+            let size = scale_to_fit(texture.size_vec2(), max_size);
+            ui.image(texture.id(), size)
+        }
+        None => {
+            let size = size.map_or(max_size, |size| scale_to_fit(size, max_size));
+            let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+            ui.painter()
+                .rect_filled(rect, 4., ui.visuals().extreme_bg_color);
+
+            response
+        }
+    }
+}
+
+pub(crate) fn dimensions(width: Option<u64>, height: Option<u64>) -> Option<Vec2> {
+    Some(vec2(width? as f32, height? as f32))
+}
+
+/// Scales a size down to fit in a bounding box, never scaling it up.
+pub(crate) fn scale_to_fit(size: Vec2, max_size: Vec2) -> Vec2 {
+    let scale = (max_size.x / size.x).min(max_size.y / size.y).min(1.);
+
+    if scale.is_finite() {
+        size * scale
+    } else {
+        max_size
+    }
+}
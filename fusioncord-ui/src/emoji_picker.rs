@@ -0,0 +1,447 @@
+use egui::{
+    vec2, Align, Align2, Button, Color32, ComboBox, Context, FontId, ImageButton, RichText,
+    ScrollArea, TextEdit, Ui, Window,
+};
+use fusioncord_core::{
+    cache::Cache,
+    cdn,
+    completion::fuzzy_score,
+    emoji::{self, PickedEmoji, SkinTone, UnicodeEmoji, CATEGORIES},
+    presence::now_millis,
+    role::RoleStore,
+    user_settings::UserSettingsStore,
+};
+use twilight_model::{
+    guild::{Emoji, Guild},
+    id::{
+        marker::{GuildMarker, MessageMarker},
+        Id,
+    },
+    user::PremiumType,
+};
+
+use crate::{images::ImageCache, renderer::initials};
+
+const EMOJI_SIZE: f32 = 28.;
+const SCROLL_HEIGHT: f32 = 320.;
+/// Frequently used emojis shown above the others.
+const FREQUENTLY_USED_LIMIT: usize = 16;
+const FREQUENTLY_USED: &str = "Frequently Used";
+const UNUSABLE_TINT: Color32 = Color32::from_gray(80);
+
+/// Stores read by the emoji picker.
+pub struct PickerStores<'a> {
+    pub cache: &'a Cache,
+    pub roles: &'a RoleStore,
+    pub user_settings: &'a UserSettingsStore,
+}
+
+/// Picks a Unicode or custom emoji, either browsing them by category or
+/// searching them by name.
+#[derive(Default)]
+pub struct EmojiPicker {
+    query: String,
+    /// Section that was picked from the categories, scrolled to once shown.
+    jump_to: Option<String>,
+}
+
+/// A custom emoji along with whether the current user can use it.
+struct CustomEmoji<'a> {
+    emoji: &'a Emoji,
+    usable: bool,
+}
+
+impl EmojiPicker {
+    /// Shows the picker, returning the emoji that was picked.
+    ///
+    /// `reaction` is whether the emoji is reacted with, which have their own
+    /// frequently used emojis.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        stores: &PickerStores<'_>,
+        guild_id: Option<Id<GuildMarker>>,
+        reaction: bool,
+        skin_tone: &mut SkinTone,
+        images: &mut ImageCache,
+    ) -> Option<PickedEmoji> {
+        let guilds = custom_emojis(stores, guild_id);
+        let mut picked = None;
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.query)
+                    .hint_text("Find the perfect emoji")
+                    .desired_width(240.),
+            );
+            ComboBox::from_id_source("skin_tone")
+                .width(40.)
+                .selected_text(skin_tone_sample(*skin_tone))
+                .show_ui(ui, |ui| {
+                    for tone in SkinTone::ALL {
+                        ui.selectable_value(skin_tone, tone, skin_tone_sample(tone));
+                    }
+                });
+        });
+
+        if self.query.is_empty() {
+            self.show_sections(ui, &guilds, images);
+        }
+
+        ScrollArea::vertical()
+            .max_height(SCROLL_HEIGHT)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                picked = if self.query.is_empty() {
+                    let frequent = frequently_used(stores, &guilds, reaction);
+                    self.show_all(ui, &frequent, &guilds, *skin_tone, images)
+                } else {
+                    show_results(ui, &self.query, &guilds, *skin_tone, images)
+                };
+            });
+
+        if picked.is_some() {
+            self.query.clear();
+        }
+        picked
+    }
+
+    /// Shows a button per section to scroll to it.
+    fn show_sections(
+        &mut self,
+        ui: &mut Ui,
+        guilds: &[(&Guild, Vec<CustomEmoji<'_>>)],
+        images: &mut ImageCache,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .small_button("🕘")
+                .on_hover_text(FREQUENTLY_USED)
+                .clicked()
+            {
+                self.jump_to = Some(FREQUENTLY_USED.to_owned());
+            }
+            for (guild, _) in guilds {
+                let button = match guild
+                    .icon
+                    .and_then(|icon| images.get(&cdn::guild_icon(guild.id, icon, 32)))
+                {
+                    Some(texture) => ui.add(ImageButton::new(texture.id(), vec2(16., 16.))),
+                    None => ui.small_button(initials(&guild.name)),
+                };
+                if button.on_hover_text(guild.name.as_str()).clicked() {
+                    self.jump_to = Some(guild.name.clone());
+                }
+            }
+            for category in CATEGORIES {
+                let icon = category.emojis[0].emoji;
+                if ui.small_button(icon).on_hover_text(category.name).clicked() {
+                    self.jump_to = Some(category.name.to_owned());
+                }
+            }
+        });
+    }
+
+    fn show_all(
+        &mut self,
+        ui: &mut Ui,
+        frequent: &[Frequent<'_>],
+        guilds: &[(&Guild, Vec<CustomEmoji<'_>>)],
+        skin_tone: SkinTone,
+        images: &mut ImageCache,
+    ) -> Option<PickedEmoji> {
+        let mut picked = None;
+
+        if !frequent.is_empty() {
+            self.show_heading(ui, FREQUENTLY_USED);
+            ui.horizontal_wrapped(|ui| {
+                for emoji in frequent {
+                    let clicked = match emoji {
+                        Frequent::Unicode(emoji) => show_unicode(ui, emoji, skin_tone),
+                        Frequent::Custom(custom) => show_custom(ui, custom, images),
+                    };
+                    if clicked.is_some() {
+                        picked = clicked;
+                    }
+                }
+            });
+        }
+
+        for (guild, emojis) in guilds {
+            self.show_heading(ui, &guild.name);
+            ui.horizontal_wrapped(|ui| {
+                for custom in emojis {
+                    if let Some(emoji) = show_custom(ui, custom, images) {
+                        picked = Some(emoji);
+                    }
+                }
+            });
+        }
+
+        for category in CATEGORIES {
+            self.show_heading(ui, category.name);
+            ui.horizontal_wrapped(|ui| {
+                for emoji in category.emojis {
+                    if let Some(emoji) = show_unicode(ui, emoji, skin_tone) {
+                        picked = Some(emoji);
+                    }
+                }
+            });
+        }
+
+        picked
+    }
+
+    fn show_heading(&mut self, ui: &mut Ui, name: &str) {
+        let response = ui.label(RichText::new(name.to_uppercase()).small().strong());
+        if self.jump_to.as_deref() == Some(name) {
+            response.scroll_to_me(Some(Align::TOP));
+            self.jump_to = None;
+        }
+    }
+}
+
+/// Window to pick the emoji a message is reacted with.
+#[derive(Default)]
+pub struct ReactionPicker {
+    picker: EmojiPicker,
+    message_id: Option<Id<MessageMarker>>,
+}
+
+impl ReactionPicker {
+    pub fn open(&mut self, message_id: Id<MessageMarker>) {
+        self.message_id = Some(message_id);
+    }
+
+    /// Shows the window if it's open, returning the message to react to
+    /// along with the picked emoji.
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        stores: &PickerStores<'_>,
+        guild_id: Option<Id<GuildMarker>>,
+        skin_tone: &mut SkinTone,
+        images: &mut ImageCache,
+    ) -> Option<(Id<MessageMarker>, PickedEmoji)> {
+        let message_id = self.message_id?;
+        let mut open = true;
+        let mut picked = None;
+
+        Window::new("Add Reaction")
+            .id(egui::Id::new("reaction_picker"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                picked = self
+                    .picker
+                    .show(ui, stores, guild_id, true, skin_tone, images);
+            });
+
+        if !open || picked.is_some() {
+            self.message_id = None;
+        }
+        picked.map(|emoji| (message_id, emoji))
+    }
+}
+
+/// A frequently used emoji.
+enum Frequent<'a> {
+    Unicode(&'static UnicodeEmoji),
+    Custom(&'a CustomEmoji<'a>),
+}
+
+/// Guilds with custom emojis, the current one first, along with their
+/// emojis.
+fn custom_emojis<'a>(
+    stores: &PickerStores<'a>,
+    guild_id: Option<Id<GuildMarker>>,
+) -> Vec<(&'a Guild, Vec<CustomEmoji<'a>>)> {
+    let Some(current_user) = stores.cache.current_user() else {
+        return Vec::new();
+    };
+    let premium = current_user
+        .premium_type
+        .is_some_and(|premium_type| premium_type != PremiumType::None);
+
+    let mut guilds = stores
+        .cache
+        .guilds()
+        .filter(|guild| !guild.emojis.is_empty())
+        .collect::<Vec<_>>();
+    // the sort is stable, the other guilds keep their order
+    guilds.sort_by_key(|guild| Some(guild.id) != guild_id);
+
+    guilds
+        .into_iter()
+        .map(|guild| {
+            let member_roles = stores
+                .roles
+                .member_roles(guild.id, current_user.id)
+                .unwrap_or_default();
+            let in_own_guild = Some(guild.id) == guild_id;
+            let emojis = guild
+                .emojis
+                .iter()
+                .map(|emoji| CustomEmoji {
+                    emoji,
+                    usable: emoji::usable(emoji, in_own_guild, premium, member_roles),
+                })
+                .collect();
+            (guild, emojis)
+        })
+        .collect()
+}
+
+/// The emojis the current user used the most and the most recently.
+fn frequently_used<'a>(
+    stores: &PickerStores<'_>,
+    guilds: &'a [(&Guild, Vec<CustomEmoji<'a>>)],
+    reaction: bool,
+) -> Vec<Frequent<'a>> {
+    let settings = stores.user_settings.frecency();
+    let frecency = if reaction {
+        &settings.emoji_reaction_frecency
+    } else {
+        &settings.emoji_frecency
+    };
+    let Some(frecency) = frecency else {
+        return Vec::new();
+    };
+
+    let now = now_millis();
+    let custom = guilds.iter().flat_map(|(_, emojis)| emojis);
+
+    frecency
+        .ranked(now)
+        .into_iter()
+        // custom emojis are keyed by their id, Unicode ones by their name
+        .filter_map(|key| match key.parse::<u64>() {
+            Ok(id) => custom
+                .clone()
+                .find(|custom| custom.emoji.id.get() == id && custom.usable)
+                .map(Frequent::Custom),
+            Err(_) => emoji::by_name(key).map(Frequent::Unicode),
+        })
+        .take(FREQUENTLY_USED_LIMIT)
+        .collect()
+}
+
+/// Shows the emojis matching the search, the best matches first.
+fn show_results(
+    ui: &mut Ui,
+    query: &str,
+    guilds: &[(&Guild, Vec<CustomEmoji<'_>>)],
+    skin_tone: SkinTone,
+    images: &mut ImageCache,
+) -> Option<PickedEmoji> {
+    let query = query.trim_matches(':');
+    let custom = guilds
+        .iter()
+        .flat_map(|(_, emojis)| emojis)
+        .filter_map(|custom| {
+            let score = fuzzy_score(&custom.emoji.name, query)?;
+            Some((score, Frequent::Custom(custom)))
+        });
+    let unicode = emoji::unicode_emojis().filter_map(|emoji| {
+        let score = emoji
+            .names
+            .iter()
+            .filter_map(|name| fuzzy_score(name, query))
+            .max()?;
+        Some((score, Frequent::Unicode(emoji)))
+    });
+
+    let mut results = custom.chain(unicode).collect::<Vec<_>>();
+    if results.is_empty() {
+        ui.weak("No emojis match your search.");
+        return None;
+    }
+    // the sort is stable, custom emojis come first among equal matches
+    results.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let mut picked = None;
+    ui.horizontal_wrapped(|ui| {
+        for (_, result) in &results {
+            let clicked = match result {
+                Frequent::Unicode(emoji) => show_unicode(ui, emoji, skin_tone),
+                Frequent::Custom(custom) => show_custom(ui, custom, images),
+            };
+            if clicked.is_some() {
+                picked = clicked;
+            }
+        }
+    });
+
+    picked
+}
+
+fn show_unicode(ui: &mut Ui, emoji: &UnicodeEmoji, skin_tone: SkinTone) -> Option<PickedEmoji> {
+    let toned = emoji.with_skin_tone(skin_tone);
+    let shortcodes = emoji
+        .names
+        .iter()
+        .map(|name| format!(":{name}:"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    ui.add(Button::new(RichText::new(&toned).size(EMOJI_SIZE - 8.)).frame(false))
+        .on_hover_text(shortcodes)
+        .clicked()
+        .then(|| PickedEmoji::Unicode {
+            emoji: toned,
+            name: emoji.name(),
+        })
+}
+
+/// Shows a custom emoji, greyed out when it can't be used.
+fn show_custom(
+    ui: &mut Ui,
+    custom: &CustomEmoji<'_>,
+    images: &mut ImageCache,
+) -> Option<PickedEmoji> {
+    let CustomEmoji { emoji, usable } = custom;
+    let response = match images.get(&cdn::emoji(emoji.id, emoji.animated, 48)) {
+        Some(texture) => {
+            let mut button =
+                ImageButton::new(texture.id(), vec2(EMOJI_SIZE, EMOJI_SIZE)).frame(false);
+            if !usable {
+                button = button.tint(UNUSABLE_TINT);
+            }
+            ui.add_enabled(*usable, button)
+        }
+        None => ui.add_enabled(
+            *usable,
+            Button::new(format!(":{}:", emoji.name)).frame(false),
+        ),
+    };
+
+    // animated emojis are marked with a badge
+    if emoji.animated {
+        ui.painter().text(
+            response.rect.right_bottom(),
+            Align2::RIGHT_BOTTOM,
+            "GIF",
+            FontId::proportional(8.),
+            ui.visuals().strong_text_color(),
+        );
+    }
+
+    let reason = if !emoji.available {
+        "This emoji is unavailable"
+    } else {
+        "You can't use this emoji here"
+    };
+    response
+        .on_hover_text(format!(":{}:", emoji.name))
+        .on_disabled_hover_text(format!(":{}: {reason}", emoji.name))
+        .clicked()
+        .then(|| PickedEmoji::custom(emoji))
+}
+
+/// Emoji shown in the skin tone selector for a skin tone.
+fn skin_tone_sample(skin_tone: SkinTone) -> String {
+    emoji::by_name("raised_hands")
+        .map(|emoji| emoji.with_skin_tone(skin_tone))
+        .unwrap_or_default()
+}
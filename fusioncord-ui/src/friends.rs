@@ -0,0 +1,336 @@
+use std::{future::Future, sync::mpsc::Sender};
+
+use egui::{vec2, Align, Button, Color32, Key, Layout, RichText, ScrollArea, Sense, TextEdit, Ui};
+use fusioncord_core::{
+    http::{HttpClient, HttpError},
+    message::{notify, RenderMessage},
+    relationship::{display_name, RelationshipStore},
+    user_presence::UserPresenceStore,
+};
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    gateway::presence::{Activity, ActivityType, Status},
+    user::{Relationship, RelationshipType},
+};
+
+use crate::{
+    images::ImageCache,
+    presence::paint_status,
+    renderer::{avatar_url, full_uv},
+};
+
+const AVATAR_SIZE: f32 = 32.;
+const ONLINE_COLOR: Color32 = Color32::from_rgb(35, 165, 90);
+const IDLE_COLOR: Color32 = Color32::from_rgb(240, 178, 50);
+const DND_COLOR: Color32 = Color32::from_rgb(242, 63, 67);
+const OFFLINE_COLOR: Color32 = Color32::from_rgb(128, 132, 142);
+
+/// Filter of the friends tab.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FriendsTab {
+    #[default]
+    Online,
+    All,
+    Pending,
+    Blocked,
+}
+
+/// Friends tab listing the relationships of the current user, with a form to
+/// send friend requests.
+pub struct FriendsView {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+    /// Username to send a friend request to.
+    username: String,
+    error: Option<String>,
+}
+
+impl FriendsView {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self {
+            http,
+            runtime,
+            tx,
+            username: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        relationships: &RelationshipStore,
+        presences: &UserPresenceStore,
+        images: &mut ImageCache,
+        tab: &mut FriendsTab,
+    ) {
+        ui.horizontal(|ui| {
+            ui.heading("👥 Friends");
+            ui.separator();
+
+            let pending = relationships.incoming_request_count();
+            for (value, label) in [
+                (FriendsTab::Online, "Online".to_owned()),
+                (FriendsTab::All, "All".to_owned()),
+                (FriendsTab::Pending, pending_label(pending)),
+                (FriendsTab::Blocked, "Blocked".to_owned()),
+            ] {
+                ui.selectable_value(tab, value, label);
+            }
+        });
+        ui.separator();
+
+        self.show_add_friend(ui);
+        ui.separator();
+
+        let kinds: &[_] = match tab {
+            FriendsTab::Online | FriendsTab::All => &[RelationshipType::Friend],
+            FriendsTab::Pending => &[
+                RelationshipType::PendingIncoming,
+                RelationshipType::PendingOutgoing,
+            ],
+            FriendsTab::Blocked => &[RelationshipType::Blocked],
+        };
+        let shown = relationships
+            .relationships(kinds)
+            .into_iter()
+            .filter(|relationship| {
+                *tab != FriendsTab::Online
+                    || !matches!(
+                        presences.status(None, relationship.id),
+                        Status::Offline | Status::Invisible
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        ui.label(
+            RichText::new(format!("{} — {}", tab_name(*tab), shown.len()))
+                .small()
+                .strong(),
+        );
+
+        ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                if shown.is_empty() {
+                    ui.weak(match tab {
+                        FriendsTab::Online => "No one is around",
+                        FriendsTab::All => "You don't have any friends yet",
+                        FriendsTab::Pending => "There are no pending friend requests",
+                        FriendsTab::Blocked => "You haven't blocked anyone",
+                    });
+                }
+
+                for relationship in shown {
+                    self.show_relationship(ui, presences, images, relationship);
+                    ui.separator();
+                }
+            });
+    }
+
+    /// Marks a friend request as failed to send.
+    pub fn fail_friend_request(&mut self, username: &str) {
+        self.error = Some(format!(
+            "Failed to send a friend request to {username}, double check the username"
+        ));
+    }
+
+    fn show_add_friend(&mut self, ui: &mut Ui) {
+        ui.label(RichText::new("ADD FRIEND").small().strong());
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                TextEdit::singleline(&mut self.username)
+                    .hint_text("Enter a username")
+                    .desired_width(300.),
+            );
+            let entered = response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+            let valid = !self.username.trim().is_empty();
+            let clicked = ui
+                .add_enabled(valid, Button::new("Send Friend Request"))
+                .clicked();
+
+            if valid && (entered || clicked) {
+                self.send_friend_request();
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(DND_COLOR, error.as_str());
+        }
+    }
+
+    fn send_friend_request(&mut self) {
+        let username = self.username.trim().to_owned();
+        self.username.clear();
+        self.error = None;
+
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.send_friend_request(&username).await {
+                warn!("Failed to send friend request: {e}");
+                notify(&tx, RenderMessage::FriendRequestFailed { username });
+            }
+        });
+    }
+
+    fn show_relationship(
+        &self,
+        ui: &mut Ui,
+        presences: &UserPresenceStore,
+        images: &mut ImageCache,
+        relationship: &Relationship,
+    ) {
+        let user = &relationship.user;
+        let presence = presences.presence(None, relationship.id);
+        let status = presences.status(None, relationship.id);
+
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(vec2(AVATAR_SIZE, AVATAR_SIZE), Sense::hover());
+            let url = avatar_url(user.id, user.avatar, user.discriminator);
+            if let Some(texture) = images.get(&url) {
+                ui.painter()
+                    .image(texture.id(), rect, full_uv(), Color32::WHITE);
+            }
+            if relationship.kind == RelationshipType::Friend {
+                let client_status = presence.map(|presence| &presence.client_status);
+                paint_status(
+                    ui,
+                    rect.right_bottom() - vec2(4., 4.),
+                    status,
+                    client_status,
+                );
+            }
+
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(display_name(relationship)).strong());
+                    ui.weak(user.name.as_str());
+                });
+
+                let subtitle = match relationship.kind {
+                    RelationshipType::PendingIncoming => "Incoming Friend Request".to_owned(),
+                    RelationshipType::PendingOutgoing => "Outgoing Friend Request".to_owned(),
+                    RelationshipType::Blocked => "Blocked".to_owned(),
+                    _ => presence
+                        .and_then(|presence| presence.activities.iter().find_map(activity_text))
+                        .unwrap_or_else(|| status_name(status).to_owned()),
+                };
+                ui.label(RichText::new(subtitle).small().weak());
+            });
+
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                self.show_actions(ui, relationship);
+            });
+        });
+    }
+
+    fn show_actions(&self, ui: &mut Ui, relationship: &Relationship) {
+        let user_id = relationship.id;
+
+        match relationship.kind {
+            RelationshipType::Friend => {
+                if ui.button("Block").clicked() {
+                    self.spawn("block user", move |http| async move {
+                        http.block_user(user_id).await
+                    });
+                }
+                if ui.button("Remove Friend").clicked() {
+                    self.spawn("remove friend", move |http| async move {
+                        http.remove_relationship(user_id).await
+                    });
+                }
+            }
+            RelationshipType::PendingIncoming => {
+                if ui.button("Decline").clicked() {
+                    self.spawn("decline friend request", move |http| async move {
+                        http.remove_relationship(user_id).await
+                    });
+                }
+                if ui.button("Accept").clicked() {
+                    self.spawn("accept friend request", move |http| async move {
+                        http.accept_friend_request(user_id).await
+                    });
+                }
+            }
+            RelationshipType::PendingOutgoing if ui.button("Cancel").clicked() => {
+                self.spawn("cancel friend request", move |http| async move {
+                    http.remove_relationship(user_id).await
+                });
+            }
+            RelationshipType::Blocked if ui.button("Unblock").clicked() => {
+                self.spawn("unblock user", move |http| async move {
+                    http.remove_relationship(user_id).await
+                });
+            }
+            _ => (),
+        }
+    }
+
+    /// Sends a relationship request, the relationship is updated once the
+    /// gateway confirms the change.
+    fn spawn<F, Fut>(&self, action: &'static str, request: F)
+    where
+        F: FnOnce(HttpClient) -> Fut,
+        Fut: Future<Output = Result<(), HttpError>> + Send + 'static,
+    {
+        let request = request(self.http.clone());
+        self.runtime.spawn(async move {
+            if let Err(e) = request.await {
+                warn!("Failed to {action}: {e}");
+            }
+        });
+    }
+}
+
+pub(crate) const fn status_color(status: Status) -> Color32 {
+    match status {
+        Status::Online => ONLINE_COLOR,
+        Status::Idle => IDLE_COLOR,
+        Status::DoNotDisturb => DND_COLOR,
+        Status::Invisible | Status::Offline => OFFLINE_COLOR,
+    }
+}
+
+/// Short description of an activity, such as `Playing <game>`.
+pub(crate) fn activity_text(activity: &Activity) -> Option<String> {
+    match activity.kind {
+        ActivityType::Custom => activity.state.clone(),
+        ActivityType::Playing => Some(format!("Playing {}", activity.name)),
+        ActivityType::Listening => Some(format!("Listening to {}", activity.name)),
+        ActivityType::Watching => Some(format!("Watching {}", activity.name)),
+        ActivityType::Streaming => Some(format!("Streaming {}", activity.name)),
+        _ => None,
+    }
+}
+
+pub(crate) const fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Online => "Online",
+        Status::Idle => "Idle",
+        Status::DoNotDisturb => "Do Not Disturb",
+        // only the current user sees themselves as invisible
+        Status::Invisible => "Invisible",
+        Status::Offline => "Offline",
+    }
+}
+
+const fn tab_name(tab: FriendsTab) -> &'static str {
+    match tab {
+        FriendsTab::Online => "ONLINE",
+        FriendsTab::All => "ALL FRIENDS",
+        FriendsTab::Pending => "PENDING",
+        FriendsTab::Blocked => "BLOCKED",
+    }
+}
+
+fn pending_label(incoming: usize) -> String {
+    if incoming == 0 {
+        "Pending".to_owned()
+    } else {
+        format!("Pending ({incoming})")
+    }
+}
@@ -0,0 +1,413 @@
+use egui::{
+    pos2, vec2, Align2, Button, Color32, CursorIcon, FontId, Pos2, Rect, Response, Rounding, Sense,
+    Stroke, TextEdit, Ui, Window,
+};
+use fusioncord_core::{
+    cache::Cache,
+    cdn,
+    guild_folder::{self, DropTarget, GuildListKey},
+    read_state::{GuildReadState, ReadStateStore},
+    user_settings::{GuildFolder, UserSettingsStore},
+};
+use twilight_model::guild::Guild;
+
+use crate::{
+    images::ImageCache,
+    renderer::{full_uv, initials, paint_mention_badge},
+    state::UiState,
+};
+
+pub const CIRCLE_RADIUS: f32 = 23.;
+pub const CIRCLE_DIAMETER: f32 = CIRCLE_RADIUS * 2.;
+pub const CIRCLE_MARGIN: f32 = 10.;
+const BLURPLE: Color32 = Color32::from_rgb(88, 101, 242);
+/// Share of the height of an entry at its top and bottom edge that drops
+/// before or after it instead of into it.
+const DROP_EDGE: f32 = 0.25;
+
+/// Change the user made to the guild list, which must be written back to the
+/// settings of the current user.
+pub enum GuildListAction {
+    Move {
+        dragged: GuildListKey,
+        target: DropTarget,
+    },
+    UpdateFolder {
+        folder_id: i64,
+        name: Option<String>,
+        color: Option<u64>,
+    },
+    /// Replaces a folder with its guilds.
+    RemoveFolder(i64),
+}
+
+/// Folder whose name and color are being edited.
+struct FolderEdit {
+    folder_id: i64,
+    name: String,
+    color: [u8; 3],
+}
+
+/// Guilds of the server list, grouped in the folders of the current user and
+/// reorderable by dragging them.
+#[derive(Default)]
+pub struct GuildList {
+    dragging: Option<GuildListKey>,
+    /// Where the dragged entry would be dropped in the last frame.
+    target: Option<DropTarget>,
+    folder_edit: Option<FolderEdit>,
+}
+
+impl GuildList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        cache: &Cache,
+        read_states: &ReadStateStore,
+        user_settings: &UserSettingsStore,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) -> Option<GuildListAction> {
+        let settings = user_settings.preloaded().guild_folders.as_ref();
+        let mut action = None;
+        self.target = None;
+
+        for folder in guild_folder::guild_folders(cache, settings) {
+            match folder.id {
+                Some(folder_id) => {
+                    action = action.or(self.show_folder(
+                        ui,
+                        cache,
+                        read_states,
+                        images,
+                        state,
+                        &folder,
+                        folder_id,
+                    ));
+                }
+                None => {
+                    for guild in folder.guild_ids.iter().filter_map(|&id| cache.guild(id)) {
+                        self.show_guild(ui, read_states, images, state, guild);
+                    }
+                }
+            }
+        }
+
+        if self.dragging.is_some() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+            if ui.input(|input| input.pointer.any_released()) {
+                let dragged = self.dragging.take();
+                if let (Some(dragged), Some(target)) = (dragged, self.target.take()) {
+                    action = Some(GuildListAction::Move { dragged, target });
+                }
+            }
+        }
+
+        action.or_else(|| self.show_folder_settings(ui))
+    }
+
+    fn show_guild(
+        &mut self,
+        ui: &mut Ui,
+        read_states: &ReadStateStore,
+        images: &mut ImageCache,
+        state: &mut UiState,
+        guild: &Guild,
+    ) {
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(CIRCLE_DIAMETER, CIRCLE_DIAMETER),
+            Sense::click_and_drag(),
+        );
+        let selected = state.selected_guild == Some(guild.id);
+        let read_state = read_states.guild(guild.id);
+
+        paint_guild(ui, images, guild, rect, selected || response.hovered());
+        paint_pill(ui, rect, selected, read_state.unread);
+        if read_state.mention_count > 0 {
+            paint_mention_badge(ui, rect, read_state.mention_count);
+        }
+
+        let key = GuildListKey::Guild(guild.id);
+        self.handle_drag(ui, &response, rect, key);
+
+        if response.clicked() {
+            state.selected_guild = Some(guild.id);
+        }
+        response.on_hover_text(guild.name.as_str());
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn show_folder(
+        &mut self,
+        ui: &mut Ui,
+        cache: &Cache,
+        read_states: &ReadStateStore,
+        images: &mut ImageCache,
+        state: &mut UiState,
+        folder: &GuildFolder,
+        folder_id: i64,
+    ) -> Option<GuildListAction> {
+        let guilds = folder
+            .guild_ids
+            .iter()
+            .filter_map(|&id| cache.guild(id))
+            .collect::<Vec<_>>();
+        let expanded = state.expanded_folders.contains(&folder_id);
+        let color = folder_color(folder);
+        let mut action = None;
+
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(CIRCLE_DIAMETER, CIRCLE_DIAMETER),
+            Sense::click_and_drag(),
+        );
+        let painter = ui.painter();
+        painter.rect_filled(rect, Rounding::same(12.), color.linear_multiply(0.4));
+
+        if expanded {
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                "📁",
+                FontId::proportional(20.),
+                color,
+            );
+        } else {
+            // the first four guilds are shown as a grid of small icons
+            let size = CIRCLE_DIAMETER / 2. - 4.;
+            for (index, guild) in guilds.iter().take(4).enumerate() {
+                let min = rect.min
+                    + vec2(3., 3.)
+                    + vec2((index % 2) as f32, (index / 2) as f32) * (size + 2.);
+                paint_guild(
+                    ui,
+                    images,
+                    guild,
+                    Rect::from_min_size(min, vec2(size, size)),
+                    false,
+                );
+            }
+
+            let read_state = folder_read_state(read_states, &guilds);
+            let selected = guilds
+                .iter()
+                .any(|guild| state.selected_guild == Some(guild.id));
+            paint_pill(ui, rect, selected, read_state.unread);
+            if read_state.mention_count > 0 {
+                paint_mention_badge(ui, rect, read_state.mention_count);
+            }
+        }
+
+        self.handle_drag(ui, &response, rect, GuildListKey::Folder(folder_id));
+
+        if response.clicked() && !state.expanded_folders.remove(&folder_id) {
+            state.expanded_folders.insert(folder_id);
+        }
+        let name = folder.name.as_deref().filter(|name| !name.is_empty());
+        response
+            .context_menu(|ui| {
+                if ui.button("Folder Settings").clicked() {
+                    let [r, g, b, _] = color.to_array();
+                    self.folder_edit = Some(FolderEdit {
+                        folder_id,
+                        name: name.unwrap_or_default().to_owned(),
+                        color: [r, g, b],
+                    });
+                    ui.close_menu();
+                }
+                if ui.button("Remove Folder").clicked() {
+                    action = Some(GuildListAction::RemoveFolder(folder_id));
+                    ui.close_menu();
+                }
+            })
+            .on_hover_text(name.map_or_else(|| folder_name(&guilds), ToOwned::to_owned));
+
+        if expanded {
+            let top = ui.cursor().top();
+            for guild in &guilds {
+                self.show_guild(ui, read_states, images, state, guild);
+            }
+
+            // the guilds of an open folder are shown on the color of the folder
+            let background = Rect::from_min_max(
+                pos2(rect.left() - 4., rect.top() - 4.),
+                pos2(rect.right() + 4., ui.cursor().top() - CIRCLE_MARGIN + 4.),
+            );
+            if background.bottom() > top {
+                ui.painter().rect_stroke(
+                    background,
+                    Rounding::same(CIRCLE_RADIUS),
+                    Stroke::new(1., color.linear_multiply(0.6)),
+                );
+            }
+        }
+
+        action
+    }
+
+    /// Starts dragging an entry and shows where the dragged entry would be
+    /// dropped when the pointer is over this one.
+    fn handle_drag(&mut self, ui: &Ui, response: &Response, rect: Rect, key: GuildListKey) {
+        if response.drag_started() {
+            self.dragging = Some(key);
+        }
+
+        let Some(dragged) = self.dragging else {
+            return;
+        };
+        let Some(pointer) = ui.input(|input| input.pointer.interact_pos()) else {
+            return;
+        };
+        // the margin between entries belongs to the closest entry
+        let area = rect.expand2(vec2(CIRCLE_MARGIN, CIRCLE_MARGIN / 2.));
+        if dragged == key || !area.contains(pointer) {
+            return;
+        }
+
+        let target = drop_target(rect, pointer, key, dragged);
+        let painter = ui.painter();
+        match target {
+            DropTarget::Before(_) => {
+                let y = rect.top() - CIRCLE_MARGIN / 2.;
+                painter.hline(rect.x_range(), y, Stroke::new(2., Color32::WHITE));
+            }
+            DropTarget::After(_) => {
+                let y = rect.bottom() + CIRCLE_MARGIN / 2.;
+                painter.hline(rect.x_range(), y, Stroke::new(2., Color32::WHITE));
+            }
+            DropTarget::Into(_) => {
+                painter.rect_stroke(
+                    rect.expand(2.),
+                    Rounding::same(12.),
+                    Stroke::new(2., BLURPLE),
+                );
+            }
+        }
+
+        self.target = Some(target);
+    }
+
+    fn show_folder_settings(&mut self, ui: &Ui) -> Option<GuildListAction> {
+        let edit = self.folder_edit.as_mut()?;
+        let mut open = true;
+        let mut action = None;
+
+        Window::new("Folder Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ui.ctx(), |ui| {
+                ui.label("Folder Name");
+                ui.add(TextEdit::singleline(&mut edit.name).hint_text("Unnamed folder"));
+                ui.horizontal(|ui| {
+                    ui.label("Folder Color");
+                    ui.color_edit_button_srgb(&mut edit.color);
+                });
+
+                if ui.add(Button::new("Save")).clicked() {
+                    let [r, g, b] = edit.color;
+                    let name = edit.name.trim();
+                    action = Some(GuildListAction::UpdateFolder {
+                        folder_id: edit.folder_id,
+                        name: (!name.is_empty()).then(|| name.to_owned()),
+                        color: Some(u64::from(r) << 16 | u64::from(g) << 8 | u64::from(b)),
+                    });
+                }
+            });
+
+        if !open || action.is_some() {
+            self.folder_edit = None;
+        }
+
+        action
+    }
+}
+
+/// Paints the icon of a guild, or its initials if it doesn't have one.
+fn paint_guild(ui: &Ui, images: &mut ImageCache, guild: &Guild, rect: Rect, highlighted: bool) {
+    let icon = guild
+        .icon
+        .map(|icon| cdn::guild_icon(guild.id, icon, 64))
+        .and_then(|url| images.get(&url));
+    let painter = ui.painter();
+
+    if let Some(texture) = icon {
+        painter.image(texture.id(), rect, full_uv(), Color32::WHITE);
+        return;
+    }
+
+    let fill = if highlighted {
+        BLURPLE
+    } else {
+        Color32::DARK_GRAY
+    };
+    painter.circle_filled(rect.center(), rect.width() / 2., fill);
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        initials(&guild.name),
+        FontId::proportional(rect.width() * 0.3),
+        Color32::WHITE,
+    );
+}
+
+/// Paints the pill left of an entry, tall for the open guild.
+fn paint_pill(ui: &Ui, rect: Rect, selected: bool, unread: bool) {
+    let height = match (selected, unread) {
+        (true, _) => 36.,
+        (false, true) => 8.,
+        (false, false) => return,
+    };
+
+    let center = pos2(rect.left() - CIRCLE_MARGIN / 2., rect.center().y);
+    ui.painter().rect_filled(
+        Rect::from_center_size(center, vec2(4., height)),
+        2.,
+        Color32::WHITE,
+    );
+}
+
+fn drop_target(rect: Rect, pointer: Pos2, key: GuildListKey, dragged: GuildListKey) -> DropTarget {
+    let relative = (pointer.y - rect.top()) / rect.height();
+    // folders can only be dropped between entries
+    let into = !matches!(dragged, GuildListKey::Folder(_));
+
+    if relative < DROP_EDGE || (!into && relative < 0.5) {
+        DropTarget::Before(key)
+    } else if relative > 1. - DROP_EDGE || !into {
+        DropTarget::After(key)
+    } else {
+        DropTarget::Into(key)
+    }
+}
+
+fn folder_read_state(read_states: &ReadStateStore, guilds: &[&Guild]) -> GuildReadState {
+    guilds.iter().map(|guild| read_states.guild(guild.id)).fold(
+        GuildReadState::default(),
+        |folder, guild| GuildReadState {
+            unread: folder.unread || guild.unread,
+            mention_count: folder.mention_count + guild.mention_count,
+        },
+    )
+}
+
+fn folder_color(folder: &GuildFolder) -> Color32 {
+    match folder.color {
+        Some(color) => Color32::from_rgb((color >> 16) as u8, (color >> 8) as u8, color as u8),
+        None => BLURPLE,
+    }
+}
+
+/// Name shown for folders that weren't named, the names of their guilds.
+fn folder_name(guilds: &[&Guild]) -> String {
+    guilds
+        .iter()
+        .map(|guild| guild.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,129 @@
+use egui::{
+    text::{LayoutJob, TextFormat},
+    vec2, Align2, Color32, FontId, Frame, Rect, ScrollArea, Sense, TextStyle, Ui, Vec2,
+};
+use fusioncord_core::highlight::{self, Grammar, HighlightCache, Token, TokenKind};
+use twilight_model::id::{marker::MessageMarker, Id};
+
+const COPY_BUTTON_SIZE: Vec2 = Vec2::new(24., 20.);
+
+/// Code blocks of the message being rendered, highlighted through the cache.
+pub struct MessageCode<'a> {
+    pub highlights: &'a mut HighlightCache,
+    pub message_id: Id<MessageMarker>,
+}
+
+/// Shows a fenced code block, with a button to copy its code while it's
+/// hovered.
+pub fn show_code_block(ui: &mut Ui, code: &str, job: LayoutJob) {
+    let id = ui.auto_id_with("code_block");
+
+    let rect = Frame::group(ui.style())
+        .fill(ui.visuals().extreme_bg_color)
+        .show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            ScrollArea::horizontal()
+                .id_source(id)
+                .auto_shrink([false, true])
+                .show(ui, |ui| ui.label(job));
+        })
+        .response
+        .rect;
+
+    if !ui.rect_contains_pointer(rect) {
+        return;
+    }
+
+    let button_rect = Rect::from_min_size(
+        rect.right_top() + vec2(-COPY_BUTTON_SIZE.x - 4., 4.),
+        COPY_BUTTON_SIZE,
+    );
+    let response = ui.interact(button_rect, id.with("copy"), Sense::click());
+    let visuals = ui.style().interact(&response);
+    ui.painter().rect(
+        button_rect,
+        visuals.rounding,
+        visuals.bg_fill,
+        visuals.bg_stroke,
+    );
+    ui.painter().text(
+        button_rect.center(),
+        Align2::CENTER_CENTER,
+        "📋",
+        FontId::proportional(12.),
+        visuals.text_color(),
+    );
+
+    if response.on_hover_text("Copy code").clicked() {
+        ui.output_mut(|o| o.copied_text = code.to_owned());
+    }
+}
+
+/// Lays code out in the monospace font, highlighted if its language is
+/// known and it isn't too large to highlight.
+///
+/// Lines aren't wrapped, code is meant to be shown in a horizontal scroll
+/// area.
+pub fn layout_job(ui: &Ui, code: &str, grammar: Option<&Grammar>) -> LayoutJob {
+    match grammar.filter(|_| code.len() <= highlight::MAX_HIGHLIGHTED_LEN) {
+        Some(grammar) => layout_tokens(ui, code, Some(&highlight::highlight(code, grammar))),
+        None => layout_tokens(ui, code, None),
+    }
+}
+
+/// Lays out code that was highlighted already, or plain code.
+pub fn layout_tokens(ui: &Ui, code: &str, tokens: Option<&[Token]>) -> LayoutJob {
+    let font_id = TextStyle::Monospace.resolve(ui.style());
+    let dark_mode = ui.visuals().dark_mode;
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = f32::INFINITY;
+
+    let Some(tokens) = tokens else {
+        let color = color(TokenKind::Plain, ui);
+        job.append(code, 0., TextFormat::simple(font_id, color));
+        return job;
+    };
+
+    for token in tokens {
+        let color = if dark_mode {
+            color(token.kind, ui)
+        } else {
+            light_color(token.kind, ui)
+        };
+        job.append(
+            &code[token.range.clone()],
+            0.,
+            TextFormat::simple(font_id.clone(), color),
+        );
+    }
+    job
+}
+
+fn color(kind: TokenKind, ui: &Ui) -> Color32 {
+    match kind {
+        TokenKind::Plain => ui.visuals().text_color(),
+        TokenKind::Keyword => Color32::from_rgb(198, 120, 221),
+        TokenKind::Literal | TokenKind::Number => Color32::from_rgb(209, 154, 102),
+        TokenKind::String => Color32::from_rgb(152, 195, 121),
+        TokenKind::Comment => Color32::from_rgb(127, 132, 142),
+        TokenKind::Type => Color32::from_rgb(229, 192, 123),
+        TokenKind::Function => Color32::from_rgb(97, 175, 239),
+        TokenKind::Inserted => Color32::from_rgb(67, 181, 129),
+        TokenKind::Deleted => Color32::from_rgb(240, 71, 71),
+    }
+}
+
+/// Darker variants of the colors, readable on light backgrounds.
+fn light_color(kind: TokenKind, ui: &Ui) -> Color32 {
+    match kind {
+        TokenKind::Plain => ui.visuals().text_color(),
+        TokenKind::Keyword => Color32::from_rgb(166, 38, 164),
+        TokenKind::Literal | TokenKind::Number => Color32::from_rgb(152, 104, 1),
+        TokenKind::String => Color32::from_rgb(80, 161, 79),
+        TokenKind::Comment => Color32::from_rgb(160, 161, 167),
+        TokenKind::Type => Color32::from_rgb(193, 132, 1),
+        TokenKind::Function => Color32::from_rgb(64, 120, 242),
+        TokenKind::Inserted => Color32::from_rgb(45, 125, 70),
+        TokenKind::Deleted => Color32::from_rgb(202, 18, 67),
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::mpsc::Sender;
+
+use fusioncord_core::{
+    cache::Cache,
+    http::HttpClient,
+    message::{notify, RenderMessage},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::id::{marker::ChannelMarker, Id};
+
+/// Fetches the messages that were sent before a channel was opened.
+pub struct HistoryClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+}
+
+impl HistoryClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self { http, runtime, tx }
+    }
+
+    /// Fetches the recent messages of a channel, unless they already were or
+    /// are being fetched.
+    pub fn fetch(&self, cache: &mut Cache, channel_id: Id<ChannelMarker>) {
+        if !cache.start_history_fetch(channel_id) {
+            return;
+        }
+
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            let messages = http
+                .messages(channel_id)
+                .await
+                .inspect_err(|e| warn!("Failed to fetch the messages of {channel_id}: {e}"))
+                .ok();
+
+            notify(
+                &tx,
+                RenderMessage::History {
+                    channel_id,
+                    messages,
+                },
+            );
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use egui::Context;
+use fusioncord_core::presence::now_millis;
+
+/// Time without input after which the current user is away, like Discord
+/// clients.
+const IDLE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// Detects when the current user stops using the client, from the input the
+/// window receives.
+#[derive(Debug)]
+pub struct IdleDetector {
+    last_input: Instant,
+    /// When the last input was received, in milliseconds since the unix
+    /// epoch.
+    last_input_ms: u64,
+    focused: bool,
+}
+
+impl IdleDetector {
+    pub fn new() -> Self {
+        Self {
+            last_input: Instant::now(),
+            last_input_ms: now_millis(),
+            focused: false,
+        }
+    }
+
+    /// Tracks the input of a frame, returning since when the current user
+    /// is away in milliseconds since the unix epoch, `None` if they aren't.
+    pub fn update(&mut self, ctx: &Context) -> Option<u64> {
+        let (focused, has_input) = ctx.input(|input| (input.focused, !input.events.is_empty()));
+        // focusing the window counts as input, even without any event
+        let gained_focus = focused && !self.focused;
+        self.focused = focused;
+
+        if has_input || gained_focus {
+            self.last_input = Instant::now();
+            self.last_input_ms = now_millis();
+        }
+
+        let elapsed = self.last_input.elapsed();
+        if elapsed >= IDLE_AFTER {
+            return Some(self.last_input_ms);
+        }
+
+        // frames aren't drawn without input, so wake up when the user becomes idle
+        ctx.request_repaint_after(IDLE_AFTER - elapsed);
+        None
+    }
+}
+
+impl Default for IdleDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+use egui::{ColorImage, Context, TextureHandle, TextureOptions};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::message::sticker::StickerFormatType,
+    id::{marker::StickerMarker, Id},
+};
+
+use crate::sticker::{self, DecodedSticker, Sticker};
+
+enum ImageState {
+    Loading,
+    Loaded(TextureHandle),
+    Failed,
+}
+
+enum StickerState {
+    Loading,
+    Loaded(Sticker),
+    Failed,
+}
+
+/// Downloads images and stickers in the background and keeps them around as
+/// textures.
+pub struct ImageCache {
+    ctx: Context,
+    runtime: Handle,
+    images: HashMap<String, ImageState>,
+    tx: Sender<(String, Option<ColorImage>)>,
+    rx: Receiver<(String, Option<ColorImage>)>,
+    stickers: HashMap<Id<StickerMarker>, StickerState>,
+    sticker_tx: Sender<(Id<StickerMarker>, Option<DecodedSticker>)>,
+    sticker_rx: Receiver<(Id<StickerMarker>, Option<DecodedSticker>)>,
+}
+
+impl ImageCache {
+    pub fn new(ctx: Context, runtime: Handle) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (sticker_tx, sticker_rx) = mpsc::channel();
+
+        Self {
+            ctx,
+            runtime,
+            images: HashMap::new(),
+            tx,
+            rx,
+            stickers: HashMap::new(),
+            sticker_tx,
+            sticker_rx,
+        }
+    }
+
+    /// Returns the texture of an image, starting to download it if that
+    /// didn't happen yet.
+    ///
+    /// Returns `None` while the image is loading or if it failed to load.
+    pub fn get(&mut self, url: &str) -> Option<&TextureHandle> {
+        if !self.images.contains_key(url) {
+            self.load(url.to_owned());
+        }
+
+        match self.images.get(url) {
+            Some(ImageState::Loaded(texture)) => Some(texture),
+            _ => None,
+        }
+    }
+
+    /// Returns a sticker, starting to download it if that didn't happen yet.
+    ///
+    /// Returns `None` while the sticker is loading or if it failed to load.
+    pub fn sticker(
+        &mut self,
+        sticker_id: Id<StickerMarker>,
+        format: StickerFormatType,
+    ) -> Option<&Sticker> {
+        if !self.stickers.contains_key(&sticker_id) {
+            self.load_sticker(sticker_id, format);
+        }
+
+        match self.stickers.get(&sticker_id) {
+            Some(StickerState::Loaded(sticker)) => Some(sticker),
+            _ => None,
+        }
+    }
+
+    /// Turns all images and stickers that were downloaded since the last call
+    /// into textures.
+    pub fn poll(&mut self) {
+        while let Ok((url, image)) = self.rx.try_recv() {
+            let state = match image {
+                Some(image) => {
+                    ImageState::Loaded(self.ctx.load_texture(&url, image, TextureOptions::LINEAR))
+                }
+                None => ImageState::Failed,
+            };
+
+            self.images.insert(url, state);
+        }
+
+        while let Ok((sticker_id, sticker)) = self.sticker_rx.try_recv() {
+            let state = match sticker {
+                Some(sticker) => StickerState::Loaded(sticker.into_sticker(&self.ctx, sticker_id)),
+                None => StickerState::Failed,
+            };
+
+            self.stickers.insert(sticker_id, state);
+        }
+    }
+
+    fn load(&mut self, url: String) {
+        self.images.insert(url.clone(), ImageState::Loading);
+
+        let ctx = self.ctx.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let image = match fetch_image(&url).await {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    warn!("Failed to load image {url}: {e}");
+                    None
+                }
+            };
+
+            // the cache may already be gone
+            let _ = tx.send((url, image));
+            ctx.request_repaint();
+        });
+    }
+
+    fn load_sticker(&mut self, sticker_id: Id<StickerMarker>, format: StickerFormatType) {
+        self.stickers.insert(sticker_id, StickerState::Loading);
+
+        let ctx = self.ctx.clone();
+        let tx = self.sticker_tx.clone();
+
+        self.runtime.spawn(async move {
+            let sticker = match sticker::fetch_sticker(sticker_id, format).await {
+                Ok(sticker) => Some(sticker),
+                Err(e) => {
+                    warn!("Failed to load sticker {sticker_id}: {e}");
+                    None
+                }
+            };
+
+            // the cache may already be gone
+            let _ = tx.send((sticker_id, sticker));
+            ctx.request_repaint();
+        });
+    }
+}
+
+async fn fetch_image(url: &str) -> Result<ColorImage, Box<dyn std::error::Error + Send + Sync>> {
+    let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+    let image = image::load_from_memory(&bytes)?.into_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
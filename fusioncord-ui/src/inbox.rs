@@ -0,0 +1,102 @@
+use egui::{vec2, Align2, Color32, Context, RichText, ScrollArea, Sense, Ui, Window};
+use fusioncord_core::{cache::snowflake_timestamp, inbox::InboxStore, message::MessageLink};
+use twilight_model::user::NotificationCenterItem;
+
+use crate::{
+    images::ImageCache,
+    renderer::{format_timestamp, full_uv},
+    state::UiState,
+};
+
+const ICON_SIZE: f32 = 40.;
+
+/// Shows the inbox of the current user if it's open, returning the message
+/// of the item that was clicked.
+pub fn show_inbox(
+    ctx: &Context,
+    inbox: &mut InboxStore,
+    images: &mut ImageCache,
+    state: &mut UiState,
+) -> Option<MessageLink> {
+    if !state.inbox_open {
+        return None;
+    }
+    // items are seen as soon as the inbox is open
+    inbox.ack_all();
+
+    let mut clicked = None;
+    Window::new("Inbox")
+        .open(&mut state.inbox_open)
+        .collapsible(false)
+        .default_size([400., 500.])
+        .anchor(Align2::LEFT_TOP, [80., 40.])
+        .show(ctx, |ui| {
+            if inbox.items().is_empty() {
+                ui.weak("You're all caught up!");
+                return;
+            }
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for item in inbox.items() {
+                    if show_item(ui, images, item) {
+                        clicked = Some(item);
+                    }
+                    ui.separator();
+                }
+            });
+        });
+
+    let item = clicked?;
+    match (item.message_channel_id, item.message_id) {
+        (Some(channel_id), Some(message_id)) => {
+            state.inbox_open = false;
+            Some(MessageLink {
+                guild_id: item.guild_id,
+                channel_id,
+                message_id,
+            })
+        }
+        _ => {
+            ctx.output_mut(|output| {
+                output.open_url = Some(egui::output::OpenUrl::new_tab(&item.deeplink));
+            });
+            None
+        }
+    }
+}
+
+/// Shows an item of the inbox, returning whether it was clicked.
+fn show_item(ui: &mut Ui, images: &mut ImageCache, item: &NotificationCenterItem) -> bool {
+    let response = ui
+        .horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(vec2(ICON_SIZE, ICON_SIZE), Sense::hover());
+            match item.icon_url.as_deref().and_then(|url| images.get(url)) {
+                Some(texture) => {
+                    ui.painter()
+                        .image(texture.id(), rect, full_uv(), Color32::WHITE);
+                }
+                None => {
+                    ui.painter()
+                        .circle_filled(rect.center(), ICON_SIZE / 2., Color32::DARK_GRAY);
+                }
+            }
+
+            ui.vertical(|ui| {
+                // item bodies only use bold text
+                let body = item.body.replace("**", "");
+                let mut text = RichText::new(body);
+                if item.completed {
+                    text = text.weak().strikethrough();
+                }
+                ui.label(text);
+
+                ui.weak(format_timestamp(snowflake_timestamp(item.id)));
+            });
+        })
+        .response
+        .interact(Sense::click());
+
+    response
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .clicked()
+}
@@ -0,0 +1,218 @@
+use std::sync::mpsc::Sender;
+
+use egui::{Align2, Context, RichText, TextEdit, Window};
+use fusioncord_core::{
+    cache::Cache,
+    http::{CreateInteraction, HttpClient},
+    interaction::InteractionStore,
+    message::{notify, RenderMessage},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    application::interaction::{
+        message_component::MessageComponentInteractionData,
+        modal::{
+            ModalInteractionData, ModalInteractionDataActionRow, ModalInteractionDataComponent,
+        },
+    },
+    channel::message::{
+        component::{ComponentType, TextInput, TextInputStyle},
+        Component, Message,
+    },
+    gateway::payload::incoming::InteractionModalCreate,
+};
+
+/// A modal that is being filled in.
+struct ModalForm {
+    modal: InteractionModalCreate,
+    /// Text inputs of the modal, per action row, along with their values.
+    rows: Vec<Vec<(TextInput, String)>>,
+}
+
+/// Sends interactions of the current user and shows the modals applications
+/// open in response.
+pub struct InteractionClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+    form: Option<ModalForm>,
+}
+
+impl InteractionClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self {
+            http,
+            runtime,
+            tx,
+            form: None,
+        }
+    }
+
+    /// Sends the interaction of a button or select menu of a message.
+    pub fn send_component(
+        &self,
+        interactions: &mut InteractionStore,
+        message: &Message,
+        data: MessageComponentInteractionData,
+    ) {
+        match interactions.component_interaction(message, data) {
+            Some(interaction) => self.send(interaction),
+            None => warn!("Can't send an interaction before the session is ready"),
+        }
+    }
+
+    /// Shows the modal an application opened, if any.
+    pub fn show_modal(
+        &mut self,
+        ctx: &Context,
+        interactions: &mut InteractionStore,
+        cache: &Cache,
+    ) {
+        if let Some(modal) = interactions.take_modal() {
+            self.form = Some(ModalForm::new(modal));
+        }
+
+        let Some(form) = &mut self.form else {
+            return;
+        };
+
+        let mut open = true;
+        let mut submit = false;
+        let mut cancel = false;
+
+        Window::new(form.modal.title.as_str())
+            .id(egui::Id::new(("modal", form.modal.id)))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(form.modal.application.name.as_str())
+                        .small()
+                        .weak(),
+                );
+
+                for (input, value) in form.rows.iter_mut().flatten() {
+                    let label = if input.required.unwrap_or(true) {
+                        format!("{} *", input.label)
+                    } else {
+                        input.label.clone()
+                    };
+                    ui.label(RichText::new(label).strong());
+
+                    let text_edit = match input.style {
+                        TextInputStyle::Paragraph => TextEdit::multiline(value),
+                        _ => TextEdit::singleline(value),
+                    };
+                    let text_edit = text_edit
+                        .hint_text(input.placeholder.as_deref().unwrap_or_default())
+                        .char_limit(input.max_length.map_or(4000, usize::from))
+                        .desired_width(400.);
+                    ui.add(text_edit);
+                    ui.add_space(8.);
+                }
+
+                ui.horizontal(|ui| {
+                    submit = ui
+                        .add_enabled(form.is_valid(), egui::Button::new("Submit"))
+                        .clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if submit {
+            let guild_id = cache
+                .channel(form.modal.channel_id)
+                .and_then(|channel| channel.guild_id);
+
+            match interactions.modal_submit(&form.modal, guild_id, form.data()) {
+                Some(interaction) => self.send(interaction),
+                None => warn!("Can't submit a modal before the session is ready"),
+            }
+        }
+
+        if submit || cancel || !open {
+            self.form = None;
+        }
+    }
+
+    /// Sends an interaction, reporting it as failed if sending it fails.
+    pub fn send(&self, interaction: CreateInteraction) {
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            if let Err(e) = http.create_interaction(&interaction).await {
+                warn!("Failed to send interaction: {e}");
+                notify(
+                    &tx,
+                    RenderMessage::InteractionFailed {
+                        nonce: interaction.nonce,
+                    },
+                );
+            }
+        });
+    }
+}
+
+impl ModalForm {
+    fn new(modal: InteractionModalCreate) -> Self {
+        let rows = modal
+            .components
+            .iter()
+            .filter_map(|component| match component {
+                Component::ActionRow(row) => Some(
+                    row.components
+                        .iter()
+                        .filter_map(|component| match component {
+                            Component::TextInput(input) => {
+                                Some((input.clone(), input.value.clone().unwrap_or_default()))
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+
+        Self { modal, rows }
+    }
+
+    /// Whether every required input is filled in with a value of a valid
+    /// length.
+    fn is_valid(&self) -> bool {
+        self.rows.iter().flatten().all(|(input, value)| {
+            let length = value.chars().count();
+            let min_length = input.min_length.map_or(0, usize::from);
+
+            if value.is_empty() {
+                !input.required.unwrap_or(true)
+            } else {
+                length >= min_length
+            }
+        })
+    }
+
+    fn data(&self) -> ModalInteractionData {
+        ModalInteractionData {
+            components: self
+                .rows
+                .iter()
+                .map(|row| ModalInteractionDataActionRow {
+                    components: row
+                        .iter()
+                        .map(|(input, value)| ModalInteractionDataComponent {
+                            custom_id: input.custom_id.clone(),
+                            kind: ComponentType::TextInput,
+                            value: Some(value.clone()),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            custom_id: self.modal.custom_id.clone(),
+        }
+    }
+}
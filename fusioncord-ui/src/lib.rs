@@ -1,2 +1,32 @@
+mod account;
 pub mod app;
+mod attachment;
+mod command;
+mod completion;
+mod component;
+mod composer;
+mod embed;
+mod emoji_picker;
+mod friends;
+mod guild_list;
+mod highlight;
+mod history;
+mod idle;
+mod images;
+mod inbox;
+mod interaction;
+mod markdown;
+mod member_list;
+mod notification;
+mod presence;
+mod private_channel;
+mod profile;
+mod reaction;
+mod read_state;
 pub mod renderer;
+mod rpc;
+mod settings;
+mod state;
+mod status;
+mod sticker;
+mod thread;
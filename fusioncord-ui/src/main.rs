@@ -1,9 +1,9 @@
 use std::{env, error::Error, io, sync::mpsc};
 
 use eframe::NativeOptions;
-use fusioncord_core::client::Client;
+use fusioncord_core::{client::Client, http::HttpClient, presence::identify_presence};
 use fusioncord_ui::app::Application;
-use tokio::runtime::Builder;
+use tokio::{runtime::Builder, sync::mpsc::unbounded_channel};
 use twilight_model::gateway::{
     payload::outgoing::identify::{IdentifyInfo, IdentifyProperties},
     Intents, ShardId,
@@ -24,11 +24,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
         buf
     });
 
+    let http = HttpClient::new(&token);
+
     let identify = IdentifyInfo {
         compress: false,
         intents: Intents::all(),
         large_threshold: 50,
-        presence: None,
+        presence: Some(identify_presence()),
         properties: IdentifyProperties::new("chrome", "web", "windows"),
         shard: Some(ShardId::ONE),
         token,
@@ -45,6 +47,8 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let _ = rt.enter();
 
     let (tx, rx) = mpsc::channel();
+    let ui_tx = tx.clone();
+    let (commands_tx, commands) = unbounded_channel();
 
     rt.spawn(async move {
         let mut client = Client::new()
@@ -53,16 +57,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .await?
             .identify(identify)
             .await?
-            .wait_for_ready(tx)
+            .wait_for_ready(tx, commands)
             .await?;
 
         client.run().await
     });
 
+    let runtime = rt.handle().clone();
+
     eframe::run_native(
         "app",
         native_options,
-        Box::new(move |cc| Box::new(Application::new(cc, rx))),
+        Box::new(move |cc| Box::new(Application::new(cc, ui_tx, rx, commands_tx, runtime, http))),
     )?;
 
     Ok(())
@@ -0,0 +1,336 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use egui::{vec2, Color32, Id as WidgetId, Label, RichText, Sense, Stroke, Ui};
+use fusioncord_core::{
+    cache::Cache,
+    cdn,
+    highlight::grammar_for_language,
+    markdown::{self, Block, Inline, Style},
+};
+use twilight_model::{
+    channel::message::Mention,
+    id::{
+        marker::{GuildMarker, MessageMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+
+use crate::{
+    highlight::{self, MessageCode},
+    images::ImageCache,
+    renderer::format_timestamp,
+};
+
+const LINK_COLOR: Color32 = Color32::from_rgb(0, 168, 252);
+const MENTION_COLOR: Color32 = Color32::from_rgb(201, 205, 251);
+const MENTION_BACKGROUND: Color32 = Color32::from_rgb(60, 66, 112);
+const SPOILER_COLOR: Color32 = Color32::from_gray(30);
+
+/// State needed to show mentions by name.
+pub struct MentionContext<'a> {
+    pub cache: &'a Cache,
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// Users mentioned in the message, which are sent along with their names.
+    pub users: &'a [Mention],
+    /// Message the text is shown in, which spoilers are remembered by.
+    pub message_id: Id<MessageMarker>,
+}
+
+impl MentionContext<'_> {
+    fn user_name(&self, user_id: Id<UserMarker>) -> String {
+        if let Some(user) = self.users.iter().find(|user| user.id == user_id) {
+            return user
+                .member
+                .as_ref()
+                .and_then(|member| member.nick.clone())
+                .unwrap_or_else(|| user.name.clone());
+        }
+
+        self.guild_id
+            .and_then(|guild_id| self.cache.guild(guild_id))
+            .and_then(|guild| {
+                guild
+                    .members
+                    .iter()
+                    .find(|member| member.user.id == user_id)
+            })
+            .map_or_else(
+                || "unknown-user".to_owned(),
+                |member| {
+                    member
+                        .nick
+                        .clone()
+                        .unwrap_or_else(|| member.user.name.clone())
+                },
+            )
+    }
+}
+
+/// Renders text written in markdown.
+///
+/// The code blocks of messages are highlighted through the cache, the ones
+/// of embeds every frame.
+pub fn show(
+    ui: &mut Ui,
+    text: &str,
+    images: &mut ImageCache,
+    mentions: &MentionContext<'_>,
+    code: Option<&mut MessageCode<'_>>,
+) {
+    let context = Context {
+        mentions,
+        // the text tells apart the content of a message from its embeds
+        spoilers: WidgetId::new(("spoilers", mentions.message_id, text)),
+    };
+    show_blocks(ui, &markdown::parse(text), images, &context, code);
+}
+
+struct Context<'a> {
+    mentions: &'a MentionContext<'a>,
+    /// Identifies the spoilers of the text, to remember which are revealed.
+    spoilers: WidgetId,
+}
+
+fn show_blocks(
+    ui: &mut Ui,
+    blocks: &[Block],
+    images: &mut ImageCache,
+    context: &Context<'_>,
+    mut code: Option<&mut MessageCode<'_>>,
+) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(content) => show_inlines(ui, content, None, images, context),
+            Block::Heading { level, content } => {
+                let size = match level {
+                    1 => 22.,
+                    2 => 19.,
+                    _ => 16.,
+                };
+                show_inlines(ui, content, Some(size), images, context);
+            }
+            Block::Quote(blocks) => {
+                let response = ui
+                    .horizontal_top(|ui| {
+                        ui.add_space(10.);
+                        ui.vertical(|ui| {
+                            show_blocks(ui, blocks, images, context, code.as_deref_mut());
+                        });
+                    })
+                    .response;
+
+                let rect = response.rect;
+                ui.painter().vline(
+                    rect.left() + 2.,
+                    rect.y_range(),
+                    Stroke::new(4., Color32::GRAY),
+                );
+            }
+            Block::CodeBlock {
+                language,
+                code: block,
+            } => {
+                let language = language.as_deref();
+                let job = match code.as_deref_mut() {
+                    Some(code) => {
+                        let tokens = code.highlights.highlight(code.message_id, language, block);
+                        highlight::layout_tokens(ui, block, tokens)
+                    }
+                    // embeds are short enough to be highlighted every frame
+                    None => {
+                        highlight::layout_job(ui, block, language.and_then(grammar_for_language))
+                    }
+                };
+                highlight::show_code_block(ui, block, job);
+            }
+            Block::ListItem {
+                depth,
+                number,
+                content,
+            } => {
+                ui.horizontal_top(|ui| {
+                    ui.add_space(f32::from(*depth) * 16.);
+                    match number {
+                        Some(number) => ui.label(format!("{number}.")),
+                        None => ui.label("•"),
+                    };
+                    show_inlines(ui, content, None, images, context);
+                });
+            }
+        }
+    }
+}
+
+fn show_inlines(
+    ui: &mut Ui,
+    inlines: &[Inline],
+    size: Option<f32>,
+    images: &mut ImageCache,
+    context: &Context<'_>,
+) {
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing = vec2(0., 2.);
+
+        for inline in inlines {
+            show_inline(ui, inline, size, images, context);
+        }
+    });
+}
+
+fn show_inline(
+    ui: &mut Ui,
+    inline: &Inline,
+    size: Option<f32>,
+    images: &mut ImageCache,
+    context: &Context<'_>,
+) {
+    match inline {
+        Inline::Text { text, style } => {
+            ui.label(styled(text, *style, size));
+        }
+        Inline::Code(code) => {
+            ui.label(RichText::new(code.as_str()).code());
+        }
+        Inline::Link { text, url, style } => {
+            ui.hyperlink_to(styled(text, *style, size).color(LINK_COLOR), url)
+                .on_hover_text(url.as_str());
+        }
+        Inline::Spoiler { index, content } => {
+            show_spoiler(ui, *index, content, size, images, context);
+        }
+        Inline::UserMention(user_id) => {
+            mention(ui, format!("@{}", context.mentions.user_name(*user_id)));
+        }
+        Inline::RoleMention(role_id) => {
+            let role = context
+                .mentions
+                .guild_id
+                .and_then(|guild_id| context.mentions.cache.guild(guild_id))
+                .and_then(|guild| guild.roles.iter().find(|role| role.id == *role_id));
+
+            match role {
+                Some(role) => mention(ui, format!("@{}", role.name)),
+                None => mention(ui, "@deleted-role".to_owned()),
+            }
+        }
+        Inline::ChannelMention(channel_id) => {
+            let name = context
+                .mentions
+                .cache
+                .channel(*channel_id)
+                .and_then(|channel| channel.name.as_deref())
+                .unwrap_or("unknown");
+            mention(ui, format!("#{name}"));
+        }
+        Inline::EveryoneMention(everyone) => mention(ui, (*everyone).to_owned()),
+        Inline::CustomEmoji { id, name, animated } => {
+            let size = size.unwrap_or(20.);
+            match images.get(&cdn::emoji(*id, *animated, 48)) {
+                Some(texture) => {
+                    ui.image(texture.id(), vec2(size, size))
+                        .on_hover_text(format!(":{name}:"));
+                }
+                None => {
+                    ui.label(format!(":{name}:"));
+                }
+            }
+        }
+        Inline::Timestamp { secs, style } => {
+            let text = match (style, Timestamp::from_secs(*secs)) {
+                (Some('R'), Ok(_)) => relative_time(*secs),
+                (_, Ok(timestamp)) => format_timestamp(timestamp),
+                (_, Err(_)) => "Invalid Date".to_owned(),
+            };
+            ui.label(RichText::new(text).background_color(ui.visuals().faint_bg_color));
+        }
+        Inline::LineBreak => ui.end_row(),
+    }
+}
+
+fn show_spoiler(
+    ui: &mut Ui,
+    index: usize,
+    inlines: &[Inline],
+    size: Option<f32>,
+    images: &mut ImageCache,
+    context: &Context<'_>,
+) {
+    let id = context.spoilers.with(index);
+    let revealed = ui.data(|data| data.get_temp::<bool>(id)).unwrap_or(false);
+
+    if revealed {
+        for inline in inlines {
+            show_inline(ui, inline, size, images, context);
+        }
+        return;
+    }
+
+    let text = RichText::new(markdown::inline_plain_text(inlines))
+        .color(SPOILER_COLOR)
+        .background_color(SPOILER_COLOR);
+
+    if ui.add(Label::new(text).sense(Sense::click())).clicked() {
+        ui.data_mut(|data| data.insert_temp(id, true));
+    }
+}
+
+fn mention(ui: &mut Ui, text: String) {
+    ui.label(
+        RichText::new(text)
+            .color(MENTION_COLOR)
+            .background_color(MENTION_BACKGROUND),
+    );
+}
+
+fn styled(text: &str, style: Style, size: Option<f32>) -> RichText {
+    let mut text = RichText::new(text);
+
+    if let Some(size) = size {
+        text = text.size(size).strong();
+    }
+    if style.bold {
+        text = text.strong();
+    }
+    if style.italic {
+        text = text.italics();
+    }
+    if style.underline {
+        text = text.underline();
+    }
+    if style.strikethrough {
+        text = text.strikethrough();
+    }
+
+    text
+}
+
+/// Formats a unix timestamp relative to now, such as "in 5 minutes".
+fn relative_time(secs: i64) -> String {
+    const UNITS: [(i64, &str); 6] = [
+        (365 * 24 * 3600, "year"),
+        (30 * 24 * 3600, "month"),
+        (24 * 3600, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64);
+    let difference = secs - now;
+
+    let (unit_secs, unit) = UNITS
+        .into_iter()
+        .find(|(unit_secs, _)| difference.abs() >= *unit_secs)
+        .unwrap_or((1, "second"));
+    let amount = difference.abs() / unit_secs;
+    let plural = if amount == 1 { "" } else { "s" };
+
+    if difference >= 0 {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use egui::{vec2, Color32, RichText, ScrollArea, Sense, Ui};
+use fusioncord_core::{
+    role::{MemberAppearance, RoleStore},
+    user_presence::UserPresenceStore,
+};
+use twilight_model::{
+    gateway::presence::Status,
+    guild::{Guild, Member},
+    id::{
+        marker::{GuildMarker, RoleMarker},
+        Id,
+    },
+};
+
+use crate::{
+    friends::activity_text,
+    images::ImageCache,
+    presence::paint_status,
+    profile::{to_color, ProfilePopout},
+    renderer::{avatar_url, full_uv, show_badges, show_role_icon},
+};
+
+const AVATAR_SIZE: f32 = 32.;
+
+/// Shows the members of a guild the current user knows of, opening the
+/// profile of the one that is clicked.
+///
+/// Online members are listed under their highest hoisted role, the offline
+/// ones last.
+pub fn show_member_list(
+    ui: &mut Ui,
+    guild: &Guild,
+    presences: &UserPresenceStore,
+    roles: &RoleStore,
+    profile: &mut ProfilePopout,
+    images: &mut ImageCache,
+) {
+    let mut members = guild.members.iter().collect::<Vec<_>>();
+    members.sort_by_cached_key(|member| display_name(member).to_lowercase());
+    let (online, offline): (Vec<_>, Vec<_>) = members
+        .into_iter()
+        .partition(|member| presences.status(Some(guild.id), member.user.id) != Status::Offline);
+
+    let mut hoisted = HashMap::<Id<RoleMarker>, Vec<&Member>>::new();
+    let mut unhoisted = Vec::new();
+    for member in online {
+        match roles
+            .appearance(guild.id, member.user.id)
+            .and_then(|appearance| appearance.hoist)
+        {
+            Some(role_id) => hoisted.entry(role_id).or_default().push(member),
+            None => unhoisted.push(member),
+        }
+    }
+    let hoisted_ids = hoisted.keys().copied().collect::<Vec<_>>();
+    let mut groups = roles
+        .sorted_roles(guild.id, &hoisted_ids)
+        .into_iter()
+        .map(|role| {
+            let members = hoisted.remove(&role.id).unwrap_or_default();
+            (role.name.to_uppercase(), members)
+        })
+        .collect::<Vec<_>>();
+    groups.push(("ONLINE".to_owned(), unhoisted));
+    groups.push(("OFFLINE".to_owned(), offline));
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for (title, members) in groups {
+                if members.is_empty() {
+                    continue;
+                }
+                ui.add_space(8.);
+                ui.label(
+                    RichText::new(format!("{title} — {}", members.len()))
+                        .small()
+                        .strong(),
+                );
+
+                for member in members {
+                    let appearance = roles
+                        .appearance(guild.id, member.user.id)
+                        .cloned()
+                        .unwrap_or_default();
+                    if show_member(ui, guild.id, member, &appearance, presences, images) {
+                        profile.open(Some(guild.id), member.user.clone(), member.nick.clone());
+                    }
+                }
+            }
+        });
+}
+
+/// Shows a member with their status and activity, returning whether they
+/// were clicked.
+fn show_member(
+    ui: &mut Ui,
+    guild_id: Id<GuildMarker>,
+    member: &Member,
+    appearance: &MemberAppearance,
+    presences: &UserPresenceStore,
+    images: &mut ImageCache,
+) -> bool {
+    let user = &member.user;
+    let presence = presences.presence(Some(guild_id), user.id);
+    let status = presence.map_or(Status::Offline, |presence| presence.status);
+
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(vec2(AVATAR_SIZE, AVATAR_SIZE), Sense::hover());
+        let url = avatar_url(user.id, user.avatar, user.discriminator);
+        // offline members are dimmed, like Discord clients do
+        let tint = if presence.is_some() {
+            Color32::WHITE
+        } else {
+            Color32::from_white_alpha(96)
+        };
+        if let Some(texture) = images.get(&url) {
+            ui.painter().image(texture.id(), rect, full_uv(), tint);
+        }
+        if presence.is_some() {
+            paint_status(
+                ui,
+                rect.right_bottom() - vec2(4., 4.),
+                status,
+                presence.map(|presence| &presence.client_status),
+            );
+        }
+
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                let name = RichText::new(display_name(member));
+                let name = match (appearance.color, presence.is_some()) {
+                    (Some(color), true) => name.strong().color(to_color(color)),
+                    (Some(color), false) => name.color(to_color(color).linear_multiply(0.5)),
+                    (None, true) => name.strong(),
+                    (None, false) => name.weak(),
+                };
+                ui.label(name);
+                if let Some(icon) = &appearance.icon {
+                    show_role_icon(ui, icon, images);
+                }
+                show_badges(ui, appearance, member.user.bot);
+            });
+            if let Some(activity) =
+                presence.and_then(|presence| presence.activities.iter().find_map(activity_text))
+            {
+                ui.label(RichText::new(activity).small().weak());
+            }
+        });
+    })
+    .response
+    .interact(Sense::click())
+    .clicked()
+}
+
+/// Name of a member, preferring their guild nickname.
+fn display_name(member: &Member) -> &str {
+    member
+        .nick
+        .as_deref()
+        .or(member.user.global_name.as_deref())
+        .unwrap_or(&member.user.name)
+}
@@ -0,0 +1,103 @@
+use std::sync::mpsc::Sender;
+#[cfg(target_os = "linux")]
+use std::sync::{Arc, OnceLock};
+
+#[cfg(target_os = "linux")]
+use fusioncord_core::desktop_notification::{DesktopNotification, DesktopNotifier};
+use fusioncord_core::{
+    cache::Cache,
+    message::{notify, RenderMessage},
+};
+use tokio::runtime::Handle;
+#[cfg(target_os = "linux")]
+use tracing::warn;
+use twilight_model::{
+    channel::Message,
+    id::{marker::ChannelMarker, Id},
+};
+
+/// Shows desktop notifications for new messages, through the notification
+/// service of the session on Linux and not at all on other platforms.
+///
+/// Clicking a notification sends a [`RenderMessage::OpenMessage`].
+pub struct NotificationClient {
+    runtime: Handle,
+    /// The notifier, once connected to the notification service.
+    #[cfg(target_os = "linux")]
+    notifier: Arc<OnceLock<DesktopNotifier>>,
+}
+
+impl NotificationClient {
+    pub fn new(runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        #[cfg(target_os = "linux")]
+        let notifier = Arc::new(OnceLock::new());
+
+        #[cfg(target_os = "linux")]
+        {
+            let notifier = Arc::clone(&notifier);
+            runtime.spawn(async move {
+                let notifier = match DesktopNotifier::session().await {
+                    Ok(connected) => notifier.get_or_init(|| connected),
+                    Err(e) => {
+                        warn!("Failed to connect to the notification service: {e}");
+                        return;
+                    }
+                };
+
+                let result = notifier
+                    .listen(|link| {
+                        notify(&tx, RenderMessage::OpenMessage(link));
+                    })
+                    .await;
+                if let Err(e) = result {
+                    warn!("Failed to listen for notification clicks: {e}");
+                }
+            });
+        }
+        #[cfg(not(target_os = "linux"))]
+        drop(tx);
+
+        Self {
+            runtime,
+            #[cfg(target_os = "linux")]
+            notifier,
+        }
+    }
+
+    /// Shows a notification for a message, replacing the one of its channel.
+    pub fn notify(&self, cache: &Cache, message: &Message) {
+        #[cfg(target_os = "linux")]
+        {
+            let notification = DesktopNotification::new(cache, message);
+            let notifier = Arc::clone(&self.notifier);
+            self.runtime.spawn(async move {
+                let Some(notifier) = notifier.get() else {
+                    return;
+                };
+                if let Err(e) = notifier.notify(notification).await {
+                    warn!("Failed to show notification: {e}");
+                }
+            });
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = (&self.runtime, cache, message);
+    }
+
+    /// Closes the notification of a channel once it's opened.
+    pub fn clear(&self, channel_id: Id<ChannelMarker>) {
+        #[cfg(target_os = "linux")]
+        {
+            let notifier = Arc::clone(&self.notifier);
+            self.runtime.spawn(async move {
+                let Some(notifier) = notifier.get() else {
+                    return;
+                };
+                if let Err(e) = notifier.clear(channel_id).await {
+                    warn!("Failed to close notification: {e}");
+                }
+            });
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = channel_id;
+    }
+}
@@ -0,0 +1,310 @@
+use std::time::Duration;
+
+use egui::{
+    vec2, Align2, Button, Color32, FontId, Frame, Pos2, ProgressBar, Rect, RichText, Sense, Ui,
+};
+use fusioncord_core::{
+    cdn,
+    presence::now_millis,
+    user_presence::{custom_status, shown_activities},
+};
+use twilight_model::{
+    gateway::presence::{Activity, ActivityButton, ActivityType, ClientStatus, Status},
+    id::{marker::EmojiMarker, Id},
+};
+
+use crate::{
+    friends::{status_color, status_name},
+    images::ImageCache,
+    renderer::full_uv,
+};
+
+const LARGE_IMAGE_SIZE: f32 = 60.;
+const SMALL_IMAGE_SIZE: f32 = 20.;
+const EMOJI_SIZE: f32 = 16.;
+
+/// Paints the status of a user at the corner of their avatar.
+///
+/// Users that are only online on their phone are shown with a phone instead
+/// of a dot, like Discord clients do.
+pub(crate) fn paint_status(
+    ui: &Ui,
+    center: Pos2,
+    status: Status,
+    client_status: Option<&ClientStatus>,
+) {
+    let painter = ui.painter();
+    if status == Status::Online && client_status.is_some_and(is_mobile_only) {
+        painter.rect_filled(
+            Rect::from_center_size(center, vec2(10., 14.)),
+            3.,
+            ui.visuals().panel_fill,
+        );
+        painter.text(
+            center,
+            Align2::CENTER_CENTER,
+            "📱",
+            FontId::proportional(10.),
+            status_color(status),
+        );
+        return;
+    }
+
+    painter.circle_filled(center, 6., ui.visuals().panel_fill);
+    painter.circle_filled(center, 4., status_color(status));
+}
+
+/// Shows the status of a user along with the icons of the clients they're
+/// connected on.
+pub(crate) fn show_client_status(ui: &mut Ui, status: Status, client_status: &ClientStatus) {
+    ui.horizontal(|ui| {
+        ui.colored_label(status_color(status), "●");
+        ui.label(status_name(status));
+        for (client, status) in clients(client_status) {
+            ui.colored_label(status_color(status), client_icon(client))
+                .on_hover_text(format!("{} on {client}", status_name(status)));
+        }
+    });
+}
+
+/// Describes on which clients a user is shown as connected, such as
+/// `Online on desktop, idle on mobile`.
+pub(crate) fn client_summary(client_status: &ClientStatus) -> String {
+    let clients = clients(client_status)
+        .map(|(client, status)| format!("{} on {client}", status_name(status).to_lowercase()))
+        .collect::<Vec<_>>();
+
+    if clients.is_empty() {
+        "Not connected on any client".to_owned()
+    } else {
+        capitalize(&clients.join(", "))
+    }
+}
+
+pub(crate) fn client_icon(client: &str) -> &'static str {
+    match client {
+        "desktop" => "🖥",
+        "mobile" => "📱",
+        "web" => "🌐",
+        _ => "❓",
+    }
+}
+
+pub(crate) fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Shows the custom status of a user followed by a card for each of their
+/// other activities.
+pub(crate) fn show_activities(ui: &mut Ui, activities: &[Activity], images: &mut ImageCache) {
+    if let Some(activity) = custom_status(activities) {
+        show_custom_status(ui, activity, images);
+    }
+
+    let mut timed = false;
+    for activity in shown_activities(activities) {
+        show_activity(ui, activity, images);
+        timed |= activity.timestamps.is_some();
+    }
+
+    // elapsed times are counted every second
+    if timed {
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+}
+
+fn show_custom_status(ui: &mut Ui, activity: &Activity, images: &mut ImageCache) {
+    ui.horizontal_wrapped(|ui| {
+        if let Some(emoji) = &activity.emoji {
+            let custom_emoji = emoji
+                .id
+                .as_deref()
+                .and_then(|id| id.parse::<Id<EmojiMarker>>().ok());
+
+            match custom_emoji {
+                Some(emoji_id) => {
+                    let url = cdn::emoji(emoji_id, emoji.animated.unwrap_or_default(), 32);
+                    let (rect, response) =
+                        ui.allocate_exact_size(vec2(EMOJI_SIZE, EMOJI_SIZE), Sense::hover());
+                    if let Some(texture) = images.get(&url) {
+                        ui.painter()
+                            .image(texture.id(), rect, full_uv(), Color32::WHITE);
+                    }
+                    response.on_hover_text(format!(":{}:", emoji.name));
+                }
+                None => {
+                    ui.label(emoji.name.as_str());
+                }
+            }
+        }
+        if let Some(state) = &activity.state {
+            ui.label(state.as_str());
+        }
+    });
+}
+
+fn show_activity(ui: &mut Ui, activity: &Activity, images: &mut ImageCache) {
+    Frame::group(ui.style()).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        ui.label(RichText::new(activity_heading(activity)).small().strong());
+
+        ui.horizontal(|ui| {
+            show_assets(ui, activity, images);
+
+            ui.vertical(|ui| {
+                ui.label(RichText::new(activity.name.as_str()).strong());
+                if let Some(details) = &activity.details {
+                    ui.label(details.as_str());
+                }
+                if let Some(state) = &activity.state {
+                    ui.label(state.as_str());
+                }
+                show_timestamps(ui, activity);
+            });
+        });
+
+        if let (ActivityType::Streaming, Some(url)) = (activity.kind, &activity.url) {
+            ui.hyperlink_to("Watch Stream", url);
+        }
+        show_buttons(ui, activity);
+    });
+}
+
+/// Shows the large image of an activity with its small image in the corner.
+fn show_assets(ui: &mut Ui, activity: &Activity, images: &mut ImageCache) {
+    let Some(assets) = &activity.assets else {
+        return;
+    };
+    let Some(large_image) = assets.large_image.as_deref().and_then(|asset| {
+        cdn::activity_asset(activity.application_id, asset, LARGE_IMAGE_SIZE as u16 * 2)
+    }) else {
+        return;
+    };
+
+    let (rect, response) =
+        ui.allocate_exact_size(vec2(LARGE_IMAGE_SIZE, LARGE_IMAGE_SIZE), Sense::hover());
+    if let Some(texture) = images.get(&large_image) {
+        ui.painter()
+            .image(texture.id(), rect, full_uv(), Color32::WHITE);
+    }
+
+    let small_image = assets.small_image.as_deref().and_then(|asset| {
+        cdn::activity_asset(activity.application_id, asset, SMALL_IMAGE_SIZE as u16 * 2)
+    });
+    if let Some(small_image) = small_image {
+        let center = rect.right_bottom() - vec2(SMALL_IMAGE_SIZE / 4., SMALL_IMAGE_SIZE / 4.);
+        if let Some(texture) = images.get(&small_image) {
+            ui.painter()
+                .circle_filled(center, SMALL_IMAGE_SIZE / 2. + 2., ui.visuals().panel_fill);
+            ui.painter().image(
+                texture.id(),
+                Rect::from_center_size(center, vec2(SMALL_IMAGE_SIZE, SMALL_IMAGE_SIZE)),
+                full_uv(),
+                Color32::WHITE,
+            );
+        }
+    }
+
+    let hover_text = [&assets.large_text, &assets.small_text]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    if !hover_text.is_empty() {
+        response.on_hover_text(hover_text.join("\n"));
+    }
+}
+
+/// Shows how long an activity has been going on, or the progress of the
+/// song for the activities that end.
+fn show_timestamps(ui: &mut Ui, activity: &Activity) {
+    let Some(timestamps) = &activity.timestamps else {
+        return;
+    };
+    let now = now_millis();
+
+    match (timestamps.start, timestamps.end) {
+        (Some(start), Some(end)) if activity.kind == ActivityType::Listening && end > start => {
+            let elapsed = now.saturating_sub(start).min(end - start);
+            ui.add(ProgressBar::new(elapsed as f32 / (end - start) as f32).desired_width(160.));
+            ui.weak(format!(
+                "{} / {}",
+                format_duration(elapsed),
+                format_duration(end - start)
+            ));
+        }
+        (_, Some(end)) => {
+            ui.weak(format!("{} left", format_duration(end.saturating_sub(now))));
+        }
+        (Some(start), None) => {
+            ui.weak(format!(
+                "{} elapsed",
+                format_duration(now.saturating_sub(start))
+            ));
+        }
+        (None, None) => (),
+    }
+}
+
+/// Shows the buttons of an activity, only their labels are sent for the
+/// activities of other users.
+fn show_buttons(ui: &mut Ui, activity: &Activity) {
+    if activity.buttons.is_empty() {
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for button in &activity.buttons {
+            match button {
+                ActivityButton::Link(link) => {
+                    ui.hyperlink_to(link.label.as_str(), &link.url);
+                }
+                ActivityButton::Text(text) => {
+                    ui.add_enabled(false, Button::new(text.label.as_str()));
+                }
+                ActivityButton::Unknown => (),
+            }
+        }
+    });
+}
+
+fn activity_heading(activity: &Activity) -> String {
+    match activity.kind {
+        ActivityType::Playing => "PLAYING A GAME".to_owned(),
+        ActivityType::Streaming => "LIVE ON STREAM".to_owned(),
+        ActivityType::Listening => format!("LISTENING TO {}", activity.name.to_uppercase()),
+        ActivityType::Watching => format!("WATCHING {}", activity.name.to_uppercase()),
+        ActivityType::Competing => "COMPETING".to_owned(),
+        _ => "ACTIVITY".to_owned(),
+    }
+}
+
+/// Formats a duration in milliseconds as `1:02:03`, or `02:03` under an hour.
+pub(crate) fn format_duration(millis: u64) -> String {
+    let seconds = millis / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+fn clients(client_status: &ClientStatus) -> impl Iterator<Item = (&'static str, Status)> {
+    [
+        ("desktop", client_status.desktop),
+        ("mobile", client_status.mobile),
+        ("web", client_status.web),
+    ]
+    .into_iter()
+    .filter_map(|(client, status)| Some((client, status?)))
+}
+
+fn is_mobile_only(client_status: &ClientStatus) -> bool {
+    client_status.mobile.is_some() && client_status.desktop.is_none() && client_status.web.is_none()
+}
@@ -0,0 +1,387 @@
+use std::{fs, future::Future};
+
+use egui::{
+    vec2, Align2, Button, Color32, ComboBox, Context, FontId, Rect, RichText, ScrollArea, Sense,
+    TextEdit, Ui, Window,
+};
+use fusioncord_core::{
+    cache::{private_channel_name, Cache},
+    cdn,
+    http::{image_data_uri, HttpClient, HttpError, UpdateGroupDm},
+    read_state::ReadStateStore,
+    relationship::{display_name, RelationshipStore},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::{Channel, ChannelType},
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+    user::RelationshipType,
+};
+
+use crate::{
+    images::ImageCache,
+    renderer::{avatar_url, full_uv},
+    state::UiState,
+};
+
+const ICON_SIZE: f32 = 32.;
+const GROUP_ICON_SIZE: f32 = 64.;
+/// Maximum amount of users in a group DM, including the current user.
+const MAX_GROUP_SIZE: usize = 10;
+
+/// Renders the home tab of the side panel, the entry of the friends tab
+/// followed by the DMs and group DMs of the current user.
+pub fn show_private_channels(
+    ui: &mut Ui,
+    cache: &Cache,
+    read_states: &ReadStateStore,
+    relationships: &RelationshipStore,
+    images: &mut ImageCache,
+    state: &mut UiState,
+) {
+    let requests = relationships.incoming_request_count();
+    let friends = if requests > 0 {
+        format!("👥 Friends ({requests})")
+    } else {
+        "👥 Friends".to_owned()
+    };
+    if ui
+        .selectable_label(state.selected_private_channel.is_none(), friends)
+        .clicked()
+    {
+        state.select_private_channel(None);
+    }
+
+    ui.add_space(8.);
+    ui.label(RichText::new("DIRECT MESSAGES").small().strong());
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for channel in cache.private_channels() {
+                let selected = state.selected_private_channel == Some(channel.id);
+                let read_state = read_states.channel(channel.id);
+                let muted = read_states.is_channel_muted(channel.id);
+                let unread = read_state.is_unread() && !muted;
+
+                ui.horizontal(|ui| {
+                    show_icon(ui, images, channel, ICON_SIZE);
+
+                    let mut text = RichText::new(private_channel_name(channel));
+                    if selected || unread {
+                        text = text.strong();
+                    } else if muted {
+                        text = text.weak();
+                    }
+                    if ui.selectable_label(selected, text).clicked() {
+                        state.select_private_channel(Some(channel.id));
+                    }
+
+                    if read_state.mention_count > 0 {
+                        ui.colored_label(Color32::RED, read_state.mention_count.to_string());
+                    }
+                });
+
+                if channel.kind == ChannelType::Group {
+                    let members = channel.recipients.as_ref().map_or(0, Vec::len) + 1;
+                    ui.weak(format!("{members} Members"));
+                }
+            }
+        });
+}
+
+/// Paints the avatar of the other user of a DM, or the icon of a group DM.
+pub fn show_icon(ui: &mut Ui, images: &mut ImageCache, channel: &Channel, size: f32) {
+    let (rect, _) = ui.allocate_exact_size(vec2(size, size), Sense::hover());
+
+    let url = match (channel.kind, channel.icon) {
+        (ChannelType::Group, Some(icon)) => Some(cdn::channel_icon(channel.id, icon, 64)),
+        (ChannelType::Group, None) => None,
+        _ => channel
+            .recipients
+            .iter()
+            .flatten()
+            .next()
+            .map(|user| avatar_url(user.id, user.avatar, user.discriminator)),
+    };
+
+    match url.as_deref().and_then(|url| images.get(url)) {
+        Some(texture) => {
+            ui.painter()
+                .image(texture.id(), rect, full_uv(), Color32::WHITE);
+        }
+        None => paint_placeholder(ui, rect),
+    }
+}
+
+fn paint_placeholder(ui: &Ui, rect: Rect) {
+    ui.painter()
+        .circle_filled(rect.center(), rect.width() / 2., Color32::DARK_GRAY);
+    ui.painter().text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        "👥",
+        FontId::proportional(rect.width() / 2.),
+        Color32::WHITE,
+    );
+}
+
+/// Window to rename a group DM, change its icon, add and remove its
+/// recipients and transfer its ownership.
+///
+/// Changes are sent over the REST API and shown once the gateway confirms
+/// them.
+pub struct GroupDmSettings {
+    http: HttpClient,
+    runtime: Handle,
+    /// The group DM the window is open for.
+    channel_id: Option<Id<ChannelMarker>>,
+    name: String,
+    /// Friend picked to be added to the group DM.
+    friend: Option<Id<UserMarker>>,
+    error: Option<String>,
+}
+
+impl GroupDmSettings {
+    pub fn new(http: HttpClient, runtime: Handle) -> Self {
+        Self {
+            http,
+            runtime,
+            channel_id: None,
+            name: String::new(),
+            friend: None,
+            error: None,
+        }
+    }
+
+    pub fn open(&mut self, channel: &Channel) {
+        self.channel_id = Some(channel.id);
+        self.name = channel.name.clone().unwrap_or_default();
+        self.friend = None;
+        self.error = None;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        cache: &Cache,
+        relationships: &RelationshipStore,
+        images: &mut ImageCache,
+    ) {
+        let Some(channel) = self.channel_id.and_then(|id| cache.channel(id)) else {
+            self.channel_id = None;
+            return;
+        };
+        let Some(current_user) = cache.current_user() else {
+            return;
+        };
+        let owner = channel.owner_id == Some(current_user.id);
+        let mut open = true;
+
+        Window::new("Group Settings")
+            .id(egui::Id::new(("group_dm_settings", channel.id)))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    show_icon(ui, images, channel, GROUP_ICON_SIZE);
+                    ui.vertical(|ui| {
+                        ui.weak("Drop an image here to change the icon");
+                        if channel.icon.is_some() && ui.button("Remove Icon").clicked() {
+                            self.update(
+                                channel.id,
+                                UpdateGroupDm {
+                                    icon: Some(None),
+                                    ..UpdateGroupDm::default()
+                                },
+                            );
+                        }
+                    });
+                });
+                self.collect_dropped_icon(ui, channel.id);
+                if let Some(error) = &self.error {
+                    ui.colored_label(Color32::RED, error.as_str());
+                }
+                ui.separator();
+
+                ui.label(RichText::new("GROUP NAME").small().strong());
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.name)
+                            .hint_text(private_channel_name(channel)),
+                    );
+                    let changed = channel.name.as_deref().unwrap_or_default() != self.name.trim();
+                    if ui.add_enabled(changed, Button::new("Save")).clicked() {
+                        self.update(
+                            channel.id,
+                            UpdateGroupDm {
+                                name: Some(self.name.trim().to_owned()),
+                                ..UpdateGroupDm::default()
+                            },
+                        );
+                    }
+                });
+                ui.separator();
+
+                let recipients = channel.recipients.as_deref().unwrap_or_default();
+                ui.label(
+                    RichText::new(format!("MEMBERS — {}", recipients.len() + 1))
+                        .small()
+                        .strong(),
+                );
+                for user in recipients {
+                    ui.horizontal(|ui| {
+                        let url = avatar_url(user.id, user.avatar, user.discriminator);
+                        let (rect, _) =
+                            ui.allocate_exact_size(vec2(ICON_SIZE, ICON_SIZE), Sense::hover());
+                        if let Some(texture) = images.get(&url) {
+                            ui.painter()
+                                .image(texture.id(), rect, full_uv(), Color32::WHITE);
+                        }
+
+                        ui.label(user.global_name.as_deref().unwrap_or(&user.name));
+                        if channel.owner_id == Some(user.id) {
+                            ui.label("👑").on_hover_text("Group Owner");
+                        }
+
+                        // only the owner can manage the other members
+                        if owner {
+                            let (channel_id, user_id) = (channel.id, user.id);
+                            if ui.button("Remove").clicked() {
+                                self.spawn("remove group DM recipient", move |http| async move {
+                                    http.remove_recipient(channel_id, user_id).await
+                                });
+                            }
+                            if ui.button("Make Owner").clicked() {
+                                self.update(
+                                    channel_id,
+                                    UpdateGroupDm {
+                                        owner: Some(user_id),
+                                        ..UpdateGroupDm::default()
+                                    },
+                                );
+                            }
+                        }
+                    });
+                }
+                ui.separator();
+
+                self.show_add_recipient(ui, channel, relationships);
+            });
+
+        if !open {
+            self.channel_id = None;
+        }
+    }
+
+    fn show_add_recipient(
+        &mut self,
+        ui: &mut Ui,
+        channel: &Channel,
+        relationships: &RelationshipStore,
+    ) {
+        let recipients = channel.recipients.as_deref().unwrap_or_default();
+        if recipients.len() + 1 >= MAX_GROUP_SIZE {
+            ui.weak("This group is full");
+            return;
+        }
+
+        // users can only be added by their friends
+        let friends = relationships
+            .relationships(&[RelationshipType::Friend])
+            .into_iter()
+            .filter(|friend| !recipients.iter().any(|user| user.id == friend.id))
+            .collect::<Vec<_>>();
+
+        ui.horizontal(|ui| {
+            let selected = self
+                .friend
+                .and_then(|user_id| relationships.relationship(user_id))
+                .map_or("Select a friend", display_name);
+
+            ComboBox::from_id_source(("group_dm_add", channel.id))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for friend in &friends {
+                        ui.selectable_value(
+                            &mut self.friend,
+                            Some(friend.id),
+                            display_name(friend),
+                        );
+                    }
+                });
+
+            if ui
+                .add_enabled(self.friend.is_some(), Button::new("Add"))
+                .clicked()
+            {
+                if let Some(user_id) = self.friend.take() {
+                    let channel_id = channel.id;
+                    self.spawn("add group DM recipient", move |http| async move {
+                        http.add_recipient(channel_id, user_id).await
+                    });
+                }
+            }
+        });
+    }
+
+    /// Uploads an image dropped on the window as the new icon.
+    fn collect_dropped_icon(&mut self, ui: &Ui, channel_id: Id<ChannelMarker>) {
+        let Some(file) = ui.input(|input| input.raw.dropped_files.first().cloned()) else {
+            return;
+        };
+
+        let bytes = match (&file.bytes, &file.path) {
+            (Some(bytes), _) => bytes.to_vec(),
+            (None, Some(path)) => match fs::read(path) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    warn!("Failed to read dropped file {}: {e}", path.display());
+                    return;
+                }
+            },
+            (None, None) => return,
+        };
+
+        match image_data_uri(&bytes) {
+            Some(icon) => {
+                self.error = None;
+                self.update(
+                    channel_id,
+                    UpdateGroupDm {
+                        icon: Some(Some(icon)),
+                        ..UpdateGroupDm::default()
+                    },
+                );
+            }
+            None => {
+                self.error = Some("Icons must be PNG, JPEG, GIF or WebP images".to_owned());
+            }
+        }
+    }
+
+    fn update(&self, channel_id: Id<ChannelMarker>, update: UpdateGroupDm) {
+        self.spawn("update group DM", move |http| async move {
+            http.update_group_dm(channel_id, &update).await
+        });
+    }
+
+    fn spawn<F, Fut>(&self, action: &'static str, request: F)
+    where
+        F: FnOnce(HttpClient) -> Fut,
+        Fut: Future<Output = Result<(), HttpError>> + Send + 'static,
+    {
+        let request = request(self.http.clone());
+        self.runtime.spawn(async move {
+            if let Err(e) = request.await {
+                warn!("Failed to {action}: {e}");
+            }
+        });
+    }
+}
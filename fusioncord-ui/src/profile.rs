@@ -0,0 +1,491 @@
+use std::sync::mpsc::Sender;
+
+use egui::{vec2, Color32, Context, RichText, Sense, TextEdit, Ui, Window};
+use fusioncord_core::{
+    cache::Cache,
+    cdn,
+    http::HttpClient,
+    message::{notify, RenderMessage},
+    profile::{badges, connection_url, Badge, Profile, ProfileStore},
+    role::{role_icon, RoleStore},
+    user_presence::UserPresenceStore,
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    gateway::presence::Status,
+    id::{
+        marker::{GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    user::{ProfileResponse, User, UserFlags},
+};
+
+use crate::{
+    images::ImageCache,
+    presence::{capitalize, paint_status, show_activities, show_client_status},
+    renderer::{avatar_url, format_timestamp, full_uv, show_role_icon},
+};
+
+const WIDTH: f32 = 300.;
+const BANNER_HEIGHT: f32 = 100.;
+const AVATAR_SIZE: f32 = 64.;
+/// Banner color of users that didn't pick one.
+const DEFAULT_BANNER_COLOR: Color32 = Color32::from_rgb(88, 101, 242);
+
+/// User whose profile is open.
+#[derive(Debug)]
+struct ProfileTarget {
+    /// Guild the profile was opened from, whose member profile is shown.
+    guild_id: Option<Id<GuildMarker>>,
+    user: User,
+    nick: Option<String>,
+    /// Note being edited, `None` until the note is known.
+    note: Option<String>,
+}
+
+/// Profile of a user opened from the member list or a message, fetched when
+/// it's opened.
+pub struct ProfilePopout {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+    target: Option<ProfileTarget>,
+}
+
+impl ProfilePopout {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self {
+            http,
+            runtime,
+            tx,
+            target: None,
+        }
+    }
+
+    /// Opens the profile of a user as it's shown in the given guild.
+    pub fn open(&mut self, guild_id: Option<Id<GuildMarker>>, user: User, nick: Option<String>) {
+        self.target = Some(ProfileTarget {
+            guild_id,
+            user,
+            nick,
+            note: None,
+        });
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        cache: &Cache,
+        presences: &UserPresenceStore,
+        roles: &RoleStore,
+        profiles: &mut ProfileStore,
+        images: &mut ImageCache,
+    ) {
+        let Some(target) = &self.target else {
+            return;
+        };
+        let (user_id, guild_id) = (target.user.id, target.guild_id);
+        if profiles.start_fetch(user_id, guild_id) {
+            self.fetch(user_id, guild_id);
+        }
+
+        let Some(target) = &mut self.target else {
+            return;
+        };
+        if target.note.is_none() {
+            target.note = profiles.note(user_id).map(str::to_owned);
+        }
+
+        let profile = match profiles.profile(user_id, guild_id) {
+            Some(Profile::Loaded(profile)) => Some(&**profile),
+            _ => None,
+        };
+        let failed = matches!(profiles.profile(user_id, guild_id), Some(Profile::Failed));
+        let mut open = true;
+        let mut saved_note = None;
+
+        Window::new(display_name(target, profile))
+            .id(egui::Id::new("profile_popout"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(WIDTH)
+            .show(ctx, |ui| {
+                ui.set_width(WIDTH);
+                show_header(ui, presences, images, target, profile);
+                ui.separator();
+                show_presence(ui, presences, images, target);
+
+                match profile {
+                    Some(profile) => show_details(ui, cache, roles, images, target, profile),
+                    None if failed => {
+                        ui.weak("Failed to load the profile.");
+                    }
+                    None => {
+                        ui.spinner();
+                    }
+                }
+
+                ui.add_space(8.);
+                ui.label(RichText::new("NOTE").small().strong());
+                match &mut target.note {
+                    Some(note) => {
+                        let response = ui.add(
+                            TextEdit::multiline(note)
+                                .hint_text("Click to add a note")
+                                .desired_rows(2)
+                                .desired_width(f32::INFINITY)
+                                .char_limit(256),
+                        );
+                        // notes are saved once the user is done editing them
+                        if response.lost_focus() && profiles.note(user_id) != Some(note.as_str()) {
+                            saved_note = Some(note.clone());
+                        }
+                    }
+                    None => {
+                        ui.spinner();
+                    }
+                }
+            });
+
+        if let Some(note) = saved_note {
+            self.save_note(profiles, user_id, note);
+        }
+        if !open {
+            self.target = None;
+        }
+    }
+
+    /// Fetches the profile of a user along with the note the current user
+    /// keeps about them.
+    fn fetch(&self, user_id: Id<UserMarker>, guild_id: Option<Id<GuildMarker>>) {
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let (profile, note) = tokio::join!(
+                http.user_profile(user_id, guild_id),
+                http.user_note(user_id)
+            );
+
+            let profile = profile
+                .inspect_err(|e| warn!("Failed to fetch the profile of user {user_id}: {e}"))
+                .ok()
+                .map(Box::new);
+            notify(
+                &tx,
+                RenderMessage::Profile {
+                    user_id,
+                    guild_id,
+                    profile,
+                },
+            );
+
+            match note {
+                Ok(note) => {
+                    notify(&tx, RenderMessage::UserNote { user_id, note });
+                }
+                Err(e) => warn!("Failed to fetch the note of user {user_id}: {e}"),
+            }
+        });
+    }
+
+    /// Saves the note of a user, other sessions receive it through the
+    /// gateway.
+    fn save_note(&self, profiles: &mut ProfileStore, user_id: Id<UserMarker>, note: String) {
+        profiles.set_note(user_id, note.clone());
+
+        let http = self.http.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.update_user_note(user_id, &note).await {
+                warn!("Failed to update the note of user {user_id}: {e}");
+            }
+        });
+    }
+}
+
+/// Shows the banner with the avatar, names and badges of the user.
+fn show_header(
+    ui: &mut Ui,
+    presences: &UserPresenceStore,
+    images: &mut ImageCache,
+    target: &ProfileTarget,
+    profile: Option<&ProfileResponse>,
+) {
+    let user = &target.user;
+    let (banner_rect, _) = ui.allocate_exact_size(vec2(WIDTH, BANNER_HEIGHT), Sense::hover());
+    let color = profile
+        .and_then(accent_color)
+        .map_or(DEFAULT_BANNER_COLOR, to_color);
+    ui.painter().rect_filled(banner_rect, 4., color);
+    if let Some(url) = profile.and_then(|profile| banner_url(target, profile)) {
+        if let Some(texture) = images.get(&url) {
+            ui.painter()
+                .image(texture.id(), banner_rect, full_uv(), Color32::WHITE);
+        }
+    }
+
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(vec2(AVATAR_SIZE, AVATAR_SIZE), Sense::hover());
+        if let Some(texture) = images.get(&avatar(target, profile)) {
+            ui.painter()
+                .image(texture.id(), rect, full_uv(), Color32::WHITE);
+        }
+        let presence = presences.presence(target.guild_id, user.id);
+        paint_status(
+            ui,
+            rect.right_bottom() - vec2(6., 6.),
+            presence.map_or(Status::Offline, |presence| presence.status),
+            presence.map(|presence| &presence.client_status),
+        );
+
+        ui.vertical(|ui| {
+            ui.label(
+                RichText::new(display_name(target, profile))
+                    .heading()
+                    .strong(),
+            );
+            let pronouns = profile
+                .and_then(|profile| profile.user_profile.as_ref())
+                .map(|metadata| metadata.pronouns.as_str())
+                .unwrap_or_default();
+            if pronouns.is_empty() {
+                ui.weak(user.name.as_str());
+            } else {
+                ui.weak(format!("{} • {pronouns}", user.name));
+            }
+
+            if let Some(profile) = profile {
+                show_badges(ui, profile);
+            }
+        });
+    });
+}
+
+fn show_badges(ui: &mut Ui, profile: &ProfileResponse) {
+    let flags = profile.user.flags.unwrap_or_else(UserFlags::empty);
+    let badges = badges(flags, profile.premium_type);
+    if badges.is_empty() {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        for badge in badges {
+            let description = match (badge, profile.premium_since) {
+                (Badge::Nitro, Some(since)) => {
+                    format!("Subscriber since {}", format_timestamp(since))
+                }
+                _ => badge.description().to_owned(),
+            };
+            ui.label(badge_icon(badge)).on_hover_text(description);
+        }
+    });
+}
+
+fn show_presence(
+    ui: &mut Ui,
+    presences: &UserPresenceStore,
+    images: &mut ImageCache,
+    target: &ProfileTarget,
+) {
+    if let Some(presence) = presences.presence(target.guild_id, target.user.id) {
+        show_client_status(ui, presence.status, &presence.client_status);
+        show_activities(ui, &presence.activities, images);
+    }
+}
+
+fn show_details(
+    ui: &mut Ui,
+    cache: &Cache,
+    roles: &RoleStore,
+    images: &mut ImageCache,
+    target: &ProfileTarget,
+    profile: &ProfileResponse,
+) {
+    let bio = profile
+        .guild_member_profile
+        .as_ref()
+        .map(|metadata| metadata.bio.as_str())
+        .filter(|bio| !bio.is_empty())
+        .or_else(|| {
+            profile
+                .user_profile
+                .as_ref()
+                .map(|metadata| metadata.bio.as_str())
+        })
+        .unwrap_or_default();
+    if !bio.is_empty() {
+        section(ui, "ABOUT ME");
+        ui.label(bio);
+    }
+
+    if let (Some(guild_id), Some(member)) = (target.guild_id, &profile.guild_member) {
+        show_roles(ui, roles, images, guild_id, &member.roles);
+    }
+
+    if !profile.connected_accounts.is_empty() {
+        section(ui, "CONNECTIONS");
+        for account in &profile.connected_accounts {
+            ui.horizontal(|ui| {
+                ui.weak(capitalize(&account.kind));
+                match connection_url(account) {
+                    Some(url) => {
+                        ui.hyperlink_to(account.name.as_str(), url);
+                    }
+                    None => {
+                        ui.label(account.name.as_str());
+                    }
+                }
+                if account.verified {
+                    ui.label("✔").on_hover_text("Verified");
+                }
+            });
+        }
+    }
+
+    if !profile.mutual_guilds.is_empty() {
+        section(
+            ui,
+            &format!("MUTUAL SERVERS — {}", profile.mutual_guilds.len()),
+        );
+        for mutual_guild in &profile.mutual_guilds {
+            let Some(guild) = cache.guild(mutual_guild.id) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                ui.label(guild.name.as_str());
+                if let Some(nick) = &mutual_guild.nick {
+                    ui.weak(format!("as {nick}"));
+                }
+            });
+        }
+    }
+
+    if !profile.mutual_friends.is_empty() {
+        section(
+            ui,
+            &format!("MUTUAL FRIENDS — {}", profile.mutual_friends.len()),
+        );
+        for friend in &profile.mutual_friends {
+            ui.label(friend.global_name.as_deref().unwrap_or(&friend.name));
+        }
+    }
+}
+
+/// Shows the roles of a member, the highest first.
+fn show_roles(
+    ui: &mut Ui,
+    roles: &RoleStore,
+    images: &mut ImageCache,
+    guild_id: Id<GuildMarker>,
+    role_ids: &[Id<RoleMarker>],
+) {
+    let roles = roles.sorted_roles(guild_id, role_ids);
+    if roles.is_empty() {
+        return;
+    }
+
+    section(ui, "ROLES");
+    ui.horizontal_wrapped(|ui| {
+        for role in roles {
+            // roles without a color don't change the color of their members
+            let color = match role.color {
+                0 => ui.visuals().weak_text_color(),
+                color => to_color(color),
+            };
+            ui.group(|ui| {
+                ui.colored_label(color, "●");
+                if let Some(icon) = role_icon(role) {
+                    show_role_icon(ui, &icon, images);
+                }
+                ui.label(role.name.as_str());
+            });
+        }
+    });
+}
+
+fn section(ui: &mut Ui, title: &str) {
+    ui.add_space(8.);
+    ui.label(RichText::new(title).small().strong());
+}
+
+/// Name of the user, preferring their nickname in the guild.
+fn display_name<'a>(target: &'a ProfileTarget, profile: Option<&'a ProfileResponse>) -> &'a str {
+    profile
+        .and_then(|profile| profile.guild_member.as_ref())
+        .and_then(|member| member.nick.as_deref())
+        .or(target.nick.as_deref())
+        .or(target.user.global_name.as_deref())
+        .unwrap_or(&target.user.name)
+}
+
+/// Avatar of the user, preferring the one they use in the guild.
+fn avatar(target: &ProfileTarget, profile: Option<&ProfileResponse>) -> String {
+    let user = &target.user;
+    let member_avatar = profile
+        .and_then(|profile| profile.guild_member.as_ref())
+        .and_then(|member| member.avatar);
+
+    match (target.guild_id, member_avatar) {
+        (Some(guild_id), Some(avatar)) => cdn::member_avatar(guild_id, user.id, avatar, 128),
+        _ => avatar_url(user.id, user.avatar, user.discriminator),
+    }
+}
+
+/// Banner of the user, preferring the one they use in the guild.
+fn banner_url(target: &ProfileTarget, profile: &ProfileResponse) -> Option<String> {
+    let user_id = target.user.id;
+    let member_banner = profile
+        .guild_member_profile
+        .as_ref()
+        .and_then(|metadata| metadata.banner);
+
+    match (target.guild_id, member_banner) {
+        (Some(guild_id), Some(banner)) => Some(cdn::member_banner(guild_id, user_id, banner, 600)),
+        _ => profile
+            .user_profile
+            .as_ref()
+            .and_then(|metadata| metadata.banner)
+            .or(profile.user.banner)
+            .map(|banner| cdn::user_banner(user_id, banner, 600)),
+    }
+}
+
+/// Accent color of the banner, preferring the one used in the guild.
+fn accent_color(profile: &ProfileResponse) -> Option<u32> {
+    profile
+        .guild_member_profile
+        .as_ref()
+        .and_then(|metadata| metadata.accent_color)
+        .or_else(|| {
+            profile
+                .user_profile
+                .as_ref()
+                .and_then(|metadata| metadata.accent_color)
+        })
+        .or(profile.user.accent_color)
+}
+
+/// Converts an integer representation of a hexadecimal color code.
+pub(crate) const fn to_color(color: u32) -> Color32 {
+    Color32::from_rgb((color >> 16) as u8, (color >> 8) as u8, color as u8)
+}
+
+const fn badge_icon(badge: Badge) -> &'static str {
+    match badge {
+        Badge::Staff => "🛠",
+        Badge::Partner => "♾",
+        Badge::ModeratorProgramsAlumni => "🛡",
+        Badge::HypeSquadEvents => "🎉",
+        Badge::HypeSquadBravery => "🟣",
+        Badge::HypeSquadBrilliance => "🟠",
+        Badge::HypeSquadBalance => "🟢",
+        Badge::BugHunter => "🐛",
+        Badge::GoldBugHunter => "🐞",
+        Badge::ActiveDeveloper => "💻",
+        Badge::VerifiedDeveloper => "🤖",
+        Badge::EarlySupporter => "🕰",
+        Badge::Nitro => "💎",
+    }
+}
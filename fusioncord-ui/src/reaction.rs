@@ -0,0 +1,329 @@
+use std::sync::mpsc::Sender;
+
+use egui::{vec2, Button, Color32, RichText, Stroke, Ui};
+use fusioncord_core::{
+    cdn,
+    http::HttpClient,
+    message::{notify, RenderMessage},
+    reaction::{same_emoji, ReactionStore, Reactors},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::message::{Message, Reaction, ReactionType},
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
+};
+
+use crate::images::ImageCache;
+
+const HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(56, 60, 110);
+const HIGHLIGHT_STROKE: Color32 = Color32::from_rgb(88, 101, 242);
+const BURST_COLOR: Color32 = Color32::from_rgb(240, 180, 60);
+/// Amount of reactors listed by name when hovering a reaction.
+const MAX_REACTORS_SHOWN: usize = 10;
+
+/// Action the user took on the reactions of a message.
+pub enum ReactionAction {
+    Toggle {
+        emoji: ReactionType,
+        burst: bool,
+    },
+    /// The reactors of a reaction are hovered but weren't fetched yet.
+    FetchReactors {
+        emoji: ReactionType,
+        burst: bool,
+    },
+    /// The emoji picker is opened to add a new reaction.
+    OpenPicker,
+}
+
+/// Renders the reactions of a message as chips, burst reactions get a chip
+/// of their own.
+pub fn show_reactions(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    message: &Message,
+    reactions: &ReactionStore,
+) -> Option<ReactionAction> {
+    if message.reactions.is_empty() {
+        return None;
+    }
+
+    let mut action = None;
+
+    ui.horizontal_wrapped(|ui| {
+        for reaction in &message.reactions {
+            let (normal, burst) = reaction
+                .count_details
+                .map_or((reaction.count, 0), |details| {
+                    (details.normal, details.burst)
+                });
+
+            for (burst, count) in [(true, burst), (false, normal)] {
+                if count == 0 {
+                    continue;
+                }
+
+                if let Some(used) =
+                    show_chip(ui, images, message.id, reaction, burst, count, reactions)
+                {
+                    action = Some(used);
+                }
+            }
+        }
+    });
+
+    action
+}
+
+fn show_chip(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    message_id: Id<MessageMarker>,
+    reaction: &Reaction,
+    burst: bool,
+    count: u64,
+    reactions: &ReactionStore,
+) -> Option<ReactionAction> {
+    let mut action = None;
+    let me = if burst {
+        reaction.me_burst
+    } else {
+        reaction.me
+    };
+    let text = match (&reaction.emoji, burst) {
+        (ReactionType::Unicode { name }, false) => format!("{name} {count}"),
+        (ReactionType::Unicode { name }, true) => format!("{name} ✨{count}"),
+        (ReactionType::Custom { .. }, false) => count.to_string(),
+        (ReactionType::Custom { .. }, true) => format!("✨{count}"),
+    };
+
+    let texture = match &reaction.emoji {
+        ReactionType::Custom { id, animated, .. } => images
+            .get(&cdn::emoji(*id, *animated, 48))
+            .map(|texture| texture.id()),
+        ReactionType::Unicode { .. } => None,
+    };
+    let button = match texture {
+        Some(texture) => Button::image_and_text(texture, vec2(16., 16.), RichText::new(text)),
+        None => Button::new(RichText::new(text)),
+    };
+
+    let stroke_color = match (burst, me) {
+        (true, _) => burst_color(reaction).unwrap_or(BURST_COLOR),
+        (false, true) => HIGHLIGHT_STROKE,
+        (false, false) => Color32::TRANSPARENT,
+    };
+    let fill = if me {
+        HIGHLIGHT_COLOR
+    } else {
+        ui.visuals().faint_bg_color
+    };
+
+    let response = ui
+        .add(
+            button
+                .fill(fill)
+                .stroke(Stroke::new(1., stroke_color))
+                .rounding(8.),
+        )
+        .on_hover_ui(|ui| {
+            match reactions.reactors(message_id, &reaction.emoji, burst) {
+                Some(Reactors::Loaded(users)) => {
+                    let mut names = users
+                        .iter()
+                        .take(MAX_REACTORS_SHOWN)
+                        .map(|user| user.global_name.as_deref().unwrap_or(&user.name))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let others = (count as usize).saturating_sub(MAX_REACTORS_SHOWN);
+                    if others > 0 {
+                        names.push_str(&format!(" and {others} others"));
+                    }
+                    ui.label(names);
+                }
+                Some(Reactors::Loading) => {
+                    ui.spinner();
+                }
+                Some(Reactors::Failed) => {
+                    ui.weak("Failed to load reactions");
+                }
+                None => {
+                    ui.spinner();
+                    action = Some(ReactionAction::FetchReactors {
+                        emoji: reaction.emoji.clone(),
+                        burst,
+                    });
+                }
+            }
+
+            let name = match &reaction.emoji {
+                ReactionType::Custom { name, .. } => {
+                    format!(":{}:", name.as_deref().unwrap_or("unknown"))
+                }
+                ReactionType::Unicode { name } => name.clone(),
+            };
+            ui.weak(if burst {
+                format!("Super reacted with {name}")
+            } else {
+                format!("Reacted with {name}")
+            });
+        });
+
+    if response.clicked() {
+        action = Some(ReactionAction::Toggle {
+            emoji: reaction.emoji.clone(),
+            burst,
+        });
+    }
+
+    if !burst && !reaction.me_burst {
+        response.context_menu(|ui| {
+            let credits = reactions.burst_credits();
+            let label = match credits {
+                Some(credits) => format!("Add Super Reaction ({credits} left)"),
+                None => "Add Super Reaction".to_owned(),
+            };
+
+            if ui
+                .add_enabled(credits != Some(0), Button::new(label))
+                .clicked()
+            {
+                action = Some(ReactionAction::Toggle {
+                    emoji: reaction.emoji.clone(),
+                    burst: true,
+                });
+                ui.close_menu();
+            }
+        });
+    }
+
+    action
+}
+
+/// First colour of the burst animation, sent as a hex string like `#ff0000`.
+fn burst_color(reaction: &Reaction) -> Option<Color32> {
+    let hex = reaction.burst_colors.first()?.strip_prefix('#')?;
+    let [_, r, g, b] = u32::from_str_radix(hex, 16).ok()?.to_be_bytes();
+
+    Some(Color32::from_rgb(r, g, b))
+}
+
+/// Whether the current user reacted to a message with an emoji.
+fn reacted(message: &Message, emoji: &ReactionType, burst: bool) -> bool {
+    message.reactions.iter().any(|reaction| {
+        same_emoji(&reaction.emoji, emoji)
+            && if burst {
+                reaction.me_burst
+            } else {
+                reaction.me
+            }
+    })
+}
+
+/// Sends reaction requests of the current user.
+pub struct ReactionClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+}
+
+impl ReactionClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self { http, runtime, tx }
+    }
+
+    /// Adds the reaction of the current user, or removes it if they already
+    /// reacted.
+    ///
+    /// The message is updated once the gateway confirms the change, the
+    /// fetched reactors are forgotten right away.
+    pub fn toggle(
+        &self,
+        reactions: &mut ReactionStore,
+        message: &Message,
+        emoji: ReactionType,
+        burst: bool,
+    ) {
+        let remove = reacted(message, &emoji, burst);
+        self.send(reactions, message, emoji, burst, remove);
+    }
+
+    /// Adds the reaction of the current user, unless they already reacted.
+    pub fn add(
+        &self,
+        reactions: &mut ReactionStore,
+        message: &Message,
+        emoji: ReactionType,
+        burst: bool,
+    ) {
+        if !reacted(message, &emoji, burst) {
+            self.send(reactions, message, emoji, burst, false);
+        }
+    }
+
+    fn send(
+        &self,
+        reactions: &mut ReactionStore,
+        message: &Message,
+        emoji: ReactionType,
+        burst: bool,
+        remove: bool,
+    ) {
+        reactions.evict(message.id, &emoji, burst);
+
+        let http = self.http.clone();
+        let (channel_id, message_id) = (message.channel_id, message.id);
+        self.runtime.spawn(async move {
+            let result = if remove {
+                http.delete_own_reaction(channel_id, message_id, &emoji, burst)
+                    .await
+            } else {
+                http.create_reaction(channel_id, message_id, &emoji, burst)
+                    .await
+            };
+
+            if let Err(e) = result {
+                warn!("Failed to update reaction: {e}");
+            }
+        });
+    }
+
+    /// Fetches the users that reacted with an emoji, unless they already
+    /// were.
+    pub fn fetch_reactors(
+        &self,
+        reactions: &mut ReactionStore,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        emoji: ReactionType,
+        burst: bool,
+    ) {
+        if !reactions.start_fetch(message_id, &emoji, burst) {
+            return;
+        }
+
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            let users = http
+                .reactions(channel_id, message_id, &emoji, burst)
+                .await
+                .inspect_err(|e| warn!("Failed to fetch reactions: {e}"))
+                .ok();
+
+            notify(
+                &tx,
+                RenderMessage::Reactors {
+                    message_id,
+                    emoji,
+                    burst,
+                    users,
+                },
+            );
+        });
+    }
+}
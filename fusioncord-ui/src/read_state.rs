@@ -0,0 +1,31 @@
+use fusioncord_core::{http::HttpClient, read_state::ReadStateStore};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::id::{marker::ChannelMarker, Id};
+
+/// Acknowledges the channels the current user has read.
+pub struct ReadStateClient {
+    http: HttpClient,
+    runtime: Handle,
+}
+
+impl ReadStateClient {
+    pub fn new(http: HttpClient, runtime: Handle) -> Self {
+        Self { http, runtime }
+    }
+
+    /// Marks a channel as read up to its last message, unless it already
+    /// was.
+    pub fn ack(&self, read_states: &mut ReadStateStore, channel_id: Id<ChannelMarker>) {
+        let Some(message_id) = read_states.ack_latest(channel_id) else {
+            return;
+        };
+
+        let http = self.http.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = http.ack_message(channel_id, message_id).await {
+                warn!("Failed to acknowledge {channel_id}: {e}");
+            }
+        });
+    }
+}
@@ -1,4 +1,61 @@
-use egui::{pos2, Align, CentralPanel, Color32, Layout, ScrollArea, SidePanel, Stroke};
+use egui::{
+    pos2, vec2, Align2, CentralPanel, Color32, FontId, Label, Rect, RichText, ScrollArea, Sense,
+    SidePanel, TopBottomPanel, Ui,
+};
+use fusioncord_core::{
+    attachment::AttachmentStore,
+    cache::{is_archived, is_private, private_channel_name, Cache, PendingMessage},
+    cdn,
+    highlight::HighlightCache,
+    http::CreateInteraction,
+    inbox::InboxStore,
+    interaction::InteractionStore,
+    presence::PresenceStore,
+    reaction::ReactionStore,
+    read_state::ReadStateStore,
+    relationship::RelationshipStore,
+    role::{MemberAppearance, RoleIcon, RoleStore},
+    session::SessionStore,
+    thread::{is_forum, ThreadStore},
+    user_presence::UserPresenceStore,
+    user_settings::UserSettingsStore,
+};
+use time::{macros::format_description, OffsetDateTime, UtcOffset};
+use twilight_model::{
+    application::interaction::message_component::MessageComponentInteractionData,
+    channel::{message::MessageType, Channel, ChannelType, Message},
+    id::{
+        marker::{AttachmentMarker, MessageMarker, UserMarker},
+        Id,
+    },
+    util::{ImageHash, Timestamp},
+};
+
+use crate::{
+    account, attachment, component,
+    composer::{Composer, ComposerStores},
+    embed,
+    friends::FriendsView,
+    guild_list::{GuildList, GuildListAction, CIRCLE_DIAMETER, CIRCLE_MARGIN, CIRCLE_RADIUS},
+    highlight::MessageCode,
+    images::ImageCache,
+    markdown::{self, MentionContext},
+    member_list, private_channel,
+    profile::{to_color, ProfilePopout},
+    reaction::{self, ReactionAction},
+    state::UiState,
+    status::{StatusAction, StatusPicker},
+    sticker,
+    thread::{self, ThreadAction},
+};
+
+/// Messages of the same author sent within this window are shown as a group.
+const MESSAGE_GROUP_WINDOW_MICROS: i64 = 7 * 60 * 1_000_000;
+const AVATAR_SIZE: f32 = 40.;
+const ROLE_ICON_SIZE: f32 = 16.;
+const STICKER_SIZE: f32 = 160.;
+const BLURPLE: Color32 = Color32::from_rgb(88, 101, 242);
+const BOOSTER_COLOR: Color32 = Color32::from_rgb(244, 127, 255);
 
 pub struct Renderer {
     ctx: egui::Context,
@@ -9,69 +66,840 @@ impl Renderer {
         Self { ctx }
     }
 
-    pub fn render_server_list(&mut self, servercount: u32) {
-        const CIRCLE_RADIUS: f32 = 23.;
-        const CIRCLE_DIAMETER: f32 = CIRCLE_RADIUS * 2.;
-        const CIRCLE_MARGIN: f32 = 10.;
+    /// Renders the home button and the guild list, returning the change the
+    /// user made to the guild list, if any.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_server_list(
+        &mut self,
+        cache: &Cache,
+        read_states: &ReadStateStore,
+        relationships: &RelationshipStore,
+        user_settings: &UserSettingsStore,
+        inbox: &InboxStore,
+        guild_list: &mut GuildList,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) -> Option<GuildListAction> {
+        let mut action = None;
 
         SidePanel::left("servers_panel")
             .exact_width(CIRCLE_DIAMETER + 2. * CIRCLE_MARGIN)
+            .resizable(false)
             .show(&self.ctx, |ui| {
-                for i in 0..=servercount {
-                    let x = CIRCLE_MARGIN + CIRCLE_RADIUS;
-                    let y = (i as f32) * (CIRCLE_DIAMETER + CIRCLE_MARGIN)
-                        + CIRCLE_MARGIN
-                        + CIRCLE_RADIUS;
-
-                    ui.painter().circle(
-                        pos2(x, y),
-                        CIRCLE_RADIUS,
-                        Color32::LIGHT_GRAY,
-                        Stroke::default(),
-                    );
-                }
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.spacing_mut().item_spacing.y = CIRCLE_MARGIN;
+                        ui.add_space(CIRCLE_MARGIN);
+
+                        let (rect, response) = ui.allocate_exact_size(
+                            vec2(CIRCLE_DIAMETER, CIRCLE_DIAMETER),
+                            Sense::click(),
+                        );
+                        let selected = state.selected_guild.is_none();
+                        let fill = if selected || response.hovered() {
+                            BLURPLE
+                        } else {
+                            Color32::DARK_GRAY
+                        };
+                        ui.painter()
+                            .circle_filled(rect.center(), CIRCLE_RADIUS, fill);
+                        ui.painter().text(
+                            rect.center(),
+                            Align2::CENTER_CENTER,
+                            "🏠",
+                            FontId::proportional(20.),
+                            Color32::WHITE,
+                        );
+                        // friend requests and mentions in DMs both need attention
+                        let badge = relationships.incoming_request_count() as u32
+                            + cache
+                                .private_channels()
+                                .into_iter()
+                                .map(|channel| read_states.channel(channel.id).mention_count)
+                                .sum::<u32>();
+                        if badge > 0 {
+                            paint_mention_badge(ui, rect, badge);
+                        }
+                        if response.clicked() {
+                            state.selected_guild = None;
+                        }
+                        response.on_hover_text("Home");
+
+                        let (rect, response) = ui.allocate_exact_size(
+                            vec2(CIRCLE_DIAMETER, CIRCLE_DIAMETER),
+                            Sense::click(),
+                        );
+                        let fill = if state.inbox_open || response.hovered() {
+                            BLURPLE
+                        } else {
+                            Color32::DARK_GRAY
+                        };
+                        ui.painter()
+                            .circle_filled(rect.center(), CIRCLE_RADIUS, fill);
+                        ui.painter().text(
+                            rect.center(),
+                            Align2::CENTER_CENTER,
+                            "📥",
+                            FontId::proportional(20.),
+                            Color32::WHITE,
+                        );
+                        let unacked = inbox.unacked_count();
+                        if unacked > 0 {
+                            paint_mention_badge(ui, rect, unacked as u32);
+                        }
+                        if response.clicked() {
+                            state.inbox_open = !state.inbox_open;
+                        }
+                        response.on_hover_text("Inbox");
+                        ui.separator();
+
+                        action =
+                            guild_list.show(ui, cache, read_states, user_settings, images, state);
+                    });
+                });
             });
+
+        action
     }
 
-    pub fn render_channels(&mut self, channelcount: u32) {
+    /// Renders the channel list with the current user below it, returning
+    /// the change they made to their status, if any.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_channels(
+        &mut self,
+        cache: &Cache,
+        read_states: &ReadStateStore,
+        threads: &ThreadStore,
+        relationships: &RelationshipStore,
+        sessions: &SessionStore,
+        presence: &PresenceStore,
+        status_picker: &mut StatusPicker,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) -> Option<StatusAction> {
+        let mut action = None;
+
         SidePanel::left("side_panel").show(&self.ctx, |ui| {
-            ui.heading("Channels");
+            TopBottomPanel::bottom("user_panel").show_inside(ui, |ui| {
+                action = account::show_user_panel(
+                    ui,
+                    cache,
+                    sessions,
+                    presence,
+                    status_picker,
+                    images,
+                    state,
+                );
+            });
 
-            for i in 0..=channelcount {
-                ui.label(format!("channel {i}"));
-            }
+            let Some(guild_id) = state.selected_guild else {
+                private_channel::show_private_channels(
+                    ui,
+                    cache,
+                    read_states,
+                    relationships,
+                    images,
+                    state,
+                );
+                return;
+            };
+            let Some(guild) = cache.guild(guild_id) else {
+                return;
+            };
+
+            ui.heading(guild.name.as_str());
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for group in cache.channel_groups(guild.id) {
+                        let collapsed = match group.category {
+                            Some(category) => {
+                                let collapsed = state.collapsed_categories.contains(&category.id);
+                                let arrow = if collapsed { "▶" } else { "▼" };
+                                let name = category.name.as_deref().unwrap_or_default();
+                                let header =
+                                    RichText::new(format!("{arrow} {}", name.to_uppercase()))
+                                        .small()
+                                        .strong();
+
+                                ui.add_space(8.);
+                                if ui.add(Label::new(header).sense(Sense::click())).clicked() {
+                                    state.toggle_category(category.id);
+                                }
+
+                                collapsed
+                            }
+                            None => false,
+                        };
+
+                        for channel in group.channels {
+                            let selected = state.selected_channel() == Some(channel.id);
+                            let read_state = read_states.channel(channel.id);
+                            let muted = read_states.is_channel_muted(channel.id);
+                            let unread = read_state.is_unread() && !muted;
+
+                            // collapsed categories still show the open and unread channels
+                            if collapsed && !selected && !unread && read_state.mention_count == 0 {
+                                continue;
+                            }
+
+                            let name = channel.name.as_deref().unwrap_or_default();
+                            let mut text =
+                                RichText::new(format!("{} {name}", channel_icon(channel)));
+                            if selected || unread {
+                                text = text.strong();
+                            } else if muted {
+                                text = text.weak();
+                            }
+
+                            ui.horizontal(|ui| {
+                                let response = ui.selectable_label(selected, text);
+                                if read_state.mention_count > 0 {
+                                    ui.colored_label(
+                                        Color32::RED,
+                                        read_state.mention_count.to_string(),
+                                    );
+                                }
 
-            ui.with_layout(Layout::bottom_up(Align::LEFT), |ui| {
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 0.0;
-                    ui.label("powered by ");
-                    ui.hyperlink_to("egui", "https://github.com/emilk/egui");
-                    ui.label(" and ");
-                    ui.hyperlink_to(
-                        "eframe",
-                        "https://github.com/emilk/egui/tree/master/crates/eframe",
-                    );
-                    ui.label(".");
+                                if response.clicked()
+                                    && (is_text_channel(channel) || is_forum(channel))
+                                {
+                                    state.select_channel(guild.id, channel.id);
+                                }
+                            });
+
+                            render_joined_threads(ui, cache, read_states, threads, channel, state);
+                        }
+                    }
                 });
+        });
+
+        action
+    }
+
+    /// Renders the members of the open guild, opening the profile of the
+    /// one the user clicks.
+    pub fn render_member_list(
+        &mut self,
+        cache: &Cache,
+        presences: &UserPresenceStore,
+        roles: &RoleStore,
+        profile: &mut ProfilePopout,
+        images: &mut ImageCache,
+        state: &UiState,
+    ) {
+        let Some(guild) = state
+            .selected_guild
+            .and_then(|guild_id| cache.guild(guild_id))
+        else {
+            return;
+        };
+
+        SidePanel::right("member_list").show(&self.ctx, |ui| {
+            member_list::show_member_list(ui, guild, presences, roles, profile, images);
+        });
+    }
+
+    /// Renders the composer of the open channel, returning the interaction
+    /// to send when a command was used.
+    pub fn render_composer(
+        &mut self,
+        composer: &mut Composer,
+        stores: ComposerStores<'_>,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) -> Option<CreateInteraction> {
+        let channel_id = state.selected_channel()?;
+        // posts are created from the forum view instead
+        if stores.cache.channel(channel_id).is_some_and(is_forum) {
+            return None;
+        }
+        let guild_id = state.selected_guild;
+
+        TopBottomPanel::bottom("composer_panel")
+            .show(&self.ctx, |ui| {
+                ui.add_space(4.);
+                let interaction = composer.show(ui, stores, guild_id, channel_id, state, images);
+                ui.add_space(4.);
+                interaction
+            })
+            .inner
+    }
+
+    /// Renders the messages of the open channel, returning the action the
+    /// user took on one of them, if any.
+    pub fn render_messages(
+        &mut self,
+        stores: &MessageStores<'_>,
+        images: &mut ImageCache,
+        highlights: &mut HighlightCache,
+        state: &UiState,
+    ) -> Option<MessageAction> {
+        let cache = stores.cache;
+        let mut action = None;
+
+        CentralPanel::default().show(&self.ctx, |ui| {
+            let Some(channel) = state.selected_channel().and_then(|id| cache.channel(id)) else {
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                // threads link back to the channel they were created in
+                if let Some(parent) = channel
+                    .parent_id
+                    .filter(|_| channel.kind.is_thread())
+                    .and_then(|parent_id| cache.channel(parent_id))
+                {
+                    let name = parent.name.as_deref().unwrap_or_default();
+                    if ui
+                        .link(format!("{} {name}", channel_icon(parent)))
+                        .clicked()
+                    {
+                        action = Some(MessageAction::Thread(ThreadAction::Open(parent.id)));
+                    }
+                    ui.label("›");
+                }
+
+                if is_private(channel) {
+                    private_channel::show_icon(ui, images, channel, 24.);
+                    ui.heading(private_channel_name(channel));
+
+                    if channel.kind == ChannelType::Group && ui.button("⚙ Settings").clicked() {
+                        action = Some(MessageAction::GroupDmSettings);
+                    }
+                    return;
+                }
+
+                ui.heading(format!(
+                    "{} {}",
+                    channel_icon(channel),
+                    channel.name.as_deref().unwrap_or_default()
+                ));
+                if let Some(topic) = &channel.topic {
+                    ui.separator();
+                    ui.weak(topic.as_str());
+                }
+
+                let thread_action = if channel.kind.is_thread() {
+                    thread::show_join_button(ui, stores.threads, channel)
+                } else {
+                    thread::show_threads_menu(ui, cache, stores.threads, channel)
+                };
+                if thread_action.is_some() {
+                    action = thread_action.map(MessageAction::Thread);
+                }
             });
+            ui.separator();
+
+            let output = ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    let current_user_id = cache.current_user().map(|user| user.id);
+                    let mut previous = None;
+
+                    for message in cache.messages(channel.id) {
+                        let own = Some(message.author.id) == current_user_id;
+
+                        if starts_group(previous, message) {
+                            ui.add_space(12.);
+                            render_group_start(
+                                ui,
+                                stores,
+                                images,
+                                highlights,
+                                message,
+                                own,
+                                &mut action,
+                            );
+                        } else {
+                            indented(ui, |ui| {
+                                render_content(
+                                    ui,
+                                    stores,
+                                    images,
+                                    highlights,
+                                    message,
+                                    own,
+                                    &mut action,
+                                );
+                            });
+                        }
+
+                        previous = Some(message);
+                    }
+
+                    for message in cache.pending_messages(channel.id) {
+                        indented(ui, |ui| render_pending(ui, message, &mut action));
+                    }
+                });
+
+            // the channel is read once it's scrolled to the bottom while the
+            // window has focus, which it is when it's opened
+            let bottom = output.state.offset.y + output.inner_rect.height();
+            if action.is_none()
+                && bottom >= output.content_size.y - 1.
+                && ui.input(|input| input.focused)
+            {
+                action = Some(MessageAction::MarkAsRead);
+            }
         });
+
+        action
     }
 
-    pub fn render_messages(&mut self, messagecount: u32) {
+    /// Renders the friends tab, shown when the home tab is open without a DM.
+    pub fn render_friends(
+        &mut self,
+        friends: &mut FriendsView,
+        relationships: &RelationshipStore,
+        presences: &UserPresenceStore,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) {
         CentralPanel::default().show(&self.ctx, |ui| {
-            ScrollArea::vertical().show(ui, |ui| {
-                ui.heading("eframe template");
-                ui.hyperlink("https://github.com/emilk/eframe_template");
-                ui.add(egui::github_link_file!(
-                    "https://github.com/emilk/eframe_template/blob/master/",
-                    "Source code."
+            friends.show(ui, relationships, presences, images, &mut state.friends_tab);
+        });
+    }
+
+    /// Renders the posts of the open forum, returning the action the user
+    /// took on one of them, if any.
+    pub fn render_forum(
+        &mut self,
+        cache: &Cache,
+        threads: &ThreadStore,
+        images: &mut ImageCache,
+        state: &mut UiState,
+    ) -> Option<ThreadAction> {
+        let mut action = None;
+
+        CentralPanel::default().show(&self.ctx, |ui| {
+            let Some(forum) = state.selected_channel().and_then(|id| cache.channel(id)) else {
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                ui.heading(format!(
+                    "{} {}",
+                    channel_icon(forum),
+                    forum.name.as_deref().unwrap_or_default()
                 ));
+                if let Some(topic) = &forum.topic {
+                    ui.separator();
+                    ui.weak(topic.as_str());
+                }
+            });
+            ui.separator();
+
+            let view = state.forums.entry(forum.id).or_default();
+            action = thread::show_forum(ui, cache, threads, images, forum, view);
+        });
+
+        action
+    }
+}
+
+/// Stores read while rendering messages.
+pub struct MessageStores<'a> {
+    pub attachments: &'a AttachmentStore,
+    pub cache: &'a Cache,
+    pub interactions: &'a InteractionStore,
+    pub reactions: &'a ReactionStore,
+    pub roles: &'a RoleStore,
+    pub threads: &'a ThreadStore,
+}
+
+/// Action the user took on a message.
+pub enum MessageAction {
+    Reply(Id<MessageMarker>),
+    Edit(Id<MessageMarker>),
+    /// Removes a message that failed to send.
+    DismissPending(String),
+    /// Sends the interaction of a button or select menu.
+    Component {
+        message_id: Id<MessageMarker>,
+        data: MessageComponentInteractionData,
+    },
+    Reaction {
+        message_id: Id<MessageMarker>,
+        action: ReactionAction,
+    },
+    /// Action on the open thread or the threads of the open channel.
+    Thread(ThreadAction),
+    /// Opens the settings of the open group DM.
+    GroupDmSettings,
+    /// Opens the profile of the author of a message.
+    OpenProfile(Id<MessageMarker>),
+    /// Fetches the text of a text file to preview it.
+    FetchAttachmentText {
+        attachment_id: Id<AttachmentMarker>,
+        url: String,
+    },
+    /// Acknowledges the open channel, its newest messages are shown.
+    MarkAsRead,
+}
 
-                for i in 0..=messagecount {
-                    ui.label(format!("message {i}"));
+/// Renders the first message of a group along with the avatar and name of
+/// its author.
+fn render_group_start(
+    ui: &mut Ui,
+    stores: &MessageStores<'_>,
+    images: &mut ImageCache,
+    highlights: &mut HighlightCache,
+    message: &Message,
+    own: bool,
+    action: &mut Option<MessageAction>,
+) {
+    if let Some(referenced) = &message.referenced_message {
+        indented(ui, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "↪ {}: {}",
+                    display_name(referenced),
+                    first_line(&referenced.content)
+                ))
+                .small()
+                .weak(),
+            );
+        });
+    }
+
+    ui.horizontal_top(|ui| {
+        let author = &message.author;
+        let url = avatar_url(author.id, author.avatar, author.discriminator);
+        let (rect, avatar) = ui.allocate_exact_size(vec2(AVATAR_SIZE, AVATAR_SIZE), Sense::click());
+        match images.get(&url) {
+            Some(texture) => {
+                ui.painter()
+                    .image(texture.id(), rect, full_uv(), Color32::WHITE);
+            }
+            None => {
+                ui.painter()
+                    .circle_filled(rect.center(), AVATAR_SIZE / 2., Color32::DARK_GRAY);
+            }
+        }
+        if avatar.clicked() {
+            *action = Some(MessageAction::OpenProfile(message.id));
+        }
+
+        let appearance = message
+            .guild_id
+            .map(
+                |guild_id| match stores.roles.appearance(guild_id, author.id) {
+                    Some(appearance) => appearance.clone(),
+                    // the roles of members the gateway didn't send are in their messages
+                    None => stores.roles.resolve(
+                        guild_id,
+                        message
+                            .member
+                            .as_ref()
+                            .map_or(&[][..], |member| &member.roles),
+                    ),
+                },
+            )
+            .unwrap_or_default();
+
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                let mut name = RichText::new(display_name(message)).strong();
+                if let Some(color) = appearance.color {
+                    name = name.color(to_color(color));
+                }
+                if ui.add(Label::new(name).sense(Sense::click())).clicked() {
+                    *action = Some(MessageAction::OpenProfile(message.id));
+                }
+                if let Some(icon) = &appearance.icon {
+                    show_role_icon(ui, icon, images);
                 }
+                show_badges(ui, &appearance, author.bot);
+                ui.label(
+                    RichText::new(format_timestamp(message.timestamp))
+                        .small()
+                        .weak(),
+                );
             });
-            egui::warn_if_debug_build(ui);
+            render_content(ui, stores, images, highlights, message, own, action);
         });
+    });
+}
+
+/// Renders the markdown content and stickers of a message followed by its
+/// attachments, embeds, components and reactions.
+fn render_content(
+    ui: &mut Ui,
+    stores: &MessageStores<'_>,
+    images: &mut ImageCache,
+    highlights: &mut HighlightCache,
+    message: &Message,
+    own: bool,
+    action: &mut Option<MessageAction>,
+) {
+    let mentions = MentionContext {
+        cache: stores.cache,
+        guild_id: message.guild_id,
+        users: &message.mentions,
+        message_id: message.id,
+    };
+
+    let rect = ui
+        .vertical(|ui| {
+            if !message.content.is_empty() {
+                let mut code = MessageCode {
+                    highlights,
+                    message_id: message.id,
+                };
+                markdown::show(ui, &message.content, images, &mentions, Some(&mut code));
+            }
+            for sticker in &message.sticker_items {
+                sticker::show_sticker(
+                    ui,
+                    images,
+                    sticker.id,
+                    sticker.format_type,
+                    &sticker.name,
+                    STICKER_SIZE,
+                );
+            }
+
+            if let Some(edited_timestamp) = message.edited_timestamp {
+                ui.label(RichText::new("(edited)").small().weak())
+                    .on_hover_text(format_timestamp(edited_timestamp));
+            }
+        })
+        .response
+        .rect;
+
+    ui.interact(rect, ui.id().with(message.id), Sense::click())
+        .context_menu(|ui| {
+            if ui.button("Reply").clicked() {
+                *action = Some(MessageAction::Reply(message.id));
+                ui.close_menu();
+            }
+            if own && ui.button("Edit").clicked() {
+                *action = Some(MessageAction::Edit(message.id));
+                ui.close_menu();
+            }
+            if ui.button("Add Reaction").clicked() {
+                *action = Some(MessageAction::Reaction {
+                    message_id: message.id,
+                    action: ReactionAction::OpenPicker,
+                });
+                ui.close_menu();
+            }
+        });
+
+    if let Some((attachment_id, url)) =
+        attachment::show_attachments(ui, images, stores.attachments, &message.attachments)
+    {
+        *action = Some(MessageAction::FetchAttachmentText { attachment_id, url });
     }
+
+    embed::show_embeds(ui, &message.embeds, images, &mentions);
+
+    if let Some(data) = component::show_components(ui, images, message, stores.interactions) {
+        *action = Some(MessageAction::Component {
+            message_id: message.id,
+            data,
+        });
+    }
+
+    if let Some(reaction_action) = reaction::show_reactions(ui, images, message, stores.reactions) {
+        *action = Some(MessageAction::Reaction {
+            message_id: message.id,
+            action: reaction_action,
+        });
+    }
+}
+
+/// Renders the active threads of a channel the current user joined, below
+/// the channel.
+fn render_joined_threads(
+    ui: &mut Ui,
+    cache: &Cache,
+    read_states: &ReadStateStore,
+    threads: &ThreadStore,
+    channel: &Channel,
+    state: &mut UiState,
+) {
+    let Some(guild_id) = channel.guild_id else {
+        return;
+    };
+    let mut joined = cache
+        .threads(channel.id)
+        .filter(|thread| threads.is_joined(thread.id) && !is_archived(thread))
+        .collect::<Vec<_>>();
+    joined.sort_by_key(|thread| thread.id);
+
+    for thread in joined {
+        let selected = state.selected_channel() == Some(thread.id);
+        let name = thread.name.as_deref().unwrap_or_default();
+        let mut text = RichText::new(format!("└ {name}"));
+        if selected || read_states.channel(thread.id).is_unread() {
+            text = text.strong();
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_space(12.);
+            if ui.selectable_label(selected, text).clicked() {
+                state.select_channel(guild_id, thread.id);
+            }
+        });
+    }
+}
+
+fn render_pending(ui: &mut Ui, message: &PendingMessage, action: &mut Option<MessageAction>) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(RichText::new(message.content.as_str()).weak());
+
+        if message.failed {
+            ui.colored_label(Color32::RED, "Failed to send");
+            if ui.small_button("Dismiss").clicked() {
+                *action = Some(MessageAction::DismissPending(message.nonce.clone()));
+            }
+        }
+    });
+}
+
+/// Lays out content aligned with the messages next to an avatar.
+fn indented(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    ui.horizontal_top(|ui| {
+        ui.add_space(AVATAR_SIZE + ui.spacing().item_spacing.x);
+        ui.vertical(add_contents);
+    });
+}
+
+pub(crate) fn paint_mention_badge(ui: &Ui, rect: Rect, mention_count: u32) {
+    let center = rect.right_bottom() - vec2(6., 6.);
+    let text = if mention_count > 99 {
+        "99+".to_owned()
+    } else {
+        mention_count.to_string()
+    };
+
+    ui.painter().circle_filled(center, 8., Color32::RED);
+    ui.painter().text(
+        center,
+        Align2::CENTER_CENTER,
+        text,
+        FontId::proportional(10.),
+        Color32::WHITE,
+    );
+}
+
+/// Whether a message starts a new group rather than continuing the one of
+/// the previous message.
+fn starts_group(previous: Option<&Message>, message: &Message) -> bool {
+    let Some(previous) = previous else {
+        return true;
+    };
+    let elapsed = message.timestamp.as_micros() - previous.timestamp.as_micros();
+
+    previous.author.id != message.author.id
+        || message.kind == MessageType::Reply
+        || elapsed > MESSAGE_GROUP_WINDOW_MICROS
+}
+
+/// Name of the author of a message, preferring their guild nickname.
+fn display_name(message: &Message) -> &str {
+    message
+        .member
+        .as_ref()
+        .and_then(|member| member.nick.as_deref())
+        .or(message.author.global_name.as_deref())
+        .unwrap_or(&message.author.name)
+}
+
+pub(crate) fn avatar_url(
+    user_id: Id<UserMarker>,
+    avatar: Option<ImageHash>,
+    discriminator: u16,
+) -> String {
+    match avatar {
+        Some(avatar) => cdn::user_avatar(user_id, avatar, 64),
+        None => cdn::default_user_avatar(user_id, discriminator),
+    }
+}
+
+/// Shows the icon of a role next to the name of a member.
+pub(crate) fn show_role_icon(ui: &mut Ui, icon: &RoleIcon, images: &mut ImageCache) {
+    match icon {
+        RoleIcon::Image { role_id, icon } => {
+            let (rect, _) =
+                ui.allocate_exact_size(vec2(ROLE_ICON_SIZE, ROLE_ICON_SIZE), Sense::hover());
+            if let Some(texture) = images.get(&cdn::role_icon(*role_id, *icon, 32)) {
+                ui.painter()
+                    .image(texture.id(), rect, full_uv(), Color32::WHITE);
+            }
+        }
+        RoleIcon::Emoji(emoji) => {
+            ui.label(emoji.as_str());
+        }
+    }
+}
+
+/// Shows the badges of a member next to their name, from the tags of their
+/// roles.
+pub(crate) fn show_badges(ui: &mut Ui, appearance: &MemberAppearance, bot: bool) {
+    if bot || appearance.bot {
+        ui.label(
+            RichText::new("BOT")
+                .small()
+                .strong()
+                .color(Color32::WHITE)
+                .background_color(BLURPLE),
+        );
+    }
+    if appearance.booster {
+        ui.label(RichText::new("◆").color(BOOSTER_COLOR))
+            .on_hover_text("Server Booster");
+    }
+}
+
+fn first_line(content: &str) -> &str {
+    content.lines().next().unwrap_or_default()
+}
+
+pub(crate) fn format_timestamp(timestamp: Timestamp) -> String {
+    let offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+    let Ok(datetime) = OffsetDateTime::from_unix_timestamp(timestamp.as_secs()) else {
+        return String::new();
+    };
+    let datetime = datetime.to_offset(offset);
+
+    let format = if datetime.date() == OffsetDateTime::now_utc().to_offset(offset).date() {
+        format_description!("Today at [hour]:[minute]")
+    } else {
+        format_description!("[day]/[month]/[year] [hour]:[minute]")
+    };
+
+    datetime.format(format).unwrap_or_default()
+}
+
+/// First letter of every word of a guild name, shown when it has no icon.
+pub(crate) fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .collect()
+}
+
+fn channel_icon(channel: &Channel) -> &'static str {
+    match channel.kind {
+        ChannelType::GuildVoice | ChannelType::GuildStageVoice => "🔊",
+        ChannelType::GuildAnnouncement => "📢",
+        ChannelType::GuildForum => "💬",
+        _ => "#",
+    }
+}
+
+fn is_text_channel(channel: &Channel) -> bool {
+    matches!(
+        channel.kind,
+        ChannelType::GuildText | ChannelType::GuildAnnouncement
+    )
+}
+
+pub(crate) fn full_uv() -> Rect {
+    Rect::from_min_max(pos2(0., 0.), pos2(1., 1.))
 }
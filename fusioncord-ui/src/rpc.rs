@@ -0,0 +1,100 @@
+use std::sync::mpsc::Sender;
+
+use fusioncord_core::{
+    http::HttpClient,
+    message::{notify, RenderMessage},
+};
+#[cfg(unix)]
+use fusioncord_core::{presence::ActivityUpdate, rpc::RpcServer};
+use tokio::{runtime::Handle, task::JoinHandle};
+#[cfg(unix)]
+use tracing::warn;
+#[cfg(unix)]
+use twilight_model::id::{marker::ApplicationMarker, Id};
+use twilight_model::user::CurrentUser;
+
+/// Runs the local rich presence server while it's enabled, the activities
+/// applications set are sent as [`RenderMessage::LocalActivity`].
+///
+/// Rich presence is only supported on Unix, where it uses the same sockets
+/// as Discord.
+pub struct RpcClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+    /// Whether the server is enabled, starting it isn't retried after it
+    /// failed until it's enabled again.
+    enabled: bool,
+    server: Option<JoinHandle<()>>,
+}
+
+impl RpcClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self {
+            http,
+            runtime,
+            tx,
+            enabled: false,
+            server: None,
+        }
+    }
+
+    /// Starts the server once the current user is known, stopping it when it
+    /// gets disabled.
+    pub fn set_enabled(&mut self, enabled: bool, user: Option<&CurrentUser>) {
+        if enabled == self.enabled {
+            return;
+        }
+
+        if enabled {
+            let Some(user) = user else {
+                return;
+            };
+            self.server = self.start(user.clone());
+        } else if let Some(server) = self.server.take() {
+            // dropping the server removes its socket
+            server.abort();
+        }
+        self.enabled = enabled;
+    }
+
+    #[cfg(unix)]
+    fn start(&self, user: CurrentUser) -> Option<JoinHandle<()>> {
+        // binding needs the runtime for the listener
+        let _guard = self.runtime.enter();
+        let server = match RpcServer::bind() {
+            Ok(server) => server,
+            Err(e) => {
+                warn!("Failed to start the rich presence server: {e}");
+                return None;
+            }
+        };
+
+        let http = self.http.clone();
+        let application_name = move |application_id: Id<ApplicationMarker>| {
+            let http = http.clone();
+            async move {
+                http.application_rpc(application_id)
+                    .await
+                    .inspect_err(|e| warn!("Failed to fetch application {application_id}: {e}"))
+                    .ok()
+                    .map(|application| application.name)
+            }
+        };
+        let tx = self.tx.clone();
+        let on_update = move |update: ActivityUpdate| {
+            notify(&tx, RenderMessage::LocalActivity(Box::new(update)));
+        };
+
+        Some(
+            self.runtime
+                .spawn(server.run(user, application_name, on_update)),
+        )
+    }
+
+    #[cfg(not(unix))]
+    fn start(&self, user: CurrentUser) -> Option<JoinHandle<()>> {
+        let _ = (&self.http, &self.runtime, &self.tx, user);
+        None
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::mpsc::Sender;
+
+use fusioncord_core::{
+    http::HttpClient,
+    message::{notify, RenderMessage},
+    proto::Message,
+    user_settings::encode_settings,
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::gateway::payload::incoming::UserSettingsProtoType;
+
+/// Fetches and changes the protobuf settings of the current user, the
+/// results are sent as [`RenderMessage::UserSettings`].
+pub struct SettingsClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+}
+
+impl SettingsClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self { http, runtime, tx }
+    }
+
+    /// Fetches settings that aren't sent along with the READY payload.
+    pub fn fetch(&self, kind: UserSettingsProtoType) {
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            match http.user_settings(kind).await {
+                Ok(settings) => {
+                    notify(&tx, RenderMessage::UserSettings { kind, settings });
+                }
+                Err(e) => warn!("Failed to fetch the {} user settings: {e}", kind.name()),
+            }
+        });
+    }
+
+    /// Writes changed settings back, the settings must be the complete ones
+    /// including the fields that weren't changed.
+    pub fn update<M: Message>(&self, kind: UserSettingsProtoType, settings: &M) {
+        let proto = encode_settings(settings);
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            match http.update_user_settings(kind, &proto).await {
+                Ok(settings) => {
+                    notify(&tx, RenderMessage::UserSettings { kind, settings });
+                }
+                Err(e) => warn!("Failed to update the {} user settings: {e}", kind.name()),
+            }
+        });
+    }
+}
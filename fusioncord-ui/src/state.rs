@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+
+use fusioncord_core::{
+    command::{CommandEntry, OptionInput},
+    emoji::SkinTone,
+    message::MessageLink,
+};
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+    channel::forum::{ForumLayout, ForumSortOrder},
+    http::attachment::Attachment,
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker, TagMarker},
+        Id,
+    },
+};
+
+use crate::friends::FriendsTab;
+
+/// State of the user interface that is persisted between restarts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// The guild that is currently open, `None` for the home tab.
+    pub selected_guild: Option<Id<GuildMarker>>,
+    /// Last opened channel of every guild.
+    pub selected_channels: HashMap<Id<GuildMarker>, Id<ChannelMarker>>,
+    /// DM or group DM opened in the home tab, `None` for the friends tab.
+    pub selected_private_channel: Option<Id<ChannelMarker>>,
+    pub collapsed_categories: HashSet<Id<ChannelMarker>>,
+    /// Guild folders that are open in the server list.
+    pub expanded_folders: HashSet<i64>,
+    pub drafts: HashMap<Id<ChannelMarker>, Draft>,
+    /// How the posts of every opened forum are shown.
+    pub forums: HashMap<Id<ChannelMarker>, ForumView>,
+    pub friends_tab: FriendsTab,
+    #[serde(skip)]
+    pub inbox_open: bool,
+    #[serde(skip)]
+    pub account_open: bool,
+    /// Whether local applications can set the activity of the current user
+    /// through rich presence.
+    pub rpc_enabled: bool,
+    /// Skin tone of the emojis picked from the emoji picker.
+    pub skin_tone: SkinTone,
+}
+
+impl UiState {
+    /// The channel that is currently open.
+    pub fn selected_channel(&self) -> Option<Id<ChannelMarker>> {
+        match self.selected_guild {
+            Some(guild_id) => self.selected_channels.get(&guild_id).copied(),
+            None => self.selected_private_channel,
+        }
+    }
+
+    pub fn select_channel(&mut self, guild_id: Id<GuildMarker>, channel_id: Id<ChannelMarker>) {
+        self.selected_guild = Some(guild_id);
+        self.selected_channels.insert(guild_id, channel_id);
+    }
+
+    /// Opens a DM or group DM, `None` to open the friends tab.
+    pub fn select_private_channel(&mut self, channel_id: Option<Id<ChannelMarker>>) {
+        self.selected_guild = None;
+        self.selected_private_channel = channel_id;
+    }
+
+    /// Opens the channel of a message.
+    pub fn open_message(&mut self, link: MessageLink) {
+        match link.guild_id {
+            Some(guild_id) => self.select_channel(guild_id, link.channel_id),
+            None => self.select_private_channel(Some(link.channel_id)),
+        }
+    }
+
+    pub fn toggle_category(&mut self, category_id: Id<ChannelMarker>) {
+        if !self.collapsed_categories.remove(&category_id) {
+            self.collapsed_categories.insert(category_id);
+        }
+    }
+}
+
+/// Unsent message of a channel.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Draft {
+    pub text: String,
+    #[serde(skip)]
+    pub reply: Option<Reply>,
+    /// Message of the current user that is being edited instead of sending a
+    /// new one.
+    #[serde(skip)]
+    pub editing: Option<Id<MessageMarker>>,
+    #[serde(skip)]
+    pub attachments: Vec<Attachment>,
+    /// Command whose options are being filled in instead of writing a
+    /// message.
+    #[serde(skip)]
+    pub command: Option<CommandDraft>,
+}
+
+impl Draft {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Command picked in the composer along with the values entered for its
+/// options.
+#[derive(Debug)]
+pub struct CommandDraft {
+    pub entry: CommandEntry,
+    pub inputs: HashMap<String, OptionInput>,
+    /// Option that was last typed in, whose suggested choices are shown.
+    pub focused: Option<String>,
+}
+
+impl CommandDraft {
+    pub fn new(entry: CommandEntry) -> Self {
+        Self {
+            entry,
+            inputs: HashMap::new(),
+            focused: None,
+        }
+    }
+}
+
+/// How the posts of a forum are shown, `None` to use the defaults of the
+/// forum.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForumView {
+    pub layout: Option<ForumLayout>,
+    pub sort_order: Option<ForumSortOrder>,
+    /// Tags the posts are filtered by.
+    #[serde(skip)]
+    pub tags: HashSet<Id<TagMarker>>,
+}
+
+/// Message a draft replies to.
+#[derive(Debug)]
+pub struct Reply {
+    pub message_id: Id<MessageMarker>,
+    pub author_name: String,
+    /// Whether the author of the message is mentioned.
+    pub mention: bool,
+}
@@ -0,0 +1,202 @@
+use egui::{ComboBox, Context, RichText, TextEdit, Ui, Window};
+use fusioncord_core::{
+    presence::{now_millis, PresenceStore},
+    user_settings::CustomStatus,
+};
+use time::{Duration, OffsetDateTime, Time, UtcOffset};
+use twilight_model::gateway::presence::Status;
+
+use crate::friends::{status_color, status_name};
+
+/// Statuses the current user can pick, in the order they're listed.
+const STATUSES: [Status; 4] = [
+    Status::Online,
+    Status::Idle,
+    Status::DoNotDisturb,
+    Status::Invisible,
+];
+
+/// Change the current user made to their status.
+#[derive(Debug)]
+pub enum StatusAction {
+    SetStatus(Status),
+    /// Sets the custom status, `None` to clear it.
+    SetCustomStatus(Option<CustomStatus>),
+}
+
+/// When a custom status is cleared.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ClearAfter {
+    #[default]
+    Today,
+    FourHours,
+    OneHour,
+    ThirtyMinutes,
+    Never,
+}
+
+/// Custom status that is being edited.
+#[derive(Debug, Default)]
+struct CustomStatusEditor {
+    text: String,
+    emoji: String,
+    clear_after: ClearAfter,
+}
+
+/// Lets the current user pick their status and edit their custom status.
+#[derive(Debug, Default)]
+pub struct StatusPicker {
+    editor: Option<CustomStatusEditor>,
+}
+
+impl StatusPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the button that opens the status menu.
+    pub fn show_menu(&mut self, ui: &mut Ui, presence: &PresenceStore) -> Option<StatusAction> {
+        let status = presence.status();
+        let custom_status = presence.custom_status(now_millis());
+        let mut action = None;
+
+        let button = RichText::new("●").color(status_color(status));
+        ui.menu_button(button, |ui| {
+            for status in STATUSES {
+                let text =
+                    RichText::new(format!("● {}", status_name(status))).color(status_color(status));
+                if ui.button(text).clicked() {
+                    action = Some(StatusAction::SetStatus(status));
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+
+            let edit = if custom_status.is_some() {
+                "Edit Custom Status"
+            } else {
+                "Set Custom Status"
+            };
+            if ui.button(edit).clicked() {
+                self.editor = Some(CustomStatusEditor {
+                    text: custom_status
+                        .map(|custom_status| custom_status.text.clone())
+                        .unwrap_or_default(),
+                    emoji: custom_status
+                        .map(|custom_status| custom_status.emoji_name.clone())
+                        .unwrap_or_default(),
+                    clear_after: ClearAfter::default(),
+                });
+                ui.close_menu();
+            }
+            if custom_status.is_some() && ui.button("Clear Custom Status").clicked() {
+                action = Some(StatusAction::SetCustomStatus(None));
+                ui.close_menu();
+            }
+        })
+        .response
+        .on_hover_text(status_name(status));
+
+        action
+    }
+
+    /// Shows the custom status editor if it's open.
+    pub fn show_editor(&mut self, ctx: &Context) -> Option<StatusAction> {
+        let editor = self.editor.as_mut()?;
+        let mut open = true;
+        let mut action = None;
+
+        Window::new("Set a custom status")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut editor.emoji)
+                            .hint_text("😀")
+                            .desired_width(32.),
+                    );
+                    ui.add(
+                        TextEdit::singleline(&mut editor.text)
+                            .hint_text("What's cookin'?")
+                            .char_limit(128),
+                    );
+                });
+
+                ui.label(RichText::new("CLEAR AFTER").small().strong());
+                ComboBox::from_id_source("custom_status_clear_after")
+                    .selected_text(clear_after_name(editor.clear_after))
+                    .show_ui(ui, |ui| {
+                        for clear_after in [
+                            ClearAfter::Today,
+                            ClearAfter::FourHours,
+                            ClearAfter::OneHour,
+                            ClearAfter::ThirtyMinutes,
+                            ClearAfter::Never,
+                        ] {
+                            ui.selectable_value(
+                                &mut editor.clear_after,
+                                clear_after,
+                                clear_after_name(clear_after),
+                            );
+                        }
+                    });
+
+                ui.separator();
+                if ui.button("Save").clicked() {
+                    action = Some(StatusAction::SetCustomStatus(custom_status(editor)));
+                }
+            });
+
+        if !open || action.is_some() {
+            self.editor = None;
+        }
+
+        action
+    }
+}
+
+/// The custom status an editor describes, `None` if it's empty.
+fn custom_status(editor: &CustomStatusEditor) -> Option<CustomStatus> {
+    let text = editor.text.trim();
+    let emoji = editor.emoji.trim();
+    if text.is_empty() && emoji.is_empty() {
+        return None;
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let expires_at = match editor.clear_after {
+        ClearAfter::Today => {
+            let offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+            let today = now.to_offset(offset).replace_time(Time::MIDNIGHT);
+            Some(today + Duration::days(1))
+        }
+        ClearAfter::FourHours => Some(now + Duration::hours(4)),
+        ClearAfter::OneHour => Some(now + Duration::hours(1)),
+        ClearAfter::ThirtyMinutes => Some(now + Duration::minutes(30)),
+        ClearAfter::Never => None,
+    };
+
+    Some(CustomStatus {
+        text: text.to_owned(),
+        emoji_name: emoji.to_owned(),
+        expires_at_ms: expires_at.map_or(0, to_millis),
+        created_at_ms: to_millis(now),
+        ..CustomStatus::default()
+    })
+}
+
+const fn clear_after_name(clear_after: ClearAfter) -> &'static str {
+    match clear_after {
+        ClearAfter::Today => "Today",
+        ClearAfter::FourHours => "4 hours",
+        ClearAfter::OneHour => "1 hour",
+        ClearAfter::ThirtyMinutes => "30 minutes",
+        ClearAfter::Never => "Don't clear",
+    }
+}
+
+fn to_millis(datetime: OffsetDateTime) -> u64 {
+    (datetime.unix_timestamp_nanos() / 1_000_000) as u64
+}
@@ -0,0 +1,337 @@
+use std::{io::Cursor, time::Duration};
+
+use egui::{
+    epaint::PathShape, pos2, vec2, Align2, Color32, ColorImage, Context, FontId, Rect, Response,
+    RichText, ScrollArea, Sense, Stroke, TextEdit, TextureHandle, TextureOptions, Ui,
+};
+use fusioncord_core::{
+    cache::Cache,
+    cdn,
+    lottie::{Animation, Style},
+};
+use image::{
+    codecs::{gif::GifDecoder, png::PngDecoder},
+    AnimationDecoder, DynamicImage, Frame,
+};
+use twilight_model::{
+    channel::message::sticker::{Sticker as GuildSticker, StickerFormatType},
+    guild::Guild,
+    id::{
+        marker::{GuildMarker, StickerMarker},
+        Id,
+    },
+    user::PremiumType,
+};
+
+use crate::images::ImageCache;
+
+const PICKER_STICKER_SIZE: f32 = 80.;
+const SCROLL_HEIGHT: f32 = 320.;
+/// Browsers play frames with a shorter delay than this at 10 frames per
+/// second, which some GIFs rely on.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+/// Laid over the stickers that can't be used.
+const UNUSABLE_OVERLAY: Color32 = Color32::from_black_alpha(160);
+
+/// Sticker ready to be shown.
+pub enum Sticker {
+    /// Frames of a PNG, APNG or GIF sticker along with how long they're
+    /// shown, still stickers have a single frame.
+    Frames(Vec<(TextureHandle, Duration)>),
+    Lottie(Animation),
+}
+
+/// Sticker that was downloaded but whose frames aren't textures yet.
+pub enum DecodedSticker {
+    Frames(Vec<(ColorImage, Duration)>),
+    Lottie(Animation),
+}
+
+impl DecodedSticker {
+    pub fn into_sticker(self, ctx: &Context, sticker_id: Id<StickerMarker>) -> Sticker {
+        match self {
+            Self::Frames(frames) => Sticker::Frames(
+                frames
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (image, delay))| {
+                        let name = format!("sticker-{sticker_id}-{index}");
+                        (ctx.load_texture(name, image, TextureOptions::LINEAR), delay)
+                    })
+                    .collect(),
+            ),
+            Self::Lottie(animation) => Sticker::Lottie(animation),
+        }
+    }
+}
+
+pub async fn fetch_sticker(
+    sticker_id: Id<StickerMarker>,
+    format: StickerFormatType,
+) -> Result<DecodedSticker, Box<dyn std::error::Error + Send + Sync>> {
+    let url = cdn::sticker(sticker_id, format);
+    let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+
+    let frames = match format {
+        StickerFormatType::Lottie => return Ok(DecodedSticker::Lottie(Animation::parse(&bytes)?)),
+        StickerFormatType::Gif => GifDecoder::new(Cursor::new(bytes))?
+            .into_frames()
+            .collect_frames()?,
+        _ => {
+            let decoder = PngDecoder::new(Cursor::new(bytes))?;
+            if decoder.is_apng() {
+                decoder.apng().into_frames().collect_frames()?
+            } else {
+                vec![Frame::new(
+                    DynamicImage::from_decoder(decoder)?.into_rgba8(),
+                )]
+            }
+        }
+    };
+
+    let frames = frames
+        .into_iter()
+        .map(|frame| {
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let delay = Duration::from_secs_f64(
+                f64::from(numerator) / f64::from(denominator.max(1)) / 1000.,
+            );
+            let delay = if delay < MIN_FRAME_DELAY {
+                DEFAULT_FRAME_DELAY
+            } else {
+                delay
+            };
+
+            let buffer = frame.into_buffer();
+            let size = [buffer.width() as usize, buffer.height() as usize];
+            (
+                ColorImage::from_rgba_unmultiplied(size, buffer.as_raw()),
+                delay,
+            )
+        })
+        .collect();
+    Ok(DecodedSticker::Frames(frames))
+}
+
+/// Shows a sticker, playing it if it's animated, or its name while it's
+/// loading.
+pub fn show_sticker(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    sticker_id: Id<StickerMarker>,
+    format: StickerFormatType,
+    name: &str,
+    size: f32,
+) -> Response {
+    let (rect, response) = ui.allocate_exact_size(vec2(size, size), Sense::click());
+
+    match images.sticker(sticker_id, format) {
+        Some(Sticker::Frames(frames)) => {
+            if let Some((texture, remaining)) = current_frame(frames, ui.input(|input| input.time))
+            {
+                if let Some(remaining) = remaining {
+                    ui.ctx().request_repaint_after(remaining);
+                }
+                let rect = Rect::from_center_size(rect.center(), fit(texture.size_vec2(), size));
+                let uv = Rect::from_min_max(pos2(0., 0.), pos2(1., 1.));
+                ui.painter().image(texture.id(), rect, uv, Color32::WHITE);
+            }
+        }
+        Some(Sticker::Lottie(animation)) => paint_lottie(ui, rect, animation),
+        None => {
+            ui.painter().text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                name,
+                FontId::default(),
+                ui.visuals().weak_text_color(),
+            );
+        }
+    }
+
+    response.on_hover_text(name)
+}
+
+/// The frame shown at some point in time, along with how long it's still
+/// shown for animated stickers.
+fn current_frame(
+    frames: &[(TextureHandle, Duration)],
+    time: f64,
+) -> Option<(&TextureHandle, Option<Duration>)> {
+    let total = frames.iter().map(|(_, delay)| *delay).sum::<Duration>();
+    if frames.len() < 2 || total.is_zero() {
+        return frames.first().map(|(texture, _)| (texture, None));
+    }
+
+    let mut elapsed = Duration::from_secs_f64(time % total.as_secs_f64());
+    for (texture, delay) in frames {
+        if elapsed < *delay {
+            return Some((texture, Some(*delay - elapsed)));
+        }
+        elapsed -= *delay;
+    }
+    frames.last().map(|(texture, _)| (texture, None))
+}
+
+/// Scales an image down to fit in a square, keeping its aspect ratio.
+fn fit(image_size: egui::Vec2, size: f32) -> egui::Vec2 {
+    image_size * (size / image_size.max_elem()).min(1.)
+}
+
+/// Paints the current frame of a Lottie animation in a rectangle.
+///
+/// egui fills concave paths as if they were convex, which most shapes of
+/// stickers are close enough to.
+fn paint_lottie(ui: &Ui, rect: Rect, animation: &Animation) {
+    let elapsed = Duration::from_secs_f64(ui.input(|input| input.time));
+    let shapes = animation.render(animation.frame_at(elapsed));
+
+    let scale = (rect.width() / animation.width).min(rect.height() / animation.height);
+    let origin = rect.center() - vec2(animation.width, animation.height) * scale / 2.;
+    let painter = ui.painter_at(rect);
+
+    for shape in shapes {
+        let (fill, stroke) = match shape.style {
+            Style::Fill([r, g, b, a]) => {
+                (Color32::from_rgba_premultiplied(r, g, b, a), Stroke::NONE)
+            }
+            Style::Stroke {
+                color: [r, g, b, a],
+                width,
+            } => (
+                Color32::TRANSPARENT,
+                Stroke::new(width * scale, Color32::from_rgba_premultiplied(r, g, b, a)),
+            ),
+        };
+
+        for path in shape.paths {
+            let points = path
+                .points
+                .iter()
+                .map(|[x, y]| origin + vec2(*x, *y) * scale)
+                .collect();
+            painter.add(PathShape {
+                points,
+                // filled paths are always closed
+                closed: path.closed || fill != Color32::TRANSPARENT,
+                fill,
+                stroke,
+            });
+        }
+    }
+
+    if animation.frame_rate > 0. {
+        ui.ctx()
+            .request_repaint_after(Duration::from_secs_f32(1. / animation.frame_rate));
+    }
+}
+
+/// Picks one of the stickers of the guilds, searching them by name or tag.
+#[derive(Default)]
+pub struct StickerPicker {
+    query: String,
+}
+
+impl StickerPicker {
+    /// Shows the picker, returning the sticker that was picked.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        cache: &Cache,
+        guild_id: Option<Id<GuildMarker>>,
+        images: &mut ImageCache,
+    ) -> Option<Id<StickerMarker>> {
+        let premium = cache
+            .current_user()?
+            .premium_type
+            .is_some_and(|premium_type| premium_type != PremiumType::None);
+        let mut guilds = cache
+            .guilds()
+            .filter(|guild| !guild.stickers.is_empty())
+            .collect::<Vec<_>>();
+        // the sort is stable, the other guilds keep their order
+        guilds.sort_by_key(|guild| Some(guild.id) != guild_id);
+
+        ui.add(
+            TextEdit::singleline(&mut self.query)
+                .hint_text("Find the perfect sticker")
+                .desired_width(240.),
+        );
+        let query = self.query.to_lowercase();
+        let mut picked = None;
+
+        ScrollArea::vertical()
+            .max_height(SCROLL_HEIGHT)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if guilds.is_empty() {
+                    ui.weak("None of your servers have stickers.");
+                }
+                for guild in guilds {
+                    let usable = guild_id == Some(guild.id) || premium;
+                    if let Some(sticker_id) = show_guild(ui, guild, &query, usable, images) {
+                        picked = Some(sticker_id);
+                    }
+                }
+            });
+
+        if picked.is_some() {
+            self.query.clear();
+        }
+        picked
+    }
+}
+
+/// Shows the stickers of a guild that match the query.
+fn show_guild(
+    ui: &mut Ui,
+    guild: &Guild,
+    query: &str,
+    usable: bool,
+    images: &mut ImageCache,
+) -> Option<Id<StickerMarker>> {
+    let stickers = guild
+        .stickers
+        .iter()
+        .filter(|sticker| matches(sticker, query))
+        .collect::<Vec<_>>();
+    if stickers.is_empty() {
+        return None;
+    }
+
+    let mut picked = None;
+    ui.label(RichText::new(guild.name.to_uppercase()).small().strong());
+    ui.horizontal_wrapped(|ui| {
+        for sticker in stickers {
+            let usable = usable && sticker.available;
+            let response = show_sticker(
+                ui,
+                images,
+                sticker.id,
+                sticker.format_type,
+                &sticker.name,
+                PICKER_STICKER_SIZE,
+            );
+            if !usable {
+                ui.painter()
+                    .rect_filled(response.rect, 0., UNUSABLE_OVERLAY);
+            }
+            if usable && response.clicked() {
+                picked = Some(sticker.id);
+            }
+        }
+    });
+    picked
+}
+
+/// Whether the name or one of the tags of a sticker contains the lowercase
+/// query.
+fn matches(sticker: &GuildSticker, query: &str) -> bool {
+    query.is_empty()
+        || sticker.name.to_lowercase().contains(query)
+        || sticker
+            .tags
+            .split(',')
+            .any(|tag| tag.trim().to_lowercase().contains(query))
+}
@@ -0,0 +1,430 @@
+use std::{cmp::Reverse, sync::mpsc::Sender};
+
+use egui::{vec2, Color32, ComboBox, Frame, RichText, ScrollArea, Sense, Ui, Vec2};
+use fusioncord_core::{
+    cache::{is_archived, Cache},
+    cdn,
+    http::HttpClient,
+    message::{notify, RenderMessage},
+    thread::{self, ThreadStore},
+};
+use tokio::runtime::Handle;
+use tracing::warn;
+use twilight_model::{
+    channel::{
+        forum::{ForumLayout, ForumSortOrder, ForumTag},
+        Channel, ChannelFlags,
+    },
+    id::{
+        marker::{ChannelMarker, EmojiMarker},
+        Id,
+    },
+};
+
+use crate::{images::ImageCache, renderer::format_timestamp, state::ForumView};
+
+const CARD_SIZE: Vec2 = Vec2::new(240., 140.);
+const EMOJI_SIZE: f32 = 16.;
+
+/// Action the user took on a thread.
+pub enum ThreadAction {
+    Open(Id<ChannelMarker>),
+    /// Fetches the next page of archived threads of a channel.
+    FetchArchived(Id<ChannelMarker>),
+    Join(Id<ChannelMarker>),
+    Leave(Id<ChannelMarker>),
+}
+
+/// Renders a menu listing the threads of a channel, the active ones first.
+pub fn show_threads_menu(
+    ui: &mut Ui,
+    cache: &Cache,
+    threads: &ThreadStore,
+    channel: &Channel,
+) -> Option<ThreadAction> {
+    let mut action = None;
+
+    ui.menu_button("🧵 Threads", |ui| {
+        let (mut active, mut archived): (Vec<_>, Vec<_>) = cache
+            .threads(channel.id)
+            .partition(|thread| !is_archived(thread));
+        active.sort_by_key(|thread| Reverse(last_activity(thread)));
+        archived.sort_by_key(|thread| {
+            Reverse(
+                thread
+                    .thread_metadata
+                    .as_ref()
+                    .map(|metadata| metadata.archive_timestamp.as_micros()),
+            )
+        });
+
+        ScrollArea::vertical().max_height(400.).show(ui, |ui| {
+            ui.label(RichText::new("ACTIVE THREADS").small().strong());
+            if active.is_empty() {
+                ui.weak("No active threads");
+            }
+            for thread in active {
+                if show_thread_button(ui, threads, thread) {
+                    action = Some(ThreadAction::Open(thread.id));
+                    ui.close_menu();
+                }
+            }
+
+            ui.add_space(8.);
+            ui.label(RichText::new("ARCHIVED THREADS").small().strong());
+            for thread in archived {
+                if show_thread_button(ui, threads, thread) {
+                    action = Some(ThreadAction::Open(thread.id));
+                    ui.close_menu();
+                }
+            }
+
+            if let Some(fetch) = show_archived_footer(ui, threads, channel.id) {
+                action = Some(fetch);
+            }
+        });
+    });
+
+    action
+}
+
+/// Renders a button joining or leaving a thread.
+pub fn show_join_button(
+    ui: &mut Ui,
+    threads: &ThreadStore,
+    thread: &Channel,
+) -> Option<ThreadAction> {
+    if threads.is_joined(thread.id) {
+        ui.button("Leave Thread")
+            .clicked()
+            .then_some(ThreadAction::Leave(thread.id))
+    } else {
+        ui.button("Join Thread")
+            .clicked()
+            .then_some(ThreadAction::Join(thread.id))
+    }
+}
+
+/// Renders the posts of a forum as a list or as a gallery of cards, along
+/// with the tags they can be filtered by.
+pub fn show_forum(
+    ui: &mut Ui,
+    cache: &Cache,
+    threads: &ThreadStore,
+    images: &mut ImageCache,
+    forum: &Channel,
+    view: &mut ForumView,
+) -> Option<ThreadAction> {
+    let mut action = None;
+    let tags = forum.available_tags.as_deref().unwrap_or_default();
+
+    if !tags.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            for tag in tags {
+                show_tag_emoji(ui, images, tag);
+                if ui
+                    .selectable_label(view.tags.contains(&tag.id), tag.name.as_str())
+                    .clicked()
+                    && !view.tags.remove(&tag.id)
+                {
+                    view.tags.insert(tag.id);
+                }
+            }
+
+            if !view.tags.is_empty() && ui.small_button("Clear").clicked() {
+                view.tags.clear();
+            }
+        });
+    }
+
+    let mut sort_order = view
+        .sort_order
+        .or(forum.default_sort_order)
+        .unwrap_or(ForumSortOrder::LatestActivity);
+    let mut layout = match view.layout.or(forum.default_forum_layout) {
+        Some(ForumLayout::GalleryView) => ForumLayout::GalleryView,
+        _ => ForumLayout::ListView,
+    };
+
+    ui.horizontal(|ui| {
+        ComboBox::from_label("Sort by")
+            .selected_text(sort_order_name(sort_order))
+            .show_ui(ui, |ui| {
+                for order in [ForumSortOrder::LatestActivity, ForumSortOrder::CreationDate] {
+                    ui.selectable_value(&mut sort_order, order, sort_order_name(order));
+                }
+            });
+        ui.separator();
+        ui.selectable_value(&mut layout, ForumLayout::ListView, "☰ List");
+        ui.selectable_value(&mut layout, ForumLayout::GalleryView, "▦ Gallery");
+    });
+    view.sort_order = Some(sort_order);
+    view.layout = Some(layout);
+    ui.separator();
+
+    let posts = thread::forum_posts(cache, forum.id, sort_order, &view.tags);
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            if posts.is_empty() && !threads.archived(forum.id).loading {
+                ui.weak(if view.tags.is_empty() {
+                    "There are no posts yet"
+                } else {
+                    "No posts have the selected tags"
+                });
+            }
+
+            if layout == ForumLayout::GalleryView {
+                ui.horizontal_wrapped(|ui| {
+                    for post in &posts {
+                        let clicked = ui
+                            .allocate_ui(CARD_SIZE, |ui| {
+                                show_post(ui, threads, images, forum, post, true)
+                            })
+                            .inner;
+                        if clicked {
+                            action = Some(ThreadAction::Open(post.id));
+                        }
+                    }
+                });
+            } else {
+                for post in &posts {
+                    if show_post(ui, threads, images, forum, post, false) {
+                        action = Some(ThreadAction::Open(post.id));
+                    }
+                }
+            }
+
+            ui.add_space(8.);
+            if let Some(fetch) = show_archived_footer(ui, threads, forum.id) {
+                action = Some(fetch);
+            }
+        });
+
+    action
+}
+
+/// Renders a post of a forum, returning whether it was clicked.
+fn show_post(
+    ui: &mut Ui,
+    threads: &ThreadStore,
+    images: &mut ImageCache,
+    forum: &Channel,
+    post: &Channel,
+    card: bool,
+) -> bool {
+    let response = Frame::group(ui.style())
+        .fill(ui.visuals().faint_bg_color)
+        .show(ui, |ui| {
+            if card {
+                ui.set_min_size(CARD_SIZE - vec2(16., 16.));
+                ui.set_max_width(CARD_SIZE.x - 16.);
+            } else {
+                ui.set_width(ui.available_width());
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                if post
+                    .flags
+                    .is_some_and(|flags| flags.contains(ChannelFlags::PINNED))
+                {
+                    ui.label("📌");
+                }
+                ui.label(RichText::new(post.name.as_deref().unwrap_or_default()).strong());
+                if is_archived(post) {
+                    ui.weak("Archived");
+                }
+                if threads.is_joined(post.id) {
+                    ui.weak("Following");
+                }
+            });
+
+            let applied_tags = forum
+                .available_tags
+                .iter()
+                .flatten()
+                .filter(|tag| post.applied_tags.iter().flatten().any(|id| *id == tag.id))
+                .collect::<Vec<_>>();
+            if !applied_tags.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    for tag in applied_tags {
+                        show_tag_emoji(ui, images, tag);
+                        ui.label(RichText::new(tag.name.as_str()).small());
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                show_default_reaction(ui, images, forum);
+                ui.weak(format!("💬 {}", post.message_count.unwrap_or_default()));
+                if let Some(created) = post
+                    .thread_metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.create_timestamp)
+                {
+                    ui.weak(format_timestamp(created));
+                }
+            });
+        })
+        .response
+        .interact(Sense::click());
+
+    response.clicked()
+}
+
+/// Renders the state of the archived threads of a channel, with a button to
+/// fetch the next page when there may be more.
+fn show_archived_footer(
+    ui: &mut Ui,
+    threads: &ThreadStore,
+    channel_id: Id<ChannelMarker>,
+) -> Option<ThreadAction> {
+    let archived = threads.archived(channel_id);
+
+    if archived.failed {
+        ui.colored_label(Color32::RED, "Failed to load archived threads");
+    }
+
+    if archived.loading {
+        ui.spinner();
+        None
+    } else if archived.can_fetch() {
+        let label = if archived.failed {
+            "Retry"
+        } else {
+            "Load archived threads"
+        };
+
+        ui.button(label)
+            .clicked()
+            .then_some(ThreadAction::FetchArchived(channel_id))
+    } else {
+        None
+    }
+}
+
+fn show_thread_button(ui: &mut Ui, threads: &ThreadStore, thread: &Channel) -> bool {
+    let name = thread.name.as_deref().unwrap_or_default();
+    let text = if threads.is_joined(thread.id) {
+        RichText::new(format!("🧵 {name}")).strong()
+    } else {
+        RichText::new(format!("🧵 {name}"))
+    };
+
+    ui.horizontal(|ui| {
+        let clicked = ui.selectable_label(false, text).clicked();
+        ui.weak(format!("💬 {}", thread.message_count.unwrap_or_default()));
+
+        clicked
+    })
+    .inner
+}
+
+/// Renders the emoji posts of a forum are reacted to with by default.
+fn show_default_reaction(ui: &mut Ui, images: &mut ImageCache, forum: &Channel) {
+    if let Some(reaction) = &forum.default_reaction_emoji {
+        show_emoji(
+            ui,
+            images,
+            reaction.emoji_id,
+            reaction.emoji_name.as_deref(),
+        );
+    }
+}
+
+fn show_tag_emoji(ui: &mut Ui, images: &mut ImageCache, tag: &ForumTag) {
+    show_emoji(ui, images, tag.emoji_id, tag.emoji_name.as_deref());
+}
+
+/// Renders a custom emoji, or a unicode emoji if there is no custom one.
+fn show_emoji(
+    ui: &mut Ui,
+    images: &mut ImageCache,
+    emoji_id: Option<Id<EmojiMarker>>,
+    emoji_name: Option<&str>,
+) {
+    match (emoji_id, emoji_name) {
+        (Some(emoji_id), _) => {
+            if let Some(texture) = images.get(&cdn::emoji(emoji_id, false, 48)) {
+                ui.image(texture.id(), vec2(EMOJI_SIZE, EMOJI_SIZE));
+            }
+        }
+        (None, Some(name)) => {
+            ui.label(name);
+        }
+        (None, None) => (),
+    }
+}
+
+/// ID of the last message sent in a thread, or of the thread itself if
+/// there are none, snowflakes are ordered by the time they were created at.
+fn last_activity(thread: &Channel) -> u64 {
+    thread
+        .last_message_id
+        .map_or(thread.id.get(), |id| id.get())
+}
+
+const fn sort_order_name(sort_order: ForumSortOrder) -> &'static str {
+    match sort_order {
+        ForumSortOrder::CreationDate => "Creation Date",
+        _ => "Recent Activity",
+    }
+}
+
+/// Fetches archived threads and joins or leaves threads as the current user.
+pub struct ThreadClient {
+    http: HttpClient,
+    runtime: Handle,
+    tx: Sender<RenderMessage>,
+}
+
+impl ThreadClient {
+    pub fn new(http: HttpClient, runtime: Handle, tx: Sender<RenderMessage>) -> Self {
+        Self { http, runtime, tx }
+    }
+
+    /// Fetches the next page of archived threads of a channel, unless one is
+    /// already being fetched or all of them were.
+    pub fn fetch_archived(&self, threads: &mut ThreadStore, channel_id: Id<ChannelMarker>) {
+        let Some(before) = threads.start_fetch(channel_id) else {
+            return;
+        };
+
+        let http = self.http.clone();
+        let tx = self.tx.clone();
+        self.runtime.spawn(async move {
+            let listing = http
+                .archived_threads(channel_id, before)
+                .await
+                .inspect_err(|e| warn!("Failed to fetch archived threads: {e}"))
+                .ok();
+
+            notify(
+                &tx,
+                RenderMessage::ArchivedThreads {
+                    channel_id,
+                    listing,
+                },
+            );
+        });
+    }
+
+    /// Joins or leaves a thread.
+    ///
+    /// The joined state is updated once the gateway confirms the change.
+    pub fn set_joined(&self, thread_id: Id<ChannelMarker>, join: bool) {
+        let http = self.http.clone();
+        self.runtime.spawn(async move {
+            let result = if join {
+                http.join_thread(thread_id).await
+            } else {
+                http.leave_thread(thread_id).await
+            };
+
+            if let Err(e) = result {
+                warn!("Failed to update thread membership: {e}");
+            }
+        });
+    }
+}
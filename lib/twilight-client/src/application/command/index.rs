@@ -0,0 +1,76 @@
+use super::Command;
+use crate::{
+    id::{
+        marker::{ApplicationMarker, UserMarker},
+        Id,
+    },
+    util::ImageHash,
+};
+use serde::{Deserialize, Serialize};
+
+/// Commands the current user can use in a guild or channel, along with the
+/// applications they belong to.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CommandIndex {
+    pub applications: Vec<IndexApplication>,
+    pub application_commands: Vec<Command>,
+}
+
+/// Application listed in a [`CommandIndex`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct IndexApplication {
+    /// Bot user of the application, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_id: Option<Id<UserMarker>>,
+    #[serde(default)]
+    pub description: String,
+    pub icon: Option<ImageHash>,
+    pub id: Id<ApplicationMarker>,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandIndex;
+    use crate::{application::command::CommandOptionType, id::Id};
+
+    #[test]
+    fn command_index() {
+        const JSON: &str = r#"{
+            "applications": [{
+                "id": "1",
+                "name": "Ferris",
+                "description": "",
+                "icon": null,
+                "bot_id": "1"
+            }],
+            "application_commands": [{
+                "id": "2",
+                "type": 1,
+                "application_id": "1",
+                "version": "3",
+                "name": "crab",
+                "description": "Summons a crab",
+                "default_member_permissions": null,
+                "dm_permission": true,
+                "options": [{
+                    "type": 4,
+                    "name": "count",
+                    "description": "How many crabs",
+                    "required": true,
+                    "min_value": 1,
+                    "max_value": 10
+                }]
+            }],
+            "version": "1120379526549585920"
+        }"#;
+
+        let index = serde_json::from_str::<CommandIndex>(JSON).unwrap();
+
+        assert_eq!(index.applications[0].bot_id, Some(Id::new(1)));
+        let command = &index.application_commands[0];
+        assert_eq!(command.application_id, Some(Id::new(1)));
+        assert_eq!(command.options[0].kind, CommandOptionType::Integer);
+        assert_eq!(command.options[0].required, Some(true));
+    }
+}
@@ -10,10 +10,12 @@
 pub mod permissions;
 
 mod command_type;
+mod index;
 mod option;
 
 pub use self::{
     command_type::CommandType,
+    index::{CommandIndex, IndexApplication},
     option::{
         CommandOption, CommandOptionChoice, CommandOptionChoiceValue, CommandOptionType,
         CommandOptionValue,
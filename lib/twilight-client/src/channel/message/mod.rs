@@ -26,7 +26,7 @@ pub use self::{
     interaction::MessageInteraction,
     kind::MessageType,
     mention::Mention,
-    reaction::{Reaction, ReactionType},
+    reaction::{Reaction, ReactionCountDetails, ReactionType},
     reference::MessageReference,
     role_subscription_data::RoleSubscriptionData,
     sticker::Sticker,
@@ -45,7 +45,7 @@ use crate::{
     user::User,
     util::Timestamp,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Text message sent in a [`Channel`].
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -157,6 +157,15 @@ pub struct Message {
     pub mention_roles: Vec<Id<RoleMarker>>,
     /// Users mentioned in the message.
     pub mentions: Vec<Mention>,
+    /// Nonce the message was sent with, used to verify that it was sent.
+    ///
+    /// Nonces sent as integers are turned into strings.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nonce",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub nonce: Option<String>,
     /// Whether the message is pinned.
     pub pinned: bool,
     /// List of reactions to the message.
@@ -193,6 +202,24 @@ pub struct Message {
     pub webhook_id: Option<Id<WebhookMarker>>,
 }
 
+fn deserialize_nonce<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Nonce {
+        Integer(i64),
+        String(String),
+    }
+
+    Ok(
+        Option::<Nonce>::deserialize(deserializer)?.map(|nonce| match nonce {
+            Nonce::Integer(nonce) => nonce.to_string(),
+            Nonce::String(nonce) => nonce,
+        }),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -269,6 +296,7 @@ mod tests {
             mention_everyone: false,
             mention_roles: Vec::new(),
             mentions: Vec::new(),
+            nonce: None,
             pinned: false,
             reactions: Vec::new(),
             reference: None,
@@ -476,13 +504,17 @@ mod tests {
             mention_everyone: false,
             mention_roles: Vec::new(),
             mentions: Vec::new(),
+            nonce: None,
             pinned: false,
             reactions: vec![Reaction {
+                burst_colors: Vec::new(),
                 count: 7,
+                count_details: None,
                 emoji: ReactionType::Unicode {
                     name: "a".to_owned(),
                 },
                 me: true,
+                me_burst: false,
             }],
             reference: Some(MessageReference {
                 channel_id: Some(Id::new(1)),
@@ -709,4 +741,21 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn nonce() -> Result<(), serde_json::Error> {
+        let integer = super::deserialize_nonce(&mut serde_json::Deserializer::from_str(
+            "1163098745120391168",
+        ))?;
+        let string = super::deserialize_nonce(&mut serde_json::Deserializer::from_str(
+            "\"1163098745120391168\"",
+        ))?;
+        let null = super::deserialize_nonce(&mut serde_json::Deserializer::from_str("null"))?;
+
+        assert_eq!(integer.as_deref(), Some("1163098745120391168"));
+        assert_eq!(string, integer);
+        assert_eq!(null, None);
+
+        Ok(())
+    }
 }
@@ -4,12 +4,31 @@ use serde::{Deserialize, Serialize};
 /// Reaction below a message.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct Reaction {
-    /// Amount of reactions this emoji has.
+    /// Colours used for the animation of burst reactions, as hex strings such
+    /// as `#ff0000`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub burst_colors: Vec<String>,
+    /// Amount of reactions this emoji has, including burst reactions.
     pub count: u64,
+    /// Amount of normal and burst reactions this emoji has.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count_details: Option<ReactionCountDetails>,
     /// Emoji of this reaction.
     pub emoji: ReactionType,
     /// Whether the current user has reacted with this emoji.
     pub me: bool,
+    /// Whether the current user has burst reacted with this emoji.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub me_burst: bool,
+}
+
+/// Breakdown of the [`Reaction::count`] into normal and burst reactions.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ReactionCountDetails {
+    /// Amount of burst reactions, also called super reactions.
+    pub burst: u64,
+    /// Amount of normal reactions.
+    pub normal: u64,
 }
 
 /// Type of [`Reaction`].
@@ -48,18 +67,21 @@ pub enum ReactionType {
 
 #[cfg(test)]
 mod tests {
-    use super::{Reaction, ReactionType};
+    use super::{Reaction, ReactionCountDetails, ReactionType};
     use crate::id::Id;
     use serde_test::Token;
 
     #[test]
     fn message_reaction_unicode() {
         let value = Reaction {
+            burst_colors: Vec::new(),
             count: 7,
+            count_details: None,
             emoji: ReactionType::Unicode {
                 name: "a".to_owned(),
             },
             me: true,
+            me_burst: false,
         };
 
         serde_test::assert_tokens(
@@ -86,6 +108,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn message_reaction_burst() {
+        let value = Reaction {
+            burst_colors: vec!["#f0c040".to_owned()],
+            count: 3,
+            count_details: Some(ReactionCountDetails {
+                burst: 1,
+                normal: 2,
+            }),
+            emoji: ReactionType::Unicode {
+                name: "a".to_owned(),
+            },
+            me: false,
+            me_burst: true,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Reaction",
+                    len: 6,
+                },
+                Token::Str("burst_colors"),
+                Token::Seq { len: Some(1) },
+                Token::Str("#f0c040"),
+                Token::SeqEnd,
+                Token::Str("count"),
+                Token::U64(3),
+                Token::Str("count_details"),
+                Token::Some,
+                Token::Struct {
+                    name: "ReactionCountDetails",
+                    len: 2,
+                },
+                Token::Str("burst"),
+                Token::U64(1),
+                Token::Str("normal"),
+                Token::U64(2),
+                Token::StructEnd,
+                Token::Str("emoji"),
+                Token::Struct {
+                    name: "ReactionType",
+                    len: 1,
+                },
+                Token::Str("name"),
+                Token::Str("a"),
+                Token::StructEnd,
+                Token::Str("me"),
+                Token::Bool(false),
+                Token::Str("me_burst"),
+                Token::Bool(true),
+                Token::StructEnd,
+            ],
+        );
+    }
+
     #[test]
     fn custom() {
         let value = ReactionType::Custom {
@@ -4,8 +4,8 @@ use crate::guild::UnavailableGuild;
 
 use super::{super::payload::incoming::*, Event, EventConversionError, EventType};
 use serde::{
-    de::{Deserialize, DeserializeSeed, Deserializer, Error as DeError, IgnoredAny},
-    Serialize,
+    de::{DeserializeSeed, Deserializer, Error as DeError, IgnoredAny},
+    Deserialize, Serialize,
 };
 
 /// A dispatch event, containing information about a created guild, a member
@@ -84,6 +84,27 @@ pub enum DispatchEvent {
     GiftCodeUpdate,
     PresencesReplace,
     UnavailableGuild(UnavailableGuild),
+
+    // User account specific
+    ApplicationCommandAutocompleteResponse(ApplicationCommandAutocompleteResponse),
+    BurstCreditBalanceUpdate(BurstCreditBalanceUpdate),
+    ChannelRecipientAdd(Box<ChannelRecipientAdd>),
+    ChannelRecipientRemove(ChannelRecipientRemove),
+    ChannelUnreadUpdate(ChannelUnreadUpdate),
+    InteractionFailure(InteractionFailure),
+    InteractionModalCreate(InteractionModalCreate),
+    InteractionQueued(InteractionQueued),
+    InteractionSuccess(InteractionSuccess),
+    MessageAck(MessageAck),
+    NotificationCenterItemCompleted(NotificationCenterItemCompleted),
+    NotificationCenterItemCreate(Box<NotificationCenterItemCreate>),
+    ReadySupplemental(ReadySupplemental),
+    RelationshipAdd(Box<RelationshipAdd>),
+    RelationshipRemove(RelationshipRemove),
+    SessionsReplace(SessionsReplace),
+    UserGuildSettingsUpdate(UserGuildSettingsUpdate),
+    UserNoteUpdate(UserNoteUpdate),
+    UserSettingsProtoUpdate(UserSettingsProtoUpdate),
 }
 
 impl DispatchEvent {
@@ -91,15 +112,22 @@ impl DispatchEvent {
     /// Returns the type of event that this event is.
     pub const fn kind(&self) -> EventType {
         match self {
+            Self::ApplicationCommandAutocompleteResponse(_) => {
+                EventType::ApplicationCommandAutocompleteResponse
+            }
             Self::AutoModerationActionExecution(_) => EventType::AutoModerationActionExecution,
             Self::AutoModerationRuleCreate(_) => EventType::AutoModerationRuleCreate,
             Self::AutoModerationRuleDelete(_) => EventType::AutoModerationRuleDelete,
             Self::AutoModerationRuleUpdate(_) => EventType::AutoModerationRuleUpdate,
             Self::GuildBanAdd(_) => EventType::BanAdd,
             Self::GuildBanRemove(_) => EventType::BanRemove,
+            Self::BurstCreditBalanceUpdate(_) => EventType::BurstCreditBalanceUpdate,
             Self::ChannelCreate(_) => EventType::ChannelCreate,
             Self::ChannelDelete(_) => EventType::ChannelDelete,
             Self::ChannelPinsUpdate(_) => EventType::ChannelPinsUpdate,
+            Self::ChannelRecipientAdd(_) => EventType::ChannelRecipientAdd,
+            Self::ChannelRecipientRemove(_) => EventType::ChannelRecipientRemove,
+            Self::ChannelUnreadUpdate(_) => EventType::ChannelUnreadUpdate,
             Self::ChannelUpdate(_) => EventType::ChannelUpdate,
             Self::CommandPermissionsUpdate(_) => EventType::CommandPermissionsUpdate,
             Self::GiftCodeUpdate => EventType::GiftCodeUpdate,
@@ -118,17 +146,23 @@ impl DispatchEvent {
             Self::IntegrationCreate(_) => EventType::IntegrationCreate,
             Self::IntegrationDelete(_) => EventType::IntegrationDelete,
             Self::IntegrationUpdate(_) => EventType::IntegrationUpdate,
-            Self::InteractionCreate(_) => EventType::InteractionCreate,
+            Self::InteractionCreate(_) | Self::InteractionQueued(_) => EventType::InteractionCreate,
+            Self::InteractionFailure(_) => EventType::InteractionFailure,
+            Self::InteractionModalCreate(_) => EventType::InteractionModalCreate,
+            Self::InteractionSuccess(_) => EventType::InteractionSuccess,
             Self::InviteCreate(_) => EventType::InviteCreate,
             Self::InviteDelete(_) => EventType::InviteDelete,
             Self::GuildMemberAdd(_) => EventType::MemberAdd,
             Self::GuildMemberRemove(_) => EventType::MemberRemove,
             Self::GuildMemberUpdate(_) => EventType::MemberUpdate,
             Self::GuildMembersChunk(_) => EventType::MemberChunk,
+            Self::MessageAck(_) => EventType::MessageAck,
             Self::MessageCreate(_) => EventType::MessageCreate,
             Self::MessageDelete(_) => EventType::MessageDelete,
             Self::MessageDeleteBulk(_) => EventType::MessageDeleteBulk,
             Self::MessageUpdate(_) => EventType::MessageUpdate,
+            Self::NotificationCenterItemCompleted(_) => EventType::NotificationCenterItemCompleted,
+            Self::NotificationCenterItemCreate(_) => EventType::NotificationCenterItemCreate,
             Self::PresenceUpdate(_) => EventType::PresenceUpdate,
             Self::PresencesReplace => EventType::PresencesReplace,
             Self::ReactionAdd(_) => EventType::ReactionAdd,
@@ -136,10 +170,14 @@ impl DispatchEvent {
             Self::ReactionRemoveAll(_) => EventType::ReactionRemoveAll,
             Self::ReactionRemoveEmoji(_) => EventType::ReactionRemoveEmoji,
             Self::Ready(_) => EventType::Ready,
+            Self::ReadySupplemental(_) => EventType::ReadySupplemental,
+            Self::RelationshipAdd(_) => EventType::RelationshipAdd,
+            Self::RelationshipRemove(_) => EventType::RelationshipRemove,
             Self::Resumed => EventType::Resumed,
             Self::GuildRoleCreate(_) => EventType::RoleCreate,
             Self::GuildRoleDelete(_) => EventType::RoleDelete,
             Self::GuildRoleUpdate(_) => EventType::RoleUpdate,
+            Self::SessionsReplace(_) => EventType::SessionsReplace,
             Self::StageInstanceCreate(_) => EventType::StageInstanceCreate,
             Self::StageInstanceDelete(_) => EventType::StageInstanceDelete,
             Self::StageInstanceUpdate(_) => EventType::StageInstanceUpdate,
@@ -151,6 +189,9 @@ impl DispatchEvent {
             Self::ThreadUpdate(_) => EventType::ThreadUpdate,
             Self::TypingStart(_) => EventType::TypingStart,
             Self::UnavailableGuild(_) => EventType::UnavailableGuild,
+            Self::UserGuildSettingsUpdate(_) => EventType::UserGuildSettingsUpdate,
+            Self::UserNoteUpdate(_) => EventType::UserNoteUpdate,
+            Self::UserSettingsProtoUpdate(_) => EventType::UserSettingsProtoUpdate,
             Self::UserUpdate(_) => EventType::UserUpdate,
             Self::VoiceServerUpdate(_) => EventType::VoiceServerUpdate,
             Self::VoiceStateUpdate(_) => EventType::VoiceStateUpdate,
@@ -165,15 +206,22 @@ impl TryFrom<Event> for DispatchEvent {
 
     fn try_from(event: Event) -> Result<Self, Self::Error> {
         Ok(match event {
+            Event::ApplicationCommandAutocompleteResponse(v) => {
+                Self::ApplicationCommandAutocompleteResponse(v)
+            }
             Event::AutoModerationActionExecution(v) => Self::AutoModerationActionExecution(v),
             Event::AutoModerationRuleCreate(v) => Self::AutoModerationRuleCreate(v),
             Event::AutoModerationRuleDelete(v) => Self::AutoModerationRuleDelete(v),
             Event::AutoModerationRuleUpdate(v) => Self::AutoModerationRuleUpdate(v),
             Event::BanAdd(v) => Self::GuildBanAdd(v),
             Event::BanRemove(v) => Self::GuildBanRemove(v),
+            Event::BurstCreditBalanceUpdate(v) => Self::BurstCreditBalanceUpdate(v),
             Event::ChannelCreate(v) => Self::ChannelCreate(v),
             Event::ChannelDelete(v) => Self::ChannelDelete(v),
             Event::ChannelPinsUpdate(v) => Self::ChannelPinsUpdate(v),
+            Event::ChannelRecipientAdd(v) => Self::ChannelRecipientAdd(v),
+            Event::ChannelRecipientRemove(v) => Self::ChannelRecipientRemove(v),
+            Event::ChannelUnreadUpdate(v) => Self::ChannelUnreadUpdate(v),
             Event::ChannelUpdate(v) => Self::ChannelUpdate(v),
             Event::CommandPermissionsUpdate(v) => Self::CommandPermissionsUpdate(v),
             Event::GiftCodeUpdate => Self::GiftCodeUpdate,
@@ -192,16 +240,23 @@ impl TryFrom<Event> for DispatchEvent {
             Event::IntegrationDelete(v) => Self::IntegrationDelete(v),
             Event::IntegrationUpdate(v) => Self::IntegrationUpdate(v),
             Event::InteractionCreate(v) => Self::InteractionCreate(v),
+            Event::InteractionFailure(v) => Self::InteractionFailure(v),
+            Event::InteractionModalCreate(v) => Self::InteractionModalCreate(v),
+            Event::InteractionQueued(v) => Self::InteractionQueued(v),
+            Event::InteractionSuccess(v) => Self::InteractionSuccess(v),
             Event::InviteCreate(v) => Self::InviteCreate(v),
             Event::InviteDelete(v) => Self::InviteDelete(v),
             Event::MemberAdd(v) => Self::GuildMemberAdd(v),
             Event::MemberRemove(v) => Self::GuildMemberRemove(v),
             Event::MemberUpdate(v) => Self::GuildMemberUpdate(v),
             Event::MemberChunk(v) => Self::GuildMembersChunk(v),
+            Event::MessageAck(v) => Self::MessageAck(v),
             Event::MessageCreate(v) => Self::MessageCreate(v),
             Event::MessageDelete(v) => Self::MessageDelete(v),
             Event::MessageDeleteBulk(v) => Self::MessageDeleteBulk(v),
             Event::MessageUpdate(v) => Self::MessageUpdate(v),
+            Event::NotificationCenterItemCompleted(v) => Self::NotificationCenterItemCompleted(v),
+            Event::NotificationCenterItemCreate(v) => Self::NotificationCenterItemCreate(v),
             Event::PresenceUpdate(v) => Self::PresenceUpdate(v),
             Event::PresencesReplace => Self::PresencesReplace,
             Event::ReactionAdd(v) => Self::ReactionAdd(v),
@@ -209,10 +264,14 @@ impl TryFrom<Event> for DispatchEvent {
             Event::ReactionRemoveAll(v) => Self::ReactionRemoveAll(v),
             Event::ReactionRemoveEmoji(v) => Self::ReactionRemoveEmoji(v),
             Event::Ready(v) => Self::Ready(v),
+            Event::ReadySupplemental(v) => Self::ReadySupplemental(v),
+            Event::RelationshipAdd(v) => Self::RelationshipAdd(v),
+            Event::RelationshipRemove(v) => Self::RelationshipRemove(v),
             Event::Resumed => Self::Resumed,
             Event::RoleCreate(v) => Self::GuildRoleCreate(v),
             Event::RoleDelete(v) => Self::GuildRoleDelete(v),
             Event::RoleUpdate(v) => Self::GuildRoleUpdate(v),
+            Event::SessionsReplace(v) => Self::SessionsReplace(v),
             Event::StageInstanceCreate(v) => Self::StageInstanceCreate(v),
             Event::StageInstanceDelete(v) => Self::StageInstanceDelete(v),
             Event::StageInstanceUpdate(v) => Self::StageInstanceUpdate(v),
@@ -224,6 +283,9 @@ impl TryFrom<Event> for DispatchEvent {
             Event::ThreadUpdate(v) => Self::ThreadUpdate(v),
             Event::TypingStart(v) => Self::TypingStart(v),
             Event::UnavailableGuild(v) => Self::UnavailableGuild(v),
+            Event::UserGuildSettingsUpdate(v) => Self::UserGuildSettingsUpdate(v),
+            Event::UserNoteUpdate(v) => Self::UserNoteUpdate(v),
+            Event::UserSettingsProtoUpdate(v) => Self::UserSettingsProtoUpdate(v),
             Event::UserUpdate(v) => Self::UserUpdate(v),
             Event::VoiceServerUpdate(v) => Self::VoiceServerUpdate(v),
             Event::VoiceStateUpdate(v) => Self::VoiceStateUpdate(v),
@@ -253,6 +315,11 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
     #[allow(clippy::too_many_lines)]
     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
         Ok(match self.0 {
+            "APPLICATION_COMMAND_AUTOCOMPLETE_RESPONSE" => {
+                DispatchEvent::ApplicationCommandAutocompleteResponse(
+                    ApplicationCommandAutocompleteResponse::deserialize(deserializer)?,
+                )
+            }
             "AUTO_MODERATION_ACTION_EXECUTION" => DispatchEvent::AutoModerationActionExecution(
                 AutoModerationActionExecution::deserialize(deserializer)?,
             ),
@@ -265,6 +332,9 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "AUTO_MODERATION_RULE_UPDATE" => DispatchEvent::AutoModerationRuleUpdate(Box::new(
                 AutoModerationRuleUpdate::deserialize(deserializer)?,
             )),
+            "BURST_CREDIT_BALANCE_UPDATE" => DispatchEvent::BurstCreditBalanceUpdate(
+                BurstCreditBalanceUpdate::deserialize(deserializer)?,
+            ),
             "CHANNEL_CREATE" => {
                 DispatchEvent::ChannelCreate(Box::new(ChannelCreate::deserialize(deserializer)?))
             }
@@ -274,6 +344,15 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "CHANNEL_PINS_UPDATE" => {
                 DispatchEvent::ChannelPinsUpdate(ChannelPinsUpdate::deserialize(deserializer)?)
             }
+            "CHANNEL_RECIPIENT_ADD" => DispatchEvent::ChannelRecipientAdd(Box::new(
+                ChannelRecipientAdd::deserialize(deserializer)?,
+            )),
+            "CHANNEL_RECIPIENT_REMOVE" => DispatchEvent::ChannelRecipientRemove(
+                ChannelRecipientRemove::deserialize(deserializer)?,
+            ),
+            "CHANNEL_UNREAD_UPDATE" => {
+                DispatchEvent::ChannelUnreadUpdate(ChannelUnreadUpdate::deserialize(deserializer)?)
+            }
             "CHANNEL_UPDATE" => {
                 DispatchEvent::ChannelUpdate(Box::new(ChannelUpdate::deserialize(deserializer)?))
             }
@@ -352,15 +431,27 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "INTEGRATION_UPDATE" => DispatchEvent::IntegrationUpdate(Box::new(
                 IntegrationUpdate::deserialize(deserializer)?,
             )),
-            "INTERACTION_CREATE" => DispatchEvent::InteractionCreate(Box::new(
-                InteractionCreate::deserialize(deserializer)?,
-            )),
+            // user accounts only receive the ID of their own interactions
+            "INTERACTION_CREATE" => match AnyInteractionCreate::deserialize(deserializer)? {
+                AnyInteractionCreate::Bot(event) => DispatchEvent::InteractionCreate(event),
+                AnyInteractionCreate::User(event) => DispatchEvent::InteractionQueued(event),
+            },
+            "INTERACTION_FAILURE" => {
+                DispatchEvent::InteractionFailure(InteractionFailure::deserialize(deserializer)?)
+            }
+            "INTERACTION_MODAL_CREATE" => DispatchEvent::InteractionModalCreate(
+                InteractionModalCreate::deserialize(deserializer)?,
+            ),
+            "INTERACTION_SUCCESS" => {
+                DispatchEvent::InteractionSuccess(InteractionSuccess::deserialize(deserializer)?)
+            }
             "INVITE_CREATE" => {
                 DispatchEvent::InviteCreate(Box::new(InviteCreate::deserialize(deserializer)?))
             }
             "INVITE_DELETE" => {
                 DispatchEvent::InviteDelete(InviteDelete::deserialize(deserializer)?)
             }
+            "MESSAGE_ACK" => DispatchEvent::MessageAck(MessageAck::deserialize(deserializer)?),
             "MESSAGE_CREATE" => {
                 DispatchEvent::MessageCreate(Box::new(MessageCreate::deserialize(deserializer)?))
             }
@@ -385,6 +476,12 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "MESSAGE_UPDATE" => {
                 DispatchEvent::MessageUpdate(Box::new(MessageUpdate::deserialize(deserializer)?))
             }
+            "NOTIFICATION_CENTER_ITEM_COMPLETED" => DispatchEvent::NotificationCenterItemCompleted(
+                NotificationCenterItemCompleted::deserialize(deserializer)?,
+            ),
+            "NOTIFICATION_CENTER_ITEM_CREATE" => DispatchEvent::NotificationCenterItemCreate(
+                Box::new(NotificationCenterItemCreate::deserialize(deserializer)?),
+            ),
             "PRESENCE_UPDATE" => {
                 DispatchEvent::PresenceUpdate(Box::new(PresenceUpdate::deserialize(deserializer)?))
             }
@@ -393,10 +490,22 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
                 DispatchEvent::PresencesReplace
             }
             "READY" => DispatchEvent::Ready(Box::new(Ready::deserialize(deserializer)?)),
+            "READY_SUPPLEMENTAL" => {
+                DispatchEvent::ReadySupplemental(ReadySupplemental::deserialize(deserializer)?)
+            }
+            "RELATIONSHIP_ADD" => DispatchEvent::RelationshipAdd(Box::new(
+                RelationshipAdd::deserialize(deserializer)?,
+            )),
+            "RELATIONSHIP_REMOVE" => {
+                DispatchEvent::RelationshipRemove(RelationshipRemove::deserialize(deserializer)?)
+            }
             "RESUMED" => {
                 deserializer.deserialize_ignored_any(IgnoredAny)?;
                 DispatchEvent::Resumed
             }
+            "SESSIONS_REPLACE" => {
+                DispatchEvent::SessionsReplace(SessionsReplace::deserialize(deserializer)?)
+            }
             "STAGE_INSTANCE_CREATE" => {
                 DispatchEvent::StageInstanceCreate(StageInstanceCreate::deserialize(deserializer)?)
             }
@@ -427,6 +536,15 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
             "TYPING_START" => {
                 DispatchEvent::TypingStart(Box::new(TypingStart::deserialize(deserializer)?))
             }
+            "USER_GUILD_SETTINGS_UPDATE" => DispatchEvent::UserGuildSettingsUpdate(
+                UserGuildSettingsUpdate::deserialize(deserializer)?,
+            ),
+            "USER_NOTE_UPDATE" => {
+                DispatchEvent::UserNoteUpdate(UserNoteUpdate::deserialize(deserializer)?)
+            }
+            "USER_SETTINGS_PROTO_UPDATE" => DispatchEvent::UserSettingsProtoUpdate(
+                UserSettingsProtoUpdate::deserialize(deserializer)?,
+            ),
             "USER_UPDATE" => DispatchEvent::UserUpdate(UserUpdate::deserialize(deserializer)?),
             "VOICE_SERVER_UPDATE" => {
                 DispatchEvent::VoiceServerUpdate(VoiceServerUpdate::deserialize(deserializer)?)
@@ -447,9 +565,19 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
     }
 }
 
+/// Payload of an `INTERACTION_CREATE` event, which differs between bots and
+/// user accounts.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyInteractionCreate {
+    Bot(Box<InteractionCreate>),
+    User(InteractionQueued),
+}
+
 #[cfg(test)]
 mod tests {
     use super::{DispatchEvent, DispatchEventWithTypeDeserializer};
+    use crate::{gateway::payload::incoming::InteractionQueued, id::Id};
     use serde::de::DeserializeSeed;
     use serde_json::Deserializer;
 
@@ -466,4 +594,24 @@ mod tests {
 
         assert_eq!(event, DispatchEvent::GiftCodeUpdate);
     }
+
+    #[test]
+    fn user_interaction_create() {
+        let input = r#"{
+            "id": "1",
+            "nonce": "1120379526549585920"
+        }"#;
+
+        let deserializer = DispatchEventWithTypeDeserializer::new("INTERACTION_CREATE");
+        let mut json_deserializer = Deserializer::from_str(input);
+        let event = deserializer.deserialize(&mut json_deserializer).unwrap();
+
+        assert_eq!(
+            event,
+            DispatchEvent::InteractionQueued(InteractionQueued {
+                id: Id::new(1),
+                nonce: Some("1120379526549585920".to_owned()),
+            })
+        );
+    }
 }
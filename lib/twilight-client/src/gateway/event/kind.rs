@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventType {
+    ApplicationCommandAutocompleteResponse,
     AutoModerationActionExecution,
     AutoModerationRuleCreate,
     AutoModerationRuleDelete,
@@ -14,9 +15,13 @@ pub enum EventType {
     BanAdd,
     #[serde(rename = "GUILD_BAN_REMOVE")]
     BanRemove,
+    BurstCreditBalanceUpdate,
     ChannelCreate,
     ChannelDelete,
     ChannelPinsUpdate,
+    ChannelRecipientAdd,
+    ChannelRecipientRemove,
+    ChannelUnreadUpdate,
     ChannelUpdate,
     #[serde(rename = "APPLICATION_COMMAND_PERMISSIONS_UPDATE")]
     CommandPermissionsUpdate,
@@ -43,6 +48,9 @@ pub enum EventType {
     IntegrationDelete,
     IntegrationUpdate,
     InteractionCreate,
+    InteractionFailure,
+    InteractionModalCreate,
+    InteractionSuccess,
     InviteCreate,
     InviteDelete,
     #[serde(rename = "GUILD_MEMBER_ADD")]
@@ -53,10 +61,13 @@ pub enum EventType {
     MemberRemove,
     #[serde(rename = "GUILD_MEMBER_UPDATE")]
     MemberUpdate,
+    MessageAck,
     MessageCreate,
     MessageDelete,
     MessageDeleteBulk,
     MessageUpdate,
+    NotificationCenterItemCompleted,
+    NotificationCenterItemCreate,
     PresenceUpdate,
     PresencesReplace,
     #[serde(rename = "MESSAGE_REACTION_ADD")]
@@ -68,6 +79,9 @@ pub enum EventType {
     #[serde(rename = "MESSAGE_REACTION_REMOVE_EMOJI")]
     ReactionRemoveEmoji,
     Ready,
+    ReadySupplemental,
+    RelationshipAdd,
+    RelationshipRemove,
     Resumed,
     #[serde(rename = "GUILD_ROLE_CREATE")]
     RoleCreate,
@@ -75,6 +89,7 @@ pub enum EventType {
     RoleDelete,
     #[serde(rename = "GUILD_ROLE_UPDATE")]
     RoleUpdate,
+    SessionsReplace,
     StageInstanceCreate,
     StageInstanceDelete,
     StageInstanceUpdate,
@@ -86,6 +101,9 @@ pub enum EventType {
     ThreadUpdate,
     TypingStart,
     UnavailableGuild,
+    UserGuildSettingsUpdate,
+    UserNoteUpdate,
+    UserSettingsProtoUpdate,
     UserUpdate,
     VoiceServerUpdate,
     VoiceStateUpdate,
@@ -95,15 +113,22 @@ pub enum EventType {
 impl EventType {
     pub const fn name(self) -> Option<&'static str> {
         match self {
+            Self::ApplicationCommandAutocompleteResponse => {
+                Some("APPLICATION_COMMAND_AUTOCOMPLETE_RESPONSE")
+            }
             Self::AutoModerationActionExecution => Some("AUTO_MODERATION_ACTION_EXECUTION"),
             Self::AutoModerationRuleCreate => Some("AUTO_MODERATION_RULE_CREATE"),
             Self::AutoModerationRuleDelete => Some("AUTO_MODERATION_RULE_DELETE"),
             Self::AutoModerationRuleUpdate => Some("AUTO_MODERATION_RULE_UPDATE"),
             Self::BanAdd => Some("GUILD_BAN_ADD"),
             Self::BanRemove => Some("GUILD_BAN_REMOVE"),
+            Self::BurstCreditBalanceUpdate => Some("BURST_CREDIT_BALANCE_UPDATE"),
             Self::ChannelCreate => Some("CHANNEL_CREATE"),
             Self::ChannelDelete => Some("CHANNEL_DELETE"),
             Self::ChannelPinsUpdate => Some("CHANNEL_PINS_UPDATE"),
+            Self::ChannelRecipientAdd => Some("CHANNEL_RECIPIENT_ADD"),
+            Self::ChannelRecipientRemove => Some("CHANNEL_RECIPIENT_REMOVE"),
+            Self::ChannelUnreadUpdate => Some("CHANNEL_UNREAD_UPDATE"),
             Self::ChannelUpdate => Some("CHANNEL_UPDATE"),
             Self::CommandPermissionsUpdate => Some("APPLICATION_COMMAND_PERMISSIONS_UPDATE"),
             Self::GiftCodeUpdate => Some("GIFT_CODE_UPDATE"),
@@ -123,16 +148,22 @@ impl EventType {
             Self::IntegrationDelete => Some("INTEGRATION_DELETE"),
             Self::IntegrationUpdate => Some("INTEGRATION_UPDATE"),
             Self::InteractionCreate => Some("INTERACTION_CREATE"),
+            Self::InteractionFailure => Some("INTERACTION_FAILURE"),
+            Self::InteractionModalCreate => Some("INTERACTION_MODAL_CREATE"),
+            Self::InteractionSuccess => Some("INTERACTION_SUCCESS"),
             Self::InviteCreate => Some("INVITE_CREATE"),
             Self::InviteDelete => Some("INVITE_DELETE"),
             Self::MemberAdd => Some("GUILD_MEMBER_ADD"),
             Self::MemberChunk => Some("GUILD_MEMBERS_CHUNK"),
             Self::MemberRemove => Some("GUILD_MEMBER_REMOVE"),
             Self::MemberUpdate => Some("GUILD_MEMBER_UPDATE"),
+            Self::MessageAck => Some("MESSAGE_ACK"),
             Self::MessageCreate => Some("MESSAGE_CREATE"),
             Self::MessageDelete => Some("MESSAGE_DELETE"),
             Self::MessageDeleteBulk => Some("MESSAGE_DELETE_BULK"),
             Self::MessageUpdate => Some("MESSAGE_UPDATE"),
+            Self::NotificationCenterItemCompleted => Some("NOTIFICATION_CENTER_ITEM_COMPLETED"),
+            Self::NotificationCenterItemCreate => Some("NOTIFICATION_CENTER_ITEM_CREATE"),
             Self::PresencesReplace => Some("PRESENCES_REPLACE"),
             Self::PresenceUpdate => Some("PRESENCE_UPDATE"),
             Self::ReactionAdd => Some("MESSAGE_REACTION_ADD"),
@@ -140,10 +171,14 @@ impl EventType {
             Self::ReactionRemoveAll => Some("MESSAGE_REACTION_REMOVE_ALL"),
             Self::ReactionRemoveEmoji => Some("MESSAGE_REACTION_REMOVE_EMOJI"),
             Self::Ready => Some("READY"),
+            Self::ReadySupplemental => Some("READY_SUPPLEMENTAL"),
+            Self::RelationshipAdd => Some("RELATIONSHIP_ADD"),
+            Self::RelationshipRemove => Some("RELATIONSHIP_REMOVE"),
             Self::Resumed => Some("RESUMED"),
             Self::RoleCreate => Some("GUILD_ROLE_CREATE"),
             Self::RoleDelete => Some("GUILD_ROLE_DELETE"),
             Self::RoleUpdate => Some("GUILD_ROLE_UPDATE"),
+            Self::SessionsReplace => Some("SESSIONS_REPLACE"),
             Self::StageInstanceCreate => Some("STAGE_INSTANCE_CREATE"),
             Self::StageInstanceDelete => Some("STAGE_INSTANCE_DELETE"),
             Self::StageInstanceUpdate => Some("STAGE_INSTANCE_UPDATE"),
@@ -155,6 +190,9 @@ impl EventType {
             Self::ThreadUpdate => Some("THREAD_UPDATE"),
             Self::TypingStart => Some("TYPING_START"),
             Self::UnavailableGuild => Some("UNAVAILABLE_GUILD"),
+            Self::UserGuildSettingsUpdate => Some("USER_GUILD_SETTINGS_UPDATE"),
+            Self::UserNoteUpdate => Some("USER_NOTE_UPDATE"),
+            Self::UserSettingsProtoUpdate => Some("USER_SETTINGS_PROTO_UPDATE"),
             Self::UserUpdate => Some("USER_UPDATE"),
             Self::VoiceServerUpdate => Some("VOICE_SERVER_UPDATE"),
             Self::VoiceStateUpdate => Some("VOICE_STATE_UPDATE"),
@@ -174,10 +212,17 @@ impl<'a> TryFrom<&'a str> for EventType {
 
     fn try_from(event_type: &'a str) -> Result<Self, Self::Error> {
         match event_type {
+            "APPLICATION_COMMAND_AUTOCOMPLETE_RESPONSE" => {
+                Ok(Self::ApplicationCommandAutocompleteResponse)
+            }
             "AUTO_MODERATION_ACTION_EXECUTION" => Ok(Self::AutoModerationActionExecution),
             "AUTO_MODERATION_RULE_CREATE" => Ok(Self::AutoModerationRuleCreate),
             "AUTO_MODERATION_RULE_DELETE" => Ok(Self::AutoModerationRuleDelete),
             "AUTO_MODERATION_RULE_UPDATE" => Ok(Self::AutoModerationRuleUpdate),
+            "BURST_CREDIT_BALANCE_UPDATE" => Ok(Self::BurstCreditBalanceUpdate),
+            "CHANNEL_RECIPIENT_ADD" => Ok(Self::ChannelRecipientAdd),
+            "CHANNEL_RECIPIENT_REMOVE" => Ok(Self::ChannelRecipientRemove),
+            "CHANNEL_UNREAD_UPDATE" => Ok(Self::ChannelUnreadUpdate),
             "GUILD_AUDIT_LOG_ENTRY_CREATE" => Ok(Self::GuildAuditLogEntryCreate),
             "GUILD_BAN_ADD" => Ok(Self::BanAdd),
             "GUILD_BAN_REMOVE" => Ok(Self::BanRemove),
@@ -201,16 +246,22 @@ impl<'a> TryFrom<&'a str> for EventType {
             "INTEGRATION_DELETE" => Ok(Self::IntegrationDelete),
             "INTEGRATION_UPDATE" => Ok(Self::IntegrationUpdate),
             "INTERACTION_CREATE" => Ok(Self::InteractionCreate),
+            "INTERACTION_FAILURE" => Ok(Self::InteractionFailure),
+            "INTERACTION_MODAL_CREATE" => Ok(Self::InteractionModalCreate),
+            "INTERACTION_SUCCESS" => Ok(Self::InteractionSuccess),
             "INVITE_CREATE" => Ok(Self::InviteCreate),
             "INVITE_DELETE" => Ok(Self::InviteDelete),
             "GUILD_MEMBER_ADD" => Ok(Self::MemberAdd),
             "GUILD_MEMBER_REMOVE" => Ok(Self::MemberRemove),
             "GUILD_MEMBER_UPDATE" => Ok(Self::MemberUpdate),
             "GUILD_MEMBERS_CHUNK" => Ok(Self::MemberChunk),
+            "MESSAGE_ACK" => Ok(Self::MessageAck),
             "MESSAGE_CREATE" => Ok(Self::MessageCreate),
             "MESSAGE_DELETE" => Ok(Self::MessageDelete),
             "MESSAGE_DELETE_BULK" => Ok(Self::MessageDeleteBulk),
             "MESSAGE_UPDATE" => Ok(Self::MessageUpdate),
+            "NOTIFICATION_CENTER_ITEM_COMPLETED" => Ok(Self::NotificationCenterItemCompleted),
+            "NOTIFICATION_CENTER_ITEM_CREATE" => Ok(Self::NotificationCenterItemCreate),
             "PRESENCE_UPDATE" => Ok(Self::PresenceUpdate),
             "PRESENCES_REPLACE" => Ok(Self::PresencesReplace),
             "MESSAGE_REACTION_ADD" => Ok(Self::ReactionAdd),
@@ -218,10 +269,14 @@ impl<'a> TryFrom<&'a str> for EventType {
             "MESSAGE_REACTION_REMOVE_ALL" => Ok(Self::ReactionRemoveAll),
             "MESSAGE_REACTION_REMOVE_EMOJI" => Ok(Self::ReactionRemoveEmoji),
             "READY" => Ok(Self::Ready),
+            "READY_SUPPLEMENTAL" => Ok(Self::ReadySupplemental),
+            "RELATIONSHIP_ADD" => Ok(Self::RelationshipAdd),
+            "RELATIONSHIP_REMOVE" => Ok(Self::RelationshipRemove),
             "RESUMED" => Ok(Self::Resumed),
             "GUILD_ROLE_CREATE" => Ok(Self::RoleCreate),
             "GUILD_ROLE_DELETE" => Ok(Self::RoleDelete),
             "GUILD_ROLE_UPDATE" => Ok(Self::RoleUpdate),
+            "SESSIONS_REPLACE" => Ok(Self::SessionsReplace),
             "STAGE_INSTANCE_CREATE" => Ok(Self::StageInstanceCreate),
             "STAGE_INSTANCE_DELETE" => Ok(Self::StageInstanceDelete),
             "STAGE_INSTANCE_UPDATE" => Ok(Self::StageInstanceUpdate),
@@ -233,6 +288,9 @@ impl<'a> TryFrom<&'a str> for EventType {
             "THREAD_UPDATE" => Ok(Self::ThreadUpdate),
             "TYPING_START" => Ok(Self::TypingStart),
             "UNAVAILABLE_GUILD" => Ok(Self::UnavailableGuild),
+            "USER_GUILD_SETTINGS_UPDATE" => Ok(Self::UserGuildSettingsUpdate),
+            "USER_NOTE_UPDATE" => Ok(Self::UserNoteUpdate),
+            "USER_SETTINGS_PROTO_UPDATE" => Ok(Self::UserSettingsProtoUpdate),
             "USER_UPDATE" => Ok(Self::UserUpdate),
             "VOICE_SERVER_UPDATE" => Ok(Self::VoiceServerUpdate),
             "VOICE_STATE_UPDATE" => Ok(Self::VoiceStateUpdate),
@@ -260,6 +318,10 @@ mod tests {
     #[allow(clippy::too_many_lines)]
     #[test]
     fn variants() {
+        assert_variant(
+            EventType::ApplicationCommandAutocompleteResponse,
+            "APPLICATION_COMMAND_AUTOCOMPLETE_RESPONSE",
+        );
         assert_variant(
             EventType::AutoModerationActionExecution,
             "AUTO_MODERATION_ACTION_EXECUTION",
@@ -278,9 +340,19 @@ mod tests {
         );
         assert_variant(EventType::BanAdd, "GUILD_BAN_ADD");
         assert_variant(EventType::BanRemove, "GUILD_BAN_REMOVE");
+        assert_variant(
+            EventType::BurstCreditBalanceUpdate,
+            "BURST_CREDIT_BALANCE_UPDATE",
+        );
         assert_variant(EventType::ChannelCreate, "CHANNEL_CREATE");
         assert_variant(EventType::ChannelDelete, "CHANNEL_DELETE");
         assert_variant(EventType::ChannelPinsUpdate, "CHANNEL_PINS_UPDATE");
+        assert_variant(EventType::ChannelRecipientAdd, "CHANNEL_RECIPIENT_ADD");
+        assert_variant(
+            EventType::ChannelRecipientRemove,
+            "CHANNEL_RECIPIENT_REMOVE",
+        );
+        assert_variant(EventType::ChannelUnreadUpdate, "CHANNEL_UNREAD_UPDATE");
         assert_variant(EventType::ChannelUpdate, "CHANNEL_UPDATE");
         assert_variant(
             EventType::CommandPermissionsUpdate,
@@ -332,16 +404,31 @@ mod tests {
         assert_variant(EventType::IntegrationDelete, "INTEGRATION_DELETE");
         assert_variant(EventType::IntegrationUpdate, "INTEGRATION_UPDATE");
         assert_variant(EventType::InteractionCreate, "INTERACTION_CREATE");
+        assert_variant(EventType::InteractionFailure, "INTERACTION_FAILURE");
+        assert_variant(
+            EventType::InteractionModalCreate,
+            "INTERACTION_MODAL_CREATE",
+        );
+        assert_variant(EventType::InteractionSuccess, "INTERACTION_SUCCESS");
         assert_variant(EventType::InviteCreate, "INVITE_CREATE");
         assert_variant(EventType::InviteDelete, "INVITE_DELETE");
         assert_variant(EventType::MemberAdd, "GUILD_MEMBER_ADD");
         assert_variant(EventType::MemberChunk, "GUILD_MEMBERS_CHUNK");
         assert_variant(EventType::MemberRemove, "GUILD_MEMBER_REMOVE");
         assert_variant(EventType::MemberUpdate, "GUILD_MEMBER_UPDATE");
+        assert_variant(EventType::MessageAck, "MESSAGE_ACK");
         assert_variant(EventType::MessageCreate, "MESSAGE_CREATE");
         assert_variant(EventType::MessageDelete, "MESSAGE_DELETE");
         assert_variant(EventType::MessageDeleteBulk, "MESSAGE_DELETE_BULK");
         assert_variant(EventType::MessageUpdate, "MESSAGE_UPDATE");
+        assert_variant(
+            EventType::NotificationCenterItemCompleted,
+            "NOTIFICATION_CENTER_ITEM_COMPLETED",
+        );
+        assert_variant(
+            EventType::NotificationCenterItemCreate,
+            "NOTIFICATION_CENTER_ITEM_CREATE",
+        );
         assert_variant(EventType::PresenceUpdate, "PRESENCE_UPDATE");
         assert_variant(EventType::PresencesReplace, "PRESENCES_REPLACE");
         assert_variant(EventType::ReactionAdd, "MESSAGE_REACTION_ADD");
@@ -352,10 +439,14 @@ mod tests {
             "MESSAGE_REACTION_REMOVE_EMOJI",
         );
         assert_variant(EventType::Ready, "READY");
+        assert_variant(EventType::ReadySupplemental, "READY_SUPPLEMENTAL");
+        assert_variant(EventType::RelationshipAdd, "RELATIONSHIP_ADD");
+        assert_variant(EventType::RelationshipRemove, "RELATIONSHIP_REMOVE");
         assert_variant(EventType::Resumed, "RESUMED");
         assert_variant(EventType::RoleCreate, "GUILD_ROLE_CREATE");
         assert_variant(EventType::RoleDelete, "GUILD_ROLE_DELETE");
         assert_variant(EventType::RoleUpdate, "GUILD_ROLE_UPDATE");
+        assert_variant(EventType::SessionsReplace, "SESSIONS_REPLACE");
         assert_variant(EventType::StageInstanceCreate, "STAGE_INSTANCE_CREATE");
         assert_variant(EventType::StageInstanceDelete, "STAGE_INSTANCE_DELETE");
         assert_variant(EventType::StageInstanceUpdate, "STAGE_INSTANCE_UPDATE");
@@ -367,6 +458,15 @@ mod tests {
         assert_variant(EventType::ThreadUpdate, "THREAD_UPDATE");
         assert_variant(EventType::TypingStart, "TYPING_START");
         assert_variant(EventType::UnavailableGuild, "UNAVAILABLE_GUILD");
+        assert_variant(
+            EventType::UserGuildSettingsUpdate,
+            "USER_GUILD_SETTINGS_UPDATE",
+        );
+        assert_variant(EventType::UserNoteUpdate, "USER_NOTE_UPDATE");
+        assert_variant(
+            EventType::UserSettingsProtoUpdate,
+            "USER_SETTINGS_PROTO_UPDATE",
+        );
         assert_variant(EventType::UserUpdate, "USER_UPDATE");
         assert_variant(EventType::VoiceServerUpdate, "VOICE_SERVER_UPDATE");
         assert_variant(EventType::VoiceStateUpdate, "VOICE_STATE_UPDATE");
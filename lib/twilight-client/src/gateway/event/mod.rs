@@ -27,6 +27,8 @@ use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 /// [gateway close event]: Self::GatewayClose
 #[derive(Clone, Debug, PartialEq)]
 pub enum Event {
+    /// Choices suggested for the focused option of a command being typed.
+    ApplicationCommandAutocompleteResponse(ApplicationCommandAutocompleteResponse),
     /// Message was blocked by AutoMod according to a rule.
     AutoModerationActionExecution(AutoModerationActionExecution),
     /// Sent when an auto moderation rule is created.
@@ -39,12 +41,20 @@ pub enum Event {
     BanAdd(BanAdd),
     /// A user's ban from a guild was removed.
     BanRemove(BanRemove),
+    /// The amount of burst reactions the current user can send changed.
+    BurstCreditBalanceUpdate(BurstCreditBalanceUpdate),
     /// A channel was created.
     ChannelCreate(Box<ChannelCreate>),
     /// A channel was deleted.
     ChannelDelete(Box<ChannelDelete>),
     /// A channel's pins were updated.
     ChannelPinsUpdate(ChannelPinsUpdate),
+    /// A user was added to a group DM.
+    ChannelRecipientAdd(Box<ChannelRecipientAdd>),
+    /// A user was removed from a group DM.
+    ChannelRecipientRemove(ChannelRecipientRemove),
+    /// The latest messages of the channels of a guild were updated.
+    ChannelUnreadUpdate(ChannelUnreadUpdate),
     /// A channel was updated.
     ChannelUpdate(Box<ChannelUpdate>),
     /// A command's permissions were updated.
@@ -98,6 +108,15 @@ pub enum Event {
     IntegrationUpdate(Box<IntegrationUpdate>),
     /// An interaction was invoked by a user.
     InteractionCreate(Box<InteractionCreate>),
+    /// An interaction sent by the current user failed.
+    InteractionFailure(InteractionFailure),
+    /// An application opened a modal in response to an interaction of the
+    /// current user.
+    InteractionModalCreate(InteractionModalCreate),
+    /// An interaction sent by the current user is waiting for a response.
+    InteractionQueued(InteractionQueued),
+    /// An interaction sent by the current user succeeded.
+    InteractionSuccess(InteractionSuccess),
     /// A invite was made.
     InviteCreate(Box<InviteCreate>),
     /// A invite was deleted.
@@ -110,6 +129,8 @@ pub enum Event {
     MemberUpdate(Box<MemberUpdate>),
     /// A chunk of members were received from the gateway.
     MemberChunk(MemberChunk),
+    /// A channel was acknowledged up to a message by the current user.
+    MessageAck(MessageAck),
     /// A message was created in a channel.
     MessageCreate(Box<MessageCreate>),
     /// A message was deleted in a channel.
@@ -118,6 +139,10 @@ pub enum Event {
     MessageDeleteBulk(MessageDeleteBulk),
     /// A message was updated in a channel.
     MessageUpdate(Box<MessageUpdate>),
+    /// The task of inbox items was completed.
+    NotificationCenterItemCompleted(NotificationCenterItemCompleted),
+    /// An item was added to the inbox of the current user.
+    NotificationCenterItemCreate(Box<NotificationCenterItemCreate>),
     /// A user's active presence (such as game or online status) was updated.
     PresenceUpdate(Box<PresenceUpdate>),
     /// Multiple presences outside of a guild were updated.
@@ -135,6 +160,12 @@ pub enum Event {
     ReactionRemoveEmoji(ReactionRemoveEmoji),
     /// A shard is now "ready" and fully connected.
     Ready(Box<Ready>),
+    /// Sent to user accounts after [`Ready`], with the presences of their friends.
+    ReadySupplemental(ReadySupplemental),
+    /// A relationship of the current user was created or changed.
+    RelationshipAdd(Box<RelationshipAdd>),
+    /// A relationship of the current user was removed.
+    RelationshipRemove(RelationshipRemove),
     /// A shard has successfully resumed.
     Resumed,
     /// A role was created in a guild.
@@ -143,6 +174,8 @@ pub enum Event {
     RoleDelete(RoleDelete),
     /// A role was updated in a guild.
     RoleUpdate(RoleUpdate),
+    /// The sessions of the current user changed.
+    SessionsReplace(SessionsReplace),
     /// A stage instance was created in a stage channel.
     StageInstanceCreate(StageInstanceCreate),
     /// A stage instance was deleted in a stage channel.
@@ -166,6 +199,12 @@ pub enum Event {
     TypingStart(Box<TypingStart>),
     /// A guild is now unavailable.
     UnavailableGuild(UnavailableGuild),
+    /// The notification settings of the current user for a guild were updated.
+    UserGuildSettingsUpdate(UserGuildSettingsUpdate),
+    /// The current user changed their private note about another user.
+    UserNoteUpdate(UserNoteUpdate),
+    /// Protobuf encoded settings of the current user changed.
+    UserSettingsProtoUpdate(UserSettingsProtoUpdate),
     /// The current user was updated.
     UserUpdate(UserUpdate),
     /// A voice server update was sent.
@@ -189,6 +228,7 @@ impl Event {
             Event::ChannelCreate(e) => e.0.guild_id,
             Event::ChannelDelete(e) => e.0.guild_id,
             Event::ChannelPinsUpdate(e) => e.guild_id,
+            Event::ChannelUnreadUpdate(e) => Some(e.guild_id),
             Event::ChannelUpdate(e) => e.0.guild_id,
             Event::CommandPermissionsUpdate(e) => Some(e.0.guild_id),
             Event::GuildAuditLogEntryCreate(e) => e.0.guild_id,
@@ -236,34 +276,59 @@ impl Event {
             Event::ThreadUpdate(e) => e.0.guild_id,
             Event::TypingStart(e) => e.guild_id,
             Event::UnavailableGuild(e) => Some(e.id),
+            Event::UserGuildSettingsUpdate(e) => e.0.guild_id,
             Event::VoiceServerUpdate(e) => Some(e.guild_id),
             Event::VoiceStateUpdate(e) => e.0.guild_id,
             Event::WebhooksUpdate(e) => Some(e.guild_id),
-            Event::GatewayClose(_)
+            Event::ApplicationCommandAutocompleteResponse(_)
+            | Event::BurstCreditBalanceUpdate(_)
+            | Event::ChannelRecipientAdd(_)
+            | Event::ChannelRecipientRemove(_)
+            | Event::GatewayClose(_)
             | Event::GatewayHeartbeat(_)
             | Event::GatewayHeartbeatAck
             | Event::GatewayHello(_)
             | Event::GatewayInvalidateSession(_)
             | Event::GatewayReconnect
             | Event::GiftCodeUpdate
+            | Event::InteractionFailure(_)
+            | Event::InteractionModalCreate(_)
+            | Event::InteractionQueued(_)
+            | Event::InteractionSuccess(_)
+            | Event::MessageAck(_)
+            | Event::NotificationCenterItemCompleted(_)
+            | Event::NotificationCenterItemCreate(_)
             | Event::PresencesReplace
             | Event::Ready(_)
+            | Event::ReadySupplemental(_)
+            | Event::RelationshipAdd(_)
+            | Event::RelationshipRemove(_)
             | Event::Resumed
+            | Event::SessionsReplace(_)
+            | Event::UserNoteUpdate(_)
+            | Event::UserSettingsProtoUpdate(_)
             | Event::UserUpdate(_) => None,
         }
     }
 
     pub const fn kind(&self) -> EventType {
         match self {
+            Self::ApplicationCommandAutocompleteResponse(_) => {
+                EventType::ApplicationCommandAutocompleteResponse
+            }
             Self::AutoModerationActionExecution(_) => EventType::AutoModerationActionExecution,
             Self::AutoModerationRuleCreate(_) => EventType::AutoModerationRuleCreate,
             Self::AutoModerationRuleDelete(_) => EventType::AutoModerationRuleDelete,
             Self::AutoModerationRuleUpdate(_) => EventType::AutoModerationRuleUpdate,
             Self::BanAdd(_) => EventType::BanAdd,
             Self::BanRemove(_) => EventType::BanRemove,
+            Self::BurstCreditBalanceUpdate(_) => EventType::BurstCreditBalanceUpdate,
             Self::ChannelCreate(_) => EventType::ChannelCreate,
             Self::ChannelDelete(_) => EventType::ChannelDelete,
             Self::ChannelPinsUpdate(_) => EventType::ChannelPinsUpdate,
+            Self::ChannelRecipientAdd(_) => EventType::ChannelRecipientAdd,
+            Self::ChannelRecipientRemove(_) => EventType::ChannelRecipientRemove,
+            Self::ChannelUnreadUpdate(_) => EventType::ChannelUnreadUpdate,
             Self::ChannelUpdate(_) => EventType::ChannelUpdate,
             Self::CommandPermissionsUpdate(_) => EventType::CommandPermissionsUpdate,
             Self::GatewayClose(_) => EventType::GatewayClose,
@@ -288,17 +353,23 @@ impl Event {
             Self::IntegrationCreate(_) => EventType::IntegrationCreate,
             Self::IntegrationDelete(_) => EventType::IntegrationDelete,
             Self::IntegrationUpdate(_) => EventType::IntegrationUpdate,
-            Self::InteractionCreate(_) => EventType::InteractionCreate,
+            Self::InteractionCreate(_) | Self::InteractionQueued(_) => EventType::InteractionCreate,
+            Self::InteractionFailure(_) => EventType::InteractionFailure,
+            Self::InteractionModalCreate(_) => EventType::InteractionModalCreate,
+            Self::InteractionSuccess(_) => EventType::InteractionSuccess,
             Self::InviteCreate(_) => EventType::InviteCreate,
             Self::InviteDelete(_) => EventType::InviteDelete,
             Self::MemberAdd(_) => EventType::MemberAdd,
             Self::MemberRemove(_) => EventType::MemberRemove,
             Self::MemberUpdate(_) => EventType::MemberUpdate,
             Self::MemberChunk(_) => EventType::MemberChunk,
+            Self::MessageAck(_) => EventType::MessageAck,
             Self::MessageCreate(_) => EventType::MessageCreate,
             Self::MessageDelete(_) => EventType::MessageDelete,
             Self::MessageDeleteBulk(_) => EventType::MessageDeleteBulk,
             Self::MessageUpdate(_) => EventType::MessageUpdate,
+            Self::NotificationCenterItemCompleted(_) => EventType::NotificationCenterItemCompleted,
+            Self::NotificationCenterItemCreate(_) => EventType::NotificationCenterItemCreate,
             Self::PresenceUpdate(_) => EventType::PresenceUpdate,
             Self::PresencesReplace => EventType::PresencesReplace,
             Self::ReactionAdd(_) => EventType::ReactionAdd,
@@ -306,10 +377,14 @@ impl Event {
             Self::ReactionRemoveAll(_) => EventType::ReactionRemoveAll,
             Self::ReactionRemoveEmoji(_) => EventType::ReactionRemoveEmoji,
             Self::Ready(_) => EventType::Ready,
+            Self::ReadySupplemental(_) => EventType::ReadySupplemental,
+            Self::RelationshipAdd(_) => EventType::RelationshipAdd,
+            Self::RelationshipRemove(_) => EventType::RelationshipRemove,
             Self::Resumed => EventType::Resumed,
             Self::RoleCreate(_) => EventType::RoleCreate,
             Self::RoleDelete(_) => EventType::RoleDelete,
             Self::RoleUpdate(_) => EventType::RoleUpdate,
+            Self::SessionsReplace(_) => EventType::SessionsReplace,
             Self::StageInstanceCreate(_) => EventType::StageInstanceCreate,
             Self::StageInstanceDelete(_) => EventType::StageInstanceDelete,
             Self::StageInstanceUpdate(_) => EventType::StageInstanceUpdate,
@@ -321,6 +396,9 @@ impl Event {
             Self::ThreadUpdate(_) => EventType::ThreadUpdate,
             Self::TypingStart(_) => EventType::TypingStart,
             Self::UnavailableGuild(_) => EventType::UnavailableGuild,
+            Self::UserGuildSettingsUpdate(_) => EventType::UserGuildSettingsUpdate,
+            Self::UserNoteUpdate(_) => EventType::UserNoteUpdate,
+            Self::UserSettingsProtoUpdate(_) => EventType::UserSettingsProtoUpdate,
             Self::UserUpdate(_) => EventType::UserUpdate,
             Self::VoiceServerUpdate(_) => EventType::VoiceServerUpdate,
             Self::VoiceStateUpdate(_) => EventType::VoiceStateUpdate,
@@ -335,14 +413,21 @@ impl From<DispatchEvent> for Event {
             DispatchEvent::AutoModerationActionExecution(v) => {
                 Self::AutoModerationActionExecution(v)
             }
+            DispatchEvent::ApplicationCommandAutocompleteResponse(v) => {
+                Self::ApplicationCommandAutocompleteResponse(v)
+            }
             DispatchEvent::AutoModerationRuleCreate(v) => Self::AutoModerationRuleCreate(v),
             DispatchEvent::AutoModerationRuleDelete(v) => Self::AutoModerationRuleDelete(v),
             DispatchEvent::AutoModerationRuleUpdate(v) => Self::AutoModerationRuleUpdate(v),
             DispatchEvent::GuildBanAdd(v) => Self::BanAdd(v),
             DispatchEvent::GuildBanRemove(v) => Self::BanRemove(v),
+            DispatchEvent::BurstCreditBalanceUpdate(v) => Self::BurstCreditBalanceUpdate(v),
             DispatchEvent::ChannelCreate(v) => Self::ChannelCreate(v),
             DispatchEvent::ChannelDelete(v) => Self::ChannelDelete(v),
             DispatchEvent::ChannelPinsUpdate(v) => Self::ChannelPinsUpdate(v),
+            DispatchEvent::ChannelRecipientAdd(v) => Self::ChannelRecipientAdd(v),
+            DispatchEvent::ChannelRecipientRemove(v) => Self::ChannelRecipientRemove(v),
+            DispatchEvent::ChannelUnreadUpdate(v) => Self::ChannelUnreadUpdate(v),
             DispatchEvent::ChannelUpdate(v) => Self::ChannelUpdate(v),
             DispatchEvent::CommandPermissionsUpdate(v) => Self::CommandPermissionsUpdate(v),
             DispatchEvent::GiftCodeUpdate => Self::GiftCodeUpdate,
@@ -364,12 +449,24 @@ impl From<DispatchEvent> for Event {
             DispatchEvent::IntegrationDelete(v) => Self::IntegrationDelete(v),
             DispatchEvent::IntegrationUpdate(v) => Self::IntegrationUpdate(v),
             DispatchEvent::InteractionCreate(v) => Self::InteractionCreate(v),
+            DispatchEvent::InteractionFailure(v) => Self::InteractionFailure(v),
+            DispatchEvent::InteractionModalCreate(v) => Self::InteractionModalCreate(v),
+            DispatchEvent::InteractionQueued(v) => Self::InteractionQueued(v),
+            DispatchEvent::InteractionSuccess(v) => Self::InteractionSuccess(v),
             DispatchEvent::InviteCreate(v) => Self::InviteCreate(v),
             DispatchEvent::InviteDelete(v) => Self::InviteDelete(v),
             DispatchEvent::GuildMemberAdd(v) => Self::MemberAdd(v),
             DispatchEvent::GuildMemberRemove(v) => Self::MemberRemove(v),
             DispatchEvent::GuildMemberUpdate(v) => Self::MemberUpdate(v),
             DispatchEvent::GuildMembersChunk(v) => Self::MemberChunk(v),
+            DispatchEvent::MessageAck(v) => Self::MessageAck(v),
+            DispatchEvent::NotificationCenterItemCompleted(v) => {
+                Self::NotificationCenterItemCompleted(v)
+            }
+            DispatchEvent::NotificationCenterItemCreate(v) => Self::NotificationCenterItemCreate(v),
+            DispatchEvent::ReadySupplemental(v) => Self::ReadySupplemental(v),
+            DispatchEvent::RelationshipAdd(v) => Self::RelationshipAdd(v),
+            DispatchEvent::RelationshipRemove(v) => Self::RelationshipRemove(v),
             DispatchEvent::GuildRoleCreate(v) => Self::RoleCreate(v),
             DispatchEvent::GuildRoleDelete(v) => Self::RoleDelete(v),
             DispatchEvent::GuildRoleUpdate(v) => Self::RoleUpdate(v),
@@ -385,6 +482,7 @@ impl From<DispatchEvent> for Event {
             DispatchEvent::ReactionRemoveEmoji(v) => Self::ReactionRemoveEmoji(v),
             DispatchEvent::Ready(v) => Self::Ready(v),
             DispatchEvent::Resumed => Self::Resumed,
+            DispatchEvent::SessionsReplace(v) => Self::SessionsReplace(v),
             DispatchEvent::StageInstanceCreate(v) => Self::StageInstanceCreate(v),
             DispatchEvent::StageInstanceDelete(v) => Self::StageInstanceDelete(v),
             DispatchEvent::StageInstanceUpdate(v) => Self::StageInstanceUpdate(v),
@@ -396,6 +494,9 @@ impl From<DispatchEvent> for Event {
             DispatchEvent::ThreadUpdate(v) => Self::ThreadUpdate(v),
             DispatchEvent::TypingStart(v) => Self::TypingStart(v),
             DispatchEvent::UnavailableGuild(v) => Self::UnavailableGuild(v),
+            DispatchEvent::UserGuildSettingsUpdate(v) => Self::UserGuildSettingsUpdate(v),
+            DispatchEvent::UserNoteUpdate(v) => Self::UserNoteUpdate(v),
+            DispatchEvent::UserSettingsProtoUpdate(v) => Self::UserSettingsProtoUpdate(v),
             DispatchEvent::UserUpdate(v) => Self::UserUpdate(v),
             DispatchEvent::VoiceServerUpdate(v) => Self::VoiceServerUpdate(v),
             DispatchEvent::VoiceStateUpdate(v) => Self::VoiceStateUpdate(v),
@@ -484,6 +585,7 @@ mod tests {
     const_assert!(mem::size_of::<AutoModerationRuleUpdate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ChannelCreate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ChannelDelete>() > EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<ChannelRecipientAdd>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ChannelUpdate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<GuildScheduledEventCreate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<GuildScheduledEventDelete>() > EVENT_THRESHOLD);
@@ -497,10 +599,12 @@ mod tests {
     const_assert!(mem::size_of::<MemberUpdate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MessageCreate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MessageUpdate>() > EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<NotificationCenterItemCreate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<PresenceUpdate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ReactionAdd>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ReactionRemove>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<Ready>() > EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<RelationshipAdd>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ThreadCreate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ThreadMemberUpdate>() > EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ThreadUpdate>() > EVENT_THRESHOLD);
@@ -508,10 +612,14 @@ mod tests {
     const_assert!(mem::size_of::<VoiceStateUpdate>() > EVENT_THRESHOLD);
 
     // Unboxed.
+    const_assert!(mem::size_of::<ApplicationCommandAutocompleteResponse>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<AutoModerationActionExecution>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<BanAdd>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<BanRemove>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<BurstCreditBalanceUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ChannelPinsUpdate>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<ChannelRecipientRemove>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<ChannelUnreadUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<CommandPermissionsUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<GuildDelete>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<GuildEmojisUpdate>() <= EVENT_THRESHOLD);
@@ -519,15 +627,24 @@ mod tests {
     const_assert!(mem::size_of::<GuildScheduledEventUserAdd>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<GuildScheduledEventUserRemove>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<IntegrationDelete>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<InteractionFailure>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<InteractionModalCreate>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<InteractionQueued>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<InteractionSuccess>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<InviteDelete>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MemberChunk>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MemberRemove>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<MessageAck>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MessageDelete>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<MessageDeleteBulk>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<NotificationCenterItemCompleted>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ReactionRemoveAll>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<ReadySupplemental>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<RelationshipRemove>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<RoleCreate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<RoleDelete>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<RoleUpdate>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<SessionsReplace>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<StageInstanceCreate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<StageInstanceDelete>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<StageInstanceUpdate>() <= EVENT_THRESHOLD);
@@ -535,6 +652,9 @@ mod tests {
     const_assert!(mem::size_of::<ThreadListSync>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<ThreadMembersUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<UnavailableGuild>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<UserGuildSettingsUpdate>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<UserNoteUpdate>() <= EVENT_THRESHOLD);
+    const_assert!(mem::size_of::<UserSettingsProtoUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<UserUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<VoiceServerUpdate>() <= EVENT_THRESHOLD);
     const_assert!(mem::size_of::<WebhooksUpdate>() <= EVENT_THRESHOLD);
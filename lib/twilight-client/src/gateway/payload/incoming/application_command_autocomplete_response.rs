@@ -0,0 +1,52 @@
+use crate::application::command::CommandOptionChoice;
+use serde::{Deserialize, Serialize};
+
+/// Choices an application suggested for the focused option of a command the
+/// current user is typing.
+///
+/// Sent in response to an `APPLICATION_COMMAND_AUTOCOMPLETE` interaction of
+/// the current session.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ApplicationCommandAutocompleteResponse {
+    pub choices: Vec<CommandOptionChoice>,
+    /// Nonce the autocomplete interaction was sent with.
+    pub nonce: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApplicationCommandAutocompleteResponse;
+    use crate::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+    #[test]
+    fn application_command_autocomplete_response() {
+        const JSON: &str = r#"{
+            "choices": [
+                { "name": "Rust", "value": "rust" },
+                { "name": "Zig", "value": "zig" }
+            ],
+            "nonce": "1120379526549585920"
+        }"#;
+
+        let value = serde_json::from_str::<ApplicationCommandAutocompleteResponse>(JSON).unwrap();
+
+        assert_eq!(
+            value,
+            ApplicationCommandAutocompleteResponse {
+                choices: vec![
+                    CommandOptionChoice {
+                        name: "Rust".to_owned(),
+                        name_localizations: None,
+                        value: CommandOptionChoiceValue::String("rust".to_owned()),
+                    },
+                    CommandOptionChoice {
+                        name: "Zig".to_owned(),
+                        name_localizations: None,
+                        value: CommandOptionChoiceValue::String("zig".to_owned()),
+                    },
+                ],
+                nonce: "1120379526549585920".to_owned(),
+            }
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::util::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// The amount of burst reactions the current user can send changed.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct BurstCreditBalanceUpdate {
+    /// Amount of burst reactions that can be sent.
+    pub amount: u32,
+    /// When the credits are replenished next.
+    pub next_replenish_at: Timestamp,
+    /// Whether the credits were already replenished today.
+    pub replenished_today: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BurstCreditBalanceUpdate;
+    use crate::util::Timestamp;
+    use serde_test::Token;
+    use std::str::FromStr;
+
+    #[test]
+    fn burst_credit_balance_update() {
+        let next_replenish_at = Timestamp::from_str("2023-08-14T00:00:00.000000+00:00").unwrap();
+
+        let value = BurstCreditBalanceUpdate {
+            amount: 2,
+            next_replenish_at,
+            replenished_today: true,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "BurstCreditBalanceUpdate",
+                    len: 3,
+                },
+                Token::Str("amount"),
+                Token::U32(2),
+                Token::Str("next_replenish_at"),
+                Token::Str("2023-08-14T00:00:00.000000+00:00"),
+                Token::Str("replenished_today"),
+                Token::Bool(true),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    id::{marker::ChannelMarker, Id},
+    user::User,
+};
+use serde::{Deserialize, Serialize};
+
+/// A user was added to a group DM.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ChannelRecipientAdd {
+    pub channel_id: Id<ChannelMarker>,
+    /// Nickname of the user in the group DM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelRecipientAdd;
+    use crate::{
+        id::Id,
+        user::{User, UserFlags},
+    };
+
+    #[test]
+    fn channel_recipient_add() {
+        const JSON: &str = r#"{
+            "channel_id": "1",
+            "user": {
+                "avatar": null,
+                "avatar_decoration": null,
+                "discriminator": "0",
+                "global_name": "Foo",
+                "id": "2",
+                "public_flags": 0,
+                "username": "foo"
+            }
+        }"#;
+
+        let value = ChannelRecipientAdd {
+            channel_id: Id::new(1),
+            nick: None,
+            user: User {
+                accent_color: None,
+                avatar: None,
+                avatar_decoration: None,
+                banner: None,
+                bot: false,
+                discriminator: 0,
+                email: None,
+                flags: None,
+                global_name: Some("Foo".to_owned()),
+                id: Id::new(2),
+                locale: None,
+                mfa_enabled: None,
+                name: "foo".to_owned(),
+                premium_type: None,
+                public_flags: Some(UserFlags::empty()),
+                system: None,
+                verified: None,
+            },
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    id::{marker::ChannelMarker, Id},
+    user::User,
+};
+use serde::{Deserialize, Serialize};
+
+/// A user was removed from or left a group DM.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ChannelRecipientRemove {
+    pub channel_id: Id<ChannelMarker>,
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelRecipientRemove;
+    use crate::{
+        id::Id,
+        user::{User, UserFlags},
+    };
+
+    #[test]
+    fn channel_recipient_remove() {
+        const JSON: &str = r#"{
+            "channel_id": "1",
+            "user": {
+                "avatar": "6961b2d4e5aac9c9d5e1e4a6a8e2b0c6",
+                "avatar_decoration": null,
+                "discriminator": "0",
+                "global_name": null,
+                "id": "2",
+                "public_flags": 64,
+                "username": "foo"
+            }
+        }"#;
+
+        let value = ChannelRecipientRemove {
+            channel_id: Id::new(1),
+            user: User {
+                accent_color: None,
+                avatar: Some("6961b2d4e5aac9c9d5e1e4a6a8e2b0c6".parse().unwrap()),
+                avatar_decoration: None,
+                banner: None,
+                bot: false,
+                discriminator: 0,
+                email: None,
+                flags: None,
+                global_name: None,
+                id: Id::new(2),
+                locale: None,
+                mfa_enabled: None,
+                name: "foo".to_owned(),
+                premium_type: None,
+                public_flags: Some(UserFlags::HYPESQUAD_ONLINE_HOUSE_1),
+                system: None,
+                verified: None,
+            },
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
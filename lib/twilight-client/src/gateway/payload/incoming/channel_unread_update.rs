@@ -0,0 +1,80 @@
+use crate::{
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Latest messages of the channels of a guild, sent when their unread state
+/// may have changed.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ChannelUnreadUpdate {
+    pub channel_unread_updates: Vec<ChannelUnread>,
+    pub guild_id: Id<GuildMarker>,
+}
+
+/// Latest message of a channel.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ChannelUnread {
+    /// ID of the channel.
+    pub id: Id<ChannelMarker>,
+    pub last_message_id: Option<Id<MessageMarker>>,
+    #[serde(default)]
+    pub last_pin_timestamp: Option<Timestamp>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelUnread, ChannelUnreadUpdate};
+    use crate::{id::Id, util::Timestamp};
+    use serde_test::Token;
+    use std::str::FromStr;
+
+    #[test]
+    fn channel_unread_update() {
+        let last_pin_timestamp = Timestamp::from_str("2021-02-17T19:29:53.999000+00:00").unwrap();
+
+        let value = ChannelUnreadUpdate {
+            channel_unread_updates: vec![ChannelUnread {
+                id: Id::new(2),
+                last_message_id: Some(Id::new(3)),
+                last_pin_timestamp: Some(last_pin_timestamp),
+            }],
+            guild_id: Id::new(1),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "ChannelUnreadUpdate",
+                    len: 2,
+                },
+                Token::Str("channel_unread_updates"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ChannelUnread",
+                    len: 3,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("2"),
+                Token::Str("last_message_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("3"),
+                Token::Str("last_pin_timestamp"),
+                Token::Some,
+                Token::Str("2021-02-17T19:29:53.999000+00:00"),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("guild_id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::id::{marker::InteractionMarker, Id};
+use serde::{Deserialize, Serialize};
+
+/// An interaction sent by the current user wasn't responded to by its
+/// application in time, or the application responded with an error.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InteractionFailure {
+    pub id: Id<InteractionMarker>,
+    /// Nonce the interaction was sent with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionFailure;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn interaction_failure() {
+        let value = InteractionFailure {
+            id: Id::new(1),
+            nonce: None,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionFailure",
+                    len: 1,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
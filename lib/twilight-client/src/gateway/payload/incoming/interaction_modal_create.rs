@@ -0,0 +1,96 @@
+use crate::{
+    channel::message::Component,
+    id::{
+        marker::{ApplicationMarker, ChannelMarker, InteractionMarker},
+        Id,
+    },
+    util::ImageHash,
+};
+use serde::{Deserialize, Serialize};
+
+/// An application responded to an interaction of the current user with a
+/// modal to fill in.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InteractionModalCreate {
+    pub application: ModalApplication,
+    pub channel_id: Id<ChannelMarker>,
+    /// Action rows containing the text inputs of the modal.
+    pub components: Vec<Component>,
+    /// User defined identifier for the modal, sent back when submitting it.
+    pub custom_id: String,
+    /// ID of the modal, sent back when submitting it.
+    pub id: Id<InteractionMarker>,
+    /// Nonce of the interaction that caused the modal to open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    pub title: String,
+}
+
+/// Application that opened a modal.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModalApplication {
+    pub icon: Option<ImageHash>,
+    pub id: Id<ApplicationMarker>,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InteractionModalCreate, ModalApplication};
+    use crate::{
+        channel::message::{
+            component::{ActionRow, TextInput, TextInputStyle},
+            Component,
+        },
+        id::Id,
+    };
+
+    #[test]
+    fn interaction_modal_create() {
+        const JSON: &str = r#"{
+            "application": { "icon": null, "id": "2", "name": "Polls" },
+            "channel_id": "3",
+            "components": [{
+                "type": 1,
+                "components": [{
+                    "type": 4,
+                    "custom_id": "question",
+                    "label": "Question",
+                    "style": 1,
+                    "required": true
+                }]
+            }],
+            "custom_id": "create_poll",
+            "id": "1",
+            "nonce": "1120379526549585920",
+            "title": "Create a poll"
+        }"#;
+
+        let value = InteractionModalCreate {
+            application: ModalApplication {
+                icon: None,
+                id: Id::new(2),
+                name: "Polls".to_owned(),
+            },
+            channel_id: Id::new(3),
+            components: vec![Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: "question".to_owned(),
+                    label: "Question".to_owned(),
+                    max_length: None,
+                    min_length: None,
+                    placeholder: None,
+                    required: Some(true),
+                    style: TextInputStyle::Short,
+                    value: None,
+                })],
+            })],
+            custom_id: "create_poll".to_owned(),
+            id: Id::new(1),
+            nonce: Some("1120379526549585920".to_owned()),
+            title: "Create a poll".to_owned(),
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
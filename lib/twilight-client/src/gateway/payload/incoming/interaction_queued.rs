@@ -0,0 +1,47 @@
+use crate::id::{marker::InteractionMarker, Id};
+use serde::{Deserialize, Serialize};
+
+/// An interaction sent by the current user was received by Discord and is
+/// waiting for the application to respond.
+///
+/// This is the user account variant of `INTERACTION_CREATE`, bots receive the
+/// full interaction instead.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InteractionQueued {
+    pub id: Id<InteractionMarker>,
+    /// Nonce the interaction was sent with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionQueued;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn interaction_queued() {
+        let value = InteractionQueued {
+            id: Id::new(1),
+            nonce: Some("1120379526549585920".to_owned()),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionQueued",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::Str("nonce"),
+                Token::Some,
+                Token::Str("1120379526549585920"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
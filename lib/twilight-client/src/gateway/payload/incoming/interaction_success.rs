@@ -0,0 +1,43 @@
+use crate::id::{marker::InteractionMarker, Id};
+use serde::{Deserialize, Serialize};
+
+/// An interaction sent by the current user was handled by its application.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InteractionSuccess {
+    pub id: Id<InteractionMarker>,
+    /// Nonce the interaction was sent with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionSuccess;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn interaction_success() {
+        let value = InteractionSuccess {
+            id: Id::new(1),
+            nonce: Some("1120379526549585920".to_owned()),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionSuccess",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::Str("nonce"),
+                Token::Some,
+                Token::Str("1120379526549585920"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
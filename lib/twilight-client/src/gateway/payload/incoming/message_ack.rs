@@ -0,0 +1,67 @@
+use crate::id::{
+    marker::{ChannelMarker, MessageMarker},
+    Id,
+};
+use serde::{Deserialize, Serialize};
+
+/// A channel was acknowledged up to a message by the current user, possibly
+/// from another session.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MessageAck {
+    pub channel_id: Id<ChannelMarker>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_viewed: Option<u64>,
+    /// Whether the channel was manually marked as unread.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub manual: bool,
+    /// Mention count of the channel after a manual acknowledgement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mention_count: Option<u32>,
+    /// ID of the message the channel was acknowledged up to.
+    pub message_id: Id<MessageMarker>,
+    pub version: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MessageAck;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn message_ack() {
+        let value = MessageAck {
+            channel_id: Id::new(1),
+            flags: None,
+            last_viewed: Some(3148),
+            manual: false,
+            mention_count: None,
+            message_id: Id::new(2),
+            version: 12,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "MessageAck",
+                    len: 4,
+                },
+                Token::Str("channel_id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::Str("last_viewed"),
+                Token::Some,
+                Token::U64(3148),
+                Token::Str("message_id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("2"),
+                Token::Str("version"),
+                Token::U64(12),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -14,15 +14,20 @@
 pub mod invite_create;
 pub mod reaction_remove_emoji;
 
+mod application_command_autocomplete_response;
 mod auto_moderation_action_execution;
 mod auto_moderation_rule_create;
 mod auto_moderation_rule_delete;
 mod auto_moderation_rule_update;
 mod ban_add;
 mod ban_remove;
+mod burst_credit_balance_update;
 mod channel_create;
 mod channel_delete;
 mod channel_pins_update;
+mod channel_recipient_add;
+mod channel_recipient_remove;
+mod channel_unread_update;
 mod channel_update;
 mod command_permissions_update;
 mod guild_audit_log_entry_create;
@@ -42,23 +47,34 @@ mod integration_create;
 mod integration_delete;
 mod integration_update;
 mod interaction_create;
+mod interaction_failure;
+mod interaction_modal_create;
+mod interaction_queued;
+mod interaction_success;
 mod invite_delete;
 mod member_add;
 mod member_chunk;
 mod member_remove;
 mod member_update;
+mod message_ack;
 mod message_create;
 mod message_delete;
 mod message_delete_bulk;
 mod message_update;
+mod notification_center_item_completed;
+mod notification_center_item_create;
 mod presence_update;
 mod reaction_add;
 mod reaction_remove;
 mod reaction_remove_all;
 mod ready;
+mod ready_supplemental;
+mod relationship_add;
+mod relationship_remove;
 mod role_create;
 mod role_delete;
 mod role_update;
+mod sessions_replace;
 mod stage_instance_create;
 mod stage_instance_delete;
 mod stage_instance_update;
@@ -69,19 +85,25 @@ mod thread_member_update;
 mod thread_members_update;
 mod thread_update;
 mod typing_start;
+mod user_guild_settings_update;
+mod user_note_update;
+mod user_settings_proto_update;
 mod user_update;
 mod voice_server_update;
 mod voice_state_update;
 mod webhooks_update;
 
 pub use self::{
+    application_command_autocomplete_response::ApplicationCommandAutocompleteResponse,
     auto_moderation_action_execution::AutoModerationActionExecution,
     auto_moderation_rule_create::AutoModerationRuleCreate,
     auto_moderation_rule_delete::AutoModerationRuleDelete,
     auto_moderation_rule_update::AutoModerationRuleUpdate, ban_add::BanAdd, ban_remove::BanRemove,
-    channel_create::ChannelCreate, channel_delete::ChannelDelete,
-    channel_pins_update::ChannelPinsUpdate, channel_update::ChannelUpdate,
-    command_permissions_update::CommandPermissionsUpdate,
+    burst_credit_balance_update::BurstCreditBalanceUpdate, channel_create::ChannelCreate,
+    channel_delete::ChannelDelete, channel_pins_update::ChannelPinsUpdate,
+    channel_recipient_add::ChannelRecipientAdd, channel_recipient_remove::ChannelRecipientRemove,
+    channel_unread_update::ChannelUnread, channel_unread_update::ChannelUnreadUpdate,
+    channel_update::ChannelUpdate, command_permissions_update::CommandPermissionsUpdate,
     guild_audit_log_entry_create::GuildAuditLogEntryCreate, guild_create::GuildCreate,
     guild_delete::GuildDelete, guild_emojis_update::GuildEmojisUpdate,
     guild_integrations_update::GuildIntegrationsUpdate,
@@ -93,18 +115,31 @@ pub use self::{
     guild_stickers_update::GuildStickersUpdate, guild_update::GuildUpdate, hello::Hello,
     integration_create::IntegrationCreate, integration_delete::IntegrationDelete,
     integration_update::IntegrationUpdate, interaction_create::InteractionCreate,
-    invite_create::InviteCreate, invite_delete::InviteDelete, member_add::MemberAdd,
-    member_chunk::MemberChunk, member_remove::MemberRemove, member_update::MemberUpdate,
+    interaction_failure::InteractionFailure, interaction_modal_create::InteractionModalCreate,
+    interaction_modal_create::ModalApplication, interaction_queued::InteractionQueued,
+    interaction_success::InteractionSuccess, invite_create::InviteCreate,
+    invite_delete::InviteDelete, member_add::MemberAdd, member_chunk::MemberChunk,
+    member_remove::MemberRemove, member_update::MemberUpdate, message_ack::MessageAck,
     message_create::MessageCreate, message_delete::MessageDelete,
     message_delete_bulk::MessageDeleteBulk, message_update::MessageUpdate,
-    presence_update::PresenceUpdate, reaction_add::ReactionAdd, reaction_remove::ReactionRemove,
+    notification_center_item_completed::NotificationCenterItemCompleted,
+    notification_center_item_create::NotificationCenterItemCreate, presence_update::PresenceUpdate,
+    reaction_add::ReactionAdd, reaction_remove::ReactionRemove,
     reaction_remove_all::ReactionRemoveAll, reaction_remove_emoji::ReactionRemoveEmoji,
-    ready::Ready, role_create::RoleCreate, role_delete::RoleDelete, role_update::RoleUpdate,
+    ready::Ready, ready::VersionedEntries, ready_supplemental::MergedPresence,
+    ready_supplemental::MergedPresences, ready_supplemental::ReadySupplemental,
+    ready_supplemental::SupplementalGuild, relationship_add::RelationshipAdd,
+    relationship_remove::RelationshipRemove, role_create::RoleCreate, role_delete::RoleDelete,
+    role_update::RoleUpdate, sessions_replace::SessionsReplace,
     stage_instance_create::StageInstanceCreate, stage_instance_delete::StageInstanceDelete,
     stage_instance_update::StageInstanceUpdate, thread_create::ThreadCreate,
     thread_delete::ThreadDelete, thread_list_sync::ThreadListSync,
     thread_member_update::ThreadMemberUpdate, thread_members_update::ThreadMembersUpdate,
-    thread_update::ThreadUpdate, typing_start::TypingStart, user_update::UserUpdate,
+    thread_update::ThreadUpdate, typing_start::TypingStart,
+    user_guild_settings_update::UserGuildSettingsUpdate, user_note_update::UserNoteUpdate,
+    user_settings_proto_update::UserSettingsProto,
+    user_settings_proto_update::UserSettingsProtoType,
+    user_settings_proto_update::UserSettingsProtoUpdate, user_update::UserUpdate,
     voice_server_update::VoiceServerUpdate, voice_state_update::VoiceStateUpdate,
     webhooks_update::WebhooksUpdate,
 };
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// The task of the inbox items with the given [`item_enum`] was completed.
+///
+/// [`item_enum`]: crate::user::NotificationCenterItem::item_enum
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NotificationCenterItemCompleted {
+    pub item_enum: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NotificationCenterItemCompleted;
+    use serde_test::Token;
+
+    #[test]
+    fn notification_center_item_completed() {
+        let value = NotificationCenterItemCompleted { item_enum: 3 };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "NotificationCenterItemCompleted",
+                    len: 1,
+                },
+                Token::Str("item_enum"),
+                Token::U64(3),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::user::NotificationCenterItem;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// An item was added to the inbox of the current user.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NotificationCenterItemCreate(pub NotificationCenterItem);
+
+impl Deref for NotificationCenterItemCreate {
+    type Target = NotificationCenterItem;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for NotificationCenterItemCreate {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -1,4 +1,7 @@
-use crate::gateway::presence::{Activity, ClientStatus, Status, UserOrId};
+use crate::{
+    gateway::presence::{Activity, ClientStatus, Status, UserOrId},
+    id::{marker::GuildMarker, Id},
+};
 use serde::{Deserialize, Serialize};
 
 /// User's presence was updated.
@@ -17,6 +20,10 @@ pub struct PresenceUpdate {
     #[serde(default)]
     pub activities: Vec<Activity>,
     pub client_status: ClientStatus,
+    /// Guild the presence is shared with, `None` for the presences of the
+    /// friends of the current user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Id<GuildMarker>>,
     pub status: Status,
     pub user: UserOrId,
 }
@@ -24,6 +31,10 @@ pub struct PresenceUpdate {
 #[cfg(test)]
 mod tests {
     use super::PresenceUpdate;
+    use crate::{
+        gateway::presence::{ClientStatus, Status, UserOrId},
+        id::Id,
+    };
     use serde::{Deserialize, Serialize};
     use static_assertions::assert_impl_all;
     use std::{fmt::Debug, hash::Hash};
@@ -39,4 +50,30 @@ mod tests {
         Send,
         Sync
     );
+
+    /// Presences of friends aren't shared in a guild.
+    #[test]
+    fn presence_update_friend() {
+        const JSON: &str = r#"{
+            "activities": [],
+            "client_status": { "mobile": "idle" },
+            "last_modified": 1687372141000,
+            "status": "idle",
+            "user": { "id": "2" }
+        }"#;
+
+        let value = PresenceUpdate {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: None,
+                mobile: Some(Status::Idle),
+                web: None,
+            },
+            guild_id: None,
+            status: Status::Idle,
+            user: UserOrId::UserId { id: Id::new(2) },
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
 }
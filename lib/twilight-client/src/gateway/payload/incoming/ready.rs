@@ -1,21 +1,103 @@
-use crate::{gateway::ShardId, guild::UnavailableGuild, user::CurrentUser};
-use serde::{Deserialize, Serialize};
+use crate::{
+    channel::Channel,
+    gateway::{presence::Session, ShardId},
+    guild::UnavailableGuild,
+    user::{CurrentUser, ReadState, Relationship, UserGuildSettings},
+};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Ready {
     pub guilds: Vec<UnavailableGuild>,
+    /// DMs and group DMs of the current user.
+    #[serde(default)]
+    pub private_channels: Vec<Channel>,
+    /// Read states of the channels of the current user.
+    #[serde(default)]
+    pub read_state: VersionedEntries<ReadState>,
+    /// Friends, blocked users and pending friend requests of the current
+    /// user.
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
     pub resume_gateway_url: String,
     pub session_id: String,
+    /// Sessions of every client the current user is logged in on, including
+    /// this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<Session>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard: Option<ShardId>,
     pub user: CurrentUser,
+    /// Notification settings of the current user per guild.
+    #[serde(default)]
+    pub user_guild_settings: VersionedEntries<UserGuildSettings>,
+    /// Base64 encoded protobuf of the preloaded settings of the current user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_settings_proto: Option<String>,
     #[serde(rename = "v")]
     pub version: u64,
 }
 
+/// List of entries sent in the [`Ready`] payload of user accounts.
+///
+/// Depending on the capabilities of the session the entries are either sent
+/// as a plain list or wrapped in an object along with a version, both of
+/// which are accepted when deserializing.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct VersionedEntries<T> {
+    pub entries: Vec<T>,
+    /// Whether only the entries that changed since the last session are sent.
+    pub partial: bool,
+    pub version: u64,
+}
+
+impl<T> Default for VersionedEntries<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            partial: false,
+            version: 0,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VersionedEntries<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            List(Vec<T>),
+            Versioned {
+                entries: Vec<T>,
+                #[serde(default)]
+                partial: bool,
+                #[serde(default)]
+                version: u64,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::List(entries) => Self {
+                entries,
+                partial: false,
+                version: 0,
+            },
+            Repr::Versioned {
+                entries,
+                partial,
+                version,
+            } => Self {
+                entries,
+                partial,
+                version,
+            },
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Ready;
+    use super::{Ready, VersionedEntries};
     use crate::{gateway::ShardId, guild::UnavailableGuild, id::Id, user::CurrentUser};
     use serde_test::Token;
 
@@ -29,8 +111,12 @@ mod tests {
 
         let ready = Ready {
             guilds,
+            private_channels: Vec::new(),
+            read_state: VersionedEntries::default(),
+            relationships: Vec::new(),
             resume_gateway_url: "wss://gateway.discord.gg".into(),
             session_id: "foo".to_owned(),
+            sessions: Vec::new(),
             shard: Some(ShardId::new(4, 7)),
             user: CurrentUser {
                 accent_color: None,
@@ -48,6 +134,8 @@ mod tests {
                 public_flags: None,
                 verified: None,
             },
+            user_guild_settings: VersionedEntries::default(),
+            user_settings_proto: None,
             version: 8,
         };
 
@@ -56,7 +144,7 @@ mod tests {
             &[
                 Token::Struct {
                     name: "Ready",
-                    len: 7,
+                    len: 11,
                 },
                 Token::Str("application"),
                 Token::Struct {
@@ -92,6 +180,25 @@ mod tests {
                 Token::Bool(true),
                 Token::StructEnd,
                 Token::SeqEnd,
+                Token::Str("private_channels"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("read_state"),
+                Token::Struct {
+                    name: "VersionedEntries",
+                    len: 3,
+                },
+                Token::Str("entries"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("partial"),
+                Token::Bool(false),
+                Token::Str("version"),
+                Token::U64(0),
+                Token::StructEnd,
+                Token::Str("relationships"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
                 Token::Str("resume_gateway_url"),
                 Token::Str("wss://gateway.discord.gg"),
                 Token::Str("session_id"),
@@ -125,10 +232,52 @@ mod tests {
                 Token::Str("username"),
                 Token::Str("bar"),
                 Token::StructEnd,
+                Token::Str("user_guild_settings"),
+                Token::Struct {
+                    name: "VersionedEntries",
+                    len: 3,
+                },
+                Token::Str("entries"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("partial"),
+                Token::Bool(false),
+                Token::Str("version"),
+                Token::U64(0),
+                Token::StructEnd,
                 Token::Str("v"),
                 Token::U64(8),
                 Token::StructEnd,
             ],
         );
     }
+
+    #[test]
+    fn versioned_entries_list() {
+        let entries: VersionedEntries<u64> = serde_json::from_str("[1, 2]").unwrap();
+
+        assert_eq!(
+            entries,
+            VersionedEntries {
+                entries: vec![1, 2],
+                partial: false,
+                version: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn versioned_entries_object() {
+        let entries: VersionedEntries<u64> =
+            serde_json::from_str(r#"{"entries": [3], "partial": true, "version": 42}"#).unwrap();
+
+        assert_eq!(
+            entries,
+            VersionedEntries {
+                entries: vec![3],
+                partial: true,
+                version: 42,
+            }
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use crate::{
+    gateway::presence::{Activity, ClientStatus, Status},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Sent to user accounts after the [`Ready`] payload, with the initial
+/// presences of their friends and of the members of their guilds.
+///
+/// [`Ready`]: super::Ready
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ReadySupplemental {
+    /// Guilds of the current user, in the same order as the presences of
+    /// their members.
+    #[serde(default)]
+    pub guilds: Vec<SupplementalGuild>,
+    #[serde(default)]
+    pub merged_presences: MergedPresences,
+}
+
+/// Guild of the [`ReadySupplemental`] payload.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SupplementalGuild {
+    pub id: Id<GuildMarker>,
+}
+
+/// Presences sent in the [`ReadySupplemental`] payload.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MergedPresences {
+    /// Presences of the friends of the current user.
+    #[serde(default)]
+    pub friends: Vec<MergedPresence>,
+    /// Presences of the members of every guild, in the order of
+    /// [`ReadySupplemental::guilds`].
+    #[serde(default)]
+    pub guilds: Vec<Vec<MergedPresence>>,
+}
+
+/// Presence of a user, identified by their ID only.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MergedPresence {
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    pub client_status: ClientStatus,
+    pub status: Status,
+    pub user_id: Id<UserMarker>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MergedPresence, MergedPresences, ReadySupplemental, SupplementalGuild};
+    use crate::{
+        gateway::presence::{ClientStatus, Status},
+        id::Id,
+    };
+
+    #[test]
+    fn ready_supplemental() {
+        const JSON: &str = r#"{
+            "guilds": [{
+                "embedded_activities": [],
+                "id": "3",
+                "voice_states": []
+            }],
+            "lazy_private_channels": [],
+            "merged_members": [],
+            "merged_presences": {
+                "friends": [{
+                    "activities": [],
+                    "client_status": { "desktop": "online" },
+                    "last_modified": 1687372141000,
+                    "status": "online",
+                    "user_id": "2"
+                }],
+                "guilds": [[{
+                    "activities": [],
+                    "client_status": { "web": "idle" },
+                    "status": "idle",
+                    "user_id": "4"
+                }]]
+            }
+        }"#;
+
+        let value = ReadySupplemental {
+            guilds: vec![SupplementalGuild { id: Id::new(3) }],
+            merged_presences: MergedPresences {
+                friends: vec![MergedPresence {
+                    activities: Vec::new(),
+                    client_status: ClientStatus {
+                        desktop: Some(Status::Online),
+                        mobile: None,
+                        web: None,
+                    },
+                    status: Status::Online,
+                    user_id: Id::new(2),
+                }],
+                guilds: vec![vec![MergedPresence {
+                    activities: Vec::new(),
+                    client_status: ClientStatus {
+                        desktop: None,
+                        mobile: None,
+                        web: Some(Status::Idle),
+                    },
+                    status: Status::Idle,
+                    user_id: Id::new(4),
+                }]],
+            },
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
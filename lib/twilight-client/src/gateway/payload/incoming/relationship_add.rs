@@ -0,0 +1,75 @@
+use crate::user::Relationship;
+use serde::{Deserialize, Serialize};
+
+/// A relationship of the current user was created or changed, such as when a
+/// friend request is received or accepted.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct RelationshipAdd {
+    #[serde(flatten)]
+    pub relationship: Relationship,
+    /// Whether the current user should be notified of the change.
+    #[serde(default)]
+    pub should_notify: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RelationshipAdd;
+    use crate::{
+        id::Id,
+        user::{Relationship, RelationshipType, User, UserFlags},
+        util::Timestamp,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn relationship_add() {
+        const JSON: &str = r#"{
+            "id": "2",
+            "nickname": null,
+            "should_notify": true,
+            "since": "2023-06-21T18:25:41.000000+00:00",
+            "type": 1,
+            "user": {
+                "avatar": null,
+                "avatar_decoration": null,
+                "discriminator": "0",
+                "global_name": "Foo",
+                "id": "2",
+                "public_flags": 0,
+                "username": "foo"
+            }
+        }"#;
+
+        let value = RelationshipAdd {
+            relationship: Relationship {
+                id: Id::new(2),
+                kind: RelationshipType::Friend,
+                nickname: None,
+                since: Some(Timestamp::from_str("2023-06-21T18:25:41.000000+00:00").unwrap()),
+                user: User {
+                    accent_color: None,
+                    avatar: None,
+                    avatar_decoration: None,
+                    banner: None,
+                    bot: false,
+                    discriminator: 0,
+                    email: None,
+                    flags: None,
+                    global_name: Some("Foo".to_owned()),
+                    id: Id::new(2),
+                    locale: None,
+                    mfa_enabled: None,
+                    name: "foo".to_owned(),
+                    premium_type: None,
+                    public_flags: Some(UserFlags::empty()),
+                    system: None,
+                    verified: None,
+                },
+            },
+            should_notify: true,
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    id::{marker::UserMarker, Id},
+    user::RelationshipType,
+};
+use serde::{Deserialize, Serialize};
+
+/// A relationship of the current user was removed, such as when a friend
+/// request is declined or a user is unblocked.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct RelationshipRemove {
+    /// ID of the other user.
+    pub id: Id<UserMarker>,
+    /// Type the relationship had before it was removed.
+    #[serde(rename = "type")]
+    pub kind: RelationshipType,
+    #[serde(default)]
+    pub nickname: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RelationshipRemove;
+    use crate::{id::Id, user::RelationshipType};
+    use serde_test::Token;
+
+    #[test]
+    fn relationship_remove() {
+        let value = RelationshipRemove {
+            id: Id::new(2),
+            kind: RelationshipType::PendingOutgoing,
+            nickname: None,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "RelationshipRemove",
+                    len: 3,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("2"),
+                Token::Str("type"),
+                Token::U8(4),
+                Token::Str("nickname"),
+                Token::None,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
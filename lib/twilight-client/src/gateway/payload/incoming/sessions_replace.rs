@@ -0,0 +1,82 @@
+use crate::gateway::presence::Session;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// The sessions of the current user changed, sent with every session of the
+/// user.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SessionsReplace(pub Vec<Session>);
+
+impl Deref for SessionsReplace {
+    type Target = Vec<Session>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SessionsReplace {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionsReplace;
+    use crate::gateway::presence::{ClientInfo, Session, Status};
+
+    #[test]
+    fn sessions_replace() {
+        const JSON: &str = r#"[
+            {
+                "activities": [],
+                "client_info": {
+                    "client": "unknown",
+                    "os": "unknown",
+                    "version": 0
+                },
+                "session_id": "all",
+                "status": "idle"
+            },
+            {
+                "active": true,
+                "activities": [],
+                "client_info": {
+                    "client": "mobile",
+                    "os": "android",
+                    "version": 0
+                },
+                "session_id": "d4e5f6",
+                "status": "idle"
+            }
+        ]"#;
+
+        let value = SessionsReplace(vec![
+            Session {
+                active: false,
+                activities: Vec::new(),
+                client_info: ClientInfo {
+                    client: "unknown".to_owned(),
+                    os: "unknown".to_owned(),
+                    version: 0,
+                },
+                session_id: "all".to_owned(),
+                status: Status::Idle,
+            },
+            Session {
+                active: true,
+                activities: Vec::new(),
+                client_info: ClientInfo {
+                    client: "mobile".to_owned(),
+                    os: "android".to_owned(),
+                    version: 0,
+                },
+                session_id: "d4e5f6".to_owned(),
+                status: Status::Idle,
+            },
+        ]);
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
@@ -0,0 +1,20 @@
+use crate::user::UserGuildSettings;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct UserGuildSettingsUpdate(pub UserGuildSettings);
+
+impl Deref for UserGuildSettingsUpdate {
+    type Target = UserGuildSettings;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for UserGuildSettingsUpdate {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,42 @@
+use crate::id::{marker::UserMarker, Id};
+use serde::{Deserialize, Serialize};
+
+/// The current user changed the private note they keep about another user.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct UserNoteUpdate {
+    /// User the note is about.
+    pub id: Id<UserMarker>,
+    /// The note, empty once it's removed.
+    pub note: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserNoteUpdate;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn user_note_update() {
+        let value = UserNoteUpdate {
+            id: Id::new(2),
+            note: "met at rustconf".to_owned(),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "UserNoteUpdate",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("2"),
+                Token::Str("note"),
+                Token::Str("met at rustconf"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+/// Protobuf encoded settings of the current user changed.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct UserSettingsProtoUpdate {
+    /// Whether only the changed fields are sent, which must be merged into
+    /// the current settings.
+    pub partial: bool,
+    pub settings: UserSettingsProto,
+}
+
+/// Base64 encoded protobuf message of a type of user settings.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct UserSettingsProto {
+    #[serde(rename = "type")]
+    pub kind: UserSettingsProtoType,
+    pub proto: String,
+}
+
+/// Type of the protobuf message of a [`UserSettingsProto`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(from = "u8", into = "u8")]
+pub enum UserSettingsProtoType {
+    /// Settings sent along with the READY payload, such as the status and
+    /// theme.
+    PreloadedUserSettings,
+    /// Favorite and frequently used emojis, stickers and GIFs.
+    FrecencyUserSettings,
+    TestSettings,
+    /// Variant value is unknown to the library.
+    Unknown(u8),
+}
+
+impl UserSettingsProtoType {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::PreloadedUserSettings => "PreloadedUserSettings",
+            Self::FrecencyUserSettings => "FrecencyUserSettings",
+            Self::TestSettings => "TestSettings",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl From<u8> for UserSettingsProtoType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::PreloadedUserSettings,
+            2 => Self::FrecencyUserSettings,
+            3 => Self::TestSettings,
+            unknown => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<UserSettingsProtoType> for u8 {
+    fn from(value: UserSettingsProtoType) -> Self {
+        match value {
+            UserSettingsProtoType::PreloadedUserSettings => 1,
+            UserSettingsProtoType::FrecencyUserSettings => 2,
+            UserSettingsProtoType::TestSettings => 3,
+            UserSettingsProtoType::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UserSettingsProto, UserSettingsProtoType, UserSettingsProtoUpdate};
+    use serde_test::Token;
+
+    #[test]
+    fn user_settings_proto_update() {
+        let value = UserSettingsProtoUpdate {
+            partial: true,
+            settings: UserSettingsProto {
+                kind: UserSettingsProtoType::PreloadedUserSettings,
+                proto: "WgoKCAoGb25saW5l".to_owned(),
+            },
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "UserSettingsProtoUpdate",
+                    len: 2,
+                },
+                Token::Str("partial"),
+                Token::Bool(true),
+                Token::Str("settings"),
+                Token::Struct {
+                    name: "UserSettingsProto",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(1),
+                Token::Str("proto"),
+                Token::Str("WgoKCAoGb25saW5l"),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn user_settings_proto_type() {
+        const MAP: &[(UserSettingsProtoType, u8, &str)] = &[
+            (
+                UserSettingsProtoType::PreloadedUserSettings,
+                1,
+                "PreloadedUserSettings",
+            ),
+            (
+                UserSettingsProtoType::FrecencyUserSettings,
+                2,
+                "FrecencyUserSettings",
+            ),
+            (UserSettingsProtoType::TestSettings, 3, "TestSettings"),
+            (UserSettingsProtoType::Unknown(99), 99, "Unknown"),
+        ];
+
+        for (kind, number, name) in MAP {
+            assert_eq!(kind.name(), *name);
+            assert_eq!(u8::from(*kind), *number);
+            assert_eq!(UserSettingsProtoType::from(*number), *kind);
+            serde_test::assert_tokens(kind, &[Token::U8(*number)]);
+        }
+    }
+}
@@ -10,14 +10,16 @@ mod activity_timestamps;
 mod activity_type;
 mod client_status;
 mod minimal_activity;
+mod session;
 mod status;
 
 pub use self::{
     activity::Activity, activity_assets::ActivityAssets, activity_button::ActivityButton,
+    activity_button::ActivityButtonLink, activity_button::ActivityButtonText,
     activity_emoji::ActivityEmoji, activity_flags::ActivityFlags, activity_party::ActivityParty,
     activity_secrets::ActivitySecrets, activity_timestamps::ActivityTimestamps,
     activity_type::ActivityType, client_status::ClientStatus, minimal_activity::MinimalActivity,
-    status::Status,
+    session::ClientInfo, session::Session, status::Status,
 };
 
 use crate::{
@@ -0,0 +1,85 @@
+use crate::gateway::presence::{Activity, Status};
+use serde::{Deserialize, Serialize};
+
+/// A gateway session of the current user, one per logged-in client.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Session {
+    /// Whether the session is the one presences of the user are taken from.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub active: bool,
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    pub client_info: ClientInfo,
+    /// ID of the session, `all` for the presence aggregated over every
+    /// session.
+    pub session_id: String,
+    pub status: Status,
+}
+
+/// Client a [`Session`] was created by.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ClientInfo {
+    /// Kind of client, such as `desktop`, `mobile` or `web`.
+    pub client: String,
+    /// Operating system of the client, such as `linux` or `android`.
+    pub os: String,
+    pub version: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientInfo, Session};
+    use crate::gateway::presence::{Activity, ActivityType, Status};
+
+    #[test]
+    fn session() {
+        const JSON: &str = r#"{
+            "activities": [
+                {
+                    "created_at": 1700000000000,
+                    "name": "Custom Status",
+                    "state": "busy",
+                    "type": 4
+                }
+            ],
+            "client_info": {
+                "client": "desktop",
+                "os": "linux",
+                "version": 0
+            },
+            "session_id": "a1b2c3",
+            "status": "dnd"
+        }"#;
+
+        let value = Session {
+            active: false,
+            activities: vec![Activity {
+                application_id: None,
+                assets: None,
+                buttons: Vec::new(),
+                created_at: Some(1_700_000_000_000),
+                details: None,
+                emoji: None,
+                flags: None,
+                id: None,
+                instance: None,
+                kind: ActivityType::Custom,
+                name: "Custom Status".to_owned(),
+                party: None,
+                secrets: None,
+                state: Some("busy".to_owned()),
+                timestamps: None,
+                url: None,
+            }],
+            client_info: ClientInfo {
+                client: "desktop".to_owned(),
+                os: "linux".to_owned(),
+                version: 0,
+            },
+            session_id: "a1b2c3".to_owned(),
+            status: Status::DoNotDisturb,
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
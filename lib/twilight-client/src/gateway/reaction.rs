@@ -10,6 +10,12 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct GatewayReaction {
+    /// Whether this is a burst reaction, also called super reaction.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub burst: bool,
+    /// Colours used for the animation of the burst reaction, as hex strings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub burst_colors: Vec<String>,
     pub channel_id: Id<ChannelMarker>,
     pub emoji: ReactionType,
     pub guild_id: Option<Id<GuildMarker>>,
@@ -42,6 +48,8 @@ mod tests {
         let flags = MemberFlags::BYPASSES_VERIFICATION | MemberFlags::DID_REJOIN;
 
         let value = GatewayReaction {
+            burst: false,
+            burst_colors: Vec::new(),
             channel_id: Id::new(2),
             emoji: ReactionType::Unicode {
                 name: "a".to_owned(),
@@ -177,6 +185,8 @@ mod tests {
     #[test]
     fn reaction_without_member() {
         let value = GatewayReaction {
+            burst: false,
+            burst_colors: Vec::new(),
             channel_id: Id::new(2),
             emoji: ReactionType::Unicode {
                 name: "a".to_owned(),
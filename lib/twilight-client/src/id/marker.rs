@@ -137,6 +137,15 @@ pub struct InteractionMarker;
 #[non_exhaustive]
 pub struct MessageMarker;
 
+/// Marker for notification center item IDs.
+///
+/// Types such as [`NotificationCenterItem`] use this ID marker.
+///
+/// [`NotificationCenterItem`]: crate::user::NotificationCenterItem
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct NotificationCenterItemMarker;
+
 /// Marker for OAuth SKU IDs.
 ///
 /// Types such as [`Application`] use this ID marker.
@@ -420,8 +420,8 @@ mod tests {
         marker::{
             ApplicationMarker, AttachmentMarker, AuditLogEntryMarker, ChannelMarker, CommandMarker,
             CommandVersionMarker, EmojiMarker, GenericMarker, GuildMarker, IntegrationMarker,
-            InteractionMarker, MessageMarker, RoleMarker, RoleSubscriptionSkuMarker, StageMarker,
-            UserMarker, WebhookMarker,
+            InteractionMarker, MessageMarker, NotificationCenterItemMarker, RoleMarker,
+            RoleSubscriptionSkuMarker, StageMarker, UserMarker, WebhookMarker,
         },
         Id,
     };
@@ -450,6 +450,7 @@ mod tests {
     assert_impl_all!(IntegrationMarker: Debug, Send, Sync);
     assert_impl_all!(InteractionMarker: Debug, Send, Sync);
     assert_impl_all!(MessageMarker: Debug, Send, Sync);
+    assert_impl_all!(NotificationCenterItemMarker: Debug, Send, Sync);
     assert_impl_all!(RoleMarker: Debug, Send, Sync);
     assert_impl_all!(RoleSubscriptionSkuMarker: Debug, Send, Sync);
     assert_impl_all!(StageMarker: Debug, Send, Sync);
@@ -0,0 +1,298 @@
+use crate::{
+    guild::DefaultMessageNotificationLevel,
+    id::{
+        marker::{ChannelMarker, GuildMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Notification settings of the current user for a guild.
+///
+/// Settings for direct messages are sent with a [`guild_id`] of `None`.
+///
+/// [`guild_id`]: Self::guild_id
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct UserGuildSettings {
+    /// Settings overriding the guild settings for specific channels.
+    #[serde(default)]
+    pub channel_overrides: Vec<ChannelOverride>,
+    #[serde(default)]
+    pub flags: u64,
+    /// ID of the guild, `None` for the direct messages settings.
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// Whether muted channels are hidden from the channel list.
+    #[serde(default)]
+    pub hide_muted_channels: bool,
+    /// Which messages of the guild notify the current user.
+    pub message_notifications: MessageNotificationLevel,
+    #[serde(default)]
+    pub mobile_push: bool,
+    /// When the mute of the guild expires, if it is muted.
+    pub mute_config: Option<MuteConfig>,
+    #[serde(default)]
+    pub mute_scheduled_events: bool,
+    pub muted: bool,
+    #[serde(default)]
+    pub notify_highlights: u8,
+    /// Whether `@everyone` and `@here` mentions are suppressed.
+    #[serde(default)]
+    pub suppress_everyone: bool,
+    /// Whether role mentions are suppressed.
+    #[serde(default)]
+    pub suppress_roles: bool,
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl UserGuildSettings {
+    /// Whether the guild is muted at the given point in time.
+    pub fn is_muted(&self, now: Timestamp) -> bool {
+        self.muted && self.mute_config.as_ref().is_none_or(|c| c.is_active(now))
+    }
+
+    /// Override of the given channel, if any.
+    pub fn channel_override(&self, channel_id: Id<ChannelMarker>) -> Option<&ChannelOverride> {
+        self.channel_overrides
+            .iter()
+            .find(|o| o.channel_id == channel_id)
+    }
+}
+
+/// Notification settings of a channel overriding the ones of its guild.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ChannelOverride {
+    pub channel_id: Id<ChannelMarker>,
+    /// Whether the channel, if it is a category, is collapsed in the channel list.
+    #[serde(default)]
+    pub collapsed: bool,
+    #[serde(default)]
+    pub flags: u64,
+    /// Which messages of the channel notify the current user.
+    pub message_notifications: MessageNotificationLevel,
+    /// When the mute of the channel expires, if it is muted.
+    pub mute_config: Option<MuteConfig>,
+    pub muted: bool,
+}
+
+impl ChannelOverride {
+    /// Whether the channel is muted at the given point in time.
+    pub fn is_muted(&self, now: Timestamp) -> bool {
+        self.muted && self.mute_config.as_ref().is_none_or(|c| c.is_active(now))
+    }
+}
+
+/// Which messages notify the current user, as chosen in the notification
+/// settings of a guild or channel.
+///
+/// Extends the [`DefaultMessageNotificationLevel`] of a guild with the levels
+/// only users can pick.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(from = "u8", into = "u8")]
+pub enum MessageNotificationLevel {
+    /// Every message notifies.
+    All,
+    /// Only messages mentioning the current user notify.
+    OnlyMentions,
+    /// No message notifies.
+    Nothing,
+    /// The level of the parent category, or of the guild for guild settings.
+    Inherit,
+    /// Variant value is unknown to the library.
+    Unknown(u8),
+}
+
+impl From<u8> for MessageNotificationLevel {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::All,
+            1 => Self::OnlyMentions,
+            2 => Self::Nothing,
+            3 => Self::Inherit,
+            unknown => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<MessageNotificationLevel> for u8 {
+    fn from(value: MessageNotificationLevel) -> Self {
+        match value {
+            MessageNotificationLevel::All => 0,
+            MessageNotificationLevel::OnlyMentions => 1,
+            MessageNotificationLevel::Nothing => 2,
+            MessageNotificationLevel::Inherit => 3,
+            MessageNotificationLevel::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+impl From<DefaultMessageNotificationLevel> for MessageNotificationLevel {
+    fn from(value: DefaultMessageNotificationLevel) -> Self {
+        match value {
+            DefaultMessageNotificationLevel::All => Self::All,
+            DefaultMessageNotificationLevel::Mentions => Self::OnlyMentions,
+            DefaultMessageNotificationLevel::Unknown(unknown) => Self::Unknown(unknown),
+        }
+    }
+}
+
+/// Duration of a guild or channel mute.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MuteConfig {
+    /// When the mute ends, `None` if the mute lasts until it is turned off.
+    pub end_time: Option<Timestamp>,
+    /// Duration of the mute in seconds as selected by the user, `-1` if it
+    /// lasts until it is turned off.
+    pub selected_time_window: i64,
+}
+
+impl MuteConfig {
+    /// Whether the mute is still active at the given point in time.
+    pub const fn is_active(&self, now: Timestamp) -> bool {
+        match self.end_time {
+            Some(end_time) => end_time.as_micros() > now.as_micros(),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelOverride, MessageNotificationLevel, MuteConfig, UserGuildSettings};
+    use crate::{guild::DefaultMessageNotificationLevel, id::Id, util::Timestamp};
+    use serde_test::Token;
+    use std::str::FromStr;
+
+    #[test]
+    fn mute_config_expiry() {
+        let end_time = Timestamp::from_str("2023-10-14T20:00:00.000000+00:00").unwrap();
+        let before = Timestamp::from_str("2023-10-14T19:00:00.000000+00:00").unwrap();
+        let after = Timestamp::from_str("2023-10-14T21:00:00.000000+00:00").unwrap();
+
+        let config = MuteConfig {
+            end_time: Some(end_time),
+            selected_time_window: 3600,
+        };
+
+        assert!(config.is_active(before));
+        assert!(!config.is_active(after));
+        assert!(MuteConfig {
+            end_time: None,
+            selected_time_window: -1,
+        }
+        .is_active(after));
+    }
+
+    #[test]
+    fn message_notification_level() {
+        const MAP: &[(MessageNotificationLevel, u8)] = &[
+            (MessageNotificationLevel::All, 0),
+            (MessageNotificationLevel::OnlyMentions, 1),
+            (MessageNotificationLevel::Nothing, 2),
+            (MessageNotificationLevel::Inherit, 3),
+            (MessageNotificationLevel::Unknown(42), 42),
+        ];
+
+        for (level, value) in MAP {
+            serde_test::assert_tokens(level, &[Token::U8(*value)]);
+            assert_eq!(MessageNotificationLevel::from(*value), *level);
+            assert_eq!(u8::from(*level), *value);
+        }
+
+        assert_eq!(
+            MessageNotificationLevel::from(DefaultMessageNotificationLevel::Mentions),
+            MessageNotificationLevel::OnlyMentions
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn user_guild_settings() {
+        let value = UserGuildSettings {
+            channel_overrides: vec![ChannelOverride {
+                channel_id: Id::new(2),
+                collapsed: true,
+                flags: 0,
+                message_notifications: MessageNotificationLevel::Inherit,
+                mute_config: None,
+                muted: true,
+            }],
+            flags: 0,
+            guild_id: Some(Id::new(1)),
+            hide_muted_channels: false,
+            message_notifications: MessageNotificationLevel::OnlyMentions,
+            mobile_push: true,
+            mute_config: None,
+            mute_scheduled_events: false,
+            muted: false,
+            notify_highlights: 0,
+            suppress_everyone: true,
+            suppress_roles: false,
+            version: 7,
+        };
+
+        assert!(!value.is_muted(Timestamp::from_secs(0).unwrap()));
+        assert!(value.channel_override(Id::new(2)).is_some());
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "UserGuildSettings",
+                    len: 13,
+                },
+                Token::Str("channel_overrides"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ChannelOverride",
+                    len: 6,
+                },
+                Token::Str("channel_id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("2"),
+                Token::Str("collapsed"),
+                Token::Bool(true),
+                Token::Str("flags"),
+                Token::U64(0),
+                Token::Str("message_notifications"),
+                Token::U8(3),
+                Token::Str("mute_config"),
+                Token::None,
+                Token::Str("muted"),
+                Token::Bool(true),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("flags"),
+                Token::U64(0),
+                Token::Str("guild_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1"),
+                Token::Str("hide_muted_channels"),
+                Token::Bool(false),
+                Token::Str("message_notifications"),
+                Token::U8(1),
+                Token::Str("mobile_push"),
+                Token::Bool(true),
+                Token::Str("mute_config"),
+                Token::None,
+                Token::Str("mute_scheduled_events"),
+                Token::Bool(false),
+                Token::Str("muted"),
+                Token::Bool(false),
+                Token::Str("notify_highlights"),
+                Token::U8(0),
+                Token::Str("suppress_everyone"),
+                Token::Bool(true),
+                Token::Str("suppress_roles"),
+                Token::Bool(false),
+                Token::Str("version"),
+                Token::U64(7),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
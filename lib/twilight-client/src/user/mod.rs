@@ -3,13 +3,27 @@ mod connection_visibility;
 mod current_user;
 mod current_user_guild;
 mod flags;
+mod guild_settings;
+mod notification_center_item;
 mod premium_type;
 mod profile;
+mod profile_response;
+mod read_state;
+mod relationship;
 
 pub use self::{
-    connection::Connection, connection_visibility::ConnectionVisibility, current_user::CurrentUser,
-    current_user_guild::CurrentUserGuild, flags::UserFlags, premium_type::PremiumType,
+    connection::Connection,
+    connection_visibility::ConnectionVisibility,
+    current_user::CurrentUser,
+    current_user_guild::CurrentUserGuild,
+    flags::UserFlags,
+    guild_settings::{ChannelOverride, MessageNotificationLevel, MuteConfig, UserGuildSettings},
+    notification_center_item::NotificationCenterItem,
+    premium_type::PremiumType,
     profile::UserProfile,
+    profile_response::{ConnectedAccount, MutualGuild, ProfileMetadata, ProfileResponse},
+    read_state::ReadState,
+    relationship::{Relationship, RelationshipType},
 };
 
 use crate::{
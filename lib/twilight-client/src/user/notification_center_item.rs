@@ -0,0 +1,82 @@
+use crate::{
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker, NotificationCenterItemMarker},
+        Id,
+    },
+    user::User,
+};
+use serde::{Deserialize, Serialize};
+
+/// Entry of the inbox of the current user, such as an accepted friend
+/// request or a reminder to finish setting up an account.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NotificationCenterItem {
+    /// Whether the current user has seen the item.
+    #[serde(default)]
+    pub acked: bool,
+    /// Text of the item, which may contain markdown.
+    pub body: String,
+    /// Items with the same bundle are grouped together.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// Whether the action of the item was completed.
+    #[serde(default)]
+    pub completed: bool,
+    /// Link opened when the item is clicked.
+    pub deeplink: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Id<GuildMarker>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    pub id: Id<NotificationCenterItemMarker>,
+    /// Identifies the task an item is about, referred to once it's completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_enum: Option<u64>,
+    /// Kind of the item, such as `friend_request_accepted`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_channel_id: Option<Id<ChannelMarker>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<Id<MessageMarker>>,
+    /// User the item is about.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_user: Option<User>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NotificationCenterItem;
+    use crate::id::Id;
+
+    #[test]
+    fn notification_center_item() {
+        const JSON: &str = r#"{
+            "acked": false,
+            "body": "**foo** accepted your friend request.",
+            "deeplink": "https://discord.com/users/2",
+            "icon_url": "https://cdn.discordapp.com/avatars/2/a.png",
+            "id": "1",
+            "item_enum": null,
+            "type": "friend_request_accepted"
+        }"#;
+
+        let value = NotificationCenterItem {
+            acked: false,
+            body: "**foo** accepted your friend request.".to_owned(),
+            bundle_id: None,
+            completed: false,
+            deeplink: "https://discord.com/users/2".to_owned(),
+            guild_id: None,
+            icon_url: Some("https://cdn.discordapp.com/avatars/2/a.png".to_owned()),
+            id: Id::new(1),
+            item_enum: None,
+            kind: "friend_request_accepted".to_owned(),
+            message_channel_id: None,
+            message_id: None,
+            other_user: None,
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+}
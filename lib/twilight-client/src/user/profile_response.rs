@@ -0,0 +1,133 @@
+use super::{PremiumType, User, UserProfile};
+use crate::{
+    guild::Member,
+    id::{marker::GuildMarker, Id},
+    util::{ImageHash, Timestamp},
+};
+use serde::{Deserialize, Serialize};
+
+/// Profile of a user, as it's shown to user accounts in their profile popout.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProfileResponse {
+    /// Accounts the user connected and made visible on their profile.
+    #[serde(default)]
+    pub connected_accounts: Vec<ConnectedAccount>,
+    /// Member of the guild the profile was requested for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_member: Option<Member>,
+    /// Profile of the user specific to the guild it was requested for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_member_profile: Option<ProfileMetadata>,
+    /// Friends of the user the current user is also friends with.
+    #[serde(default)]
+    pub mutual_friends: Vec<User>,
+    /// Guilds of the user the current user is also in.
+    #[serde(default)]
+    pub mutual_guilds: Vec<MutualGuild>,
+    /// When the user started subscribing to Nitro.
+    pub premium_since: Option<Timestamp>,
+    pub premium_type: Option<PremiumType>,
+    pub user: UserProfile,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_profile: Option<ProfileMetadata>,
+}
+
+/// Customization of a profile.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ProfileMetadata {
+    /// Accent color of the banner, as an integer representation of a
+    /// hexadecimal color code.
+    #[serde(default)]
+    pub accent_color: Option<u32>,
+    #[serde(default)]
+    pub banner: Option<ImageHash>,
+    /// About me of the user, empty if they didn't write one.
+    #[serde(default)]
+    pub bio: String,
+    #[serde(default)]
+    pub pronouns: String,
+}
+
+/// Account on another service shown on a profile.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ConnectedAccount {
+    /// ID of the account on the service.
+    pub id: String,
+    /// Service the account is on, such as `github` or `steam`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub verified: bool,
+}
+
+/// Guild shared with the user of a profile.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct MutualGuild {
+    pub id: Id<GuildMarker>,
+    /// Nickname of the user in the guild.
+    pub nick: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectedAccount, MutualGuild, ProfileResponse};
+    use crate::{id::Id, user::PremiumType};
+
+    #[test]
+    fn profile_response() {
+        const JSON: &str = r#"{
+            "user": {
+                "id": "2",
+                "username": "ferris",
+                "global_name": "Ferris",
+                "avatar": null,
+                "discriminator": "0",
+                "public_flags": 4194304,
+                "flags": 4194304,
+                "banner": null,
+                "accent_color": 16750848,
+                "bio": "crab"
+            },
+            "connected_accounts": [{
+                "type": "github",
+                "id": "5430905",
+                "name": "ferris",
+                "verified": true
+            }],
+            "premium_since": null,
+            "premium_type": 2,
+            "user_profile": {
+                "bio": "crab",
+                "accent_color": 16750848,
+                "pronouns": "it/its"
+            },
+            "mutual_guilds": [{ "id": "3", "nick": null }],
+            "mutual_friends": []
+        }"#;
+
+        let profile = serde_json::from_str::<ProfileResponse>(JSON).unwrap();
+
+        assert_eq!(profile.user.id, Id::new(2));
+        assert_eq!(profile.user.accent_color, Some(16_750_848));
+        assert_eq!(profile.premium_type, Some(PremiumType::Nitro));
+        assert_eq!(
+            profile.connected_accounts,
+            [ConnectedAccount {
+                id: "5430905".to_owned(),
+                kind: "github".to_owned(),
+                name: "ferris".to_owned(),
+                verified: true,
+            }]
+        );
+        assert_eq!(
+            profile.mutual_guilds,
+            [MutualGuild {
+                id: Id::new(3),
+                nick: None,
+            }]
+        );
+        assert_eq!(profile.user_profile.unwrap().pronouns, "it/its");
+        assert!(profile.guild_member.is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Position up to which the current user has read a channel.
+///
+/// Sent in the [`Ready`] payload of user accounts.
+///
+/// [`Ready`]: crate::gateway::payload::incoming::Ready
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ReadState {
+    #[serde(default)]
+    pub flags: u64,
+    /// ID of the channel.
+    pub id: Id<ChannelMarker>,
+    /// ID of the last message that was acknowledged.
+    #[serde(default)]
+    pub last_message_id: Option<Id<MessageMarker>>,
+    #[serde(default)]
+    pub last_pin_timestamp: Option<Timestamp>,
+    #[serde(default)]
+    pub last_viewed: Option<u64>,
+    /// Amount of unacknowledged mentions of the current user.
+    #[serde(default)]
+    pub mention_count: u32,
+    /// Type of the read state, `0` for channels.
+    ///
+    /// Other types track notification center items and guild events, their
+    /// [`id`] does not refer to a channel.
+    ///
+    /// [`id`]: Self::id
+    #[serde(default)]
+    pub read_state_type: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReadState;
+    use crate::id::Id;
+    use serde_test::Token;
+
+    #[test]
+    fn read_state() {
+        let value = ReadState {
+            flags: 0,
+            id: Id::new(706_185_253_974_442_006),
+            last_message_id: Some(Id::new(1_162_844_585_320_513_648)),
+            last_pin_timestamp: None,
+            last_viewed: Some(3148),
+            mention_count: 2,
+            read_state_type: 0,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "ReadState",
+                    len: 7,
+                },
+                Token::Str("flags"),
+                Token::U64(0),
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("706185253974442006"),
+                Token::Str("last_message_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("1162844585320513648"),
+                Token::Str("last_pin_timestamp"),
+                Token::None,
+                Token::Str("last_viewed"),
+                Token::Some,
+                Token::U64(3148),
+                Token::Str("mention_count"),
+                Token::U32(2),
+                Token::Str("read_state_type"),
+                Token::U8(0),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
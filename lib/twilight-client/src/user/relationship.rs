@@ -0,0 +1,179 @@
+use crate::{
+    id::{marker::UserMarker, Id},
+    user::User,
+    util::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Relationship of the current user with another user.
+///
+/// Sent in the [`Ready`] payload of user accounts and in the
+/// [`RelationshipAdd`] event.
+///
+/// [`Ready`]: crate::gateway::payload::incoming::Ready
+/// [`RelationshipAdd`]: crate::gateway::payload::incoming::RelationshipAdd
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Relationship {
+    /// ID of the other user.
+    pub id: Id<UserMarker>,
+    #[serde(rename = "type")]
+    pub kind: RelationshipType,
+    /// Nickname the current user gave the other user.
+    #[serde(default)]
+    pub nickname: Option<String>,
+    /// When the relationship was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<Timestamp>,
+    pub user: User,
+}
+
+/// Type of a [`Relationship`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(from = "u8", into = "u8")]
+pub enum RelationshipType {
+    /// The users are friends.
+    Friend,
+    /// The current user blocked the other user.
+    Blocked,
+    /// The other user sent a friend request to the current user.
+    PendingIncoming,
+    /// The current user sent a friend request to the other user.
+    PendingOutgoing,
+    /// The users often interact but aren't friends.
+    Implicit,
+    /// Variant value is unknown to the library.
+    Unknown(u8),
+}
+
+impl RelationshipType {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Friend => "Friend",
+            Self::Blocked => "Blocked",
+            Self::PendingIncoming => "PendingIncoming",
+            Self::PendingOutgoing => "PendingOutgoing",
+            Self::Implicit => "Implicit",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl From<u8> for RelationshipType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Friend,
+            2 => Self::Blocked,
+            3 => Self::PendingIncoming,
+            4 => Self::PendingOutgoing,
+            5 => Self::Implicit,
+            unknown => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<RelationshipType> for u8 {
+    fn from(value: RelationshipType) -> Self {
+        match value {
+            RelationshipType::Friend => 1,
+            RelationshipType::Blocked => 2,
+            RelationshipType::PendingIncoming => 3,
+            RelationshipType::PendingOutgoing => 4,
+            RelationshipType::Implicit => 5,
+            RelationshipType::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Relationship, RelationshipType};
+    use crate::{
+        id::Id,
+        user::{User, UserFlags},
+        util::Timestamp,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_tokens, Token};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash, str::FromStr};
+
+    assert_impl_all!(
+        RelationshipType: Clone,
+        Copy,
+        Debug,
+        Deserialize<'static>,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Serialize,
+        Sync
+    );
+
+    #[test]
+    fn relationship() {
+        const JSON: &str = r#"{
+            "id": "2",
+            "nickname": null,
+            "since": "2023-06-21T18:25:41.000000+00:00",
+            "type": 3,
+            "user": {
+                "avatar": null,
+                "avatar_decoration": null,
+                "discriminator": "0",
+                "global_name": "Foo",
+                "id": "2",
+                "public_flags": 0,
+                "username": "foo"
+            }
+        }"#;
+
+        let value = Relationship {
+            id: Id::new(2),
+            kind: RelationshipType::PendingIncoming,
+            nickname: None,
+            since: Some(Timestamp::from_str("2023-06-21T18:25:41.000000+00:00").unwrap()),
+            user: User {
+                accent_color: None,
+                avatar: None,
+                avatar_decoration: None,
+                banner: None,
+                bot: false,
+                discriminator: 0,
+                email: None,
+                flags: None,
+                global_name: Some("Foo".to_owned()),
+                id: Id::new(2),
+                locale: None,
+                mfa_enabled: None,
+                name: "foo".to_owned(),
+                premium_type: None,
+                public_flags: Some(UserFlags::empty()),
+                system: None,
+                verified: None,
+            },
+        };
+
+        assert_eq!(value, serde_json::from_str(JSON).unwrap());
+    }
+
+    #[test]
+    fn relationship_type() {
+        const MAP: &[(RelationshipType, u8, &str)] = &[
+            (RelationshipType::Friend, 1, "Friend"),
+            (RelationshipType::Blocked, 2, "Blocked"),
+            (RelationshipType::PendingIncoming, 3, "PendingIncoming"),
+            (RelationshipType::PendingOutgoing, 4, "PendingOutgoing"),
+            (RelationshipType::Implicit, 5, "Implicit"),
+            (RelationshipType::Unknown(99), 99, "Unknown"),
+        ];
+
+        for (kind, number, name) in MAP {
+            assert_eq!(kind.name(), *name);
+            assert_eq!(u8::from(*kind), *number);
+            assert_eq!(RelationshipType::from(*number), *kind);
+            assert_tokens(kind, &[Token::U8(*number)]);
+        }
+    }
+}